@@ -0,0 +1,41 @@
+use chrono::{NaiveDate, Weekday};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::history::History;
+
+// Share of the weekly budget someone has to have used before it's pointed out
+const HINT_AT: f64 = 0.8;
+
+/// Everyone's talk time over the series' meetings this week, against `--weekly-budget`, so
+/// the people who talk most across the week are nudged to leave room for the rest
+pub struct Airtime {
+    budget: Duration,
+    // spoken in earlier meetings since Monday
+    earlier: HashMap<String, Duration>,
+}
+
+impl Airtime {
+    /// Add up this week's talk time from the history, as of `today`
+    pub fn new(budget: Duration, history: &History, today: NaiveDate) -> Self {
+        let monday = today.week(Weekday::Mon).first_day();
+        let mut earlier: HashMap<String, Duration> = HashMap::new();
+        for meeting in history.meetings().iter().filter(|m| m.date >= monday) {
+            for turn in &meeting.turns {
+                *earlier.entry(turn.name.clone()).or_default() += Duration::from_secs(turn.secs);
+            }
+        }
+        Self { budget, earlier }
+    }
+
+    /// The percentage of their weekly budget someone has used, counting `spoken` in this
+    /// meeting, once it's worth a hint
+    pub fn used(&self, name: &str, spoken: Duration) -> Option<u32> {
+        if self.budget.is_zero() {
+            return None;
+        }
+        let week = self.earlier.get(name).copied().unwrap_or_default() + spoken;
+        let used = week.as_secs_f64() / self.budget.as_secs_f64();
+        (used >= HINT_AT).then_some((used * 100.0) as u32)
+    }
+}
@@ -1,59 +1,160 @@
+use chrono::Local;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers,
+        KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
     execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    terminal::{
+        EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+        supports_keyboard_enhancement,
+    },
 };
-use rand::seq::SliceRandom;
 use ratatui::{Terminal, backend::CrosstermBackend};
-use std::fs;
+use std::collections::HashSet;
 use std::io;
 use std::time::{Duration, Instant};
 
+use crate::avatar::Avatars;
+use crate::burndown::{Burndown, Lines};
+use crate::calendar;
+use crate::chapters::format_timestamp;
+use crate::checkin::{Attendance, CheckIn};
+use crate::command::{AppCommand, MeetingCommand};
 use crate::config::Config;
+use crate::demo::Script;
 use crate::error::{AppError, Result};
+use crate::escalation::Escalation;
+use crate::handoff::{self, Handoffs};
+use crate::health::Health;
+use crate::history::ChecklistItem;
+use crate::keys;
+use crate::meeting::Meeting;
+use crate::notify::Notifier;
+use crate::per_person::{self, PerPerson, SlotStatus};
+use crate::queue::Queue;
+use crate::report;
+use crate::roster::{Celebration, Member};
+use crate::server::Server;
+use crate::session::{Adjustment, GapChoice, PAUSE_REASONS, Pause, Phase, Speaker, TimeUp};
+use crate::sprint::Sprint;
+use crate::templates::Templates;
+use crate::theme::TimerTheme;
+use crate::topics::Topic;
+use crate::trace::Tracer;
+use crate::transcript::Transcript;
 use crate::ui::UI;
-
-// Embed the default team.txt file at compile time
-const DEFAULT_TEAM_CONTENT: &str = include_str!("../team.txt");
+#[cfg(feature = "self-update")]
+use crate::update::UpdateCheck;
+
+// How long the confirmation of a recording marker stays up
+const MARKER_NOTICE: Duration = Duration::from_secs(3);
+
+/// What the app is doing, which decides how keys are handled and what's drawn
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mode {
+    // the warm-up, before the first turn
+    PreMeeting,
+    Running,
+    Paused,
+    // typing a topic for the current speaker
+    Editing { text: String },
+    // back after the meeting stopped ticking for `gap`, asking what that time was
+    Resuming { gap: Duration },
+    // ticking off the facilitator's checklist, before the closing screen
+    Checklist { ticked: Vec<bool> },
+    // the closing screen
+    Summary,
+    Quitting,
+}
 
 /// Main application state
 pub struct App {
     config: Config,
-    names: Vec<String>,
-    per_person_timers: Vec<Duration>,
-    current_person_index: usize,
-    timer_start: Instant,
-    last_ppt_update: Instant,
-    should_quit: bool,
+    meeting: Meeting,
+    notifier: Option<Notifier>,
+    escalation: Option<Escalation>,
+    burndown: Option<Burndown>,
+    per_person: Option<PerPerson>,
+    handoffs: Option<Handoffs>,
+    tracer: Option<Tracer>,
+    transcript: Option<Transcript>,
+    queue: Queue,
+    server: Option<Server>,
+    // when the last recording marker was dropped, to confirm it briefly
+    marked_at: Option<Instant>,
+    // teams whose members are hidden in the order
+    collapsed_teams: HashSet<String>,
+    // the order is drawn as a wheel instead of a list
+    wheel: bool,
+    // the scripted meeting `nextup demo` plays
+    demo: Option<Script>,
+    avatars: Avatars,
+    #[cfg(feature = "self-update")]
+    update_check: UpdateCheck,
+    mode: Mode,
     is_dark_background: bool,
 }
 
 impl App {
     /// Create a new application instance
     pub async fn new(config: Config) -> Result<Self> {
-        let names = Self::load_names(&config.names_file)?;
-
-        if names.is_empty() {
-            return Err(AppError::NoNamesFound.into());
-        }
-
-        let per_person_timers = vec![Duration::ZERO; names.len()];
+        let meeting = Meeting::load(&config)?;
+        let templates = Templates::load(&config)?;
+        let members: Vec<Member> = meeting
+            .session
+            .speakers()
+            .iter()
+            .map(|s| s.member.clone())
+            .collect();
+        let avatars = Avatars::load(&config, &members)?;
+        let transcript = Transcript::new(&config, &templates);
+        let handoffs = if config.handoffs {
+            let phrases = handoff::load_phrases(config.handoffs_file.as_deref())?;
+            Some(Handoffs::new(phrases))
+        } else {
+            None
+        };
+        let server = match config.serve {
+            Some(addr) => {
+                Some(Server::start(addr, config.api_token.clone(), config.share_ttl).await?)
+            }
+            None => None,
+        };
 
         // Detect terminal background (default to dark if detection fails)
         let is_dark_background = Self::detect_dark_background().unwrap_or(true);
 
         Ok(Self {
+            notifier: Notifier::new(&config, &templates),
+            escalation: Escalation::new(&config, &templates),
+            burndown: Burndown::new(&config),
+            per_person: PerPerson::new(&config),
+            handoffs,
+            tracer: Tracer::new(&config),
+            transcript,
+            queue: Queue::new(&config, None),
+            server,
+            marked_at: None,
+            collapsed_teams: HashSet::new(),
+            wheel: config.wheel,
+            demo: None,
+            avatars,
+            #[cfg(feature = "self-update")]
+            update_check: UpdateCheck::start(),
             config,
-            names,
-            per_person_timers,
-            current_person_index: 0,
-            timer_start: Instant::now(),
-            last_ppt_update: Instant::now(),
-            should_quit: false,
+            meeting,
+            mode: Mode::PreMeeting,
             is_dark_background,
         })
     }
 
+    /// Play a scripted meeting, for the demo
+    pub fn with_demo(mut self, script: Script) -> Self {
+        self.demo = Some(script);
+        self
+    }
+
     /// Attempt to detect if terminal has a dark background.
     /// Returns None if detection fails, Some(true) for dark, Some(false) for light
     fn detect_dark_background() -> Option<bool> {
@@ -75,11 +176,12 @@ impl App {
         // Try to read response with timeout
         // This is a simple heuristic; if we can't detect, we'll default to dark
         if let Ok(true) = event::poll(StdDuration::from_millis(100))
-            && let Ok(Event::Key(_)) = event::read() {
-                // If we got any response, try to parse it
-                // This is a simplified check - in practice, OSC responses are complex
-                // For now, we'll use an environment variable as a more reliable fallback
-            }
+            && let Ok(Event::Key(_)) = event::read()
+        {
+            // If we got any response, try to parse it
+            // This is a simplified check - in practice, OSC responses are complex
+            // For now, we'll use an environment variable as a more reliable fallback
+        }
 
         // Fallback: Check common environment variables
         if let Ok(term_program) = std::env::var("TERM_PROGRAM") {
@@ -92,141 +194,237 @@ impl App {
         // Check COLORFGBG (set by some terminals: "foreground;background")
         if let Ok(colorfgbg) = std::env::var("COLORFGBG")
             && let Some(bg) = colorfgbg.split(';').next_back()
-                && let Ok(bg_num) = bg.parse::<u8>() {
-                    // In COLORFGBG, lower numbers (0-7) typically mean dark colors
-                    // Higher numbers (8-15) typically mean light colors
-                    return Some(bg_num < 8);
-                }
+            && let Ok(bg_num) = bg.parse::<u8>()
+        {
+            // In COLORFGBG, lower numbers (0-7) typically mean dark colors
+            // Higher numbers (8-15) typically mean light colors
+            return Some(bg_num < 8);
+        }
 
         // Default assumption: dark background (most common for terminals)
         Some(true)
     }
 
-    /// Load names from a file, falling back to embedded default if file not found
-    fn load_names(filename: &str) -> Result<Vec<String>> {
-        // Try to read from file first
-        let content = match fs::read_to_string(filename) {
-            Ok(content) => content,
-            Err(e) => {
-                // If the file doesn't exist, and we're using the default filename,
-                // fall back to the embedded content
-                if filename == "team.txt" && e.kind() == io::ErrorKind::NotFound {
-                    DEFAULT_TEAM_CONTENT.to_string()
-                } else {
-                    // For other errors or custom filenames, propagate the error
-                    return Err(AppError::NamesFileError(e).into());
-                }
-            }
-        };
-
-        let names: Vec<String> = content
-            .lines()
-            .map(|line| line.trim().to_string())
-            .filter(|line| !line.is_empty())
-            .collect();
-
-        Ok(names)
+    /// Get elapsed meeting time
+    pub fn elapsed(&self) -> Duration {
+        self.meeting.session.elapsed()
     }
 
-    /// Shuffle the names randomly
-    fn shuffle_names(&mut self) {
-        let mut rng = rand::rng();
-        self.names.shuffle(&mut rng);
-        self.reset_per_person_timers();
+    /// Planned meeting length, with any time added or taken off
+    pub fn duration(&self) -> Duration {
+        self.meeting.session.duration()
     }
 
-    /// Reset per-person timers
-    fn reset_per_person_timers(&mut self) {
-        self.per_person_timers = vec![Duration::ZERO; self.names.len()];
-        self.current_person_index = 0;
-    }
-
-    /// Reset the main timer
-    fn reset_timer(&mut self) {
-        self.timer_start = Instant::now();
-        self.last_ppt_update = Instant::now();
+    /// Get remaining meeting time
+    pub fn remaining_time(&self) -> Duration {
+        self.meeting.session.remaining_time()
     }
 
-    /// Update per-person timers
-    fn update_per_person_timers(&mut self) {
-        let now = Instant::now();
-        let elapsed = now.duration_since(self.last_ppt_update);
+    /// Handle keyboard input
+    fn handle_input(&mut self, key: event::KeyEvent) -> Result<()> {
+        // typed text stays as it is, but shortcuts go by the keyboard layout
+        let key = match self.mode {
+            Mode::Editing { .. } => key,
+            _ => self.config.keyboard.normalize(key),
+        };
 
-        // Update the current person's timer
-        if self.current_person_index < self.per_person_timers.len() {
-            self.per_person_timers[self.current_person_index] += elapsed;
+        match &mut self.mode {
+            // any key leaves the closing screen
+            Mode::Summary => self.dispatch(AppCommand::Quit),
+            Mode::Editing { text } => match key.code {
+                KeyCode::Enter => {
+                    let text = text.trim().to_string();
+                    self.leave_editing();
+                    if !text.is_empty() {
+                        let name = self.meeting.session.current_name().to_string();
+                        self.dispatch(AppCommand::Meeting(MeetingCommand::AddTopic { name, text }));
+                    }
+                }
+                KeyCode::Esc => self.leave_editing(),
+                KeyCode::Backspace => {
+                    text.pop();
+                }
+                KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => text.push(c),
+                _ => {}
+            },
+            Mode::Resuming { .. } => {
+                let command = match (key.code, key.modifiers) {
+                    (KeyCode::Char('d'), KeyModifiers::NONE) => {
+                        AppCommand::Meeting(MeetingCommand::ResolveGap(GapChoice::Discard))
+                    }
+                    (KeyCode::Char('c'), KeyModifiers::NONE) => {
+                        AppCommand::Meeting(MeetingCommand::ResolveGap(GapChoice::Count))
+                    }
+                    (KeyCode::Char('b'), KeyModifiers::NONE) => {
+                        AppCommand::Meeting(MeetingCommand::ResolveGap(GapChoice::Break))
+                    }
+                    (KeyCode::Char('q'), KeyModifiers::NONE) => AppCommand::Close,
+                    (KeyCode::Char('c'), KeyModifiers::CONTROL) => AppCommand::Quit,
+                    _ => return Ok(()),
+                };
+                self.dispatch(command);
+            }
+            Mode::Checklist { ticked } => match (key.code, key.modifiers) {
+                (KeyCode::Char(c), KeyModifiers::NONE) if c.is_ascii_digit() => {
+                    let item = c.to_digit(10).unwrap_or_default() as usize;
+                    if let Some(done) = item.checked_sub(1).and_then(|i| ticked.get_mut(i)) {
+                        *done = !*done;
+                    }
+                }
+                (KeyCode::Enter, _) => {
+                    let checklist = self
+                        .config
+                        .checklist
+                        .iter()
+                        .zip(ticked.iter())
+                        .map(|(item, done)| ChecklistItem {
+                            item: item.clone(),
+                            done: *done,
+                        })
+                        .collect();
+                    self.meeting.set_checklist(checklist);
+                    self.mode = Mode::Summary;
+                }
+                // skipped, so nothing's recorded
+                (KeyCode::Esc, _) => self.mode = Mode::Summary,
+                (KeyCode::Char('c'), KeyModifiers::CONTROL) => self.dispatch(AppCommand::Quit),
+                _ => {}
+            },
+            Mode::PreMeeting | Mode::Running | Mode::Paused => {
+                if let Some(command) = self.key_command(key) {
+                    self.dispatch(command);
+                }
+            }
+            Mode::Quitting => {}
         }
-
-        self.last_ppt_update = now;
+        Ok(())
     }
 
-    /// Get remaining meeting time
-    pub fn remaining_time(&self) -> Duration {
-        let elapsed = self.timer_start.elapsed();
-        if elapsed >= self.config.duration {
-            Duration::ZERO
-        } else {
-            self.config.duration - elapsed
+    /// What a key does while the meeting is under way
+    fn key_command(&self, key: event::KeyEvent) -> Option<AppCommand> {
+        // while paused, the number keys say why
+        if self.mode == Mode::Paused
+            && let (KeyCode::Char(c), KeyModifiers::NONE) = (key.code, key.modifiers)
+            && let Some((_, _, reason)) = PAUSE_REASONS
+                .iter()
+                .find(|(keys, _, _)| keys.starts_with(c))
+        {
+            let reason = reason.to_string();
+            return Some(AppCommand::Meeting(MeetingCommand::PauseReason(reason)));
+        }
+
+        match keys::command_for(key)? {
+            // there's no room to type in the widget
+            AppCommand::EditTopic if self.config.widget => None,
+            command => Some(command),
         }
     }
 
-    /// Handle keyboard input
-    fn handle_input(&mut self, key: event::KeyEvent) -> Result<()> {
-        match (key.code, key.modifiers) {
-            // Ctrl+R -- Reset timer and per-person timers
-            (KeyCode::Char('r'), KeyModifiers::CONTROL) => {
-                self.reset_per_person_timers();
-                self.reset_timer();
+    /// Carry out a command, whether it came from a key or the control API
+    fn dispatch(&mut self, command: AppCommand) {
+        // an attached viewer can only detach, and a meeting locked once time is up only be left
+        if self.meeting.is_read_only() || self.is_locked() {
+            match command {
+                AppCommand::Close | AppCommand::Quit => self.mode = Mode::Quitting,
+                // how it's shown is up to whoever's watching
+                AppCommand::ToggleWheel => self.wheel = !self.wheel,
+                _ => {}
             }
+            return;
+        }
 
-            // Ctrl+N -- Reshuffle names
-            (KeyCode::Char('n'), KeyModifiers::CONTROL) => {
-                self.shuffle_names();
-                self.reset_timer();
+        match command {
+            AppCommand::Meeting(command) => {
+                if command == MeetingCommand::Mark {
+                    self.marked_at = Some(Instant::now());
+                }
+                self.meeting.apply(command);
             }
-
-            // Ctrl+C or 'q' -- Quit
-            (KeyCode::Char('c'), KeyModifiers::CONTROL)
-            | (KeyCode::Char('q'), KeyModifiers::NONE) => {
-                self.should_quit = true;
+            AppCommand::Close => {
+                self.meeting.close();
+                self.mode = if self.config.checklist.is_empty() {
+                    Mode::Summary
+                } else {
+                    Mode::Checklist {
+                        ticked: vec![false; self.config.checklist.len()],
+                    }
+                };
             }
-
-            // Tab or Down Arrow -- Next person
-            (KeyCode::Tab, KeyModifiers::NONE) | (KeyCode::Down, KeyModifiers::NONE) => {
-                if self.current_person_index < self.names.len() - 1 {
-                    self.current_person_index += 1;
+            AppCommand::Quit => self.mode = Mode::Quitting,
+            AppCommand::EditTopic => {
+                self.mode = Mode::Editing {
+                    text: String::new(),
                 }
             }
-
-            // Shift+Tab or Up Arrow -- Previous person
-            (KeyCode::BackTab, KeyModifiers::NONE) | (KeyCode::Up, KeyModifiers::NONE) => {
-                if self.current_person_index > 0 {
-                    self.current_person_index -= 1;
-                }
+            AppCommand::CollapseTeams => {
+                self.collapsed_teams = self
+                    .speakers()
+                    .iter()
+                    .filter_map(|s| s.member.team.clone())
+                    .collect();
             }
+            AppCommand::ExpandTeams => self.collapsed_teams.clear(),
+            AppCommand::ToggleWheel => self.wheel = !self.wheel,
+        }
+    }
 
-            _ => {}
+    /// Go back to the meeting after typing a topic
+    fn leave_editing(&mut self) {
+        self.mode = Mode::Running;
+        self.follow_meeting();
+    }
+
+    /// Keep the mode in step with the meeting's warm-up and pauses, which the control API can change too
+    fn follow_meeting(&mut self) {
+        if !matches!(
+            self.mode,
+            Mode::PreMeeting | Mode::Running | Mode::Paused | Mode::Resuming { .. }
+        ) {
+            return;
         }
 
-        Ok(())
+        self.mode = if let Some(gap) = self.meeting.session.pending_gap() {
+            Mode::Resuming { gap }
+        } else if self.meeting.session.is_paused() {
+            Mode::Paused
+        } else if self.meeting.session.phase() == Phase::WarmUp {
+            Mode::PreMeeting
+        } else {
+            Mode::Running
+        };
     }
 
     /// Main application loop
     pub async fn run(&mut self) -> Result<()> {
         // Setup terminal -- ratatui's way of controlling terminal
-        enable_raw_mode()?;
+        enable_raw_mode().map_err(AppError::TerminalError)?;
         let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)
+            .map_err(AppError::TerminalError)?;
+        // lets Ctrl+= and Ctrl+- through in terminals that support it
+        let enhanced_keys = supports_keyboard_enhancement().unwrap_or(false);
+        if enhanced_keys {
+            execute!(
+                stdout,
+                PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
+            )?;
+        }
         let backend = CrosstermBackend::new(stdout);
         let mut terminal = Terminal::new(backend)?;
 
-        // Shuffle names initially
-        self.shuffle_names();
+        // Shuffle names initially, unless continuing a running meeting
+        if !self.meeting.is_resumed() {
+            self.meeting.open_order();
+        }
 
         // Main event loop
         let res = self.run_app(&mut terminal).await;
 
         // Restore terminal
+        self.avatars.clear(terminal.backend_mut())?;
+        if enhanced_keys {
+            execute!(terminal.backend_mut(), PopKeyboardEnhancementFlags)?;
+        }
         disable_raw_mode()?;
         execute!(
             terminal.backend_mut(),
@@ -235,6 +433,24 @@ impl App {
         )?;
         terminal.show_cursor()?;
 
+        if self.meeting.is_taken_over() {
+            eprintln!("This meeting was taken over by another nextup.");
+        }
+
+        // Remember this meeting in the series history
+        self.meeting.finish()?;
+        self.meeting.export_chapters(&self.config)?;
+        if let Some(tracer) = &mut self.tracer
+            && let Err(e) = tracer.export(&self.config, &self.meeting, &self.queue)
+        {
+            eprintln!("{}", e);
+        }
+        self.queue.finish();
+        if let Some(transcript) = &self.transcript {
+            transcript.write(&self.config, &self.meeting)?;
+        }
+        report::write(&self.config, &self.meeting)?;
+
         res
     }
 
@@ -243,21 +459,97 @@ impl App {
         &mut self,
         terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     ) -> Result<()> {
+        let mut last_size = terminal.size()?;
         loop {
-            // Update timers
-            self.update_per_person_timers();
+            // Update timers until the meeting is closed
+            if self.meeting.is_read_only() {
+                // the meeting ended elsewhere
+                if !self.meeting.follow()? {
+                    break;
+                }
+            } else if !matches!(self.mode, Mode::Summary | Mode::Checklist { .. }) {
+                // commands sent through the control API
+                while let Some(command) = self.server.as_mut().and_then(Server::next_command) {
+                    self.dispatch(AppCommand::Meeting(command));
+                }
+                // and through desktop notifications' buttons
+                while let Some(command) = self.notifier.as_mut().and_then(Notifier::next_command) {
+                    self.dispatch(AppCommand::Meeting(command));
+                }
+                while let Some(command) = self.demo.as_mut().and_then(Script::due) {
+                    self.dispatch(command);
+                }
 
-            // Render UI
-            let ui = UI::new(self);
-            terminal.draw(|f| ui.render(f))?;
+                self.meeting.session.tick();
+                // a kiosk or autopilot has nobody to ask about time it spent asleep
+                if self.config.kiosk || self.config.autopilot {
+                    self.meeting.session.resolve_gap(GapChoice::Count);
+                }
+                self.meeting.mark_current_asked();
+                self.meeting.autosave();
+                if let Some(notifier) = &mut self.notifier {
+                    notifier.update(&self.meeting.session);
+                }
+                if let Some(escalation) = &mut self.escalation {
+                    escalation.update(&self.meeting.session);
+                }
+                if let Some(burndown) = &mut self.burndown {
+                    burndown.update(&self.meeting.session);
+                }
+                if let Some(per_person) = &mut self.per_person {
+                    per_person.update(&self.meeting.session);
+                }
+                if let Some(handoffs) = &mut self.handoffs {
+                    handoffs.update(&self.meeting.session);
+                }
+                if let Some(tracer) = &mut self.tracer {
+                    tracer.update(&self.meeting);
+                }
+                if let Some(transcript) = &mut self.transcript {
+                    transcript.update(&self.meeting);
+                }
 
-            // Handle input with timeout to allow for regular updates
-            if event::poll(Duration::from_millis(500))?
-                && let Event::Key(key) = event::read()? {
-                    self.handle_input(key)?;
+                // saving and exporting happen on the way out
+                if self.meeting.session.time_up() == TimeUp::Exit
+                    && self.meeting.session.is_time_up()
+                {
+                    self.mode = Mode::Quitting;
                 }
+            }
+            self.follow_meeting();
 
-            if self.should_quit {
+            if self.mode == Mode::Quitting || self.meeting.is_taken_over() {
+                break;
+            }
+
+            if let Some(server) = &self.server {
+                server.publish(&self.config, &self.meeting, self.timer_theme());
+            }
+
+            // Render UI, then the avatars in the room it left for them
+            let resized = terminal.size()? != last_size;
+            let (placements, repainted) = {
+                let ui = UI::new(self);
+                terminal.draw(|f| ui.render(f))?;
+                let repainted = self.avatars.needs_repaint(&ui.placements());
+                if repainted {
+                    terminal.clear()?;
+                    terminal.draw(|f| ui.render(f))?;
+                }
+                (ui.placements(), repainted || resized)
+            };
+            self.avatars
+                .draw(terminal.backend_mut(), placements, repainted)?;
+            last_size = terminal.size()?;
+
+            // Handle input with timeout to allow for regular updates
+            if event::poll(self.tick_rate())?
+                && let Event::Key(key) = event::read()?
+            {
+                self.handle_input(key)?;
+            }
+
+            if self.mode == Mode::Quitting {
                 break;
             }
         }
@@ -265,21 +557,305 @@ impl App {
         Ok(())
     }
 
+    /// How long to wait for input before redrawing
+    fn tick_rate(&self) -> Duration {
+        if self.meeting.is_celebrating_now() {
+            // redraw faster so the confetti animates smoothly
+            Duration::from_millis(150)
+        } else if self.cutoff_in().is_some() || self.slot_nearly_up() || self.is_timer_blinking() {
+            // fast enough for the flashing to be noticed
+            Duration::from_millis(250)
+        } else {
+            Duration::from_millis(500)
+        }
+    }
+
     // Getter methods for UI access
     pub fn config(&self) -> &Config {
         &self.config
     }
 
-    pub fn names(&self) -> &[String] {
-        &self.names
+    pub fn speakers(&self) -> &[Speaker] {
+        self.meeting.session.speakers()
     }
 
-    pub fn per_person_timers(&self) -> &[Duration] {
-        &self.per_person_timers
+    pub fn per_person_timers(&self) -> Vec<Duration> {
+        self.meeting.session.per_person_timers()
     }
 
     pub fn current_person_index(&self) -> usize {
-        self.current_person_index
+        self.meeting.session.current_person_index()
+    }
+
+    pub fn current_question(&self) -> Option<&str> {
+        self.meeting.current_question()
+    }
+
+    pub fn topics_for<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Topic> {
+        self.meeting.topics_for(name)
+    }
+
+    pub fn unfinished_topics(&self) -> impl Iterator<Item = &Topic> {
+        self.meeting.unfinished_topics()
+    }
+
+    pub fn celebrations(&self) -> &[(String, Celebration)] {
+        self.meeting.celebrations()
+    }
+
+    pub fn is_celebrating_now(&self) -> bool {
+        self.meeting.is_celebrating_now()
+    }
+
+    pub fn round_label(&self) -> Option<String> {
+        self.meeting.session.round_label()
+    }
+
+    pub fn facilitator(&self) -> Option<&str> {
+        self.meeting.facilitator()
+    }
+
+    /// e.g. "Day 6/10" of the sprint, when sprints are set up
+    pub fn sprint_day(&self) -> Option<String> {
+        self.meeting.sprint().map(|sprint| {
+            let (day, days) = sprint.day(Local::now().date_naive());
+            format!("Day {}/{}", day, days)
+        })
+    }
+
+    /// The burndown chart's lines, when it's shown
+    pub fn burndown(&self) -> Option<Lines> {
+        self.burndown
+            .as_ref()
+            .map(|burndown| burndown.lines(&self.meeting.session))
+    }
+
+    /// The timer's theme: the configured one, or one an escalation rule switched to
+    pub fn timer_theme(&self) -> &TimerTheme {
+        self.escalation
+            .as_ref()
+            .and_then(Escalation::theme)
+            .unwrap_or(&self.config.timer_theme)
+    }
+
+    /// Whether an escalation rule has the timer's border blinking
+    pub fn is_timer_blinking(&self) -> bool {
+        self.escalation
+            .as_ref()
+            .is_some_and(Escalation::is_blinking)
+    }
+
+    /// The current speaker against their slot, with per-person limits
+    pub fn slot_status(&self) -> Option<SlotStatus> {
+        self.per_person
+            .as_ref()
+            .map(|per_person| per_person.status(&self.meeting.session))
+    }
+
+    /// With per-person limits, whether the current speaker's slot is nearly up
+    pub fn slot_nearly_up(&self) -> bool {
+        self.per_person.is_some() && per_person::nearly_up(&self.meeting.session)
+    }
+
+    /// The sprint the meeting falls in, for the closing screen
+    pub fn sprint(&self) -> Option<Sprint> {
+        self.meeting.sprint()
+    }
+
+    /// Speaking slot per round for the member at `index`
+    pub fn slot(&self, index: usize) -> Duration {
+        self.meeting.session.slot(index)
+    }
+
+    pub fn budget(&self, index: usize) -> Duration {
+        self.meeting.session.budget(index)
+    }
+
+    /// Whether slots come from the team file or --slot rather than an even split
+    pub fn has_weighted_slots(&self) -> bool {
+        self.config.slot.is_some() || self.speakers().iter().any(|s| s.member.slot.is_some())
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.meeting.session.phase()
+    }
+
+    pub fn warm_up_left(&self) -> Option<Duration> {
+        self.meeting.session.warm_up_left()
+    }
+
+    /// Time left before hard mode cuts the current speaker off, once their slot is up
+    pub fn cutoff_in(&self) -> Option<Duration> {
+        self.meeting.session.cutoff_in()
+    }
+
+    pub fn next_event(&self) -> Option<&calendar::Event> {
+        self.meeting.next_event()
+    }
+
+    pub fn runs_into_next_event(&self) -> bool {
+        self.meeting.runs_into_next_event()
+    }
+
+    pub fn demo_tip(&self) -> Option<&str> {
+        self.demo.as_ref().and_then(Script::tip)
+    }
+
+    pub fn projected_overrun(&self) -> Option<Duration> {
+        self.meeting.session.projected_overrun()
+    }
+
+    /// The meeting's score once it has ended
+    pub fn health(&self) -> Option<&Health> {
+        self.meeting.health()
+    }
+
+    pub fn trends(&self) -> &[String] {
+        self.meeting.trends()
+    }
+
+    pub fn adjustments(&self) -> &[Adjustment] {
+        self.meeting.session.adjustments()
+    }
+
+    /// The web view's URL and QR code, when serving
+    pub fn server(&self) -> Option<&Server> {
+        self.server.as_ref()
+    }
+
+    pub fn has_teams(&self) -> bool {
+        self.meeting.session.has_teams()
+    }
+
+    /// Whether the order is drawn as a wheel
+    pub fn is_wheel(&self) -> bool {
+        self.wheel
+    }
+
+    /// Whether a team's members are hidden; the current speaker's team is always shown
+    pub fn is_collapsed(&self, team: &str) -> bool {
+        let current = &self.speakers()[self.current_person_index()].member.team;
+        self.collapsed_teams.contains(team) && current.as_deref() != Some(team)
+    }
+
+    pub fn team_size(&self, team: &str) -> usize {
+        self.meeting.team_size(team)
+    }
+
+    /// Time the meeting has run over, while that keeps being counted
+    pub fn overtime(&self) -> Option<Duration> {
+        let session = &self.meeting.session;
+        let counting = matches!(session.time_up(), TimeUp::Overtime | TimeUp::FastForward);
+        (counting && session.is_time_up()).then(|| session.overtime())
+    }
+
+    /// Whether time is up and only quitting works anymore
+    pub fn is_locked(&self) -> bool {
+        let session = &self.meeting.session;
+        session.time_up() == TimeUp::Lock && session.is_time_up()
+    }
+
+    /// A newer release to point out on the closing screen
+    pub fn newer_version(&self) -> Option<&str> {
+        #[cfg(feature = "self-update")]
+        return self.update_check.newer_version();
+        #[cfg(not(feature = "self-update"))]
+        None
+    }
+
+    pub fn mode(&self) -> &Mode {
+        &self.mode
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.meeting.session.is_paused()
+    }
+
+    pub fn avatars(&self) -> &Avatars {
+        &self.avatars
+    }
+
+    /// Why the meeting is paused, if someone said
+    pub fn pause_reason(&self) -> Option<&str> {
+        self.meeting.session.pause_reason()
+    }
+
+    pub fn pauses(&self) -> &[Pause] {
+        self.meeting.session.pauses()
+    }
+
+    pub fn is_absent(&self, index: usize) -> bool {
+        self.meeting.session.is_absent(index)
+    }
+
+    pub fn last_marked_absent(&self) -> Option<&str> {
+        self.meeting.last_marked_absent()
+    }
+
+    /// How many standups it's been since someone last spoke live, once that's `--silent-after`
+    /// or more
+    pub fn silent_for(&self, name: &str) -> Option<usize> {
+        let after = self.config.silent_after;
+        self.meeting
+            .standups_since_spoke(name)
+            .filter(|standups| after > 0 && *standups >= after)
+    }
+
+    /// How much of their weekly airtime someone in the order has used, with --weekly-budget, once
+    /// it's 80% or more
+    pub fn airtime_used(&self, index: usize) -> Option<u32> {
+        self.meeting.airtime_used(index)
+    }
+
+    /// How someone checked in today, if they won't be speaking live
+    pub fn attendance(&self, name: &str) -> Option<Attendance> {
+        self.meeting.check_in(name).map(|c| c.attendance)
+    }
+
+    pub fn async_updates(&self) -> impl Iterator<Item = &CheckIn> {
+        self.meeting.async_updates()
+    }
+
+    pub fn is_hybrid(&self) -> bool {
+        self.meeting.is_hybrid()
+    }
+
+    /// Whether someone sent a written update today
+    pub fn has_update(&self, name: &str) -> bool {
+        self.meeting
+            .check_in(name)
+            .is_some_and(|c| c.update.is_some())
+    }
+
+    pub fn current_update(&self) -> Option<&str> {
+        self.meeting.current_update()
+    }
+
+    /// Who the speaker who just finished hands over to, for a few seconds after the handover
+    pub fn handoff(&self) -> Option<&str> {
+        self.handoffs.as_ref().and_then(Handoffs::current)
+    }
+
+    /// Confirmation of the recording marker just dropped
+    pub fn recent_marker(&self) -> Option<String> {
+        self.marked_at
+            .filter(|at| at.elapsed() < MARKER_NOTICE)
+            .and(self.meeting.last_chapter())
+            .map(|chapter| {
+                format!(
+                    "{} started at {} of the recording",
+                    chapter.name,
+                    format_timestamp(chapter.offset)
+                )
+            })
+    }
+
+    pub fn is_order_locked(&self) -> bool {
+        self.meeting.is_order_locked()
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.meeting.is_read_only()
     }
 
     pub fn is_dark_background(&self) -> bool {
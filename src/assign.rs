@@ -0,0 +1,170 @@
+use chrono::Local;
+use rand::seq::SliceRandom;
+use std::fs;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::export;
+use crate::fair::Ledger;
+use crate::history::History;
+use crate::sources;
+use crate::sync;
+
+// Past assignments older than this don't count towards anyone's load
+pub const LOAD_WINDOW_DAYS: i64 = 90;
+
+/// An interview slot or review to staff, and how many people it needs
+#[derive(Debug, Clone, PartialEq)]
+pub struct Slot {
+    pub label: String,
+    pub panel: usize,
+}
+
+/// Options for `nextup assign`
+pub struct AssignOptions<'a> {
+    pub slots_file: &'a str,
+    // people per slot, unless the slot gives its own `panel=`
+    pub panel: usize,
+    pub export: Option<&'a Path>,
+    // print the table without remembering it
+    pub dry_run: bool,
+}
+
+/// Read slots, one per line, optionally followed by `| panel=N`; blank lines and `#` comments are ignored
+fn load_slots(filename: &str, default_panel: usize) -> Result<Vec<Slot>> {
+    let content = fs::read_to_string(filename)
+        .map_err(|e| AppError::AssignmentError(format!("{}: {}", filename, e)))?;
+
+    let mut slots = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut fields = line.split('|').map(str::trim);
+        let label = fields.next().unwrap_or_default().to_string();
+        let mut panel = default_panel;
+        for field in fields.filter(|f| !f.is_empty()) {
+            panel = field
+                .strip_prefix("panel=")
+                .and_then(|n| n.trim().parse().ok())
+                .filter(|n| *n > 0)
+                .ok_or_else(|| {
+                    AppError::AssignmentError(format!(
+                        "line {}: expected `panel=N`, got `{}`",
+                        i + 1,
+                        field
+                    ))
+                })?;
+        }
+        slots.push(Slot { label, panel });
+    }
+
+    if slots.is_empty() {
+        return Err(AppError::AssignmentError(format!("no slots in {}", filename)).into());
+    }
+    Ok(slots)
+}
+
+/// Staff each slot with whoever has had the fewest assignments lately, and print the table
+pub fn run(config: &Config, options: &AssignOptions) -> Result<()> {
+    let slots = load_slots(options.slots_file, options.panel.max(1))?;
+    let members = sources::load_members(config)?;
+    let mut names: Vec<String> = members.iter().map(|m| m.name.clone()).collect();
+    // shuffled so equally loaded people are picked at random
+    names.shuffle(&mut rand::rng());
+
+    if let Some(slot) = slots.iter().find(|slot| slot.panel > names.len()) {
+        return Err(AppError::AssignmentError(format!(
+            "`{}` needs {} people but the team has {}",
+            slot.label,
+            slot.panel,
+            names.len()
+        ))
+        .into());
+    }
+
+    let mut history = History::load(&config.series)?;
+    history.identify(&members);
+    let today = Local::now().date_naive();
+    let since = today - chrono::Duration::days(LOAD_WINDOW_DAYS);
+
+    let mut ledger = Ledger::new(names.iter().cloned());
+    for assignment in history.assignments().iter().filter(|a| a.date >= since) {
+        for name in &assignment.names {
+            ledger.record(name);
+        }
+    }
+    let past: Vec<usize> = names.iter().map(|name| ledger.turns(name)).collect();
+
+    let mut table = Vec::new();
+    for slot in &slots {
+        let panel: Vec<String> = ledger
+            .pick(slot.panel)
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        for name in &panel {
+            ledger.record(name);
+        }
+        table.push((slot.label.clone(), panel));
+    }
+
+    print_table(&table);
+
+    // the load each person carries now, heaviest first
+    let mut load: Vec<(&String, usize, usize)> = names
+        .iter()
+        .zip(&past)
+        .map(|(name, past)| (name, *past, ledger.turns(name) - past))
+        .collect();
+    load.sort_by(|a, b| (b.1 + b.2).cmp(&(a.1 + a.2)).then(a.0.cmp(b.0)));
+    println!("\nLoad over the last {} days:", LOAD_WINDOW_DAYS);
+    for (name, past, new) in load {
+        println!("  {}: {} ({} new)", name, past + new, new);
+    }
+
+    if let Some(path) = options.export {
+        let rows: Vec<Vec<String>> = table
+            .iter()
+            .map(|(label, panel)| vec![label.clone(), panel.join("; ")])
+            .collect();
+        export::write_csv(path, Some(&["slot", "panel"]), &rows)?;
+        println!("\nSaved to {}", path.display());
+    }
+
+    if options.dry_run {
+        return Ok(());
+    }
+
+    for (label, panel) in table {
+        history.record_assignment(today, label, panel);
+    }
+    history.save()?;
+
+    // share the updated history when the state directory is a git checkout
+    if let Some(root) = config.series.root()
+        && let Err(e) = sync::push(root, &format!("nextup: {} assignments", config.series.id()))
+    {
+        eprintln!("{}", e);
+    }
+
+    Ok(())
+}
+
+/// Slots and their panels, in aligned columns
+fn print_table(table: &[(String, Vec<String>)]) {
+    let width = table
+        .iter()
+        .map(|(label, _)| label.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Slot".len());
+
+    println!("{:<width$}  Panel", "Slot", width = width);
+    for (label, panel) in table {
+        println!("{:<width$}  {}", label, panel.join(", "), width = width);
+    }
+}
@@ -0,0 +1,155 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use crate::checkin::{Attendance, CheckIns};
+use crate::config::Config;
+use crate::error::Result;
+use crate::history::History;
+use crate::privacy;
+use crate::roster::Member;
+use crate::stats::{DateRange, StatsFormat};
+
+/// How often one person made it to the meetings they were expected at
+#[derive(Debug, Serialize)]
+pub struct MemberAttendance {
+    pub name: String,
+    pub meetings: usize,
+    pub attended: usize,
+    pub percent: f64,
+    // latest meetings missed in a row
+    pub absent_streak: usize,
+    // meetings since they last spoke live, rather than with a written update
+    pub since_spoke: usize,
+}
+
+/// Everything `--format json` prints
+#[derive(Serialize)]
+struct Report<'a> {
+    series: &'a str,
+    members: Vec<MemberAttendance>,
+}
+
+/// Print attendance per member for the configured series, for `nextup attendance`
+pub fn run(config: &Config, format: StatsFormat, range: DateRange) -> Result<()> {
+    let mut history = History::load(&config.series)?;
+    privacy::identify(config, &mut history);
+    let members = attendance(&history, range);
+
+    if format == StatsFormat::Json {
+        let report = Report {
+            series: config.series.id(),
+            members,
+        };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    if members.is_empty() {
+        println!("No attendance recorded yet for {}.", config.series.id());
+        return Ok(());
+    }
+
+    if range.is_bounded() {
+        println!("Attendance for {}, {}\n", config.series.id(), range.label());
+    } else {
+        println!("Attendance for {}\n", config.series.id());
+    }
+
+    let width = members
+        .iter()
+        .map(|m| m.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    for member in &members {
+        let streak = if member.absent_streak > 1 {
+            format!("  missed the last {}", member.absent_streak)
+        } else if member.since_spoke > 1 {
+            // there, but only in writing
+            format!("  last spoke {} standups ago", member.since_spoke)
+        } else {
+            String::new()
+        };
+        println!(
+            "{:<width$}  {:>3.0}%  {}/{}{}",
+            member.name,
+            member.percent,
+            member.attended,
+            member.meetings,
+            streak,
+            width = width
+        );
+    }
+    Ok(())
+}
+
+/// Attendance for everyone the meetings in range know about, by name
+pub fn attendance(history: &History, range: DateRange) -> Vec<MemberAttendance> {
+    let meetings: Vec<_> = history
+        .meetings()
+        .iter()
+        .filter(|m| range.includes(m))
+        .collect();
+
+    let mut counts: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+    for meeting in &meetings {
+        for turn in &meeting.turns {
+            let (expected, attended) = counts.entry(&turn.name).or_default();
+            *expected += 1;
+            *attended += 1;
+        }
+        for name in &meeting.absentees {
+            counts.entry(name).or_default().0 += 1;
+        }
+    }
+
+    counts
+        .into_iter()
+        .map(|(name, (meetings, attended))| MemberAttendance {
+            name: name.to_string(),
+            meetings,
+            attended,
+            percent: attended as f64 * 100.0 / meetings.max(1) as f64,
+            absent_streak: history.absence_streak(name),
+            since_spoke: history.standups_since_spoke(name).unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Offer to check in as absent anyone who's missed the last `config.absence_streak` meetings in a
+/// row, before the meeting starts. Only asked at a terminal, and not about anyone already checked in.
+pub fn suggest_absences(config: &Config, members: &[Member]) -> Result<()> {
+    if config.absence_streak == 0 || !io::stdin().is_terminal() {
+        return Ok(());
+    }
+
+    let mut history = History::load(&config.series)?;
+    history.identify(members);
+    let mut check_ins = CheckIns::load(&config.series)?;
+    let mut changed = false;
+
+    for member in members {
+        let streak = history.absence_streak(&member.name);
+        if streak < config.absence_streak || check_ins.get(&member.name).is_some() {
+            continue;
+        }
+
+        eprint!(
+            "{} has missed the last {} meetings. Mark them absent today? [y/N] ",
+            member.name, streak
+        );
+        io::stderr().flush()?;
+        let mut answer = String::new();
+        io::stdin().lock().read_line(&mut answer)?;
+
+        if matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+            check_ins.set(&member.name, Some(Attendance::Absent), None);
+            changed = true;
+        }
+    }
+
+    if changed {
+        check_ins.save()?;
+    }
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use chrono::Local;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::error::Result;
+use crate::series::Series;
+use crate::session::Adjustment;
+
+/// Location of a series' audit trail
+pub fn path(series: &Series) -> Option<PathBuf> {
+    series.dir().map(|dir| dir.join("audit.log"))
+}
+
+/// Append a manual time adjustment to the series' audit trail
+pub fn record(series: &Series, adjustment: &Adjustment) -> Result<()> {
+    let Some(path) = path(series) else {
+        return Ok(());
+    };
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(
+        file,
+        "{}\t{}",
+        Local::now().format("%Y-%m-%dT%H:%M:%S%:z"),
+        adjustment
+    )?;
+    Ok(())
+}
+
+/// Drop someone's adjustments from the series' audit trail; how many went
+pub fn forget(series: &Series, name: &str) -> Result<usize> {
+    let Some(path) = path(series) else {
+        return Ok(0);
+    };
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Ok(0);
+    };
+
+    // each line is `<time>\t<speaker> <+/-secs>s`
+    let about = |line: &str| {
+        line.split_once('\t')
+            .and_then(|(_, adjustment)| adjustment.rsplit_once(' '))
+            .is_some_and(|(speaker, _)| speaker == name)
+    };
+    let kept: Vec<&str> = content.lines().filter(|line| !about(line)).collect();
+    let forgotten = content.lines().count() - kept.len();
+    if forgotten > 0 {
+        let mut content = kept.join("\n");
+        if !content.is_empty() {
+            content.push('\n');
+        }
+        fs::write(path, content)?;
+    }
+    Ok(forgotten)
+}
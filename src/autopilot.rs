@@ -0,0 +1,73 @@
+use chrono::Local;
+use std::fs;
+use std::io::{self, IsTerminal};
+
+use crate::app::App;
+#[cfg(feature = "slack")]
+use crate::bot::Bot;
+use crate::config::Config;
+use crate::daemon;
+use crate::error::Result;
+use crate::events;
+use crate::session::TimeUp;
+
+/// Run one meeting with nobody facilitating, for `--autopilot`: wait for the `--window` start
+/// (right away without one), run it, and exit once it has closed itself
+pub async fn run(mut config: Config) -> Result<()> {
+    let interactive = io::stdin().is_terminal() && io::stdout().is_terminal();
+    if let Some(window) = config.window
+        && !daemon::wait_for_meeting(&mut config, window, interactive, false).await?
+    {
+        return Ok(());
+    }
+    run_meeting(config, interactive).await
+}
+
+/// Run a meeting that moves itself along: turns move on when a slot runs out, the meeting
+/// closes when time is up, and the summary is written on the way out. It's posted to the
+/// `--autopilot` Slack channel when one is given, and otherwise shown on the terminal, or
+/// streamed as JSON events without one.
+pub async fn run_meeting(config: Config, interactive: bool) -> Result<()> {
+    let config = configure(config)?;
+
+    match config.autopilot_channel.clone() {
+        #[cfg(feature = "slack")]
+        Some(channel) => {
+            let token = config.slack_bot_token.clone().ok_or_else(|| {
+                anyhow::Error::msg(
+                    "--autopilot needs a bot token to post to Slack (--slack-bot-token or SLACK_BOT_TOKEN)",
+                )
+            })?;
+            Bot::new(config, token, channel)?.run().await
+        }
+        #[cfg(not(feature = "slack"))]
+        Some(_) => Err(anyhow::Error::msg(
+            "posting the meeting to a Slack channel needs the `slack` feature",
+        )),
+        None if interactive => App::new(config).await?.run().await,
+        None => events::run(config).await,
+    }
+}
+
+/// The settings nobody is there to act on taken care of. The summary goes to `--report-path`,
+/// or to the series' `reports` directory by date.
+fn configure(config: Config) -> Result<Config> {
+    let report_path = match config.report_path.clone() {
+        Some(path) => Some(path),
+        None => match config.series.dir() {
+            Some(dir) => {
+                let dir = dir.join("reports");
+                fs::create_dir_all(&dir)?;
+                Some(dir.join(format!("{}.md", Local::now().format("%Y-%m-%d"))))
+            }
+            None => None,
+        },
+    };
+
+    Ok(Config {
+        auto_advance: true,
+        time_up: TimeUp::Exit,
+        report_path,
+        ..config
+    })
+}
@@ -0,0 +1,72 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::error::{AppError, Result};
+use crate::series::Series;
+use crate::session::SessionSnapshot;
+
+/// Snapshot of a running meeting, refreshed while it runs and removed when it ends
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Autosave {
+    pub pid: u32,
+    pub started_at: DateTime<Local>,
+    pub updated_at: DateTime<Local>,
+    pub session: SessionSnapshot,
+}
+
+impl Autosave {
+    /// Location of a series' autosave
+    pub fn path(series: &Series) -> Option<PathBuf> {
+        series.dir().map(|dir| dir.join("autosave.json"))
+    }
+
+    /// Load the series' autosave, if a meeting left one behind
+    pub fn load(series: &Series) -> Result<Option<Self>> {
+        let Some(path) = Self::path(series) else {
+            return Ok(None);
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map(Some)
+                .map_err(|e| AppError::HistoryError(format!("{}: {}", path.display(), e)).into()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(AppError::HistoryError(format!("{}: {}", path.display(), e)).into()),
+        }
+    }
+
+    /// Write the autosave beside the old one and swap it in, so an instance reading it never
+    /// sees it half-written
+    pub fn save(&self, series: &Series) -> Result<()> {
+        let Some(path) = Self::path(series) else {
+            return Ok(());
+        };
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let staged = path.with_extension("json.tmp");
+        fs::write(&staged, serde_json::to_string_pretty(self)?)?;
+        fs::rename(staged, path)?;
+        Ok(())
+    }
+
+    /// Remove the autosave once the meeting is over
+    pub fn clear(series: &Series) -> Result<()> {
+        match Self::path(series).map(fs::remove_file) {
+            Some(Err(e)) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Who was speaking when the autosave was written
+    pub fn current_speaker(&self) -> Option<&str> {
+        self.session
+            .order
+            .get(self.session.current_person_index)
+            .map(String::as_str)
+    }
+}
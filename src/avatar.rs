@@ -0,0 +1,496 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Span;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::roster::Member;
+
+#[cfg(feature = "avatars")]
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+#[cfg(feature = "avatars")]
+use image::{DynamicImage, RgbaImage, imageops::FilterType};
+#[cfg(feature = "avatars")]
+use std::collections::HashMap;
+#[cfg(feature = "avatars")]
+use std::fmt::Write as _;
+#[cfg(feature = "avatars")]
+use std::fs;
+#[cfg(feature = "avatars")]
+use std::io::{Cursor, Read};
+#[cfg(feature = "avatars")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "avatars")]
+use std::time::{Duration, SystemTime};
+
+// Cells an avatar takes next to a name in the order, and for the speaker on the kiosk display
+pub const LIST_AVATAR: (u16, u16) = (2, 1);
+pub const KIOSK_AVATAR: (u16, u16) = (12, 6);
+
+// Badge backgrounds, picked per person by their name; all light enough for black initials
+const BADGE_COLORS: [(u8, u8, u8); 12] = [
+    (239, 154, 154),
+    (244, 143, 177),
+    (206, 147, 216),
+    (179, 157, 219),
+    (159, 168, 218),
+    (144, 202, 249),
+    (128, 222, 234),
+    (128, 203, 196),
+    (165, 214, 167),
+    (197, 225, 165),
+    (255, 224, 130),
+    (255, 171, 145),
+];
+
+// Cell size in pixels when the terminal doesn't say
+#[cfg(feature = "avatars")]
+const DEFAULT_CELL_PIXELS: (u32, u32) = (10, 20);
+
+// How long a Gravatar lookup may take, and how long until someone without one is looked up again
+#[cfg(feature = "avatars")]
+const GRAVATAR_TIMEOUT: Duration = Duration::from_secs(3);
+#[cfg(feature = "avatars")]
+const GRAVATAR_RETRY: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+// Base64 is sent to kitty in pieces no bigger than this
+#[cfg(feature = "avatars")]
+const KITTY_CHUNK: usize = 4096;
+
+/// How the terminal is told to draw an image
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageProtocol {
+    // kitty's graphics protocol, also in Ghostty and WezTerm
+    Kitty,
+    // iTerm2's inline images
+    Iterm2,
+    // DEC sixel graphics, e.g. in foot, mlterm and xterm -ti vt340
+    Sixel,
+}
+
+impl FromStr for ImageProtocol {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "kitty" => Ok(Self::Kitty),
+            "iterm2" | "iterm" => Ok(Self::Iterm2),
+            "sixel" => Ok(Self::Sixel),
+            _ => Err(format!(
+                "unknown image protocol `{}`, expected kitty, iterm2 or sixel",
+                s
+            )),
+        }
+    }
+}
+
+impl ImageProtocol {
+    /// Guess what the terminal supports from the environment it sets. Inside tmux images would
+    /// need passing through, so nothing is drawn there.
+    #[cfg_attr(not(feature = "avatars"), allow(dead_code))]
+    pub fn detect() -> Option<Self> {
+        let var = |name: &str| std::env::var(name).unwrap_or_default();
+        let term = var("TERM");
+        let program = var("TERM_PROGRAM");
+
+        if std::env::var_os("TMUX").is_some() {
+            None
+        } else if std::env::var_os("KITTY_WINDOW_ID").is_some()
+            || term == "xterm-kitty"
+            || term == "xterm-ghostty"
+            || program == "ghostty"
+        {
+            Some(Self::Kitty)
+        } else if program == "iTerm.app" || program == "WezTerm" {
+            Some(Self::Iterm2)
+        } else if term.starts_with("foot") || term.starts_with("mlterm") || term.contains("sixel") {
+            Some(Self::Sixel)
+        } else {
+            None
+        }
+    }
+}
+
+/// Where an avatar is drawn on screen
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placement {
+    pub name: String,
+    pub area: Rect,
+}
+
+/// The team's avatar images, from `avatar=` in the team file or Gravatar, and where they're
+/// drawn right now. Without a protocol to draw them with, people get a badge with their initials.
+#[derive(Default)]
+pub struct Avatars {
+    enabled: bool,
+    protocol: Option<ImageProtocol>,
+    #[cfg(feature = "avatars")]
+    images: HashMap<String, DynamicImage>,
+    // escape sequences ready to write, by name and size, so each is only encoded once
+    #[cfg(feature = "avatars")]
+    encoded: HashMap<(String, u16, u16), String>,
+    shown: Vec<Placement>,
+}
+
+impl Avatars {
+    /// Load everyone's avatar for `--avatars`. An `avatar=` file that can't be read stops nextup;
+    /// Gravatar not answering only leaves the initials.
+    pub fn load(config: &Config, members: &[Member]) -> Result<Self> {
+        if !config.avatars {
+            return Ok(Self::default());
+        }
+
+        // without images, everyone gets a badge
+        #[cfg(not(feature = "avatars"))]
+        {
+            let _ = members;
+            if config.image_protocol.is_some() {
+                return Err(AppError::NotBuiltIn {
+                    what: "avatar pictures",
+                    feature: "avatars",
+                }
+                .into());
+            }
+            Ok(Self {
+                enabled: true,
+                ..Self::default()
+            })
+        }
+
+        #[cfg(feature = "avatars")]
+        {
+            let protocol = config.image_protocol.or_else(ImageProtocol::detect);
+            let mut images = HashMap::new();
+            if protocol.is_some() {
+                // avatar paths are relative to the team file
+                let base = Path::new(&config.names_file)
+                    .parent()
+                    .map(Path::to_path_buf)
+                    .unwrap_or_default();
+                for member in members {
+                    if let Some(image) = image_for(member, &base)? {
+                        images.insert(member.name.clone(), image);
+                    }
+                }
+            }
+
+            Ok(Self {
+                enabled: true,
+                protocol,
+                images,
+                encoded: HashMap::new(),
+                shown: Vec::new(),
+            })
+        }
+    }
+
+    /// Whether names get an avatar, or a badge in its place
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Whether someone's avatar is drawn as an image
+    pub fn has_image(&self, name: &str) -> bool {
+        #[cfg(feature = "avatars")]
+        return self.protocol.is_some() && self.images.contains_key(name);
+        #[cfg(not(feature = "avatars"))]
+        {
+            let _ = name;
+            false
+        }
+    }
+
+    /// Whether the screen has to be redrawn before drawing `placements`: kitty takes its images
+    /// away by itself, but the other protocols leave them until the cells are written over
+    pub fn needs_repaint(&self, placements: &[Placement]) -> bool {
+        self.protocol.is_some_and(|p| p != ImageProtocol::Kitty)
+            && !self.shown.is_empty()
+            && self.shown != placements
+    }
+
+    /// Draw the avatars where the UI left room for them. Nothing is sent while they stay put,
+    /// unless the screen was redrawn from scratch.
+    pub fn draw(
+        &mut self,
+        out: &mut impl Write,
+        placements: Vec<Placement>,
+        repainted: bool,
+    ) -> io::Result<()> {
+        if self.protocol.is_none() || (placements == self.shown && !repainted) {
+            return Ok(());
+        }
+
+        self.clear(out)?;
+        for placement in &placements {
+            if let Some(sequence) = self.sequence(placement) {
+                let Rect { x, y, .. } = placement.area;
+                // drawn at the placement, leaving the cursor where it was
+                write!(out, "\x1b7\x1b[{};{}H{}\x1b8", y + 1, x + 1, sequence)?;
+            }
+        }
+        out.flush()?;
+        self.shown = placements;
+        Ok(())
+    }
+
+    /// Take kitty's images off the screen, e.g. before leaving
+    pub fn clear(&mut self, out: &mut impl Write) -> io::Result<()> {
+        if self.protocol == Some(ImageProtocol::Kitty) {
+            write!(out, "\x1b_Ga=d,d=A,q=2\x1b\\")?;
+            out.flush()?;
+        }
+        self.shown.clear();
+        Ok(())
+    }
+
+    /// The escape sequence drawing someone's avatar at a placement's size
+    #[cfg(feature = "avatars")]
+    fn sequence(&mut self, placement: &Placement) -> Option<&str> {
+        let (cols, rows) = (placement.area.width, placement.area.height);
+        let key = (placement.name.clone(), cols, rows);
+        if !self.encoded.contains_key(&key) {
+            let image = self.images.get(&placement.name)?;
+            let (cell_width, cell_height) = cell_pixels();
+            let fitted = image
+                .resize_to_fill(
+                    u32::from(cols) * cell_width,
+                    u32::from(rows) * cell_height,
+                    FilterType::Triangle,
+                )
+                .to_rgba8();
+            let sequence = match self.protocol? {
+                ImageProtocol::Kitty => kitty(&png(&fitted)?, cols, rows),
+                ImageProtocol::Iterm2 => iterm2(&png(&fitted)?, cols, rows),
+                ImageProtocol::Sixel => sixel(&fitted),
+            };
+            self.encoded.insert(key.clone(), sequence);
+        }
+        self.encoded.get(&key).map(String::as_str)
+    }
+
+    #[cfg(not(feature = "avatars"))]
+    fn sequence(&mut self, _placement: &Placement) -> Option<&str> {
+        None
+    }
+}
+
+/// Someone's initials, shown where their avatar would be: "KB" for Kyle Broflovski, "KE" for Kenny
+pub fn initials(name: &str) -> String {
+    let words: Vec<&str> = name.split_whitespace().collect();
+    let initials: String = match words.as_slice() {
+        [] => String::new(),
+        [word] => word.chars().take(2).collect(),
+        [first, .., last] => first.chars().take(1).chain(last.chars().take(1)).collect(),
+    };
+    initials.to_uppercase()
+}
+
+/// Someone's initials on a background of their own, in place of a picture. The color comes
+/// from a hash of the name, so it's the same in every view and every meeting.
+pub fn badge(name: &str) -> Span<'static> {
+    // FNV-1a, which unlike std's hasher is the same in every build
+    let hash = name.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    });
+    let (r, g, b) = BADGE_COLORS[(hash % BADGE_COLORS.len() as u64) as usize];
+    Span::styled(
+        format!("{:<width$}", initials(name), width = LIST_AVATAR.0 as usize),
+        Style::default()
+            .fg(Color::Black)
+            .bg(Color::Rgb(r, g, b))
+            .add_modifier(Modifier::BOLD),
+    )
+}
+
+/// Someone's avatar: their `avatar=` file, or their Gravatar when they have an email address
+#[cfg(feature = "avatars")]
+fn image_for(member: &Member, base: &Path) -> Result<Option<DynamicImage>> {
+    if let Some(file) = &member.avatar {
+        let path = base.join(file);
+        let image = image::open(&path)
+            .map_err(|e| AppError::AvatarError(format!("{}: {}", path.display(), e)))?;
+        return Ok(Some(image));
+    }
+    Ok(member
+        .email
+        .as_deref()
+        .and_then(gravatar)
+        .and_then(|bytes| image::load_from_memory(&bytes).ok()))
+}
+
+/// Someone's Gravatar, kept in the cache directory; None when they don't have one or it
+/// couldn't be fetched
+#[cfg(feature = "avatars")]
+fn gravatar(email: &str) -> Option<Vec<u8>> {
+    let hash = format!("{:x}", md5::compute(email.trim().to_lowercase()));
+    let cached = gravatar_cache().map(|dir| dir.join(format!("{}.img", hash)));
+
+    // an empty file remembers that they had none, for a while
+    if let Some(cached) = &cached
+        && let Ok(metadata) = fs::metadata(cached)
+    {
+        let age = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .unwrap_or_default();
+        if metadata.len() > 0 {
+            return fs::read(cached).ok();
+        } else if age < GRAVATAR_RETRY {
+            return None;
+        }
+    }
+
+    let url = format!("https://www.gravatar.com/avatar/{}?s=128&d=404", hash);
+    let bytes = match ureq::get(&url).timeout(GRAVATAR_TIMEOUT).call() {
+        Ok(response) => {
+            let mut bytes = Vec::new();
+            response.into_reader().read_to_end(&mut bytes).ok()?;
+            bytes
+        }
+        // nobody by that address
+        Err(ureq::Error::Status(404, _)) => Vec::new(),
+        Err(_) => return None,
+    };
+    if let Some(cached) = &cached {
+        if let Some(dir) = cached.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        let _ = fs::write(cached, &bytes);
+    }
+    (!bytes.is_empty()).then_some(bytes)
+}
+
+#[cfg(feature = "avatars")]
+fn gravatar_cache() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("nextup").join("avatars"))
+}
+
+/// The size of a cell in pixels, from the terminal when it says
+#[cfg(feature = "avatars")]
+fn cell_pixels() -> (u32, u32) {
+    match crossterm::terminal::window_size() {
+        Ok(size) if size.width > 0 && size.columns > 0 && size.height > 0 && size.rows > 0 => (
+            u32::from(size.width / size.columns),
+            u32::from(size.height / size.rows),
+        ),
+        _ => DEFAULT_CELL_PIXELS,
+    }
+}
+
+#[cfg(feature = "avatars")]
+fn png(image: &RgbaImage) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut bytes), image::ImageFormat::Png)
+        .ok()?;
+    Some(bytes)
+}
+
+/// A PNG sent to kitty and shown across `cols` × `rows` cells, without moving the cursor
+#[cfg(feature = "avatars")]
+fn kitty(png: &[u8], cols: u16, rows: u16) -> String {
+    let data = BASE64.encode(png);
+    let chunks: Vec<&[u8]> = data.as_bytes().chunks(KITTY_CHUNK).collect();
+    let mut sequence = String::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+        if i == 0 {
+            let _ = write!(
+                sequence,
+                "\x1b_Ga=T,f=100,c={},r={},C=1,q=2,m={};{}\x1b\\",
+                cols, rows, more, chunk
+            );
+        } else {
+            let _ = write!(sequence, "\x1b_Gm={};{}\x1b\\", more, chunk);
+        }
+    }
+    sequence
+}
+
+/// A PNG as an iTerm2 inline image across `cols` × `rows` cells
+#[cfg(feature = "avatars")]
+fn iterm2(png: &[u8], cols: u16, rows: u16) -> String {
+    format!(
+        "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=0:{}\x07",
+        png.len(),
+        cols,
+        rows,
+        BASE64.encode(png)
+    )
+}
+
+/// An image in sixels, in the colors of a 6×6×6 cube so no palette has to be worked out.
+/// Mostly transparent pixels are left out.
+#[cfg(feature = "avatars")]
+fn sixel(image: &RgbaImage) -> String {
+    let (width, height) = image.dimensions();
+    let color = |x: u32, y: u32| -> Option<usize> {
+        let [r, g, b, a] = image.get_pixel(x, y).0;
+        let level = |c: u8| (usize::from(c) * 5 + 127) / 255;
+        (a >= 128).then(|| level(r) * 36 + level(g) * 6 + level(b))
+    };
+
+    let mut sequence = format!("\x1bP0;1;0q\"1;1;{};{}", width, height);
+    for i in 0..216 {
+        let _ = write!(
+            sequence,
+            "#{};2;{};{};{}",
+            i,
+            i / 36 * 20,
+            i / 6 % 6 * 20,
+            i % 6 * 20
+        );
+    }
+
+    for top in (0..height).step_by(6) {
+        let rows = top..(top + 6).min(height);
+        let mut used = [false; 216];
+        for y in rows.clone() {
+            for x in 0..width {
+                if let Some(c) = color(x, y) {
+                    used[c] = true;
+                }
+            }
+        }
+
+        // each color in the band is drawn across it in turn, going back to its start between them
+        for c in (0..216).filter(|c| used[*c]) {
+            let _ = write!(sequence, "#{}", c);
+            let mut run = (0u8, 0usize);
+            for x in 0..width {
+                let bits = rows
+                    .clone()
+                    .filter(|y| color(x, *y) == Some(c))
+                    .fold(0u8, |bits, y| bits | 1 << (y - top));
+                if bits == run.0 {
+                    run.1 += 1;
+                } else {
+                    push_sixels(&mut sequence, run);
+                    run = (bits, 1);
+                }
+            }
+            push_sixels(&mut sequence, run);
+            sequence.push('$');
+        }
+        sequence.push('-');
+    }
+    sequence.push_str("\x1b\\");
+    sequence
+}
+
+/// `count` columns of the same sixel, run-length encoded when that's shorter
+#[cfg(feature = "avatars")]
+fn push_sixels(sequence: &mut String, (bits, count): (u8, usize)) {
+    let sixel = char::from(63 + bits);
+    match count {
+        0 => {}
+        1..=3 => sequence.extend(std::iter::repeat_n(sixel, count)),
+        _ => {
+            let _ = write!(sequence, "!{}{}", count, sixel);
+        }
+    }
+}
@@ -0,0 +1,474 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::format::format_duration;
+use crate::meeting::Meeting;
+use crate::queue::{Job, Queue};
+use crate::report;
+use crate::session::{SessionEvent, TimeUp};
+use crate::slack::SlackClient;
+use crate::templates::Templates;
+use crate::trace::Tracer;
+use crate::transcript::Transcript;
+
+// How often Slack is polled for commands and reactions
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+// Reactions on the order message that drive the meeting
+const NEXT_REACTION: &str = "arrow_forward";
+const PREV_REACTION: &str = "arrow_backward";
+const END_REACTION: &str = "checkered_flag";
+
+/// Commands the bot accepts from Slack
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BotCommand {
+    Next,
+    Prev,
+    Shuffle,
+    End,
+}
+
+impl BotCommand {
+    /// Parse a thread reply such as "next" or "/next"
+    fn parse(text: &str) -> Option<Self> {
+        match text.trim().trim_start_matches('/').to_lowercase().as_str() {
+            "next" | "n" | "done" => Some(Self::Next),
+            "prev" | "back" | "p" => Some(Self::Prev),
+            "shuffle" => Some(Self::Shuffle),
+            "end" | "stop" => Some(Self::End),
+            _ => None,
+        }
+    }
+
+    fn from_reaction(name: &str) -> Option<Self> {
+        match name {
+            NEXT_REACTION => Some(Self::Next),
+            PREV_REACTION => Some(Self::Prev),
+            END_REACTION => Some(Self::End),
+            _ => None,
+        }
+    }
+}
+
+/// Headless standup driven entirely from a Slack channel
+pub struct Bot {
+    config: Config,
+    meeting: Meeting,
+    tracer: Option<Tracer>,
+    transcript: Option<Transcript>,
+    templates: Templates,
+    slack: SlackClient,
+    // posts and edits during the meeting, sent without holding it up
+    queue: Queue,
+    channel: String,
+    thread_ts: String,
+    last_seen_ts: String,
+    reaction_counts: HashMap<String, u32>,
+}
+
+impl Bot {
+    /// Create a bot posting to the given channel
+    pub fn new(config: Config, token: String, channel: String) -> Result<Self> {
+        let meeting = Meeting::load(&config)?;
+        let templates = Templates::load(&config)?;
+
+        Ok(Self {
+            tracer: Tracer::new(&config),
+            queue: Queue::new(&config, Some(SlackClient::new(token.clone()))),
+            transcript: Transcript::new(&config, &templates),
+            templates,
+            config,
+            meeting,
+            slack: SlackClient::new(token),
+            channel,
+            thread_ts: String::new(),
+            last_seen_ts: String::new(),
+            reaction_counts: HashMap::new(),
+        })
+    }
+
+    /// Post the order and run the meeting until it ends
+    pub async fn run(&mut self) -> Result<()> {
+        // a meeting taken over from another instance keeps its order
+        if !self.meeting.is_resumed() {
+            self.meeting.open_order();
+            self.meeting.session.reset_timer();
+        }
+
+        self.thread_ts = self
+            .slack
+            .post_message(&self.channel, &self.order_text()?, None)?;
+        self.last_seen_ts = self.thread_ts.clone();
+        self.meeting.mark_current_asked();
+        self.reply(
+            "slack/start.txt",
+            StartContext {
+                resumed: self.meeting.is_resumed(),
+                speaker: self.meeting.session.current_name().to_string(),
+                question: self.meeting.current_question().map(str::to_string),
+                reactions: Reactions {
+                    next: NEXT_REACTION,
+                    prev: PREV_REACTION,
+                    end: END_REACTION,
+                },
+            },
+        );
+
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep(POLL_INTERVAL) => {}
+                _ = tokio::signal::ctrl_c() => break,
+            }
+
+            for event in self.meeting.session.tick() {
+                self.announce(event);
+            }
+            if let Some(tracer) = &mut self.tracer {
+                tracer.update(&self.meeting);
+            }
+            if let Some(transcript) = &mut self.transcript {
+                transcript.update(&self.meeting);
+            }
+            self.meeting.autosave();
+            if self.meeting.is_taken_over() {
+                self.reply("slack/announcement.txt", Announcement::new("taken_over"));
+                return Ok(());
+            }
+
+            if self.poll_commands() {
+                break;
+            }
+            if self.meeting.session.time_up() == TimeUp::Exit && self.meeting.session.is_time_up() {
+                break;
+            }
+        }
+
+        self.finish();
+
+        // Remember this meeting in the series history
+        self.meeting.finish()?;
+        if let Some(tracer) = &mut self.tracer
+            && let Err(e) = tracer.export(&self.config, &self.meeting, &self.queue)
+        {
+            eprintln!("{}", e);
+        }
+        self.queue.finish();
+        if let Some(transcript) = &self.transcript {
+            transcript.write(&self.config, &self.meeting)?;
+        }
+        report::write(&self.config, &self.meeting)?;
+        Ok(())
+    }
+
+    /// Apply any new commands from Slack. Returns true when the meeting should end.
+    fn poll_commands(&mut self) -> bool {
+        let mut commands = Vec::new();
+
+        match self
+            .slack
+            .replies(&self.channel, &self.thread_ts, &self.last_seen_ts)
+        {
+            Ok(messages) => {
+                for message in messages {
+                    self.last_seen_ts = message.ts.clone();
+                    if message.bot_id.is_none()
+                        && let Some(command) = BotCommand::parse(&message.text)
+                    {
+                        commands.push(command);
+                    }
+                }
+            }
+            Err(e) => eprintln!("Failed to read thread replies: {}", e),
+        }
+
+        match self.slack.reactions(&self.channel, &self.thread_ts) {
+            Ok(reactions) => {
+                for reaction in reactions {
+                    let seen = self
+                        .reaction_counts
+                        .insert(reaction.name.clone(), reaction.count)
+                        .unwrap_or(0);
+                    if reaction.count > seen
+                        && let Some(command) = BotCommand::from_reaction(&reaction.name)
+                    {
+                        commands.push(command);
+                    }
+                }
+            }
+            Err(e) => eprintln!("Failed to read reactions: {}", e),
+        }
+
+        for command in commands {
+            if self.apply(command) {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Apply a single command. Returns true when the meeting should end.
+    fn apply(&mut self, command: BotCommand) -> bool {
+        let session = &self.meeting.session;
+        // once time is up, a locked meeting can only be ended
+        if session.time_up() == TimeUp::Lock
+            && session.is_time_up()
+            && !matches!(command, BotCommand::End)
+        {
+            return false;
+        }
+
+        match command {
+            BotCommand::Next => match self.meeting.session.next() {
+                Some(event) => self.announce(event),
+                // advancing past the last speaker ends the meeting
+                None => return true,
+            },
+            BotCommand::Prev => {
+                if let Some(event) = self.meeting.session.prev() {
+                    self.announce(event);
+                }
+            }
+            BotCommand::Shuffle => {
+                let event = self.meeting.session.shuffle();
+                self.announce(event);
+            }
+            BotCommand::End => return true,
+        }
+
+        false
+    }
+
+    /// Reflect a session event in Slack
+    fn announce(&mut self, event: SessionEvent) {
+        self.meeting.mark_current_asked();
+        let session = &self.meeting.session;
+        let up = |event| Announcement {
+            speaker: Some(session.current_name().to_string()),
+            question: self.meeting.current_question().map(str::to_string),
+            ..Announcement::new(event)
+        };
+
+        let announcement = match event {
+            SessionEvent::Shuffled => up("shuffled"),
+            SessionEvent::SpeakerChanged { .. } => up("next"),
+            SessionEvent::RoundChanged { .. } => Announcement {
+                round: session.round_label(),
+                ..up("round")
+            },
+            SessionEvent::Warning { remaining } => Announcement {
+                remaining: Some(format_duration(remaining)),
+                ..Announcement::new("warning")
+            },
+            SessionEvent::CutOff { index } => Announcement {
+                speaker: Some(session.speakers()[index].name().to_string()),
+                ..Announcement::new("cut_off")
+            },
+            // nobody's at a keyboard to ask, so it stays counted
+            SessionEvent::Gap { length } => Announcement {
+                length: Some(format_duration(length)),
+                ..Announcement::new("gap")
+            },
+            SessionEvent::Ended => Announcement::new("time_up"),
+        };
+
+        // skipping through the order only posts where it stopped
+        let key = match event {
+            SessionEvent::Shuffled | SessionEvent::SpeakerChanged { .. } => Some("speaker"),
+            SessionEvent::RoundChanged { .. } => Some("round"),
+            _ => None,
+        };
+        if key.is_some() {
+            self.refresh_order();
+        }
+        match (key, self.reply_job("slack/announcement.txt", announcement)) {
+            (Some(key), Some(job)) => self.queue.push_coalesced(key, job),
+            (None, Some(job)) => self.queue.push(job),
+            (_, None) => {}
+        }
+    }
+
+    /// Post the health score and final per-person times
+    fn finish(&mut self) {
+        let health = self.meeting.close().clone();
+        let session = &self.meeting.session;
+        let summary = SummaryContext {
+            elapsed: format_duration(health.elapsed),
+            paused: (!health.paused.is_zero()).then(|| format_duration(health.paused)),
+            score: health.score,
+            verdict: health.verdict(),
+            sprint: self.meeting.sprint().map(|sprint| sprint.number),
+            speakers: session
+                .speakers()
+                .iter()
+                .map(|speaker| SpeakerTime {
+                    name: speaker.name().to_string(),
+                    spoken: format_duration(speaker.total()),
+                    cut_off: speaker.cut_off,
+                })
+                .collect(),
+            rounds: if session.is_multi_round() {
+                session
+                    .round_totals()
+                    .into_iter()
+                    .map(|(name, time)| RoundTime {
+                        name: name.to_string(),
+                        spoken: format_duration(time),
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            },
+        };
+
+        self.reply("slack/summary.txt", summary);
+    }
+
+    /// Render the order with the current speaker highlighted
+    fn order_text(&self) -> Result<String> {
+        let session = &self.meeting.session;
+        self.templates.render(
+            "slack/order.txt",
+            OrderContext {
+                title: self.config.title.clone(),
+                round: session.round_label(),
+                facilitator: self.meeting.facilitator().map(str::to_string),
+                celebrations: self
+                    .meeting
+                    .celebrations()
+                    .iter()
+                    .map(|(name, celebration)| celebration.message(name))
+                    .collect(),
+                speakers: session
+                    .speakers()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, speaker)| OrderSpeaker {
+                        name: speaker.name().to_string(),
+                        current: i == session.current_person_index(),
+                    })
+                    .collect(),
+            },
+        )
+    }
+
+    fn refresh_order(&self) {
+        match self.order_text() {
+            Ok(text) => self.queue.push_coalesced(
+                "order",
+                Job::SlackUpdate {
+                    channel: self.channel.clone(),
+                    ts: self.thread_ts.clone(),
+                    text,
+                },
+            ),
+            Err(e) => eprintln!("Failed to update order message: {}", e),
+        }
+    }
+
+    /// Post a message to the thread, unless its template renders to nothing
+    fn reply<S: Serialize>(&self, template: &str, context: S) {
+        if let Some(job) = self.reply_job(template, context) {
+            self.queue.push(job);
+        }
+    }
+
+    fn reply_job<S: Serialize>(&self, template: &str, context: S) -> Option<Job> {
+        let text = match self.templates.render(template, context) {
+            Ok(text) if text.is_empty() => return None,
+            Ok(text) => text,
+            Err(e) => {
+                eprintln!("{}", e);
+                return None;
+            }
+        };
+        Some(Job::SlackPost {
+            channel: self.channel.clone(),
+            text,
+            thread_ts: Some(self.thread_ts.clone()),
+        })
+    }
+}
+
+/// What the start template sees
+#[derive(Serialize)]
+struct StartContext {
+    resumed: bool,
+    speaker: String,
+    question: Option<String>,
+    reactions: Reactions,
+}
+
+#[derive(Serialize)]
+struct Reactions {
+    next: &'static str,
+    prev: &'static str,
+    end: &'static str,
+}
+
+/// What the announcement template sees; `event` says which announcement it is
+#[derive(Serialize, Default)]
+struct Announcement {
+    event: &'static str,
+    speaker: Option<String>,
+    question: Option<String>,
+    round: Option<String>,
+    remaining: Option<String>,
+    length: Option<String>,
+}
+
+impl Announcement {
+    fn new(event: &'static str) -> Self {
+        Self {
+            event,
+            ..Self::default()
+        }
+    }
+}
+
+/// What the summary template sees
+#[derive(Serialize)]
+struct SummaryContext {
+    elapsed: String,
+    // time spent paused on top of `elapsed`, if any
+    paused: Option<String>,
+    score: u8,
+    verdict: &'static str,
+    // the sprint number, when sprints are set up
+    sprint: Option<i64>,
+    speakers: Vec<SpeakerTime>,
+    // empty unless the meeting had more than one round
+    rounds: Vec<RoundTime>,
+}
+
+#[derive(Serialize)]
+struct SpeakerTime {
+    name: String,
+    spoken: String,
+    cut_off: bool,
+}
+
+#[derive(Serialize)]
+struct RoundTime {
+    name: String,
+    spoken: String,
+}
+
+/// What the order template sees
+#[derive(Serialize)]
+struct OrderContext {
+    title: String,
+    round: Option<String>,
+    facilitator: Option<String>,
+    celebrations: Vec<String>,
+    speakers: Vec<OrderSpeaker>,
+}
+
+#[derive(Serialize)]
+struct OrderSpeaker {
+    name: String,
+    current: bool,
+}
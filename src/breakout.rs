@@ -0,0 +1,212 @@
+use chrono::Local;
+use minijinja::context;
+use rand::seq::SliceRandom;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::export;
+use crate::history::History;
+use crate::pairs::{self, PAIRING_MEMORY};
+use crate::roster;
+use crate::santa::Exclusion;
+use crate::slack::SlackClient;
+use crate::sources;
+use crate::sync;
+use crate::templates::Templates;
+
+// Random splits tried before settling on the one that repeats the fewest recent groupings
+const ATTEMPTS: usize = 500;
+
+/// Options for `nextup breakout`
+pub struct BreakoutOptions<'a> {
+    pub rooms: usize,
+    // people who mustn't share a room, and people who must
+    pub apart: &'a [Exclusion],
+    pub together: &'a [Exclusion],
+    pub export: Option<&'a Path>,
+    // Slack channel to post the rooms to
+    pub announce: Option<&'a str>,
+    // print the rooms without remembering them
+    pub dry_run: bool,
+}
+
+/// Split the team into break-out rooms of near-equal size, keeping the given people apart or
+/// together and avoiding groupings from recent pairs and break-outs
+pub fn run(config: &Config, options: &BreakoutOptions) -> Result<()> {
+    let members = sources::load_members(config)?;
+    if members.len() < options.rooms {
+        return Err(AppError::AssignmentError(format!(
+            "{} rooms need at least {} people, and {} has {}",
+            options.rooms,
+            options.rooms,
+            config.names_file,
+            members.len()
+        ))
+        .into());
+    }
+
+    // constraints can name people by any identity they have
+    let preferred = |name: &str| {
+        roster::find(&members, name)
+            .map(|m| m.name.clone())
+            .ok_or_else(|| AppError::AssignmentError(format!("`{}` isn't in the team file", name)))
+    };
+    let resolve = |pairs: &[Exclusion]| {
+        pairs
+            .iter()
+            .map(|Exclusion(a, b)| Ok(Exclusion(preferred(a)?, preferred(b)?)))
+            .collect::<Result<Vec<_>>>()
+    };
+    let apart = resolve(options.apart)?;
+    let together = resolve(options.together)?;
+    let names: Vec<String> = members.iter().map(|m| m.name.clone()).collect();
+
+    let mut history = History::load(&config.series)?;
+    history.identify(&members);
+    let recent = &history.pairings()[history.pairings().len().saturating_sub(PAIRING_MEMORY)..];
+    // keeping people apart and together comes first, then avoiding repeats
+    let score = |rooms: &[Vec<String>]| {
+        (
+            broken(rooms, &apart, &together),
+            pairs::penalty(rooms, recent),
+        )
+    };
+    let attempt = || {
+        let mut rooms = split(&names, options.rooms);
+        settle(&mut rooms, &apart, &together);
+        rooms
+    };
+
+    let mut best = attempt();
+    let mut best_score = score(&best);
+    for _ in 1..ATTEMPTS {
+        if best_score == (0, 0) {
+            break;
+        }
+        let rooms = attempt();
+        let score = score(&rooms);
+        if score < best_score {
+            best = rooms;
+            best_score = score;
+        }
+    }
+    if best_score.0 > 0 {
+        return Err(AppError::AssignmentError(format!(
+            "couldn't keep everyone apart and together as asked in {} rooms",
+            options.rooms
+        ))
+        .into());
+    }
+
+    let today = Local::now().date_naive();
+    println!("Break-out rooms for {}, {}\n", config.series.id(), today);
+    for (i, room) in best.iter().enumerate() {
+        let mut line = format!("  Room {}: {}", i + 1, room.join(", "));
+        let repeats = pairs::repeats(room, recent);
+        if !repeats.is_empty() {
+            line.push_str(&format!(" (again: {})", repeats.join(", ")));
+        }
+        println!("{}", line);
+    }
+
+    if let Some(path) = options.export {
+        // one room per row, its number first
+        let rows: Vec<Vec<String>> = best
+            .iter()
+            .enumerate()
+            .map(|(i, room)| {
+                std::iter::once(format!("Room {}", i + 1))
+                    .chain(room.iter().cloned())
+                    .collect()
+            })
+            .collect();
+        export::write_csv(path, None, &rows)?;
+        println!("\nSaved to {}", path.display());
+    }
+
+    if let Some(channel) = options.announce {
+        let token = config.slack_bot_token.clone().ok_or_else(|| {
+            anyhow::Error::msg(
+                "--announce needs a bot token (--slack-bot-token or SLACK_BOT_TOKEN)",
+            )
+        })?;
+        let text = Templates::load(config)?.render(
+            "slack/breakout.txt",
+            context! { title => config.title, rooms => best },
+        )?;
+        if !text.is_empty() {
+            SlackClient::new(token).post_message(channel, &text, None)?;
+            println!("\nPosted to {}", channel);
+        }
+    }
+
+    if options.dry_run {
+        return Ok(());
+    }
+
+    // remembered with the pairs, so neither repeats the other's groupings
+    history.record_pairing(today, best);
+    history.save()?;
+
+    // share the updated history when the state directory is a git checkout
+    if let Some(root) = config.series.root()
+        && let Err(e) = sync::push(root, &format!("nextup: {} breakout", config.series.id()))
+    {
+        eprintln!("{}", e);
+    }
+
+    Ok(())
+}
+
+/// Deal everyone out into `rooms` rooms at random, so no two rooms differ by more than one person
+fn split(names: &[String], rooms: usize) -> Vec<Vec<String>> {
+    let mut names = names.to_vec();
+    names.shuffle(&mut rand::rng());
+
+    let mut split = vec![Vec::new(); rooms];
+    for (i, name) in names.into_iter().enumerate() {
+        split[i % rooms].push(name);
+    }
+    split
+}
+
+/// Swap people between rooms while that breaks fewer constraints; room sizes stay as they are
+fn settle(rooms: &mut [Vec<String>], apart: &[Exclusion], together: &[Exclusion]) {
+    let mut broken_now = broken(rooms, apart, together);
+    while broken_now > 0 {
+        let mut improved = false;
+        for a in 0..rooms.len() {
+            for b in a + 1..rooms.len() {
+                for i in 0..rooms[a].len() {
+                    for j in 0..rooms[b].len() {
+                        swap(rooms, (a, i), (b, j));
+                        let after = broken(rooms, apart, together);
+                        if after < broken_now {
+                            broken_now = after;
+                            improved = true;
+                        } else {
+                            swap(rooms, (a, i), (b, j));
+                        }
+                    }
+                }
+            }
+        }
+        if !improved {
+            break;
+        }
+    }
+}
+
+fn swap(rooms: &mut [Vec<String>], (a, i): (usize, usize), (b, j): (usize, usize)) {
+    let name = std::mem::take(&mut rooms[a][i]);
+    rooms[a][i] = std::mem::replace(&mut rooms[b][j], name);
+}
+
+/// How many of the constraints the rooms break
+fn broken(rooms: &[Vec<String>], apart: &[Exclusion], together: &[Exclusion]) -> usize {
+    let room_of = |name: &str| rooms.iter().position(|room| room.iter().any(|n| n == name));
+    let shared = |pair: &Exclusion| room_of(&pair.0) == room_of(&pair.1);
+    apart.iter().filter(|pair| shared(pair)).count()
+        + together.iter().filter(|pair| !shared(pair)).count()
+}
@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::export;
+use crate::roster;
+use crate::series::{Series, Template};
+use crate::sync;
+use crate::theme::TimerTheme;
+
+// Bumped when a bundle changes in a way older versions would misread
+const BUNDLE_VERSION: u32 = 1;
+
+// The environment variables holding secrets, which are never written out
+pub const SECRET_VARS: [&str; 4] = [
+    "NEXTUP_API_TOKEN",
+    "SLACK_USER_TOKEN",
+    "SLACK_BOT_TOKEN",
+    "OTEL_EXPORTER_OTLP_HEADERS",
+];
+
+/// A meeting setup to hand to someone else: the series template, the team file itself and the
+/// timer theme. Paths are left out, since they only mean something on the machine that wrote
+/// them, and so are tokens; the bundle only says which ones the setup used.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Bundle {
+    pub version: u32,
+    pub series: String,
+    pub template: Template,
+    // the team file's contents
+    pub team: String,
+    // only when it isn't the default theme
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<TimerTheme>,
+    // the settings holding secrets the setup used, to be set again after importing
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub secrets: Vec<String>,
+}
+
+/// Options for `nextup bundle import`
+pub struct ImportOptions<'a> {
+    pub file: &'a Path,
+    pub team_file: Option<&'a Path>,
+    pub force: bool,
+    // the series to set up instead of the one the bundle names
+    pub series: Option<Series>,
+}
+
+/// Bundle up the meeting as configured, for `nextup bundle export`
+pub fn export(config: &Config, file: Option<&Path>) -> Result<()> {
+    let bundle = Bundle {
+        version: BUNDLE_VERSION,
+        series: config.series.id().to_string(),
+        template: Template {
+            title: (!config.auto_title).then(|| config.title.clone()),
+            // importing puts the team file somewhere of its own
+            names_file: None,
+            duration_minutes: Some(config.duration.as_secs() / 60),
+            window: config.window.map(|w| w.to_string()),
+            rounds: config.rounds.clone(),
+            keyboard: Some(config.keyboard.to_string()),
+            timer_theme: None,
+            order_rules: config.order_rules.iter().map(|r| r.to_string()).collect(),
+            escalation: config.escalation.iter().map(|r| r.to_string()).collect(),
+            sprint_epoch: config.sprint_epoch,
+            sprint_weeks: Some(config.sprint_weeks),
+        },
+        team: roster::read(&config.names_file)?,
+        theme: (config.timer_theme != TimerTheme::default()).then(|| config.timer_theme.clone()),
+        secrets: secrets(config),
+    };
+    let json = serde_json::to_string_pretty(&bundle)?;
+
+    match file {
+        Some(file) => {
+            fs::write(file, json + "\n")?;
+            eprintln!("Exported {} to {}", config.series.id(), file.display());
+            if !bundle.secrets.is_empty() {
+                eprintln!("Left out: {}", bundle.secrets.join(", "));
+            }
+        }
+        None => export::to_stdout(&(json + "\n"))?,
+    }
+    Ok(())
+}
+
+/// Which of the settings holding secrets are set, by the environment variable that sets them
+fn secrets(config: &Config) -> Vec<String> {
+    let set = [
+        config.api_token.is_some(),
+        config.slack_user_token.is_some(),
+        config.slack_bot_token.is_some(),
+        !config.otlp_headers.is_empty(),
+    ];
+    SECRET_VARS
+        .into_iter()
+        .zip(set)
+        .filter(|(_, set)| *set)
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
+/// Set up a series from a bundle, for `nextup bundle import`: the team file and theme are
+/// written next to the series' state, and the template is pointed at them
+pub fn import(config: &Config, options: &ImportOptions) -> Result<()> {
+    let file = options.file.display();
+    let content = fs::read_to_string(options.file)
+        .map_err(|e| AppError::BundleError(format!("{}: {}", file, e)))?;
+    let mut bundle: Bundle = serde_json::from_str(&content)
+        .map_err(|e| AppError::BundleError(format!("{}: {}", file, e)))?;
+    if bundle.version > BUNDLE_VERSION {
+        return Err(AppError::BundleError(format!(
+            "{} is from a newer nextup (bundle version {})",
+            file, bundle.version
+        ))
+        .into());
+    }
+
+    let series = match &options.series {
+        Some(series) => series.clone(),
+        None => match config.series.root() {
+            Some(root) => Series::new(&bundle.series).in_dir(root.to_path_buf()),
+            None => Series::new(&bundle.series),
+        },
+    };
+    let Some(dir) = series.dir() else {
+        return Err(AppError::BundleError(
+            "there's no state directory to keep the series in; pass --state-dir".to_string(),
+        )
+        .into());
+    };
+
+    let team_file = options
+        .team_file
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| dir.join("team.txt"));
+    // the same checks as loading the team file, before anything is written
+    let team_name = team_file.display().to_string();
+    let members = roster::parse_members(&bundle.team, &team_name)?;
+
+    if !options.force {
+        if series.has_template() {
+            return Err(AppError::BundleError(format!(
+                "the {} series is already set up; pass --force to replace it",
+                series.id()
+            ))
+            .into());
+        }
+        if team_file.exists() {
+            return Err(AppError::BundleError(format!(
+                "{} already exists; pass --force to replace it, or --team-file to put the team elsewhere",
+                team_name
+            ))
+            .into());
+        }
+    }
+
+    fs::create_dir_all(&dir)?;
+    if let Some(parent) = team_file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&team_file, &bundle.team)?;
+    bundle.template.names_file = Some(absolute(&team_file).display().to_string());
+
+    if let Some(theme) = &bundle.theme {
+        let theme_file = dir.join("theme.json");
+        fs::write(&theme_file, serde_json::to_string_pretty(theme)?)?;
+        bundle.template.timer_theme = Some(absolute(&theme_file).display().to_string());
+    }
+    series.save_template(&bundle.template)?;
+
+    if let Some(root) = series.root()
+        && let Err(e) = sync::push(root, &format!("nextup: {} imported", series.id()))
+    {
+        eprintln!("{}", e);
+    }
+
+    eprintln!(
+        "Set up {} with a team of {} in {}",
+        series.id(),
+        members.len(),
+        team_name
+    );
+    if !bundle.secrets.is_empty() {
+        eprintln!(
+            "The setup also used {}; set them here too if you need them.",
+            bundle.secrets.join(", ")
+        );
+    }
+    eprintln!("Start it with `nextup --series {}`.", series.id());
+    Ok(())
+}
+
+/// The path from anywhere, since the template is read from other directories
+fn absolute(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
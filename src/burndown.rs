@@ -0,0 +1,69 @@
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::session::Session;
+
+/// Turns left against the meeting clock, kept for the burndown chart
+pub struct Burndown {
+    // the actual line so far, stepping down at every handover
+    points: Vec<(f64, f64)>,
+}
+
+/// The burndown chart's lines, each in minutes into the meeting and turns still to come
+pub struct Lines {
+    // how the turns have actually gone, up to now
+    pub actual: Vec<(f64, f64)>,
+    // every turn done evenly between the warm-up and the wrap-up
+    pub plan: Vec<(f64, f64)>,
+    // from now to when the last turn is projected to end, everyone using their full slot
+    pub pace: Vec<(f64, f64)>,
+    pub turns: f64,
+    // the planned length, or the projected one when that's longer
+    pub length: f64,
+}
+
+impl Burndown {
+    /// Start keeping track when the chart is shown
+    pub fn new(config: &Config) -> Option<Self> {
+        config.burndown.then(|| Self { points: Vec::new() })
+    }
+
+    /// Step the line down (or back up, after `↑`) when the turns left have changed
+    pub fn update(&mut self, session: &Session) {
+        let now = minutes(session.elapsed());
+        let left = session.turns_left() as f64;
+        match self.points.last() {
+            Some(&(_, last)) if last == left => {}
+            Some(&(_, last)) => self.points.extend([(now, last), (now, left)]),
+            None => self.points.push((now, left)),
+        }
+    }
+
+    /// The lines to draw as the meeting stands
+    pub fn lines(&self, session: &Session) -> Lines {
+        let now = minutes(session.elapsed());
+        let left = session.turns_left() as f64;
+        let mut actual = self.points.clone();
+        actual.push((now, left));
+
+        let (start, end) = session.speaking_time();
+        let turns = session.turns_total() as f64;
+        let plan = vec![(0.0, turns), (minutes(start), turns), (minutes(end), 0.0)];
+
+        let wrap_up = session.duration().saturating_sub(end);
+        let finish = minutes(session.projected_length().saturating_sub(wrap_up));
+        let pace = vec![(now, left), (finish, 0.0)];
+
+        Lines {
+            actual,
+            plan,
+            pace,
+            turns,
+            length: minutes(session.duration()).max(finish),
+        }
+    }
+}
+
+fn minutes(duration: Duration) -> f64 {
+    duration.as_secs_f64() / 60.0
+}
@@ -0,0 +1,228 @@
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use crate::error::{AppError, Result};
+
+/// Something on the calendar today
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    pub summary: String,
+    pub start: DateTime<Local>,
+}
+
+/// How a recurring event repeats, from its RRULE; only daily and weekly rules are followed
+#[derive(Debug, Default)]
+struct Repeat {
+    freq: String,
+    interval: i64,
+    until: Option<NaiveDate>,
+    days: Vec<Weekday>,
+}
+
+/// One VEVENT as read from the file
+#[derive(Debug, Default)]
+struct Entry {
+    uid: String,
+    summary: String,
+    start: Option<NaiveDateTime>,
+    repeat: Option<Repeat>,
+    // days a recurring event is skipped, or replaced by an entry of its own
+    except: HashSet<NaiveDate>,
+    // the occurrence this entry replaces
+    replaces: Option<NaiveDate>,
+    cancelled: bool,
+}
+
+/// The timed events on `day` in an exported calendar (.ics), earliest first. All-day events
+/// are left out, and times with a TZID are read as local time.
+pub fn load(path: &Path, day: NaiveDate) -> Result<Vec<Event>> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| AppError::CalendarError(format!("{}: {}", path.display(), e)))?;
+    let entries = parse(&content);
+
+    // occurrences moved or changed on their own, by event
+    let mut replaced: HashMap<&str, HashSet<NaiveDate>> = HashMap::new();
+    for entry in &entries {
+        if let Some(date) = entry.replaces {
+            replaced.entry(&entry.uid).or_default().insert(date);
+        }
+    }
+
+    let mut events: Vec<Event> = entries
+        .iter()
+        .filter(|entry| !entry.cancelled)
+        .filter(|entry| {
+            entry.replaces.is_some()
+                || !replaced
+                    .get(entry.uid.as_str())
+                    .is_some_and(|dates| dates.contains(&day))
+        })
+        .filter_map(|entry| {
+            let start = entry.start?;
+            if !occurs_on(entry, start.date(), day) {
+                return None;
+            }
+            Some(Event {
+                summary: entry.summary.clone(),
+                start: day
+                    .and_time(start.time())
+                    .and_local_timezone(Local)
+                    .earliest()?,
+            })
+        })
+        .collect();
+    events.sort_by_key(|event| event.start);
+    Ok(events)
+}
+
+/// Whether an event starting on `first` has an occurrence on `day`
+fn occurs_on(entry: &Entry, first: NaiveDate, day: NaiveDate) -> bool {
+    if entry.except.contains(&day) {
+        return false;
+    }
+    let Some(repeat) = &entry.repeat else {
+        return first == day;
+    };
+    if day < first || repeat.until.is_some_and(|until| day > until) {
+        return false;
+    }
+
+    let interval = repeat.interval.max(1);
+    match repeat.freq.as_str() {
+        "DAILY" => (day - first).num_days() % interval == 0,
+        "WEEKLY" => {
+            let on_day = if repeat.days.is_empty() {
+                day.weekday() == first.weekday()
+            } else {
+                repeat.days.contains(&day.weekday())
+            };
+            let weeks = (day.week(Weekday::Mon).first_day() - first.week(Weekday::Mon).first_day())
+                .num_weeks();
+            on_day && weeks % interval == 0
+        }
+        // anything rarer only counts on the day it starts
+        _ => first == day,
+    }
+}
+
+/// The VEVENTs in an iCalendar file, with long lines unfolded
+fn parse(content: &str) -> Vec<Entry> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    let mut entries = Vec::new();
+    let mut entry: Option<Entry> = None;
+    for line in &lines {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (name, params) = key.split_once(';').unwrap_or((key, ""));
+
+        match (name.to_ascii_uppercase().as_str(), entry.as_mut()) {
+            ("BEGIN", None) if value.eq_ignore_ascii_case("VEVENT") => {
+                entry = Some(Entry::default())
+            }
+            ("END", Some(_)) if value.eq_ignore_ascii_case("VEVENT") => {
+                entries.extend(entry.take())
+            }
+            ("UID", Some(entry)) => entry.uid = value.to_string(),
+            ("SUMMARY", Some(entry)) => entry.summary = unescape(value),
+            ("STATUS", Some(entry)) => entry.cancelled = value.eq_ignore_ascii_case("CANCELLED"),
+            ("DTSTART", Some(entry)) => entry.start = date_time(value, params),
+            ("RRULE", Some(entry)) => entry.repeat = Some(repeat(value)),
+            ("EXDATE", Some(entry)) => {
+                entry
+                    .except
+                    .extend(value.split(',').filter_map(|value| date(value, params)));
+            }
+            ("RECURRENCE-ID", Some(entry)) => entry.replaces = date(value, params),
+            _ => {}
+        }
+    }
+    entries
+}
+
+/// A DTSTART as local time; None for an all-day date
+fn date_time(value: &str, params: &str) -> Option<NaiveDateTime> {
+    if params.to_ascii_uppercase().contains("VALUE=DATE") && !params.contains("DATE-TIME") {
+        return None;
+    }
+    match value.strip_suffix('Z') {
+        Some(utc) => {
+            let utc = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+            Some(
+                Utc.from_utc_datetime(&utc)
+                    .with_timezone(&Local)
+                    .naive_local(),
+            )
+        }
+        None => NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok(),
+    }
+}
+
+/// The local date of a date or date-time value
+fn date(value: &str, params: &str) -> Option<NaiveDate> {
+    date_time(value, params)
+        .map(|time| time.date())
+        .or_else(|| NaiveDate::parse_from_str(value.get(..8)?, "%Y%m%d").ok())
+}
+
+fn repeat(value: &str) -> Repeat {
+    let mut repeat = Repeat {
+        interval: 1,
+        ..Repeat::default()
+    };
+    for part in value.split(';') {
+        let Some((key, value)) = part.split_once('=') else {
+            continue;
+        };
+        match key.to_ascii_uppercase().as_str() {
+            "FREQ" => repeat.freq = value.to_ascii_uppercase(),
+            "INTERVAL" => repeat.interval = value.parse().unwrap_or(1),
+            "UNTIL" => repeat.until = date(value, ""),
+            "BYDAY" => {
+                // e.g. MO or, in monthly rules, 2MO; only the day counts here
+                repeat.days = value
+                    .split(',')
+                    .filter_map(|day| {
+                        weekday(day.trim_start_matches(|c: char| {
+                            c.is_ascii_digit() || c == '+' || c == '-'
+                        }))
+                    })
+                    .collect();
+            }
+            _ => {}
+        }
+    }
+    repeat
+}
+
+fn weekday(code: &str) -> Option<Weekday> {
+    match code.to_ascii_uppercase().as_str() {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// Undo iCalendar text escaping
+fn unescape(value: &str) -> String {
+    value
+        .replace("\\n", " ")
+        .replace("\\N", " ")
+        .replace("\\,", ",")
+        .replace("\\;", ";")
+        .replace("\\\\", "\\")
+}
@@ -0,0 +1,152 @@
+use chrono::{DateTime, Local};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::error::Result;
+
+/// How the chapter list is written
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChapterFormat {
+    // `00:03:21 Alice` lines, as pasted into a YouTube description
+    YouTube,
+    // FFMETADATA1 file, muxed in with `ffmpeg -i rec.mp4 -i chapters.txt -map_metadata 1 -codec copy out.mp4`
+    Ffmpeg,
+}
+
+impl FromStr for ChapterFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "youtube" => Ok(Self::YouTube),
+            "ffmpeg" | "ffmetadata" => Ok(Self::Ffmpeg),
+            _ => Err(format!(
+                "unknown chapter format `{}`, expected youtube or ffmpeg",
+                s
+            )),
+        }
+    }
+}
+
+/// Where a speaker starts in the recording
+#[derive(Debug, Clone)]
+pub struct Chapter {
+    pub offset: Duration,
+    pub name: String,
+}
+
+/// Speaker markers relative to when the recording started
+pub struct Chapters {
+    recording_start: DateTime<Local>,
+    chapters: Vec<Chapter>,
+}
+
+impl Chapters {
+    pub fn new(recording_start: DateTime<Local>) -> Self {
+        Self {
+            recording_start,
+            chapters: Vec::new(),
+        }
+    }
+
+    /// How far into the recording we are now
+    fn offset(&self) -> Duration {
+        (Local::now() - self.recording_start)
+            .to_std()
+            .unwrap_or(Duration::ZERO)
+    }
+
+    /// Mark `name` as starting now
+    pub fn mark(&mut self, name: &str) -> &Chapter {
+        let chapter = Chapter {
+            offset: self.offset(),
+            name: name.to_string(),
+        };
+        self.chapters.push(chapter);
+        &self.chapters[self.chapters.len() - 1]
+    }
+
+    pub fn last(&self) -> Option<&Chapter> {
+        self.chapters.last()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chapters.is_empty()
+    }
+
+    /// The chapter list in the given format, with the recording ending now
+    pub fn render(&self, format: ChapterFormat) -> String {
+        match format {
+            ChapterFormat::YouTube => self.youtube(),
+            ChapterFormat::Ffmpeg => self.ffmetadata(self.offset()),
+        }
+    }
+
+    /// Write the chapter list to a file
+    pub fn export(&self, path: &Path, format: ChapterFormat) -> Result<()> {
+        fs::write(path, self.render(format))?;
+        Ok(())
+    }
+
+    fn youtube(&self) -> String {
+        let mut lines = Vec::new();
+
+        // YouTube only shows chapters when the first one starts at 00:00
+        if self
+            .chapters
+            .first()
+            .is_some_and(|first| first.offset.as_secs() > 0)
+        {
+            lines.push(format!("{} Start", format_timestamp(Duration::ZERO)));
+        }
+        for chapter in &self.chapters {
+            lines.push(format!(
+                "{} {}",
+                format_timestamp(chapter.offset),
+                chapter.name
+            ));
+        }
+
+        lines.join("\n") + "\n"
+    }
+
+    fn ffmetadata(&self, end: Duration) -> String {
+        let mut out = String::from(";FFMETADATA1\n");
+
+        for (i, chapter) in self.chapters.iter().enumerate() {
+            // each chapter runs until the next one starts
+            let chapter_end = self
+                .chapters
+                .get(i + 1)
+                .map_or(end, |next| next.offset)
+                .max(chapter.offset);
+            out.push_str(&format!(
+                "\n[CHAPTER]\nTIMEBASE=1/1000\nSTART={}\nEND={}\ntitle={}\n",
+                chapter.offset.as_millis(),
+                chapter_end.as_millis(),
+                escape_metadata(&chapter.name)
+            ));
+        }
+
+        out
+    }
+}
+
+/// e.g. "00:03:21"
+pub fn format_timestamp(offset: Duration) -> String {
+    let secs = offset.as_secs();
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+/// Backslash the characters FFMETADATA treats as special
+fn escape_metadata(value: &str) -> String {
+    value
+        .chars()
+        .flat_map(|c| match c {
+            '=' | ';' | '#' | '\\' | '\n' => vec!['\\', c],
+            c => vec![c],
+        })
+        .collect()
+}
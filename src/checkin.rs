@@ -0,0 +1,393 @@
+use chrono::{Local, NaiveDate};
+use minijinja::context;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::command::MeetingCommand;
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::roster::Member;
+use crate::series::Series;
+use crate::server::Server;
+use crate::slack::SlackClient;
+use crate::sources;
+use crate::sync;
+use crate::templates::Templates;
+
+// Reactions on the Slack check-in message, and what they mean
+const ABSENT_REACTION: &str = "palm_tree";
+const ASYNC_REACTION: &str = "memo";
+
+// How often `nextup checkin --serve` picks up new check-ins
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How someone is taking part today, when it isn't in person
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Attendance {
+    // out today
+    Absent,
+    // not speaking live, but posting a written update
+    Async,
+}
+
+impl FromStr for Attendance {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "absent" | "out" | "skip" => Ok(Self::Absent),
+            "async" => Ok(Self::Async),
+            _ => Err(format!("unknown status `{}`, expected absent or async", s)),
+        }
+    }
+}
+
+impl Attendance {
+    pub fn label(self) -> &'static str {
+        match self {
+            Attendance::Absent => "absent",
+            Attendance::Async => "async",
+        }
+    }
+}
+
+/// Someone's check-in for today
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckIn {
+    pub name: String,
+    pub attendance: Attendance,
+    // the written update, for async check-ins
+    #[serde(default)]
+    pub update: Option<String>,
+}
+
+/// The check-in message posted to Slack, whose reactions are read when the meeting starts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlackPost {
+    pub channel: String,
+    pub ts: String,
+}
+
+/// Today's check-ins for a series, made before the meeting starts
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CheckIns {
+    // check-ins from any other day are stale
+    #[serde(default)]
+    date: Option<NaiveDate>,
+    #[serde(default)]
+    entries: Vec<CheckIn>,
+    #[serde(default)]
+    slack_post: Option<SlackPost>,
+
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl CheckIns {
+    /// Location of a series' check-ins (e.g. `~/.local/state/nextup/series/team-daily-standup/checkins.json`)
+    pub fn path(series: &Series) -> Option<PathBuf> {
+        series.dir().map(|dir| dir.join("checkins.json"))
+    }
+
+    /// Load today's check-ins, starting fresh if there are none yet
+    pub fn load(series: &Series) -> Result<Self> {
+        let today = Local::now().date_naive();
+        let Some(path) = Self::path(series) else {
+            return Ok(Self::default());
+        };
+
+        let mut check_ins: Self = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| AppError::HistoryError(format!("{}: {}", path.display(), e)))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(e) => {
+                return Err(AppError::HistoryError(format!("{}: {}", path.display(), e)).into());
+            }
+        };
+
+        if check_ins.date != Some(today) {
+            check_ins = Self {
+                date: Some(today),
+                ..Self::default()
+            };
+        }
+        check_ins.path = Some(path);
+        Ok(check_ins)
+    }
+
+    /// Load today's check-ins along with any written updates waiting in Slack or the updates directory
+    pub fn collect(config: &Config, members: &[Member]) -> Result<Self> {
+        let mut check_ins = Self::load(&config.series)?;
+        if let Some(token) = &config.slack_bot_token
+            && let Err(e) = check_ins.pull_from_slack(&SlackClient::new(token.clone()), members)
+        {
+            eprintln!("Couldn't read check-ins from Slack: {}", e);
+        }
+        if let Some(dir) = &config.updates_dir {
+            check_ins.pull_from_dir(dir, members)?;
+        }
+        Ok(check_ins)
+    }
+
+    /// Write the check-ins, creating the series directory if needed
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Record how someone is taking part today; None checks them back in for the meeting
+    pub fn set(&mut self, name: &str, attendance: Option<Attendance>, update: Option<String>) {
+        self.entries.retain(|entry| entry.name != name);
+        if let Some(attendance) = attendance {
+            self.entries.push(CheckIn {
+                name: name.to_string(),
+                attendance,
+                update: update.filter(|text| !text.trim().is_empty()),
+            });
+        }
+    }
+
+    /// Add a written update, which makes someone async unless they're out today.
+    /// Updates already recorded aren't added again, so sources can be read more than once.
+    fn add_update(&mut self, name: &str, text: &str) {
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+
+        match self.entries.iter_mut().find(|entry| entry.name == name) {
+            Some(entry) if entry.attendance == Attendance::Absent => {}
+            Some(entry) => match &mut entry.update {
+                Some(update) if update.contains(text) => {}
+                Some(update) => {
+                    update.push('\n');
+                    update.push_str(text);
+                }
+                None => entry.update = Some(text.to_string()),
+            },
+            None => self.set(name, Some(Attendance::Async), Some(text.to_string())),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&CheckIn> {
+        self.entries.iter().find(|entry| entry.name == name)
+    }
+
+    pub fn entries(&self) -> &[CheckIn] {
+        &self.entries
+    }
+
+    /// Post the check-in message people react to
+    pub fn post_to_slack(
+        &mut self,
+        client: &SlackClient,
+        channel: &str,
+        config: &Config,
+    ) -> Result<()> {
+        let text = Templates::load(config)?.render(
+            "slack/checkin.txt",
+            context! {
+                title => config.title,
+                absent_reaction => ABSENT_REACTION,
+                async_reaction => ASYNC_REACTION,
+            },
+        )?;
+        let ts = client.post_message(channel, &text, None)?;
+        self.slack_post = Some(SlackPost {
+            channel: channel.to_string(),
+            ts,
+        });
+        Ok(())
+    }
+
+    /// Pick up check-ins made by reacting to today's Slack message, and written updates replied
+    /// in its thread, matched by `slack=` member IDs. Check-ins made elsewhere win.
+    pub fn pull_from_slack(&mut self, client: &SlackClient, members: &[Member]) -> Result<()> {
+        let Some(post) = self.slack_post.clone() else {
+            return Ok(());
+        };
+        let by_slack_id = |user: &str| members.iter().find(|m| m.slack_id.as_deref() == Some(user));
+
+        for reaction in client.reactions(&post.channel, &post.ts)? {
+            let attendance = match reaction.name.as_str() {
+                ABSENT_REACTION => Attendance::Absent,
+                ASYNC_REACTION => Attendance::Async,
+                _ => continue,
+            };
+            for user in &reaction.users {
+                if let Some(member) = by_slack_id(user)
+                    && self.get(&member.name).is_none()
+                {
+                    self.set(&member.name, Some(attendance), None);
+                }
+            }
+        }
+
+        for reply in client.replies(&post.channel, &post.ts, "0")? {
+            if let Some(member) = reply.user.as_deref().and_then(by_slack_id) {
+                self.add_update(&member.name, &reply.text);
+            }
+        }
+        Ok(())
+    }
+
+    /// Pick up written updates saved as one text file per person, e.g. `Kyle Broflovski.txt`
+    /// or `kyle-broflovski.md`
+    pub fn pull_from_dir(&mut self, dir: &Path, members: &[Member]) -> Result<()> {
+        for entry in fs::read_dir(dir).map_err(AppError::UpdatesDirError)? {
+            let path = entry.map_err(AppError::UpdatesDirError)?.path();
+            if !matches!(
+                path.extension().and_then(|ext| ext.to_str()),
+                Some("txt" | "md")
+            ) {
+                continue;
+            }
+
+            let stem = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or_default();
+            let known_as = |m: &&Member| {
+                std::iter::once(&m.name)
+                    .chain(&m.aliases)
+                    .chain(&m.github)
+                    .chain(std::iter::once(&m.id))
+                    .any(|name| file_key(name) == file_key(stem))
+            };
+            if let Some(member) = members.iter().find(known_as) {
+                let text = fs::read_to_string(&path).map_err(AppError::UpdatesDirError)?;
+                self.add_update(&member.name, &text);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A name as it might appear in a file name: case, spaces, dashes and underscores don't matter
+fn file_key(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '_'))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Options for `nextup checkin`
+pub struct CheckInOptions<'a> {
+    // post the check-in message to this Slack channel
+    pub slack: Option<(SlackClient, &'a str)>,
+    // take check-ins through the web view until Ctrl+C
+    pub serve: Option<SocketAddr>,
+}
+
+/// Collect check-ins ahead of the meeting, or list today's when there's nothing to collect
+pub async fn run(config: &Config, options: CheckInOptions<'_>) -> Result<()> {
+    let members = sources::load_members(config)?;
+
+    if options.slack.is_none() && options.serve.is_none() {
+        print(config, &CheckIns::collect(config, &members)?);
+        return Ok(());
+    }
+
+    let mut check_ins = CheckIns::load(&config.series)?;
+    if let Some((client, channel)) = &options.slack {
+        check_ins.post_to_slack(client, channel, config)?;
+        save(config, &check_ins)?;
+        println!(
+            "Posted the check-in to {}; reactions are picked up when the meeting starts.",
+            channel
+        );
+    }
+
+    if let Some(addr) = options.serve {
+        serve(config, &members, &mut check_ins, addr).await?;
+    }
+    Ok(())
+}
+
+/// Take check-ins through the web view, saving each one as it arrives
+async fn serve(
+    config: &Config,
+    members: &[Member],
+    check_ins: &mut CheckIns,
+    addr: SocketAddr,
+) -> Result<()> {
+    let mut server =
+        Server::start_check_ins(addr, config.api_token.clone(), config.share_ttl).await?;
+    println!("{}", server.qr_code());
+    println!("Taking check-ins at {} until Ctrl+C", server.url());
+
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    loop {
+        server.publish_check_ins(config, members, check_ins);
+        tokio::select! {
+            _ = &mut ctrl_c => return Ok(()),
+            _ = tokio::time::sleep(POLL_INTERVAL) => {}
+        }
+
+        while let Some(command) = server.next_command() {
+            // the meeting hasn't started, so nothing else applies yet
+            let MeetingCommand::CheckIn {
+                name,
+                attendance,
+                update,
+            } = command
+            else {
+                continue;
+            };
+            check_ins.set(&name, attendance, update);
+            save(config, check_ins)?;
+            println!(
+                "{}: {}",
+                name,
+                attendance.map_or("back in", Attendance::label)
+            );
+        }
+    }
+}
+
+/// Save the check-ins and share them with whoever runs the meeting
+fn save(config: &Config, check_ins: &CheckIns) -> Result<()> {
+    check_ins.save()?;
+
+    // share the check-ins when the state directory is a git checkout
+    if let Some(root) = config.series.root()
+        && let Err(e) = sync::push(root, &format!("nextup: {} check-in", config.series.id()))
+    {
+        eprintln!("{}", e);
+    }
+    Ok(())
+}
+
+fn print(config: &Config, check_ins: &CheckIns) {
+    if check_ins.entries().is_empty() {
+        println!("Nobody has checked in as away today.");
+        return;
+    }
+
+    println!(
+        "Check-ins for {}, {}\n",
+        config.title,
+        Local::now().date_naive()
+    );
+    for entry in check_ins.entries() {
+        let mut line = format!("  {}: {}", entry.name, entry.attendance.label());
+        if let Some(update) = &entry.update {
+            line.push_str(&format!(" -- {}", update.replace('\n', "\n      ")));
+        }
+        println!("{}", line);
+    }
+}
@@ -0,0 +1,71 @@
+use std::cmp::Ordering;
+use std::env;
+
+use crate::error::{AppError, Result};
+
+/// How names are put in alphabetical order: by the rules of a language with the `collation`
+/// feature, so that e.g. "Ødegaard" comes after "Zeller" in Norwegian and "Ángel" next to
+/// "Andrés" in Spanish, and otherwise by their letters ignoring case
+pub struct Collation {
+    #[cfg(feature = "collation")]
+    collator: icu_collator::Collator,
+}
+
+impl Collation {
+    /// Collation for `locale` (e.g. `sv` or `de-DE`), or the language in LC_ALL, LC_COLLATE
+    /// or LANG when none is given
+    pub fn new(locale: Option<&str>) -> Result<Self> {
+        #[cfg(not(feature = "collation"))]
+        {
+            if locale.is_some() {
+                return Err(AppError::NotBuiltIn {
+                    what: "sorting by language",
+                    feature: "collation",
+                }
+                .into());
+            }
+            Ok(Self {})
+        }
+
+        #[cfg(feature = "collation")]
+        {
+            let tag = match locale {
+                Some(locale) => locale.to_string(),
+                None => system_locale().unwrap_or_else(|| "und".to_string()),
+            };
+            let data_locale = tag
+                .replace('_', "-")
+                .parse()
+                .map_err(|_| AppError::UnknownLocale(tag.clone()))?;
+            let collator = icu_collator::Collator::try_new(&data_locale, Default::default())
+                .map_err(|_| AppError::UnknownLocale(tag))?;
+            Ok(Self { collator })
+        }
+    }
+
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        #[cfg(feature = "collation")]
+        {
+            self.collator.compare(a, b)
+        }
+
+        #[cfg(not(feature = "collation"))]
+        {
+            a.to_lowercase()
+                .cmp(&b.to_lowercase())
+                .then_with(|| a.cmp(b))
+        }
+    }
+}
+
+/// The language of the user's locale for sorting, e.g. `de-DE` from `de_DE.UTF-8`; none for the
+/// C locale
+#[cfg_attr(not(feature = "collation"), allow(dead_code))]
+fn system_locale() -> Option<String> {
+    // the first of these that's set wins, as with setlocale
+    let locale = ["LC_ALL", "LC_COLLATE", "LANG"]
+        .iter()
+        .find_map(|name| env::var(name).ok().filter(|value| !value.is_empty()))?;
+    let tag = locale.split(['.', '@']).next().unwrap_or_default();
+    (!matches!(tag, "" | "C" | "POSIX")).then(|| tag.replace('_', "-"))
+}
@@ -0,0 +1,65 @@
+use crate::checkin::Attendance;
+use crate::session::GapChoice;
+
+/// A change to the running meeting, from the keyboard or the HTTP API
+#[derive(Debug, Clone, PartialEq)]
+// without the `web` feature, some only come from the control API
+#[cfg_attr(not(feature = "web"), allow(dead_code))]
+pub enum MeetingCommand {
+    Next,
+    Prev,
+    Shuffle,
+    // reshuffle only those who haven't spoken yet
+    ShuffleRemaining,
+    // stop (or allow again) reshuffling
+    ToggleOrderLock,
+    Reset,
+    // pause or resume the clock
+    Pause,
+    // say why the meeting is paused, for the pause ledger
+    PauseReason(String),
+    // skip someone who didn't show up
+    Absent(String),
+    // skip the current speaker, who didn't show up, keeping them in the order
+    MarkAbsent,
+    // bring back whoever was last marked absent with MarkAbsent
+    UnmarkAbsent,
+    // someone checking in as absent or async ahead of their turn; None means they'll be there
+    CheckIn {
+        name: String,
+        attendance: Option<Attendance>,
+        update: Option<String>,
+    },
+    // add or take away seconds of the meeting's remaining time
+    AdjustRemaining(i64),
+    // credit or deduct seconds to the current speaker
+    AdjustSpeaker(i64),
+    // queue a topic for someone's turn
+    AddTopic {
+        name: String,
+        text: String,
+    },
+    // check off the current speaker's next topic
+    CheckTopic,
+    // note where the current speaker starts in the recording
+    Mark,
+    // say what the time the meeting wasn't ticking through was
+    ResolveGap(GapChoice),
+}
+
+/// Anything the TUI can be asked to do, wherever it came from
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppCommand {
+    // a change to the meeting itself
+    Meeting(MeetingCommand),
+    // end the meeting and show the closing screen
+    Close,
+    Quit,
+    // start typing a topic for the current speaker
+    EditTopic,
+    // hide every team's members but the current speaker's
+    CollapseTeams,
+    ExpandTeams,
+    // switch the order between a list and a wheel
+    ToggleWheel,
+}
@@ -1,21 +1,348 @@
+use chrono::{NaiveDate, NaiveTime};
+use std::fs;
+use std::io;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
+use crate::avatar::ImageProtocol;
+use crate::chapters::ChapterFormat;
+use crate::error::{AppError, Result};
+use crate::escalation::EscalationRule;
+use crate::format::TimeFormat;
+use crate::keys::KeyboardLayout;
+#[cfg(feature = "tts")]
+use crate::notify::SpeechPattern;
+use crate::notify::{BellPattern, OscProtocol};
+use crate::order::OrderRule;
+use crate::preflight::MeetingWindow;
+use crate::queue::{DEFAULT_COALESCE, RateLimit};
+use crate::report::ReportFormat;
+use crate::retention::Retention;
+use crate::series::{Series, Template};
+use crate::session::{Clock, TimeUp};
+use crate::theme::{TimerStyle, TimerTheme};
+use crate::transcript::TranscriptFormat;
+
+// What `--checklist` asks the facilitator when it isn't given items of its own
+pub const DEFAULT_CHECKLIST: [&str; 3] = [
+    "Started on time",
+    "Parking lot reviewed",
+    "Action items assigned",
+];
+
 // Configuration structure for the app
 #[derive(Debug, Clone)]
 pub struct Config {
     pub title: String,
+    // whether the title comes from the `title.txt` template rather than `--title`
+    pub auto_title: bool,
+    // the day sprint 1 started, and how many weeks a sprint lasts, for the sprint in the title,
+    // the sprint day in the order and `nextup stats --sprint`
+    pub sprint_epoch: Option<NaiveDate>,
+    pub sprint_weeks: u32,
     pub names_file: String,
     pub duration: Duration,
+    pub window: Option<MeetingWindow>,
+    pub rounds: Vec<String>,
+    pub warm_up: Duration,
+    pub cool_down: Duration,
+    pub slot: Option<Duration>,
+    pub auto_advance: bool,
+    // show the current speaker's slot and warn as it runs out, with a bell too if asked
+    pub per_person: bool,
+    pub per_person_bell: bool,
+    pub hard_cutoff: Option<Duration>,
+    pub time_up: TimeUp,
+    pub clock: Clock,
+    pub keyboard: KeyboardLayout,
     pub hide_timer: bool,
+    pub timer_style: TimerStyle,
+    // how speakers' times are written in the order and on the closing screen
+    pub time_format: TimeFormat,
+    pub summary_time_format: TimeFormat,
+    pub timer_theme: TimerTheme,
+    // what happens as time runs out, e.g. `2m=theme:urgent`
+    pub escalation: Vec<EscalationRule>,
+    pub bells: Option<BellPattern>,
+    pub bell_sound: Option<PathBuf>,
+    #[cfg(feature = "tts")]
+    pub speak: Option<SpeechPattern>,
+    pub terminal_notify: bool,
+    pub osc_protocol: Option<OscProtocol>,
+    // system notifications whose buttons control the meeting
+    #[cfg(feature = "desktop")]
+    pub desktop_notify: bool,
+    // an avatar (or initials) next to each name, and how images reach the terminal
+    pub avatars: bool,
+    #[cfg_attr(not(feature = "avatars"), allow(dead_code))]
+    pub image_protocol: Option<ImageProtocol>,
+    pub widget: bool,
+    // start with the order drawn as a wheel rather than a list
+    pub wheel: bool,
+    // a small bar in each row of the order filling up with the time spoken
+    pub row_bars: bool,
+    // chart of the turns left against the clock, with the planned and projected pace
+    pub burndown: bool,
+    // put each team in alphabetical order after the shuffle, by the rules of this language
+    pub sort_names: bool,
+    pub collation_locale: Option<String>,
+    pub kiosk: bool,
+    // one meeting run with nobody facilitating, posted to this Slack channel if there is one
+    pub autopilot: bool,
+    pub autopilot_channel: Option<String>,
+    pub serve: Option<SocketAddr>,
+    pub api_token: Option<String>,
+    // how long the web view's share link works
+    pub share_ttl: Duration,
+    pub recording_start: Option<NaiveTime>,
+    pub chapters_file: Option<PathBuf>,
+    pub chapter_format: ChapterFormat,
+    // transcript skeleton written when the meeting ends, and the template it's filled in from
+    pub transcript: Option<PathBuf>,
+    pub transcript_template: Option<PathBuf>,
+    // Markdown or org-mode; by default from the transcript file's extension
+    pub transcript_format: Option<TranscriptFormat>,
+    // summary written when the meeting ends; by default its format is from the file's extension
+    pub report_path: Option<PathBuf>,
+    pub report_format: Option<ReportFormat>,
+    pub questions: bool,
+    pub questions_file: Option<String>,
+    // suggest who hands over to whom at every handover, from these phrases
+    pub handoffs: bool,
+    pub handoffs_file: Option<String>,
+    pub topics_file: Option<String>,
+    pub dnd: bool,
+    pub slack_user_token: Option<String>,
+    pub slack_bot_token: Option<String>,
+    // where `nextup remind` and the daemon post that the standup is coming up, and how long before
+    pub remind_channel: Option<String>,
+    pub remind_before: Duration,
+    // lock the order the reminder announces
+    pub lock_order: bool,
+    // shuffles favour whoever has gone late in recent meetings
+    pub fair: bool,
+    // people with a written update keep their turn, which shows it; everyone else speaks live
+    pub hybrid: bool,
+    pub updates_dir: Option<PathBuf>,
+    // one member per team speaks, for a scrum of scrums
+    pub representatives: bool,
+    // OTLP/HTTP collector the meeting's trace is sent to, and headers for it
+    pub otlp_endpoint: Option<String>,
+    pub otlp_headers: Vec<(String, String)>,
+    pub chrome_trace: Option<PathBuf>,
+    pub coalesce: Duration,
+    pub rate_limits: Vec<RateLimit>,
+    pub series: Series,
+    // how long the series history is kept
+    pub retention: Option<Retention>,
+    // keep hashed identifiers for people in the history, rather than names
+    pub hash_names: bool,
+    pub rotate_facilitator: bool,
+    // rules the speaking order keeps to, e.g. `Alice before Bob`
+    pub order_rules: Vec<OrderRule>,
+    // more team sources merged into the team file, e.g. calendar attendees or a GitHub team
+    pub sources: Vec<String>,
+    // whether people only the other sources know about join the meeting
+    pub add_unknown: bool,
+    // people joining this meeting only, who the series keeps nothing about
+    pub guests: Vec<String>,
+    // exported calendar (.ics) whose next event the meeting counts down to
+    pub calendar: Option<PathBuf>,
+    // offer to check in as absent anyone who missed this many meetings in a row; 0 never asks
+    pub absence_streak: usize,
+    // flag anyone who hasn't spoken live in this many standups; 0 never does
+    pub silent_after: usize,
+    // talk time each person has across the series' meetings in a week, with hints as it runs out
+    pub weekly_budget: Option<Duration>,
+    // what the facilitator ticks off before the closing screen; none skips it
+    pub checklist: Vec<String>,
+    // follow another instance's meeting without controlling it
+    pub attach: bool,
+    // share the meeting with instances on the same network: follow one already running it, or
+    // lead it
+    pub lan: bool,
+    // the instance to follow, found on the network at startup
+    pub lan_leader: Option<SocketAddr>,
+    // continue another instance's meeting from its autosave
+    pub take_over: bool,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             title: "Team daily standup".to_string(),
+            auto_title: false,
+            sprint_epoch: None, // no sprints
+            sprint_weeks: 2,
             names_file: "team.txt".to_string(),
             duration: Duration::from_secs(15 * 60), // 15min
+            window: None,                           // no scheduled time
+            rounds: Vec::new(),                     // a single round
+            warm_up: Duration::ZERO,                // the first turn starts right away
+            cool_down: Duration::ZERO,              // no time kept for wrap-up
+            slot: None,                             // split the duration evenly
+            auto_advance: false,
+            per_person: false,
+            per_person_bell: false,
+            hard_cutoff: None,         // speakers can run over
+            time_up: TimeUp::Overtime, // keep counting
+            clock: Clock::Wall,        // time asleep counts
+            keyboard: KeyboardLayout::Letters,
             hide_timer: false,
+            timer_style: TimerStyle::Bar,
+            time_format: TimeFormat::Words, // 3m 5s
+            summary_time_format: TimeFormat::Words,
+            timer_theme: TimerTheme::default(),
+            escalation: Vec::new(), // just the gauge's colors
+            bells: None,            // silent
+            bell_sound: None,       // terminal bell
+            #[cfg(feature = "tts")]
+            speak: None, // no spoken announcements
+            terminal_notify: false,
+            osc_protocol: None, // detected from the environment
+            #[cfg(feature = "desktop")]
+            desktop_notify: false,
+            avatars: false,
+            image_protocol: None, // detected from the environment
+            widget: false,
+            wheel: false,
+            row_bars: false,
+            burndown: false,
+            sort_names: false,
+            collation_locale: None, // the language of the user's locale
+            kiosk: false,
+            autopilot: false,
+            autopilot_channel: None, // shown on the terminal
+            serve: None,             // no web view
+            api_token: None,         // control API disabled
+            share_ttl: Duration::from_secs(8 * 60 * 60),
+            recording_start: None, // when the meeting starts
+            chapters_file: None,   // printed on exit
+            chapter_format: ChapterFormat::YouTube,
+            transcript: None,          // no transcript
+            transcript_template: None, // the built-in Markdown one
+            transcript_format: None,   // from the file name
+            report_path: None,         // no report
+            report_format: None,       // from the file name
+            questions: false,
+            questions_file: None, // embedded question bank
+            handoffs: false,
+            handoffs_file: None, // embedded phrases
+            topics_file: None,   // topics only come in while the meeting runs
+            dnd: false,
+            slack_user_token: None,
+            slack_bot_token: None, // check-in reactions aren't read
+            remind_channel: None,  // no reminder
+            remind_before: Duration::from_secs(5 * 60),
+            lock_order: false,
+            fair: false,
+            hybrid: false, // async people are skipped
+            updates_dir: None,
+            representatives: false, // everyone speaks
+            otlp_endpoint: None,    // no trace
+            otlp_headers: Vec::new(),
+            chrome_trace: None, // no trace file
+            coalesce: DEFAULT_COALESCE,
+            rate_limits: Vec::new(), // unlimited
+            series: Series::new("Team daily standup"),
+            retention: None, // keep everything
+            hash_names: false,
+            rotate_facilitator: false,
+            order_rules: Vec::new(),
+            sources: Vec::new(),
+            add_unknown: false,
+            guests: Vec::new(),
+            calendar: None, // no countdown to the next event
+            absence_streak: 3,
+            silent_after: 3,
+            weekly_budget: None, // no hints
+            checklist: Vec::new(),
+            attach: false,
+            lan: false, // not shared on the network
+            lan_leader: None,
+            take_over: false,
         }
     }
 }
+
+/// Where settings shared by every series are kept (e.g. `~/.config/nextup/config.toml`)
+pub fn file_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("nextup").join("config.toml"))
+}
+
+impl Config {
+    /// Settings from the config file, which the series template and flags override; none set
+    /// when there's no file
+    pub fn load(path: &Path) -> Result<Template> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Template::default()),
+            Err(e) => {
+                return Err(AppError::HistoryError(format!("{}: {}", path.display(), e)).into());
+            }
+        };
+
+        toml::from_str(&content).map_err(|e| {
+            // where it went wrong, counting from 1 as editors do
+            let at = e.span().map_or(0, |span| span.start);
+            let before = &content[..at];
+            AppError::ConfigError {
+                path: path.to_path_buf(),
+                line: before.matches('\n').count() + 1,
+                column: before.chars().rev().take_while(|&c| c != '\n').count() + 1,
+                message: e.message().to_string(),
+            }
+            .into()
+        })
+    }
+}
+
+/// Write a config file with every setting it takes commented out, for `nextup config init`
+pub fn init(path: &Path, force: bool) -> Result<()> {
+    if path.exists() && !force {
+        println!("Kept {} (--force replaces it)", path.display());
+        return Ok(());
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, SCAFFOLD)?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+// The file `nextup config init` writes, each setting at its default, or an example for the ones
+// without one
+const SCAFFOLD: &str = r#"# nextup settings for every series. A series template (--save-template) overrides
+# these, and flags override both. Uncomment a line to set it; each shows the default, or
+# an example where there's none.
+
+# title = "Team daily standup"
+# names_file = "team.txt"
+# duration_minutes = 15
+
+# when the meeting is held, e.g. "09:30-09:45"
+# window = "09:30-09:45"
+
+# more than one round, e.g. ["Yesterday", "Today", "Blockers"]
+# rounds = []
+
+# keyboard layout the shortcuts follow: letters (the letter typed), or azerty, qwertz,
+# dvorak or colemak (where the QWERTY key would be)
+# keyboard = "letters"
+
+# default, plain, traffic, urgent, words, or a JSON theme file
+# timer_theme = "default"
+
+# what happens as time runs out: switch the timer theme, blink its border, raise a
+# notification, or run a command, e.g. ["2m=theme:urgent", "1m=blink", "0=notify"]
+# escalation = []
+
+# rules the speaking order keeps to, e.g. ["Alice before Bob", "Carol not first"]
+# order_rules = []
+
+# the day sprint 1 started, and how many weeks a sprint lasts
+# sprint_epoch = "2025-01-06"
+# sprint_weeks = 2
+"#;
@@ -0,0 +1,232 @@
+use chrono::{DateTime, Local};
+use clap::CommandFactory;
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+
+use crate::app::App;
+use crate::autopilot;
+use crate::bundle::SECRET_VARS;
+use crate::config::Config;
+use crate::error::Result;
+use crate::events;
+use crate::kiosk;
+use crate::lock::{Acquired, Lock};
+use crate::preflight::MeetingWindow;
+use crate::remind;
+use crate::title;
+
+// Name of the generated systemd user unit
+const UNIT_NAME: &str = "nextup.service";
+
+/// Wait for the meeting window on each workday and run the meeting then: the TUI when there's a
+/// terminal to show it on, otherwise headless with JSON events (and the web view with `--serve`)
+pub async fn run(mut config: Config) -> Result<()> {
+    let window = config
+        .window
+        .ok_or_else(|| anyhow::Error::msg("nextup daemon needs a --window to start meetings at"))?;
+    let interactive = io::stdin().is_terminal() && io::stdout().is_terminal();
+
+    let mut held_today = false;
+    loop {
+        if !wait_for_meeting(&mut config, window, interactive, held_today).await? {
+            return Ok(());
+        }
+        held_today = true;
+
+        // someone may have started today's meeting by hand
+        let _lock = match Lock::acquire(&config.series)? {
+            Acquired::Locked(lock) => lock,
+            Acquired::HeldBy(pid) => {
+                eprintln!(
+                    "Skipping today's meeting, it's already running (pid {})",
+                    pid
+                );
+                continue;
+            }
+        };
+
+        let result = if config.autopilot {
+            autopilot::run_meeting(config.clone(), interactive).await
+        } else if interactive {
+            match App::new(config.clone()).await {
+                Ok(mut app) => app.run().await,
+                Err(e) => Err(e),
+            }
+        } else {
+            events::run(config.clone()).await
+        };
+        // one bad meeting shouldn't stop tomorrow's
+        if let Err(e) = result {
+            eprintln!("Meeting failed: {}", e);
+        }
+    }
+}
+
+/// Wait for the next meeting in the window, today's unless it's been held already, posting the
+/// reminder on the way. Returns false if someone quit instead.
+pub async fn wait_for_meeting(
+    config: &mut Config,
+    window: MeetingWindow,
+    interactive: bool,
+    held_today: bool,
+) -> Result<bool> {
+    let start = window.next_start(Local::now(), held_today, true);
+    if let Err(e) = title::refresh(config) {
+        eprintln!("Couldn't name today's meeting: {}", e);
+    }
+
+    // a reminder only goes out ahead of time, not once the meeting is due
+    if config.remind_channel.is_some() {
+        let remind_at = start - chrono::Duration::from_std(config.remind_before)?;
+        if remind_at > Local::now() {
+            if !wait_until(config, interactive, remind_at, start).await? {
+                return Ok(false);
+            }
+            if let Err(e) = remind::post(config) {
+                eprintln!("Couldn't post the reminder: {}", e);
+            }
+        }
+    }
+    wait_until(config, interactive, start, start).await
+}
+
+/// Wait until `wake`, counting down to the meeting at `start` on a terminal. Returns false if
+/// someone quit instead.
+async fn wait_until(
+    config: &Config,
+    interactive: bool,
+    wake: DateTime<Local>,
+    start: DateTime<Local>,
+) -> Result<bool> {
+    if interactive {
+        return kiosk::wait_until(config, wake, start);
+    }
+
+    let now = Local::now();
+    if wake > now {
+        eprintln!(
+            "Next {} {} at {}",
+            config.title,
+            kiosk::day_name(start, now),
+            start.format("%H:%M")
+        );
+        let wait = (wake - now).to_std().unwrap_or_default();
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            _ = tokio::signal::ctrl_c() => return Ok(false),
+        }
+    }
+    Ok(true)
+}
+
+/// Write a systemd user unit that runs `nextup daemon` with the same options, for `nextup daemon install`
+pub fn install() -> Result<()> {
+    let dir = dirs::config_dir()
+        .map(|dir| dir.join("systemd").join("user"))
+        .ok_or_else(|| anyhow::Error::msg("couldn't find the config directory"))?;
+    let path = dir.join(UNIT_NAME);
+
+    fs::create_dir_all(&dir)?;
+    fs::write(&path, unit()?)?;
+
+    println!("Wrote {}", path.display());
+    println!("Start it now and at every login with:");
+    println!("  systemctl --user daemon-reload");
+    println!("  systemctl --user enable --now {}", UNIT_NAME);
+    println!("To keep it running while logged out: loginctl enable-linger");
+    Ok(())
+}
+
+/// The unit file, running this binary with this invocation's arguments minus `install`, and
+/// the `NEXTUP_*` settings from its environment other than secrets
+fn unit() -> Result<String> {
+    let exe = env::current_exe()?;
+    let command: Vec<String> = std::iter::once(exe.display().to_string())
+        .chain(daemon_args()?)
+        .map(|arg| quote(&arg))
+        .collect();
+    let working_dir = env::current_dir().unwrap_or_else(|_| PathBuf::from("/"));
+
+    let mut vars: Vec<(String, String)> = env::vars()
+        .filter(|(name, _)| name.starts_with("NEXTUP_") && !SECRET_VARS.contains(&name.as_str()))
+        .collect();
+    vars.sort();
+    let environment: String = vars
+        .iter()
+        .map(|(name, value)| format!("Environment={}\n", quote_env(name, value)))
+        .collect();
+
+    Ok(format!(
+        "[Unit]\n\
+         Description=nextup standup daemon\n\
+         After=network-online.target\n\
+         \n\
+         [Service]\n\
+         ExecStart={}\n\
+         WorkingDirectory={}\n\
+         {}\
+         Restart=on-failure\n\
+         RestartSec=10\n\
+         \n\
+         [Install]\n\
+         WantedBy=default.target\n",
+        command.join(" "),
+        working_dir.display(),
+        environment
+    ))
+}
+
+/// This invocation's arguments without the `install` subcommand. Which `install` that is (not,
+/// say, the value in `--title install`) is left to clap: it's the one whose removal still parses
+/// as a bare `nextup daemon`.
+fn daemon_args() -> Result<Vec<String>> {
+    let args: Vec<String> = env::args().collect();
+    (1..args.len())
+        .rev()
+        .filter(|&i| args[i] == "install")
+        .map(|i| {
+            let mut rest = args.clone();
+            rest.remove(i);
+            rest
+        })
+        .find(|rest| {
+            crate::Args::command()
+                .try_get_matches_from(rest)
+                .is_ok_and(|matches| {
+                    matches.subcommand().is_some_and(|(name, daemon)| {
+                        name == "daemon" && daemon.subcommand().is_none()
+                    })
+                })
+        })
+        .map(|rest| rest.into_iter().skip(1).collect())
+        .ok_or_else(|| anyhow::Error::msg("couldn't find `daemon install` in the arguments"))
+}
+
+/// An `Environment=` assignment, quoted so systemd takes the value as it is
+fn quote_env(name: &str, value: &str) -> String {
+    format!(
+        "\"{}={}\"",
+        name,
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('%', "%%")
+    )
+}
+
+/// Quote an argument for ExecStart when it needs it
+fn quote(arg: &str) -> String {
+    if arg.is_empty() || arg.contains([' ', '"', '\'', '\\', '$', '%']) {
+        format!(
+            "\"{}\"",
+            arg.replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('$', "$$")
+                .replace('%', "%%")
+        )
+    } else {
+        arg.to_string()
+    }
+}
@@ -0,0 +1,440 @@
+use chrono::Local;
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute,
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+};
+use ratatui::{
+    Frame, Terminal,
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Sparkline, Table, Tabs},
+};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::export;
+use crate::format::format_duration;
+use crate::history::{History, MeetingRecord};
+use crate::privacy;
+use crate::stats::{self, DateRange};
+
+// Preset ranges `R` cycles through, in days back from today; None is everything
+const RANGE_PRESETS: [Option<i64>; 4] = [None, Some(7), Some(30), Some(90)];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Tab {
+    Fairness,
+    Durations,
+    Trends,
+    Streaks,
+}
+
+impl Tab {
+    const ALL: [Tab; 4] = [Tab::Fairness, Tab::Durations, Tab::Trends, Tab::Streaks];
+
+    fn title(self) -> &'static str {
+        match self {
+            Tab::Fairness => "Fairness",
+            Tab::Durations => "Durations",
+            Tab::Trends => "Trends",
+            Tab::Streaks => "Streaks",
+        }
+    }
+}
+
+/// A tab's contents as a table, which is also what gets exported
+struct View {
+    header: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+    // a line above the table, e.g. the team's on-time streak
+    summary: Option<String>,
+}
+
+/// Interactive `nextup stats --tui`
+struct Dashboard {
+    series: String,
+    history: History,
+    tab: usize,
+    ranges: Vec<DateRange>,
+    range: usize,
+    // result of the last export, shown in place of the help line
+    status: Option<String>,
+}
+
+/// Browse the series' stats until the user quits
+pub fn run(config: &Config, range: DateRange) -> Result<()> {
+    let today = Local::now().date_naive();
+    let mut ranges = Vec::new();
+    // a range given on the command line comes first
+    if range.is_bounded() {
+        ranges.push(range);
+    }
+    ranges.extend(RANGE_PRESETS.iter().map(|days| match days {
+        Some(days) => DateRange::last_days(today, *days),
+        None => DateRange::default(),
+    }));
+
+    let mut history = History::load(&config.series)?;
+    privacy::identify(config, &mut history);
+    let mut dashboard = Dashboard {
+        series: config.series.id().to_string(),
+        history,
+        tab: 0,
+        ranges,
+        range: 0,
+        status: None,
+    };
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+    let res = dashboard.run(&mut terminal);
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+
+    res
+}
+
+impl Dashboard {
+    fn run(&mut self, terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
+        loop {
+            terminal.draw(|f| self.render(f))?;
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            match (key.code, key.modifiers) {
+                (KeyCode::Char('q') | KeyCode::Esc, _) => return Ok(()),
+                (KeyCode::Char('c'), KeyModifiers::CONTROL) => return Ok(()),
+                (KeyCode::Tab | KeyCode::Right, _) => self.tab = (self.tab + 1) % Tab::ALL.len(),
+                (KeyCode::BackTab | KeyCode::Left, _) => {
+                    self.tab = (self.tab + Tab::ALL.len() - 1) % Tab::ALL.len()
+                }
+                (KeyCode::Char(c @ '1'..='4'), _) => self.tab = c as usize - '1' as usize,
+                (KeyCode::Char('r'), _) => self.range = (self.range + 1) % self.ranges.len(),
+                (KeyCode::Char('e'), _) => self.status = Some(self.export()),
+                _ => continue,
+            }
+            if key.code != KeyCode::Char('e') {
+                self.status = None;
+            }
+        }
+    }
+
+    fn current_tab(&self) -> Tab {
+        Tab::ALL[self.tab]
+    }
+
+    fn meetings(&self) -> Vec<&MeetingRecord> {
+        let range = &self.ranges[self.range];
+        self.history
+            .meetings()
+            .iter()
+            .filter(|m| range.includes(m))
+            .collect()
+    }
+
+    /// Write the current tab to a CSV file in the working directory
+    fn export(&self) -> String {
+        let view = self.view();
+        let path = PathBuf::from(format!(
+            "nextup-{}-{}.csv",
+            self.current_tab().title().to_lowercase(),
+            Local::now().date_naive()
+        ));
+        match export::write_csv(&path, Some(&view.header), &view.rows) {
+            Ok(()) => format!("Saved {}", path.display()),
+            Err(e) => format!("Export failed: {}", e),
+        }
+    }
+
+    fn view(&self) -> View {
+        let meetings = self.meetings();
+        match self.current_tab() {
+            Tab::Fairness => fairness(&meetings),
+            Tab::Durations => durations(&meetings),
+            Tab::Trends => trends(&meetings),
+            Tab::Streaks => streaks(&meetings),
+        }
+    }
+
+    fn render(&self, f: &mut Frame) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .split(f.area());
+
+        let tabs = Tabs::new(
+            Tab::ALL
+                .iter()
+                .enumerate()
+                .map(|(i, tab)| format!("{} {}", i + 1, tab.title())),
+        )
+        .select(self.tab)
+        .highlight_style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            "{} · {}",
+            self.series,
+            self.ranges[self.range].label()
+        )));
+        f.render_widget(tabs, chunks[0]);
+
+        self.render_view(f, chunks[1]);
+
+        let help = self.status.clone().unwrap_or_else(|| {
+            "<Tab/←→> Switch view | <R> Date range | <E> Export CSV | <Q> Quit".to_string()
+        });
+        f.render_widget(
+            Paragraph::new(help)
+                .style(Style::default().fg(Color::Gray))
+                .alignment(ratatui::layout::Alignment::Center),
+            chunks[2],
+        );
+    }
+
+    fn render_view(&self, f: &mut Frame, area: Rect) {
+        let view = self.view();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(self.current_tab().title());
+
+        if view.rows.is_empty() {
+            f.render_widget(
+                Paragraph::new("No meetings recorded in this range.").block(block),
+                area,
+            );
+            return;
+        }
+
+        let mut constraints = Vec::new();
+        if view.summary.is_some() {
+            constraints.push(Constraint::Length(1));
+        }
+        // the trend gets a chart of the scores over the table
+        let chart = self.current_tab() == Tab::Trends;
+        if chart {
+            constraints.push(Constraint::Length(6));
+        }
+        constraints.push(Constraint::Min(0));
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+        let parts = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(constraints)
+            .split(inner);
+        let mut parts = parts.iter();
+
+        if let Some(summary) = &view.summary
+            && let Some(part) = parts.next()
+        {
+            f.render_widget(Paragraph::new(summary.as_str()), *part);
+        }
+        if chart && let Some(part) = parts.next() {
+            let scores: Vec<u64> = self.meetings().iter().map(|m| m.score as u64).collect();
+            let sparkline = Sparkline::default()
+                .block(Block::default().title("Health score"))
+                .data(&scores)
+                .max(100)
+                .style(Style::default().fg(Color::Green));
+            f.render_widget(sparkline, *part);
+        }
+
+        let Some(part) = parts.next() else {
+            return;
+        };
+        let widths: Vec<Constraint> = view
+            .header
+            .iter()
+            .enumerate()
+            .map(|(i, _)| {
+                let longest = view
+                    .rows
+                    .iter()
+                    .map(|row| row[i].chars().count())
+                    .chain([view.header[i].len()])
+                    .max()
+                    .unwrap_or(0);
+                Constraint::Length(longest as u16)
+            })
+            .collect();
+        let table = Table::new(
+            view.rows
+                .iter()
+                .map(|row| Row::new(row.iter().map(|cell| Cell::from(cell.as_str())))),
+            widths,
+        )
+        .header(Row::new(view.header.clone()).style(Style::default().add_modifier(Modifier::BOLD)))
+        .column_spacing(2);
+        f.render_widget(table, *part);
+    }
+}
+
+/// Everyone's share of the talk time, compared with an even split
+fn fairness(meetings: &[&MeetingRecord]) -> View {
+    let mut totals: BTreeMap<&str, (usize, u64)> = BTreeMap::new();
+    for turn in meetings.iter().flat_map(|m| &m.turns) {
+        let entry = totals.entry(&turn.name).or_default();
+        entry.0 += 1;
+        entry.1 += turn.secs;
+    }
+
+    let all: u64 = totals.values().map(|(_, secs)| secs).sum();
+    let even = 100.0 / totals.len().max(1) as f64;
+    let mut rows: Vec<(f64, Vec<String>)> = totals
+        .into_iter()
+        .map(|(name, (spoke, secs))| {
+            let share = secs as f64 * 100.0 / all.max(1) as f64;
+            (
+                share,
+                vec![
+                    name.to_string(),
+                    spoke.to_string(),
+                    format_duration(Duration::from_secs(secs)),
+                    format!("{:.0}%", share),
+                    format!("{:+.0}%", share - even),
+                ],
+            )
+        })
+        .collect();
+    rows.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    View {
+        header: vec!["Name", "Meetings", "Talk time", "Share", "vs even"],
+        rows: rows.into_iter().map(|(_, row)| row).collect(),
+        summary: None,
+    }
+}
+
+/// Turn lengths per person, as in `nextup stats`
+fn durations(meetings: &[&MeetingRecord]) -> View {
+    let owned: Vec<MeetingRecord> = meetings.iter().map(|m| (*m).clone()).collect();
+    let (bucket, speakers) = stats::turn_durations(&owned);
+    let secs = |s: u64| format_duration(Duration::from_secs(s));
+
+    View {
+        header: vec!["Name", "Turns", "Min", "Median", "Max", "Histogram"],
+        rows: speakers
+            .iter()
+            .map(|s| {
+                vec![
+                    s.name.clone(),
+                    s.turns.to_string(),
+                    secs(s.min_secs),
+                    secs(s.median_secs),
+                    secs(s.max_secs),
+                    stats::sparkline(&s.histogram),
+                ]
+            })
+            .collect(),
+        summary: (!speakers.is_empty()).then(|| {
+            format!(
+                "Each histogram bar is {}, from 0s on the left",
+                format_duration(bucket)
+            )
+        }),
+    }
+}
+
+/// Meeting by meeting, most recent first
+fn trends(meetings: &[&MeetingRecord]) -> View {
+    let average =
+        meetings.iter().map(|m| m.score as f64).sum::<f64>() / meetings.len().max(1) as f64;
+
+    View {
+        header: vec!["Date", "Score", "Length", "Overtime", "Absent"],
+        rows: meetings
+            .iter()
+            .rev()
+            .map(|m| {
+                vec![
+                    m.date.to_string(),
+                    m.score.to_string(),
+                    format_duration(Duration::from_secs(m.elapsed_secs)),
+                    format_duration(Duration::from_secs(m.overtime_secs)),
+                    m.absent.to_string(),
+                ]
+            })
+            .collect(),
+        summary: (!meetings.is_empty()).then(|| {
+            format!(
+                "Average score {:.0} over {} meetings",
+                average,
+                meetings.len()
+            )
+        }),
+    }
+}
+
+/// Runs of meetings each person spoke in, and the team's run of meetings that ended on time
+fn streaks(meetings: &[&MeetingRecord]) -> View {
+    // only meetings that recorded who spoke can tell
+    let with_turns: Vec<&&MeetingRecord> =
+        meetings.iter().filter(|m| !m.turns.is_empty()).collect();
+
+    let mut names: Vec<&str> = with_turns
+        .iter()
+        .flat_map(|m| m.turns.iter().map(|t| t.name.as_str()))
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+
+    let mut rows: Vec<(usize, Vec<String>)> = names
+        .into_iter()
+        .map(|name| {
+            let (current, longest) = runs(
+                with_turns
+                    .iter()
+                    .map(|m| m.turns.iter().any(|t| t.name == name)),
+            );
+            (
+                current,
+                vec![name.to_string(), current.to_string(), longest.to_string()],
+            )
+        })
+        .collect();
+    rows.sort_by(|a, b| b.0.cmp(&a.0).then(a.1[0].cmp(&b.1[0])));
+
+    let (on_time, longest_on_time) = runs(meetings.iter().map(|m| m.overtime_secs == 0));
+    View {
+        header: vec!["Name", "Current streak", "Longest streak"],
+        rows: rows.into_iter().map(|(_, row)| row).collect(),
+        summary: (!meetings.is_empty()).then(|| {
+            format!(
+                "On time {} meetings in a row (longest {})",
+                on_time, longest_on_time
+            )
+        }),
+    }
+}
+
+/// The current (trailing) and longest runs of `true`, oldest first
+fn runs(hits: impl Iterator<Item = bool>) -> (usize, usize) {
+    let (mut current, mut longest) = (0, 0);
+    for hit in hits {
+        current = if hit { current + 1 } else { 0 };
+        longest = longest.max(current);
+    }
+    (current, longest)
+}
@@ -0,0 +1,173 @@
+use std::fs;
+use std::process;
+use std::time::{Duration, Instant};
+
+use crate::app::App;
+use crate::command::{AppCommand, MeetingCommand};
+use crate::config::Config;
+use crate::error::Result;
+use crate::series::Series;
+
+// The made-up team the demo meeting is held with
+const DEMO_TEAM: &str = "Ada\nGrace | role=lead\nLinus\nMargaret\nKen\n";
+
+/// Something the demo does once it's been running for `at`: a command, as if its key were
+/// pressed, and a new tip explaining it
+struct Step {
+    at: Duration,
+    command: Option<AppCommand>,
+    tip: Option<&'static str>,
+}
+
+impl Step {
+    fn new(secs: u64, command: Option<AppCommand>, tip: Option<&'static str>) -> Self {
+        Self {
+            at: Duration::from_secs(secs),
+            command,
+            tip,
+        }
+    }
+}
+
+/// The scripted meeting `nextup demo` plays, going through the shortcuts one by one
+pub struct Script {
+    steps: Vec<Step>,
+    started: Instant,
+    next: usize,
+    tip: Option<&'static str>,
+}
+
+impl Script {
+    pub fn new() -> Self {
+        let meeting = |command| Some(AppCommand::Meeting(command));
+        let steps = vec![
+            Step::new(
+                0,
+                None,
+                Some(
+                    "Welcome to the nextup demo! This is a practice standup with a made-up team, and nothing is saved. Watch, or press the keys yourself; Ctrl+C leaves.",
+                ),
+            ),
+            Step::new(
+                7,
+                meeting(MeetingCommand::Shuffle),
+                Some(
+                    "Everyone gets a random speaking order. Ctrl+N reshuffles it, and Alt+N reshuffles only the people who haven't spoken yet.",
+                ),
+            ),
+            Step::new(
+                15,
+                meeting(MeetingCommand::Next),
+                Some(
+                    "Tab or ↓ hands over to the next speaker. Everyone's time is kept next to their name.",
+                ),
+            ),
+            Step::new(
+                23,
+                None,
+                Some(
+                    "The bar at the bottom counts down the whole meeting, and changes color as time runs low.",
+                ),
+            ),
+            Step::new(
+                31,
+                meeting(MeetingCommand::AddTopic {
+                    name: "Grace".to_string(),
+                    text: "Release date".to_string(),
+                }),
+                Some(
+                    "T notes a topic for the current speaker. Topics show up on their owner's turn, and Enter checks them off.",
+                ),
+            ),
+            Step::new(
+                39,
+                meeting(MeetingCommand::Next),
+                Some("↑ goes back a speaker, if someone was skipped by mistake."),
+            ),
+            Step::new(44, meeting(MeetingCommand::Prev), None),
+            Step::new(
+                49,
+                meeting(MeetingCommand::Next),
+                Some(
+                    "Ctrl+= and Ctrl+- add or take a minute off the meeting, and Alt+= and Alt+- the current speaker's time.",
+                ),
+            ),
+            Step::new(54, meeting(MeetingCommand::AdjustRemaining(60)), None),
+            Step::new(
+                59,
+                meeting(MeetingCommand::Next),
+                Some(
+                    "L locks the order so it can't be reshuffled by accident, and M marks where each speaker starts in a recording.",
+                ),
+            ),
+            Step::new(
+                67,
+                meeting(MeetingCommand::Next),
+                Some("Q ends the meeting and shows how it went."),
+            ),
+            Step::new(
+                73,
+                Some(AppCommand::Close),
+                Some(
+                    "The closing screen scores the meeting and lists everyone's talk time. Press any key to leave the demo.",
+                ),
+            ),
+        ];
+
+        Self {
+            steps,
+            started: Instant::now(),
+            next: 0,
+            tip: None,
+        }
+    }
+
+    /// The next command whose time has come, if any, moving the tip along with it
+    pub fn due(&mut self) -> Option<AppCommand> {
+        while let Some(step) = self.steps.get_mut(self.next) {
+            if self.started.elapsed() < step.at {
+                return None;
+            }
+            self.next += 1;
+            if let Some(tip) = step.tip {
+                self.tip = Some(tip);
+            }
+            if let Some(command) = step.command.take() {
+                return Some(command);
+            }
+        }
+        None
+    }
+
+    /// What the demo is pointing out right now
+    pub fn tip(&self) -> Option<&str> {
+        self.tip
+    }
+}
+
+/// Play the demo meeting, for `nextup demo`. It runs from a throwaway state directory, so the
+/// series history and autosaves it writes are gone afterwards.
+pub async fn run(config: &Config) -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("nextup-demo-{}", process::id()));
+    fs::create_dir_all(&dir)?;
+    let names_file = dir.join("team.txt");
+    fs::write(&names_file, DEMO_TEAM)?;
+
+    let demo = Config {
+        title: "nextup demo".to_string(),
+        names_file: names_file.display().to_string(),
+        duration: Duration::from_secs(5 * 60),
+        keyboard: config.keyboard,
+        timer_theme: config.timer_theme.clone(),
+        series: Series::new("demo").in_dir(dir.clone()),
+        ..Config::default()
+    };
+
+    let result = async {
+        let mut app = App::new(demo).await?.with_demo(Script::new());
+        app.run().await
+    }
+    .await;
+    let _ = fs::remove_dir_all(&dir);
+    result
+}
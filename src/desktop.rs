@@ -0,0 +1,193 @@
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use crate::command::MeetingCommand;
+use crate::notify::find_command;
+
+// How long a notification stays up waiting for a button to be pressed
+const WAIT: Duration = Duration::from_secs(30);
+
+// The buttons on every notification: what the notifier reports when one is pressed, its
+// label (which some notifiers report instead) and the command it sends to the meeting
+const ACTIONS: [(&str, &str, MeetingCommand); 2] = [
+    ("next", "Next speaker", MeetingCommand::Next),
+    ("pause", "Pause", MeetingCommand::Pause),
+];
+
+// PowerShell's own app id, which Windows lets any script raise toasts under
+#[cfg(target_os = "windows")]
+const POWERSHELL_APP_ID: &str =
+    r"{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\WindowsPowerShell\v1.0\powershell.exe";
+
+/// The system's notifier, run as a command for each notification
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum System {
+    // libnotify's notify-send 0.7.10 or newer, which prints the action pressed with --wait
+    NotifySend,
+    // alerter on macOS, which prints the label of the button pressed
+    Alerter,
+    // plain macOS notifications through AppleScript, without buttons
+    AppleScript,
+    // a Windows toast raised from PowerShell, which waits for its Activated event
+    Toast,
+}
+
+impl System {
+    fn detect() -> Option<Self> {
+        if cfg!(target_os = "windows") {
+            Some(Self::Toast)
+        } else if cfg!(target_os = "macos") {
+            Some(if find_command(&["alerter"]).is_some() {
+                Self::Alerter
+            } else {
+                Self::AppleScript
+            })
+        } else {
+            find_command(&["notify-send"]).map(|_| Self::NotifySend)
+        }
+    }
+
+    fn command(self, title: &str, body: &str) -> Command {
+        let wait = WAIT.as_secs();
+        match self {
+            Self::NotifySend => {
+                let mut command = Command::new("notify-send");
+                command
+                    .arg("--app-name=nextup")
+                    .arg(format!("--expire-time={}", wait * 1000))
+                    .arg("--wait");
+                for (key, label, _) in &ACTIONS {
+                    command.arg(format!("--action={}={}", key, label));
+                }
+                command.arg(title).arg(body);
+                command
+            }
+            Self::Alerter => {
+                let labels: Vec<&str> = ACTIONS.iter().map(|(_, label, _)| *label).collect();
+                let mut command = Command::new("alerter");
+                command
+                    .args(["-title", title, "-message", body])
+                    .args(["-actions", &labels.join(",")])
+                    .args(["-timeout", &wait.to_string()]);
+                command
+            }
+            Self::AppleScript => {
+                let quote =
+                    |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+                let mut command = Command::new("osascript");
+                command.arg("-e").arg(format!(
+                    "display notification {} with title {}",
+                    quote(body),
+                    quote(title)
+                ));
+                command
+            }
+            Self::Toast => toast(title, body),
+        }
+    }
+}
+
+/// Native desktop notifications, with buttons that control the meeting, for `--desktop-notify`
+pub struct Desktop {
+    system: System,
+    // buttons pressed, sent from the threads waiting on each notification
+    pressed: Sender<MeetingCommand>,
+    commands: Receiver<MeetingCommand>,
+}
+
+impl Desktop {
+    /// Use the system's notifier, or None with a note when there isn't one
+    pub fn new() -> Option<Self> {
+        let Some(system) = System::detect() else {
+            eprintln!(
+                "No desktop notifier found (notify-send), so there are no desktop notifications"
+            );
+            return None;
+        };
+        let (pressed, commands) = mpsc::channel();
+        Some(Self {
+            system,
+            pressed,
+            commands,
+        })
+    }
+
+    /// Raise a notification, and wait in the background for one of its buttons to be pressed
+    pub fn notify(&self, title: &str, body: &str) {
+        let mut command = self.system.command(title, body);
+        let pressed = self.pressed.clone();
+        thread::spawn(move || {
+            // a notifier that fails shouldn't disturb the meeting
+            let Ok(output) = command.stderr(Stdio::null()).output() else {
+                return;
+            };
+            let reply = String::from_utf8_lossy(&output.stdout);
+            let reply = reply.trim();
+            if let Some((_, _, action)) = ACTIONS
+                .iter()
+                .find(|(key, label, _)| reply == *key || reply == *label)
+            {
+                let _ = pressed.send(action.clone());
+            }
+        });
+    }
+
+    /// The next command from a notification's button, if any were pressed
+    pub fn next_command(&mut self) -> Option<MeetingCommand> {
+        self.commands.try_recv().ok()
+    }
+}
+
+/// PowerShell raising a toast with the buttons and printing the one pressed
+#[cfg(target_os = "windows")]
+fn toast(title: &str, body: &str) -> Command {
+    let escape = |s: &str| {
+        s.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    };
+    let actions: String = ACTIONS
+        .iter()
+        .map(|(key, label, _)| {
+            format!(
+                r#"<action content="{}" arguments="{}" activationType="foreground"/>"#,
+                label, key
+            )
+        })
+        .collect();
+    let xml = format!(
+        r#"<toast><visual><binding template="ToastGeneric"><text>{}</text><text>{}</text></binding></visual><actions>{}</actions></toast>"#,
+        escape(title),
+        escape(body),
+        actions
+    );
+    let script = format!(
+        r#"
+[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] | Out-Null
+[Windows.Data.Xml.Dom.XmlDocument, Windows.Data.Xml.Dom.XmlDocument, ContentType = WindowsRuntime] | Out-Null
+$xml = New-Object Windows.Data.Xml.Dom.XmlDocument
+$xml.LoadXml($env:NEXTUP_TOAST)
+$toast = New-Object Windows.UI.Notifications.ToastNotification $xml
+Register-ObjectEvent -InputObject $toast -EventName Activated -SourceIdentifier pressed | Out-Null
+[Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('{}').Show($toast)
+$event = Wait-Event -SourceIdentifier pressed -Timeout {}
+if ($event) {{ $event.SourceEventArgs.Arguments }}
+"#,
+        POWERSHELL_APP_ID,
+        WAIT.as_secs()
+    );
+
+    let mut command = Command::new("powershell");
+    command
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .env("NEXTUP_TOAST", xml);
+    command
+}
+
+#[cfg(not(target_os = "windows"))]
+fn toast(_title: &str, _body: &str) -> Command {
+    unreachable!("toasts are only raised on Windows")
+}
@@ -0,0 +1,128 @@
+use chrono::Local;
+use minijinja::context;
+use std::process::Command;
+
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::slack::{SlackClient, Status};
+use crate::templates::Templates;
+
+// GNOME setting that controls notification banners
+const GNOME_SCHEMA: &str = "org.gnome.desktop.notifications";
+const GNOME_KEY: &str = "show-banners";
+
+// macOS Shortcuts the user creates to toggle a Focus mode
+const MACOS_ON_SHORTCUT: &str = "nextup dnd on";
+const MACOS_OFF_SHORTCUT: &str = "nextup dnd off";
+
+/// How the OS do-not-disturb state gets restored
+enum OsState {
+    GnomeBanners { previous: String },
+    MacShortcut,
+}
+
+/// Silences notifications while the meeting runs and restores them when dropped
+pub struct DoNotDisturb {
+    os: Option<OsState>,
+    slack: Option<(SlackClient, Status)>,
+}
+
+impl DoNotDisturb {
+    /// Turn on OS do-not-disturb and, when a user token is configured, set a Slack status.
+    /// Failures are reported but never stop the meeting.
+    pub fn enable(config: &Config) -> Self {
+        let os = enable_os().unwrap_or_else(|e| {
+            eprintln!("Could not enable do-not-disturb: {}", e);
+            None
+        });
+
+        let slack = config.slack_user_token.as_ref().and_then(|token| {
+            let client = SlackClient::new(token.clone());
+            match set_slack_status(&client, config) {
+                Ok(previous) => Some((client, previous)),
+                Err(e) => {
+                    eprintln!("Could not set Slack status: {}", e);
+                    None
+                }
+            }
+        });
+
+        Self { os, slack }
+    }
+}
+
+impl Drop for DoNotDisturb {
+    fn drop(&mut self) {
+        if let Some(state) = self.os.take()
+            && let Err(e) = restore_os(state)
+        {
+            eprintln!("Could not restore do-not-disturb: {}", e);
+        }
+
+        if let Some((client, previous)) = self.slack.take()
+            && let Err(e) = client.set_status(&previous)
+        {
+            eprintln!("Could not restore Slack status: {}", e);
+        }
+    }
+}
+
+/// Set the status from the status template ("In standup, back at HH:MM") and return the one it
+/// replaced
+fn set_slack_status(client: &SlackClient, config: &Config) -> Result<Status> {
+    let previous = client.status()?;
+
+    let end = Local::now() + config.duration;
+    let text = Templates::load(config)?.render(
+        "slack/status.txt",
+        context! { back_at => end.format("%H:%M").to_string() },
+    )?;
+    client.set_status(&Status {
+        status_text: text,
+        status_emoji: ":clock2:".to_string(),
+        // let Slack clear it even if nextup never gets to
+        status_expiration: end.timestamp(),
+    })?;
+
+    Ok(previous)
+}
+
+fn enable_os() -> Result<Option<OsState>> {
+    if cfg!(target_os = "macos") {
+        run("shortcuts", &["run", MACOS_ON_SHORTCUT])?;
+        Ok(Some(OsState::MacShortcut))
+    } else if cfg!(target_os = "linux") {
+        let previous = run("gsettings", &["get", GNOME_SCHEMA, GNOME_KEY])?;
+        run("gsettings", &["set", GNOME_SCHEMA, GNOME_KEY, "false"])?;
+        Ok(Some(OsState::GnomeBanners { previous }))
+    } else {
+        Ok(None)
+    }
+}
+
+fn restore_os(state: OsState) -> Result<()> {
+    match state {
+        OsState::GnomeBanners { previous } => {
+            run("gsettings", &["set", GNOME_SCHEMA, GNOME_KEY, &previous])?;
+        }
+        OsState::MacShortcut => {
+            run("shortcuts", &["run", MACOS_OFF_SHORTCUT])?;
+        }
+    }
+    Ok(())
+}
+
+/// Run a command and return its trimmed stdout
+fn run(program: &str, args: &[&str]) -> Result<String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| AppError::DoNotDisturb(format!("{}: {}", program, e)))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::DoNotDisturb(format!("{}: {}", program, stderr.trim())).into());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
@@ -0,0 +1,528 @@
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute,
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+};
+use ratatui::{
+    Frame, Terminal,
+    backend::CrosstermBackend,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::roster::{self, parse_slot};
+
+/// A line of the team file. Everything but members and team headings is kept as it was.
+#[derive(Debug, Clone)]
+enum Entry {
+    Member(MemberLine),
+    Heading(String),
+    Other(String),
+}
+
+/// A member's line: their name and the `key=value` fields after it, in file order
+#[derive(Debug, Clone)]
+struct MemberLine {
+    name: String,
+    fields: Vec<String>,
+}
+
+impl MemberLine {
+    fn parse(line: &str) -> Self {
+        let mut parts = line.split('|').map(str::trim);
+        Self {
+            name: parts.next().unwrap_or_default().to_string(),
+            fields: parts
+                .filter(|field| !field.is_empty())
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.fields.iter().find_map(|field| {
+            let (k, v) = field.split_once('=')?;
+            (k.trim() == key).then(|| v.trim())
+        })
+    }
+
+    /// Set a field, or remove it when `value` is None
+    fn set(&mut self, key: &str, value: Option<&str>) {
+        let position = self
+            .fields
+            .iter()
+            .position(|field| field.split_once('=').is_some_and(|(k, _)| k.trim() == key));
+        match (position, value) {
+            (Some(i), Some(value)) => self.fields[i] = format!("{}={}", key, value),
+            (Some(i), None) => {
+                self.fields.remove(i);
+            }
+            (None, Some(value)) => self.fields.push(format!("{}={}", key, value)),
+            (None, None) => {}
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        !matches!(self.get("active"), Some("no" | "false"))
+    }
+
+    fn to_line(&self) -> String {
+        std::iter::once(self.name.as_str())
+            .chain(self.fields.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+}
+
+impl Entry {
+    fn parse(line: &str) -> Self {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with("@role") {
+            Entry::Other(line.to_string())
+        } else if let Some(heading) = trimmed.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            Entry::Heading(heading.trim().to_string())
+        } else {
+            Entry::Member(MemberLine::parse(trimmed))
+        }
+    }
+
+    fn to_line(&self) -> String {
+        match self {
+            Entry::Member(member) => member.to_line(),
+            Entry::Heading(team) => format!("[{}]", team),
+            Entry::Other(line) => line.clone(),
+        }
+    }
+}
+
+/// What's being typed or confirmed at the bottom of the screen
+#[derive(Debug, Clone, PartialEq)]
+enum Prompt {
+    Add(String),
+    Rename(String),
+    Role(String),
+    Slot(String),
+    Remove,
+    // quitting with unsaved changes
+    Quit,
+}
+
+/// Interactive `nextup edit-team`
+struct Editor {
+    path: String,
+    entries: Vec<Entry>,
+    // index into `entries` of the selected member
+    selected: Option<usize>,
+    prompt: Option<Prompt>,
+    dirty: bool,
+    // result of the last action, shown in place of the help line
+    status: Option<String>,
+}
+
+/// Edit the team file until the user quits
+pub fn run(config: &Config) -> Result<()> {
+    if config.names_file.ends_with(".json") {
+        return Err(anyhow::Error::msg(
+            "edit-team only edits text team files; edit a team.json in your editor, with `nextup schema team` to check it",
+        ));
+    }
+
+    let content = match fs::read_to_string(&config.names_file) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            return Err(AppError::NamesFileError {
+                path: config.names_file.clone(),
+                source: e,
+            }
+            .into());
+        }
+    };
+
+    let entries: Vec<Entry> = content.lines().map(Entry::parse).collect();
+    let mut editor = Editor {
+        path: config.names_file.clone(),
+        selected: entries.iter().position(|e| matches!(e, Entry::Member(_))),
+        entries,
+        prompt: None,
+        dirty: false,
+        status: None,
+    };
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+    let res = editor.run(&mut terminal);
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+
+    res
+}
+
+impl Editor {
+    fn run(&mut self, terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
+        loop {
+            terminal.draw(|f| self.render(f))?;
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            if let Some(prompt) = self.prompt.take() {
+                if self.handle_prompt(prompt, key.code) {
+                    return Ok(());
+                }
+                continue;
+            }
+
+            self.status = None;
+            match (key.code, key.modifiers) {
+                (KeyCode::Char('q') | KeyCode::Esc, _) if self.dirty => {
+                    self.prompt = Some(Prompt::Quit)
+                }
+                (KeyCode::Char('q') | KeyCode::Esc, _) => return Ok(()),
+                (KeyCode::Char('c'), KeyModifiers::CONTROL) => return Ok(()),
+                (KeyCode::Char('s'), KeyModifiers::CONTROL) | (KeyCode::Char('w'), _) => {
+                    self.status = Some(self.save())
+                }
+                (KeyCode::Up, KeyModifiers::SHIFT) | (KeyCode::Char('K'), _) => self.move_up(),
+                (KeyCode::Down, KeyModifiers::SHIFT) | (KeyCode::Char('J'), _) => self.move_down(),
+                (KeyCode::Up | KeyCode::Char('k'), _) => self.select(-1),
+                (KeyCode::Down | KeyCode::Char('j'), _) => self.select(1),
+                (KeyCode::Char('a'), _) => self.prompt = Some(Prompt::Add(String::new())),
+                (KeyCode::Char(' '), _) => self.toggle_active(),
+                (key, _) => {
+                    let Some(member) = self.selected_member() else {
+                        continue;
+                    };
+                    let current = |key: &str| member.get(key).unwrap_or_default().to_string();
+                    self.prompt = match key {
+                        KeyCode::Char('e') => Some(Prompt::Rename(member.name.clone())),
+                        KeyCode::Char('r') => Some(Prompt::Role(current("role"))),
+                        KeyCode::Char('s') => Some(Prompt::Slot(current("slot"))),
+                        KeyCode::Char('d') | KeyCode::Delete => Some(Prompt::Remove),
+                        _ => None,
+                    };
+                }
+            }
+        }
+    }
+
+    /// Carry on typing into (or answer) a prompt. Returns true when the editor should close.
+    fn handle_prompt(&mut self, mut prompt: Prompt, key: KeyCode) -> bool {
+        match (&mut prompt, key) {
+            (Prompt::Remove, KeyCode::Char('y')) => self.remove(),
+            (Prompt::Quit, KeyCode::Char('y')) => {
+                let status = self.save();
+                if !self.dirty {
+                    return true;
+                }
+                self.status = Some(status);
+            }
+            (Prompt::Quit, KeyCode::Char('n')) => return true,
+            (Prompt::Remove | Prompt::Quit, _) => {}
+
+            (_, KeyCode::Esc) => {}
+            (_, KeyCode::Enter) => {
+                if let Err(e) = self.apply(&prompt) {
+                    self.status = Some(e);
+                    self.prompt = Some(prompt);
+                }
+            }
+            (
+                Prompt::Add(text) | Prompt::Rename(text) | Prompt::Role(text) | Prompt::Slot(text),
+                key,
+            ) => {
+                match key {
+                    KeyCode::Backspace => {
+                        text.pop();
+                    }
+                    KeyCode::Char(c) => text.push(c),
+                    _ => {}
+                }
+                self.prompt = Some(prompt);
+            }
+        }
+        false
+    }
+
+    /// Apply a typed value, or say what's wrong with it
+    fn apply(&mut self, prompt: &Prompt) -> std::result::Result<(), String> {
+        let value = match prompt {
+            Prompt::Add(text) | Prompt::Rename(text) | Prompt::Role(text) | Prompt::Slot(text) => {
+                text.trim()
+            }
+            _ => return Ok(()),
+        };
+        if value.contains('|') {
+            return Err("`|` separates fields, so it can't be part of a value".to_string());
+        }
+
+        match prompt {
+            Prompt::Add(_) | Prompt::Rename(_) => {
+                if value.is_empty() {
+                    return Err("A name can't be empty".to_string());
+                }
+                if value.starts_with(['#', '@', '[']) {
+                    return Err("A name can't start with #, @ or [".to_string());
+                }
+                let taken = self.entries.iter().enumerate().any(|(i, entry)| {
+                    matches!(entry, Entry::Member(m) if m.name == value)
+                        && !(matches!(prompt, Prompt::Rename(_)) && Some(i) == self.selected)
+                });
+                if taken {
+                    return Err(format!("{} is already on the team", value));
+                }
+            }
+            Prompt::Role(_) if value.contains('=') => {
+                return Err("A role can't contain `=`".to_string());
+            }
+            Prompt::Slot(_) if !value.is_empty() && parse_slot(value).is_none() => {
+                return Err(format!("`{}` isn't a slot, try e.g. 2m or 90s", value));
+            }
+            _ => {}
+        }
+
+        if let Prompt::Add(_) = prompt {
+            // new people join the selected person's team, or the end of the file
+            let at = self.selected.map_or(self.entries.len(), |i| i + 1);
+            self.entries.insert(
+                at,
+                Entry::Member(MemberLine {
+                    name: value.to_string(),
+                    fields: Vec::new(),
+                }),
+            );
+            self.selected = Some(at);
+            self.dirty = true;
+            return Ok(());
+        }
+
+        let Some(Entry::Member(member)) = self.selected.and_then(|i| self.entries.get_mut(i))
+        else {
+            return Ok(());
+        };
+        let value = Some(value).filter(|v| !v.is_empty());
+        match prompt {
+            Prompt::Rename(_) => member.name = value.unwrap_or_default().to_string(),
+            Prompt::Role(_) => member.set("role", value),
+            Prompt::Slot(_) => member.set("slot", value),
+            _ => {}
+        }
+        self.dirty = true;
+        Ok(())
+    }
+
+    fn selected_member(&self) -> Option<&MemberLine> {
+        match self.entries.get(self.selected?)? {
+            Entry::Member(member) => Some(member),
+            _ => None,
+        }
+    }
+
+    /// Move the selection to the next member up (-1) or down (1)
+    fn select(&mut self, step: isize) {
+        let Some(current) = self.selected else {
+            return;
+        };
+        let mut i = current as isize + step;
+        while i >= 0 && (i as usize) < self.entries.len() {
+            if matches!(self.entries[i as usize], Entry::Member(_)) {
+                self.selected = Some(i as usize);
+                return;
+            }
+            i += step;
+        }
+    }
+
+    /// Swap the selected member with the one above, or move them into the team above
+    fn move_up(&mut self) {
+        let Some(i) = self.selected else {
+            return;
+        };
+        let Some(j) = (0..i)
+            .rev()
+            .find(|&j| !matches!(self.entries[j], Entry::Other(_)))
+        else {
+            return;
+        };
+        if matches!(self.entries[j], Entry::Member(_)) {
+            self.entries.swap(i, j);
+        } else {
+            let entry = self.entries.remove(i);
+            self.entries.insert(j, entry);
+        }
+        self.selected = Some(j);
+        self.dirty = true;
+    }
+
+    /// Swap the selected member with the one below, or move them into the team below
+    fn move_down(&mut self) {
+        let Some(i) = self.selected else {
+            return;
+        };
+        let Some(j) =
+            (i + 1..self.entries.len()).find(|&j| !matches!(self.entries[j], Entry::Other(_)))
+        else {
+            return;
+        };
+        if matches!(self.entries[j], Entry::Member(_)) {
+            self.entries.swap(i, j);
+        } else {
+            // just below the heading, which moved up one when they left
+            let entry = self.entries.remove(i);
+            self.entries.insert(j, entry);
+        }
+        self.selected = Some(j);
+        self.dirty = true;
+    }
+
+    fn toggle_active(&mut self) {
+        if let Some(Entry::Member(member)) = self.selected.and_then(|i| self.entries.get_mut(i)) {
+            let active = member.is_active();
+            member.set("active", (active).then_some("no"));
+            self.dirty = true;
+        }
+    }
+
+    fn remove(&mut self) {
+        let Some(i) = self.selected else {
+            return;
+        };
+        self.entries.remove(i);
+        self.dirty = true;
+
+        // select whoever is nearest
+        self.selected = (i..self.entries.len())
+            .chain((0..i).rev())
+            .find(|&j| matches!(self.entries[j], Entry::Member(_)));
+    }
+
+    /// Check the file reads back as a team, then write it in one go
+    fn save(&mut self) -> String {
+        let mut content: String = self
+            .entries
+            .iter()
+            .map(|entry| entry.to_line() + "\n")
+            .collect();
+        if content.trim().is_empty() {
+            content.clear();
+        }
+
+        if let Err(e) = roster::parse_members(&content, &self.path) {
+            return format!("Not saved: {}", e);
+        }
+        match write_atomically(Path::new(&self.path), &content) {
+            Ok(()) => {
+                self.dirty = false;
+                format!("Saved {}", self.path)
+            }
+            Err(e) => format!("Not saved: {}", e),
+        }
+    }
+
+    fn render(&self, f: &mut Frame) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(2)])
+            .split(f.area());
+
+        let mut selected = None;
+        let mut items = Vec::new();
+        for (i, entry) in self.entries.iter().enumerate() {
+            let item = match entry {
+                Entry::Member(member) => {
+                    let mut spans = vec![Span::raw(format!("  {}", member.name))];
+                    if !member.fields.is_empty() {
+                        spans.push(Span::styled(
+                            format!("  {}", member.fields.join("  ")),
+                            Style::default().fg(Color::Gray),
+                        ));
+                    }
+                    let style = if member.is_active() {
+                        Style::default()
+                    } else {
+                        Style::default()
+                            .fg(Color::DarkGray)
+                            .add_modifier(Modifier::CROSSED_OUT)
+                    };
+                    ListItem::new(Line::from(spans)).style(style)
+                }
+                Entry::Heading(team) => ListItem::new(team.clone()).style(
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                // blank lines aren't worth the space
+                Entry::Other(line) if line.trim().is_empty() => continue,
+                Entry::Other(line) => {
+                    ListItem::new(line.clone()).style(Style::default().fg(Color::DarkGray))
+                }
+            };
+            if Some(i) == self.selected {
+                selected = Some(items.len());
+            }
+            items.push(item);
+        }
+
+        let title = format!("{}{}", self.path, if self.dirty { " *" } else { "" });
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Style::default().bg(Color::Yellow).fg(Color::Black))
+            .highlight_symbol("> ");
+        let mut state = ListState::default();
+        state.select(selected);
+        f.render_stateful_widget(list, chunks[0], &mut state);
+
+        let name = self
+            .selected_member()
+            .map_or_else(String::new, |m| m.name.clone());
+        let prompt = self.prompt.as_ref().map(|prompt| match prompt {
+            Prompt::Add(text) => format!("New member: {}█", text),
+            Prompt::Rename(text) => format!("Name: {}█", text),
+            Prompt::Role(text) => format!("Role for {} (empty for none): {}█", name, text),
+            Prompt::Slot(text) => format!("Slot for {}, e.g. 2m (empty for none): {}█", name, text),
+            Prompt::Remove => format!("Remove {}? (y/n)", name),
+            Prompt::Quit => "Save changes before quitting? (y/n, Esc to stay)".to_string(),
+        });
+        let help = self.status.clone().unwrap_or_else(|| {
+            if self.prompt.is_some() {
+                "<Enter> OK | <Esc> Cancel".to_string()
+            } else {
+                "<↑↓> Select | <J/K> Move | <A> Add | <E> Rename | <R> Role | <S> Slot | <Space> Active | <D> Remove | <W> Save".to_string()
+            }
+        });
+
+        let mut lines = Vec::new();
+        if let Some(prompt) = prompt {
+            lines.push(Line::from(prompt));
+        }
+        lines.push(Line::from(help).style(Style::default().fg(Color::Gray)));
+        f.render_widget(
+            Paragraph::new(lines).alignment(Alignment::Center),
+            chunks[1],
+        );
+    }
+}
+
+/// Write through a temporary file next to `path`, so a crash never leaves half a team file
+fn write_atomically(path: &Path, content: &str) -> io::Result<()> {
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    fs::write(&temp, content)?;
+    fs::rename(&temp, path)
+}
@@ -1,20 +1,209 @@
+use std::io;
+use std::path::PathBuf;
+use thiserror::Error;
+
 // custom error type using `anyhow` for simple error handling
 pub type Result<T> = anyhow::Result<T>;
 
 // Application-specific error types
-#[derive(Debug)]
+#[derive(Debug, Error)]
 pub enum AppError {
-    NamesFileError(std::io::Error),
+    // settings
+    #[error("Invalid settings in {} on line {line}, column {column}: {message}", path.display())]
+    ConfigError {
+        path: PathBuf,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    #[error("Failed to read history: {0}")]
+    HistoryError(String),
+    #[cfg(feature = "collation")]
+    #[error("Unknown language to sort names by: `{0}`")]
+    UnknownLocale(String),
+
+    // team file and other meeting inputs
+    #[error("Failed to read names file {path}: {source}")]
+    NamesFileError {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error("No names found in file")]
     NoNamesFound,
+    #[error("Invalid team file entry in {path} on line {line}: {message}")]
+    RosterError {
+        path: String,
+        line: usize,
+        message: String,
+    },
+    #[error("Invalid team file {path}: {message}")]
+    TeamFileError { path: String, message: String },
+    #[error("Failed to read questions file: {0}")]
+    QuestionsFileError(#[source] io::Error),
+    #[error("No questions found in file")]
+    NoQuestionsFound,
+    #[error("Failed to read handoff phrases file: {0}")]
+    HandoffsFileError(#[source] io::Error),
+    #[error("No handoff phrases found in file")]
+    NoHandoffsFound,
+    #[error("Failed to read topics file: {0}")]
+    TopicsFileError(#[source] io::Error),
+    #[error("Invalid topic in {path} on line {line}: {message}")]
+    TopicsError {
+        path: String,
+        line: usize,
+        message: String,
+    },
+    #[error("Failed to read updates directory: {0}")]
+    UpdatesDirError(#[source] io::Error),
+    #[error("Couldn't make assignments: {0}")]
+    AssignmentError(String),
+    #[error("Couldn't load the timer theme: {0}")]
+    ThemeError(String),
+    #[error("The speaking order rules don't fit together: {0}")]
+    OrderError(String),
+    #[error("Couldn't add the guest: {0}")]
+    GuestError(String),
+    #[error("Couldn't fill in the template: {0}")]
+    TemplateError(String),
+    #[error("Failed to read the calendar: {0}")]
+    CalendarError(String),
+    #[error("Couldn't import the bundle: {0}")]
+    BundleError(String),
+    #[cfg(feature = "avatars")]
+    #[error("Couldn't load an avatar: {0}")]
+    AvatarError(String),
+
+    // integrations
+    #[error("Slack API error: {0}")]
+    Slack(String),
+    #[error("Do-not-disturb hook failed: {0}")]
+    DoNotDisturb(String),
+    #[error("Failed to sync state: {0}")]
+    SyncError(String),
+    #[cfg(feature = "web")]
+    #[error("Web server error: {0}")]
+    ServerError(String),
+    #[error("Failed to send email: {0}")]
+    MailError(String),
+    #[error("Failed to export trace: {0}")]
+    TraceError(String),
+    #[error("Couldn't follow the meeting on the network: {0}")]
+    LanError(String),
+    // only raised when a feature is left out
+    #[cfg_attr(
+        all(
+            feature = "web",
+            feature = "slack",
+            feature = "otlp",
+            feature = "avatars",
+            feature = "collation",
+            feature = "lan"
+        ),
+        allow(dead_code)
+    )]
+    #[error("This nextup was built without {what} (the `{feature}` feature)")]
+    NotBuiltIn {
+        what: &'static str,
+        feature: &'static str,
+    },
+    #[cfg(feature = "self-update")]
+    #[error("Failed to update nextup: {0}")]
+    UpdateError(String),
+
+    // running the meeting
+    #[error("Failed to lock series: {0}")]
+    LockError(String),
+    #[error("This meeting is already running (pid {pid})")]
+    AlreadyRunning { pid: u32 },
+    #[error("Terminal error: {0}")]
+    TerminalError(#[source] io::Error),
 }
 
-impl std::fmt::Display for AppError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl AppError {
+    /// What to try next, shown below the error
+    pub fn hint(&self) -> Option<&'static str> {
         match self {
-            AppError::NamesFileError(e) => write!(f, "Failed to read names file: {}", e),
-            AppError::NoNamesFound => write!(f, "No names found in file"),
+            AppError::ConfigError { .. } => {
+                Some("fix the file by hand, or delete it to go back to the defaults")
+            }
+            AppError::HistoryError(_) => {
+                Some("the state directory may be corrupt; move the file aside to start afresh")
+            }
+            #[cfg(feature = "collation")]
+            AppError::UnknownLocale(_) => {
+                Some("give a language tag such as `sv` or `de-DE` to --sort-names")
+            }
+            AppError::NamesFileError { .. } => {
+                Some("pass the team file with --names, e.g. --names team.txt")
+            }
+            AppError::NoNamesFound => Some("put one name per line in the team file"),
+            AppError::RosterError { .. } => Some(
+                "entries look like `Name | role=lead | slot=90s`, with birthday, start, id, aka, github, slack, email, avatar and active too",
+            ),
+            AppError::TeamFileError { .. } => {
+                Some("`nextup schema team` prints the JSON Schema a team.json file follows")
+            }
+            AppError::TopicsError { .. } => Some("topics look like `Name: what to bring up`"),
+            AppError::ThemeError(_) => {
+                Some("use a built-in theme (default, plain, traffic or words) or a JSON theme file")
+            }
+            AppError::OrderError(_) => Some(
+                "drop or change one of the --order rules; each names the people it's about by name or alias",
+            ),
+            AppError::GuestError(_) => Some(
+                "--guest is for people who aren't on the team; give a guest who shares a name a different one",
+            ),
+            AppError::TemplateError(_) => Some(
+                "templates use Jinja syntax, e.g. `{{ speaker.name }}` and `{% for topic in speaker.topics %}`",
+            ),
+            AppError::CalendarError(_) => {
+                Some("export the calendar as an .ics file, or pass the one your calendar app syncs")
+            }
+            AppError::BundleError(_) => Some(
+                "bundles come from `nextup bundle export`; --force replaces a series that is already set up",
+            ),
+            #[cfg(feature = "avatars")]
+            AppError::AvatarError(_) => {
+                Some("`avatar=` in the team file takes a PNG or JPEG, relative to the team file")
+            }
+            AppError::Slack(_) => {
+                Some("check the Slack token's scopes and that the bot was invited to the channel")
+            }
+            AppError::SyncError(_) => {
+                Some("the state directory is shared through git; check its remote and credentials")
+            }
+            AppError::LanError(_) => Some(
+                "the instances must be on the same network, with mDNS and the leader's port let through any firewall",
+            ),
+            #[cfg(feature = "web")]
+            AppError::ServerError(_) => {
+                Some("pick another address with --serve, e.g. --serve 0.0.0.0:8081")
+            }
+            AppError::NotBuiltIn { .. } => {
+                Some("install a build with it, e.g. `cargo install nextup-tui --features full`")
+            }
+            #[cfg(feature = "self-update")]
+            AppError::UpdateError(_) => Some(
+                "download the release by hand from https://github.com/slipperypenguin/nextup/releases",
+            ),
+            AppError::LockError(_) => Some("check that the state directory is writable"),
+            AppError::AlreadyRunning { .. } => Some(
+                "run nextup from a terminal to follow or take over that meeting, or quit it first",
+            ),
+            AppError::TerminalError(_) => Some(
+                "run nextup in an interactive terminal, or headless with --json-events or `nextup bot`",
+            ),
+            _ => None,
         }
     }
 }
 
-impl std::error::Error for AppError {}
+/// Print an error for the user, with a hint at what to try when there is one
+pub fn report(error: &anyhow::Error) {
+    eprintln!("Error: {}", error);
+    if let Some(hint) = error.downcast_ref::<AppError>().and_then(AppError::hint) {
+        eprintln!("Hint: {}", hint);
+    }
+}
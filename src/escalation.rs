@@ -0,0 +1,192 @@
+use std::fmt;
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::notify::{self, Backend, OscProtocol, Thresholds};
+use crate::roster::parse_slot;
+use crate::session::Session;
+use crate::templates::Templates;
+use crate::theme::TimerTheme;
+
+/// What an escalation rule does once the meeting's remaining time reaches it
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    // switch the timer to another theme, e.g. the built-in `urgent`, by the name it was given
+    Theme { name: String, theme: TimerTheme },
+    // flash the timer's border
+    Blink,
+    // raise a terminal notification, or ring the bell where there's none
+    Notify,
+    // run a shell command
+    Run(String),
+}
+
+/// One step of escalation, e.g. `2m=theme:urgent`, `1m=blink`, `0=notify` or
+/// `30s=run:say wrap it up`
+#[derive(Debug, Clone, PartialEq)]
+pub struct EscalationRule {
+    pub at: Duration,
+    pub action: Action,
+}
+
+impl FromStr for EscalationRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "invalid escalation rule `{}`, expected e.g. 2m=theme:urgent, 1m=blink, 0=notify \
+                 or 30s=run:COMMAND",
+                s
+            )
+        };
+
+        let (at, action) = s.split_once('=').ok_or_else(invalid)?;
+        let at = match at.trim() {
+            "0" | "0s" | "end" => Duration::ZERO,
+            at => parse_slot(at).ok_or_else(invalid)?,
+        };
+        let action = match action.trim() {
+            "blink" => Action::Blink,
+            "notify" => Action::Notify,
+            action => match action.split_once(':') {
+                Some(("theme", name)) => Action::Theme {
+                    name: name.trim().to_string(),
+                    theme: TimerTheme::load(name.trim()).map_err(|e| e.to_string())?,
+                },
+                Some(("run", command)) if !command.trim().is_empty() => {
+                    Action::Run(command.trim().to_string())
+                }
+                _ => return Err(invalid()),
+            },
+        };
+        Ok(Self { at, action })
+    }
+}
+
+impl fmt::Display for EscalationRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.at.as_secs();
+        match secs {
+            0 => write!(f, "0=")?,
+            _ if secs.is_multiple_of(60) => write!(f, "{}m=", secs / 60)?,
+            _ => write!(f, "{}s=", secs)?,
+        }
+        match &self.action {
+            Action::Theme { name, .. } => write!(f, "theme:{}", name),
+            Action::Blink => write!(f, "blink"),
+            Action::Notify => write!(f, "notify"),
+            Action::Run(command) => write!(f, "run:{}", command),
+        }
+    }
+}
+
+/// Carries out the escalation rules as the meeting counts down. The timer's look follows the
+/// rules the remaining time is within, so adding time undoes it; notifications and commands
+/// fire once as their time is reached.
+pub struct Escalation {
+    rules: Vec<EscalationRule>,
+    thresholds: Thresholds,
+    osc: Option<OscProtocol>,
+    bell: Backend,
+    templates: Templates,
+    // the theme rule in force, and whether the border blinks, as of the last update
+    theme: Option<usize>,
+    blinking: bool,
+}
+
+impl Escalation {
+    /// Follow the configured rules, or None when there are none
+    pub fn new(config: &Config, templates: &Templates) -> Option<Self> {
+        if config.escalation.is_empty() {
+            return None;
+        }
+
+        let rules = config.escalation.clone();
+        Some(Self {
+            thresholds: Thresholds::new(rules.iter().map(|rule| rule.at).collect()),
+            rules,
+            osc: config.osc_protocol.or_else(OscProtocol::detect),
+            bell: Backend::new(config),
+            templates: templates.clone(),
+            theme: None,
+            blinking: false,
+        })
+    }
+
+    /// Apply the rules the remaining time has reached
+    pub fn update(&mut self, session: &Session) {
+        let remaining = session.remaining_time();
+        let within = |rule: &&EscalationRule| remaining <= rule.at;
+
+        // of several theme rules, the one closest to the end wins
+        self.theme = self
+            .rules
+            .iter()
+            .enumerate()
+            .filter(|(_, rule)| within(rule) && matches!(rule.action, Action::Theme { .. }))
+            .min_by_key(|(_, rule)| rule.at)
+            .map(|(i, _)| i);
+        self.blinking = self
+            .rules
+            .iter()
+            .filter(within)
+            .any(|rule| rule.action == Action::Blink);
+
+        for i in self.thresholds.update(remaining) {
+            let rule = &self.rules[i];
+            match &rule.action {
+                Action::Notify => {
+                    match (self.osc, notify::reached_text(&self.templates, rule.at)) {
+                        (Some(osc), Some(text)) => osc.notify("nextup", &text),
+                        (None, _) => self.bell.beep(),
+                        // the template left it out
+                        (Some(_), None) => {}
+                    }
+                }
+                Action::Run(command) => run(command, session),
+                Action::Theme { .. } | Action::Blink => {}
+            }
+        }
+    }
+
+    /// The theme a rule has switched the timer to
+    pub fn theme(&self) -> Option<&TimerTheme> {
+        match &self.rules[self.theme?].action {
+            Action::Theme { theme, .. } => Some(theme),
+            _ => None,
+        }
+    }
+
+    pub fn is_blinking(&self) -> bool {
+        self.blinking
+    }
+}
+
+/// Start a rule's command with the shell, telling it the time left and who's speaking; fire and
+/// forget, so a slow or broken command can't hold up the meeting
+fn run(command: &str, session: &Session) {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let child = Command::new(shell)
+        .args([flag, command])
+        .env(
+            "NEXTUP_REMAINING_SECS",
+            session.remaining_time().as_secs().to_string(),
+        )
+        .env("NEXTUP_SPEAKER", session.current_name())
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    // waited on out of the way, so it doesn't linger as a zombie once it's done
+    if let Ok(mut child) = child {
+        thread::spawn(move || child.wait());
+    }
+}
@@ -0,0 +1,293 @@
+use chrono::Local;
+use serde::Serialize;
+use std::io::Write;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::meeting::Meeting;
+use crate::queue::Queue;
+use crate::report;
+use crate::server::Server;
+use crate::session::{GapChoice, SessionEvent, TimeUp};
+use crate::templates::Templates;
+use crate::trace::Tracer;
+use crate::transcript::Transcript;
+
+// How often timers are checked for warnings
+const TICK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// One line of the event stream
+#[derive(Serialize)]
+struct Envelope<'a> {
+    timestamp: String,
+    #[serde(flatten)]
+    event: &'a JsonEvent<'a>,
+}
+
+/// Events emitted on stdout, one JSON object per line
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum JsonEvent<'a> {
+    Shuffled {
+        order: Vec<&'a str>,
+    },
+    SpeakerChanged {
+        index: usize,
+        name: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        question: Option<&'a str>,
+        // their written update, in a hybrid standup
+        #[serde(skip_serializing_if = "Option::is_none")]
+        update: Option<&'a str>,
+    },
+    RoundChanged {
+        round: usize,
+        name: &'a str,
+    },
+    Warning {
+        remaining_secs: u64,
+    },
+    CutOff {
+        index: usize,
+        name: &'a str,
+    },
+    // answer with `gap discard`, `gap count` or `gap break`
+    Gap {
+        secs: u64,
+    },
+    Ended,
+    Finished {
+        elapsed_secs: u64,
+        health_score: u8,
+        speakers: Vec<NamedTime<'a>>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        rounds: Vec<NamedTime<'a>>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        cut_off: Vec<&'a str>,
+    },
+}
+
+#[derive(Serialize)]
+struct NamedTime<'a> {
+    name: &'a str,
+    secs: u64,
+}
+
+/// Run the meeting without a UI, reading commands from stdin and emitting
+/// newline-delimited JSON events on stdout.
+///
+/// Commands are `next`, `prev`, `shuffle`, `reset`, `gap discard|count|break` and `quit`,
+/// one per line.
+/// With `--serve`, the web view and its control API work too.
+/// When stdin closes the meeting keeps running until its time is up.
+pub async fn run(config: Config) -> Result<()> {
+    let mut meeting = Meeting::load(&config)?;
+    let mut tracer = Tracer::new(&config);
+    let mut queue = Queue::new(&config, None);
+    let mut transcript = Transcript::new(&config, &Templates::load(&config)?);
+    let mut server = match config.serve {
+        Some(addr) => Some(Server::start(addr, config.api_token.clone(), config.share_ttl).await?),
+        None => None,
+    };
+    if let Some(server) = &server {
+        eprintln!("Web view at {}", server.url());
+    }
+
+    // a meeting taken over from another instance keeps its order
+    if !meeting.is_resumed() {
+        meeting.open_order();
+        meeting.session.reset_timer();
+    }
+    emit(&meeting, SessionEvent::Shuffled)?;
+    emit(
+        &meeting,
+        SessionEvent::SpeakerChanged {
+            index: meeting.session.current_person_index(),
+        },
+    )?;
+    meeting.mark_current_asked();
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdin_open = true;
+    let mut ended = false;
+    let time_up = meeting.session.time_up();
+
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(TICK_INTERVAL) => {}
+            line = lines.next_line(), if stdin_open => match line? {
+                // once time is up, a locked meeting can only be quit
+                Some(line) if ended && time_up == TimeUp::Lock && line.trim() != "quit" => {
+                    eprintln!("Time is up, only quit works now");
+                }
+                Some(line) => match line.trim() {
+                    "next" => {
+                        if let Some(event) = meeting.session.next() {
+                            emit(&meeting, event)?;
+                        }
+                    }
+                    "prev" => {
+                        if let Some(event) = meeting.session.prev() {
+                            emit(&meeting, event)?;
+                        }
+                    }
+                    "shuffle" => {
+                        let event = meeting.session.shuffle();
+                        emit(&meeting, event)?;
+                        emit(&meeting, SessionEvent::SpeakerChanged { index: 0 })?;
+                    }
+                    "reset" => {
+                        meeting.session.reset_per_person_timers();
+                        meeting.session.reset_timer();
+                        ended = false;
+                    }
+                    "quit" => break,
+                    other => match other.strip_prefix("gap ").map(str::parse::<GapChoice>) {
+                        Some(Ok(choice)) => meeting.session.resolve_gap(choice),
+                        Some(Err(e)) => eprintln!("{}", e),
+                        None => eprintln!("Unknown command: {}", other),
+                    },
+                },
+                None => stdin_open = false,
+            },
+            _ = tokio::signal::ctrl_c() => break,
+        }
+
+        // commands sent through the control API
+        while let Some(command) = server.as_mut().and_then(Server::next_command) {
+            if ended && time_up == TimeUp::Lock {
+                continue;
+            }
+            if let Some(event) = meeting.apply(command) {
+                emit(&meeting, event)?;
+            }
+        }
+
+        meeting.mark_current_asked();
+        meeting.autosave();
+        for event in meeting.session.tick() {
+            ended |= event == SessionEvent::Ended;
+            emit(&meeting, event)?;
+        }
+
+        if let Some(tracer) = &mut tracer {
+            tracer.update(&meeting);
+        }
+        if let Some(transcript) = &mut transcript {
+            transcript.update(&meeting);
+        }
+        if let Some(server) = &server {
+            server.publish(&config, &meeting, &config.timer_theme);
+        }
+
+        // nobody can send commands anymore (or the meeting exits when time is up), so stop;
+        // a meeting taken over elsewhere is no longer ours to report on
+        if (ended && (!stdin_open || time_up == TimeUp::Exit)) || meeting.is_taken_over() {
+            break;
+        }
+    }
+
+    let health_score = meeting.close().score;
+    let session = &meeting.session;
+    let rounds = if session.is_multi_round() {
+        session.round_totals()
+    } else {
+        Vec::new()
+    };
+    write_line(&JsonEvent::Finished {
+        elapsed_secs: session.elapsed().as_secs(),
+        health_score,
+        speakers: session
+            .speakers()
+            .iter()
+            .map(|speaker| NamedTime {
+                name: speaker.name(),
+                secs: speaker.total().as_secs(),
+            })
+            .collect(),
+        rounds: rounds
+            .into_iter()
+            .map(|(name, time)| NamedTime {
+                name,
+                secs: time.as_secs(),
+            })
+            .collect(),
+        cut_off: session
+            .speakers()
+            .iter()
+            .filter(|s| s.cut_off)
+            .map(|s| s.name())
+            .collect(),
+    })?;
+
+    meeting.finish()?;
+    if let Some(tracer) = &mut tracer
+        && let Err(e) = tracer.export(&config, &meeting, &queue)
+    {
+        eprintln!("{}", e);
+    }
+    queue.finish();
+    if let Some(transcript) = &transcript {
+        transcript.write(&config, &meeting)?;
+    }
+    report::write(&config, &meeting)?;
+    Ok(())
+}
+
+/// Translate a session event into its JSON form and print it
+fn emit(meeting: &Meeting, event: SessionEvent) -> Result<()> {
+    let session = &meeting.session;
+
+    let event = match event {
+        SessionEvent::Shuffled => JsonEvent::Shuffled {
+            order: session.speakers().iter().map(|s| s.name()).collect(),
+        },
+        SessionEvent::SpeakerChanged { index } => JsonEvent::SpeakerChanged {
+            index,
+            name: session.current_name(),
+            question: meeting.current_question(),
+            update: meeting.current_update(),
+        },
+        SessionEvent::Warning { remaining } => JsonEvent::Warning {
+            remaining_secs: remaining.as_secs(),
+        },
+        SessionEvent::RoundChanged { round } => {
+            write_line(&JsonEvent::RoundChanged {
+                round,
+                name: session.round_name(),
+            })?;
+            // the new round starts with a new speaker
+            return emit(
+                meeting,
+                SessionEvent::SpeakerChanged {
+                    index: session.current_person_index(),
+                },
+            );
+        }
+        SessionEvent::CutOff { index } => JsonEvent::CutOff {
+            index,
+            name: session.speakers()[index].name(),
+        },
+        SessionEvent::Gap { length } => JsonEvent::Gap {
+            secs: length.as_secs(),
+        },
+        SessionEvent::Ended => JsonEvent::Ended,
+    };
+
+    write_line(&event)
+}
+
+fn write_line(event: &JsonEvent) -> Result<()> {
+    let envelope = Envelope {
+        timestamp: Local::now().to_rfc3339(),
+        event,
+    };
+
+    let mut stdout = std::io::stdout().lock();
+    serde_json::to_writer(&mut stdout, &envelope)?;
+    writeln!(stdout)?;
+    stdout.flush()?;
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::error::Result;
+
+/// Print what a script reads from stdout; a pipe closed early, e.g. by `head`, is fine
+pub fn to_stdout(text: &str) -> Result<()> {
+    match io::stdout().lock().write_all(text.as_bytes()) {
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
+    }
+}
+
+/// Write rows as CSV, with an optional header row first
+pub fn write_csv(path: &Path, header: Option<&[&str]>, rows: &[Vec<String>]) -> Result<()> {
+    fs::write(path, to_csv(header, rows))?;
+    Ok(())
+}
+
+/// Rows as CSV, with an optional header row first
+pub fn to_csv(header: Option<&[&str]>, rows: &[Vec<String>]) -> String {
+    let mut lines = Vec::new();
+    if let Some(header) = header {
+        lines.push(csv_row(header.iter().copied()));
+    }
+    lines.extend(
+        rows.iter()
+            .map(|row| csv_row(row.iter().map(String::as_str))),
+    );
+    lines.join("\n") + "\n"
+}
+
+fn csv_row<'a>(fields: impl Iterator<Item = &'a str>) -> String {
+    fields.map(csv_field).collect::<Vec<_>>().join(",")
+}
+
+/// Quote a field if it contains anything CSV treats as special
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
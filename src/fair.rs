@@ -0,0 +1,85 @@
+use rand::Rng;
+use std::collections::HashMap;
+
+use crate::history::History;
+
+// How many of the series' recent meetings count towards who has been going late, with --fair
+const RECENT_ORDERS: usize = 10;
+
+// How much likelier to be drawn first someone who always went last is than someone who always
+// went first
+const LATE_WEIGHT: f64 = 4.0;
+
+/// Who has taken on a recurring duty, and how recently: the basis for handing out the next turn fairly
+pub struct Ledger {
+    // candidates in tie-break order
+    names: Vec<String>,
+    // turns taken, and the sequence number of the latest one
+    turns: HashMap<String, (usize, usize)>,
+    sequence: usize,
+}
+
+impl Ledger {
+    pub fn new(names: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            names: names.into_iter().collect(),
+            turns: HashMap::new(),
+            sequence: 0,
+        }
+    }
+
+    /// Count a turn for `name`; turns are recorded oldest first
+    pub fn record(&mut self, name: &str) {
+        self.sequence += 1;
+        let entry = self.turns.entry(name.to_string()).or_default();
+        entry.0 += 1;
+        entry.1 = self.sequence;
+    }
+
+    pub fn turns(&self, name: &str) -> usize {
+        self.turns.get(name).map_or(0, |(turns, _)| *turns)
+    }
+
+    /// The `count` candidates who are due: fewest turns first, then whoever went longest ago
+    /// (never before first), then the order they were given in
+    pub fn pick(&self, count: usize) -> Vec<&str> {
+        let mut due: Vec<&String> = self.names.iter().collect();
+        // a stable sort keeps the given order among equals
+        due.sort_by_key(|name| self.turns.get(*name).copied().unwrap_or_default());
+        due.into_iter().take(count).map(String::as_str).collect()
+    }
+}
+
+/// How strongly a `--fair` shuffle draws each person towards the front, from how late they've
+/// gone in the series' recent meetings. Anyone it doesn't know counts as going in the middle.
+pub struct Weights {
+    lateness: HashMap<String, f64>,
+}
+
+impl Weights {
+    pub fn new(history: &History) -> Self {
+        Self {
+            lateness: history.lateness(RECENT_ORDERS),
+        }
+    }
+
+    pub fn weight(&self, name: &str) -> f64 {
+        let lateness = self.lateness.get(name).copied().unwrap_or(0.5);
+        // 1 for always first, rising evenly to LATE_WEIGHT for always last
+        1.0 + (LATE_WEIGHT - 1.0) * lateness
+    }
+}
+
+/// Shuffle so that heavier items tend to come first: each draws a random key of
+/// `u^(1/weight)`, and the highest keys go first
+pub fn weighted_shuffle<T>(items: &mut Vec<T>, weight: impl Fn(&T) -> f64, rng: &mut impl Rng) {
+    let mut keyed: Vec<(f64, T)> = items
+        .drain(..)
+        .map(|item| {
+            let key = rng.random::<f64>().powf(1.0 / weight(&item));
+            (key, item)
+        })
+        .collect();
+    keyed.sort_by(|a, b| b.0.total_cmp(&a.0));
+    items.extend(keyed.into_iter().map(|(_, item)| item));
+}
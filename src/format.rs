@@ -0,0 +1,135 @@
+use std::env;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+// Languages that write a decimal comma, e.g. "4,5s" in German or French
+const DECIMAL_COMMA_LANGUAGES: &[&str] = &[
+    "az", "be", "bg", "ca", "cs", "da", "de", "el", "es", "et", "eu", "fi", "fo", "fr", "gl", "hr",
+    "hu", "id", "is", "it", "kk", "lt", "lv", "nb", "nl", "nn", "no", "pl", "pt", "ro", "ru", "sk",
+    "sl", "sr", "sv", "tr", "uk", "vi",
+];
+
+/// How a speaker's time is written, in the order and on the closing screen
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeFormat {
+    // e.g. `3m 5s`
+    Words,
+    // e.g. `3m 5.4s`, to the tenth of a second
+    Precise,
+    // e.g. `03:05`
+    Clock,
+    // e.g. `3m`, to the nearest minute once past the first
+    Compact,
+    // e.g. `62%` of the time the speaker has
+    Percent,
+}
+
+impl FromStr for TimeFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "words" => Ok(Self::Words),
+            "precise" => Ok(Self::Precise),
+            "clock" | "mm:ss" => Ok(Self::Clock),
+            "compact" => Ok(Self::Compact),
+            "percent" | "%" => Ok(Self::Percent),
+            _ => Err(format!(
+                "unknown time format `{}`, expected words, precise, clock, compact or percent",
+                s
+            )),
+        }
+    }
+}
+
+impl TimeFormat {
+    /// `time` in this format; a percentage is of `budget`, and falls back to words without one
+    pub fn format(self, time: Duration, budget: Duration) -> String {
+        match self {
+            Self::Words => format_duration(time),
+            Self::Precise => format_precise(time),
+            Self::Clock => clock_reading(time),
+            Self::Compact => format_compact(time),
+            Self::Percent if budget.is_zero() => format_duration(time),
+            Self::Percent => format!("{:.0}%", time.as_secs_f64() / budget.as_secs_f64() * 100.0),
+        }
+    }
+}
+
+/// Format duration for display
+pub fn format_duration(duration: Duration) -> String {
+    let total_seconds = duration.as_secs();
+    let minutes = total_seconds / 60;
+    let seconds = total_seconds % 60;
+
+    if minutes > 0 {
+        format!("{}m {}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// `3m 5.4s`, with the decimal separator of the user's locale
+fn format_precise(duration: Duration) -> String {
+    let tenths = duration.as_millis() / 100;
+    let (minutes, seconds, tenths) = (tenths / 600, tenths / 10 % 60, tenths % 10);
+    let seconds = format!("{}{}{}s", seconds, decimal_separator(), tenths);
+
+    if minutes > 0 {
+        format!("{}m {}", minutes, seconds)
+    } else {
+        seconds
+    }
+}
+
+/// `45s` under a minute, then `3m` or `1h 5m` to the nearest minute
+fn format_compact(duration: Duration) -> String {
+    if duration < Duration::from_secs(60) {
+        return format!("{}s", duration.as_secs());
+    }
+    let minutes = (duration.as_secs() + 30) / 60;
+    if minutes >= 60 {
+        format!("{}h {}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// Time to something further off, to the minute: `22m` or `1h 5m`
+pub fn format_minutes(duration: Duration) -> String {
+    let minutes = duration.as_secs().div_ceil(60);
+    if minutes >= 60 {
+        format!("{}h {}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// `MM:SS`, for the big clock
+pub fn clock_reading(duration: Duration) -> String {
+    let total_seconds = duration.as_secs();
+    format!("{:02}:{:02}", total_seconds / 60, total_seconds % 60)
+}
+
+/// The decimal separator for numbers, from the locale in LC_ALL, LC_NUMERIC or LANG
+fn decimal_separator() -> char {
+    static SEPARATOR: OnceLock<char> = OnceLock::new();
+    *SEPARATOR.get_or_init(|| {
+        // the first of these that's set wins, as with setlocale
+        let locale = ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .iter()
+            .find_map(|name| env::var(name).ok().filter(|value| !value.is_empty()))
+            .unwrap_or_default();
+        let language = locale
+            .split(['_', '.', '@', '-'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        if DECIMAL_COMMA_LANGUAGES.contains(&language.as_str()) {
+            ','
+        } else {
+            '.'
+        }
+    })
+}
@@ -0,0 +1,80 @@
+use rand::seq::IndexedRandom;
+use std::fs;
+use std::time::{Duration, Instant};
+
+use crate::error::{AppError, Result};
+use crate::session::Session;
+
+// Embed the default handoff phrases at compile time
+const DEFAULT_HANDOFFS_CONTENT: &str = include_str!("../handoffs.txt");
+
+// How long a handoff suggestion stays on screen
+const HANDOFF_NOTICE: Duration = Duration::from_secs(4);
+
+/// Load the handoff phrases from a file, or the embedded default when none is given. `{from}`
+/// and `{to}` in a phrase stand for the speaker finishing and the one up next.
+pub fn load_phrases(filename: Option<&str>) -> Result<Vec<String>> {
+    let content = match filename {
+        Some(filename) => fs::read_to_string(filename).map_err(AppError::HandoffsFileError)?,
+        None => DEFAULT_HANDOFFS_CONTENT.to_string(),
+    };
+
+    let phrases: Vec<String> = content
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    if phrases.is_empty() {
+        return Err(AppError::NoHandoffsFound.into());
+    }
+
+    Ok(phrases)
+}
+
+/// Suggests who hands over to whom each time the turn moves on, so nobody has to ask
+/// "who's next?"
+pub struct Handoffs {
+    phrases: Vec<String>,
+    // the turn last seen, as (round, position in the order)
+    turn: Option<(usize, usize)>,
+    shown: Option<(String, Instant)>,
+}
+
+impl Handoffs {
+    pub fn new(phrases: Vec<String>) -> Self {
+        Self {
+            phrases,
+            turn: None,
+            shown: None,
+        }
+    }
+
+    /// Pick a phrase when the turn has moved on to someone else. Going back a speaker, or
+    /// the first turn starting, doesn't need one.
+    pub fn update(&mut self, session: &Session) {
+        let turn = (session.current_round(), session.current_person_index());
+        let Some(previous) = self.turn.replace(turn) else {
+            return;
+        };
+        if turn <= previous {
+            return;
+        }
+
+        let speakers = session.speakers();
+        let from = speakers[previous.1].name();
+        let to = speakers[turn.1].name();
+        if let Some(phrase) = self.phrases.choose(&mut rand::rng()) {
+            let text = phrase.replace("{from}", from).replace("{to}", to);
+            self.shown = Some((text, Instant::now()));
+        }
+    }
+
+    /// The suggestion for the handoff that just happened, for a few seconds after it
+    pub fn current(&self) -> Option<&str> {
+        self.shown
+            .as_ref()
+            .filter(|(_, at)| at.elapsed() < HANDOFF_NOTICE)
+            .map(|(text, _)| text.as_str())
+    }
+}
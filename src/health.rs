@@ -0,0 +1,154 @@
+use std::time::Duration;
+
+use crate::format::format_duration;
+use crate::history::MeetingRecord;
+use crate::session::Session;
+
+// Anyone who spoke for less than this is counted as absent
+pub const ABSENT_THRESHOLD: Duration = Duration::from_secs(3);
+
+// Most points each factor can cost, out of 100
+const OVERTIME_WEIGHT: f64 = 40.0;
+const SPREAD_WEIGHT: f64 = 30.0;
+const ABSENCE_WEIGHT: f64 = 30.0;
+
+// How many recent meetings make up "usual", and how many are needed before comparing
+const BASELINE_MEETINGS: usize = 10;
+const MIN_BASELINE: usize = 3;
+
+// Smallest relative difference from usual worth pointing out
+const NOTABLE_CHANGE: f64 = 0.2;
+
+/// How well a meeting went, scored out of 100
+#[derive(Debug, Clone, PartialEq)]
+pub struct Health {
+    pub score: u8,
+    // meeting time, not counting pauses
+    pub elapsed: Duration,
+    pub overtime: Duration,
+    // time spent paused, which neither the meeting nor anyone's turn counts
+    pub paused: Duration,
+    // standard deviation of talk time relative to the mean, among people who spoke
+    pub spread: f64,
+    pub absent: usize,
+}
+
+impl Health {
+    /// Score a session from its overtime, how unevenly time was shared, and absences
+    pub fn compute(session: &Session) -> Self {
+        let elapsed = session.elapsed();
+        let overtime = elapsed.saturating_sub(session.duration());
+
+        let totals = session.person_totals();
+        let spoke: Vec<f64> = totals
+            .iter()
+            .filter(|t| **t >= ABSENT_THRESHOLD)
+            .map(Duration::as_secs_f64)
+            .collect();
+        let absent = totals.len() - spoke.len();
+
+        let spread = spread(&spoke);
+
+        // running 50% over the planned duration costs all of the overtime points
+        let overtime_ratio =
+            overtime.as_secs_f64() / (session.duration().as_secs_f64() / 2.0).max(1.0);
+        let absent_ratio = absent as f64 / totals.len().max(1) as f64;
+
+        let penalty = OVERTIME_WEIGHT * overtime_ratio.min(1.0)
+            + SPREAD_WEIGHT * spread.min(1.0)
+            + ABSENCE_WEIGHT * absent_ratio;
+
+        Self {
+            score: (100.0 - penalty).round().clamp(0.0, 100.0) as u8,
+            elapsed,
+            overtime,
+            paused: session.paused_time(),
+            spread,
+            absent,
+        }
+    }
+
+    /// How this meeting compares with the series' recent ones, e.g. "Ran 40% longer than usual".
+    /// Empty until there are enough past meetings to compare with.
+    pub fn trends(&self, past: &[MeetingRecord]) -> Vec<String> {
+        let baseline = &past[past.len().saturating_sub(BASELINE_MEETINGS)..];
+        if baseline.len() < MIN_BASELINE {
+            return Vec::new();
+        }
+        let mut notes = Vec::new();
+
+        let usual = average(baseline.iter().map(|m| m.elapsed_secs as f64));
+        let change = self.elapsed.as_secs_f64() / usual.max(1.0) - 1.0;
+        if change >= NOTABLE_CHANGE {
+            notes.push(format!("Ran {:.0}% longer than usual", change * 100.0));
+        } else if change <= -NOTABLE_CHANGE {
+            notes.push(format!("Ran {:.0}% shorter than usual", -change * 100.0));
+        }
+
+        let usual_overtime = average(baseline.iter().map(|m| m.overtime_secs as f64));
+        let overtime = self.overtime.as_secs_f64();
+        let late = baseline.iter().filter(|m| m.overtime_secs > 0).count();
+        if overtime > 0.0 && late == 0 {
+            notes.push(format!("First overrun in {} meetings", baseline.len()));
+        } else if overtime > 0.0 && overtime >= usual_overtime * (1.0 + NOTABLE_CHANGE) {
+            notes.push(format!(
+                "Overran by {}, usually {}",
+                format_secs(overtime),
+                format_secs(usual_overtime)
+            ));
+        } else if overtime == 0.0 && late * 2 > baseline.len() {
+            notes.push(format!(
+                "Finished on time; {} of the last {} ran over",
+                late,
+                baseline.len()
+            ));
+        }
+
+        // older meetings didn't record who spoke, so they can't say how even it was
+        let spreads: Vec<f64> = baseline
+            .iter()
+            .filter(|m| !m.turns.is_empty())
+            .map(|m| spread(&m.turns.iter().map(|t| t.secs as f64).collect::<Vec<_>>()))
+            .collect();
+        if spreads.len() >= MIN_BASELINE {
+            let usual_spread = average(spreads.into_iter());
+            let change = self.spread / usual_spread.max(0.01) - 1.0;
+            if change >= NOTABLE_CHANGE {
+                notes.push("Talk time was shared less evenly than usual".to_string());
+            } else if change <= -NOTABLE_CHANGE {
+                notes.push("Talk time was shared more evenly than usual".to_string());
+            }
+        }
+
+        notes
+    }
+
+    /// One-word verdict for the score
+    pub fn verdict(&self) -> &'static str {
+        match self.score {
+            90.. => "Excellent",
+            75..=89 => "Good",
+            50..=74 => "Fair",
+            _ => "Needs work",
+        }
+    }
+}
+
+/// Standard deviation of talk times relative to their mean
+fn spread(times: &[f64]) -> f64 {
+    if times.is_empty() {
+        return 0.0;
+    }
+    let mean = times.iter().sum::<f64>() / times.len() as f64;
+    let variance = times.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / times.len() as f64;
+    variance.sqrt() / mean
+}
+
+fn average(values: impl Iterator<Item = f64>) -> f64 {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), v| (sum + v, count + 1));
+    sum / count.max(1) as f64
+}
+
+fn format_secs(secs: f64) -> String {
+    format_duration(Duration::from_secs_f64(secs))
+}
@@ -0,0 +1,178 @@
+use chrono::Local;
+use std::fmt;
+
+use crate::app::{App, Mode};
+use crate::format::format_duration;
+use crate::session::PAUSE_REASONS;
+use crate::title::greeting;
+
+/// A key binding offered in the help bar
+pub struct KeyHint {
+    pub keys: &'static str,
+    pub action: &'static str,
+}
+
+impl KeyHint {
+    fn new(keys: &'static str, action: &'static str) -> Self {
+        Self { keys, action }
+    }
+}
+
+/// What the help bar says: a notice about what's going on, then the keys that do something right now
+pub struct Help {
+    pub status: Option<String>,
+    pub keys: Vec<KeyHint>,
+}
+
+impl Help {
+    /// Work out the help bar from what the app is doing
+    pub fn for_app(app: &App) -> Self {
+        if let Mode::Editing { .. } = app.mode() {
+            return Self {
+                status: None,
+                keys: vec![
+                    KeyHint::new("Enter", "Add topic"),
+                    KeyHint::new("Esc", "Cancel"),
+                ],
+            };
+        }
+
+        if let Mode::Resuming { gap } = app.mode() {
+            return Self {
+                status: Some(format!("💤 Away for {}", format_duration(*gap))),
+                keys: vec![
+                    KeyHint::new("D", "Discard it"),
+                    KeyHint::new("C", "Count it"),
+                    KeyHint::new("B", "Call it a break"),
+                ],
+            };
+        }
+
+        if let Mode::Checklist { ticked } = app.mode() {
+            let done = ticked.iter().filter(|done| **done).count();
+            return Self {
+                status: Some(format!("☑ {}/{} done", done, ticked.len())),
+                keys: vec![
+                    KeyHint::new("1-9", "Tick off"),
+                    KeyHint::new("Enter", "Done"),
+                    KeyHint::new("Esc", "Skip"),
+                ],
+            };
+        }
+
+        // short-lived notices take over the whole bar
+        if let Some(marker) = app.recent_marker() {
+            return Self::notice(marker);
+        }
+        let current = app.speakers()[app.current_person_index()].name();
+        if let Some(cutoff_in) = app.cutoff_in() {
+            return Self::notice(format!(
+                "✂ {}'s slot is up, moving on in {}",
+                current,
+                format_duration(cutoff_in)
+            ));
+        }
+
+        if app.is_read_only() {
+            return Self {
+                status: Some("Following another nextup".to_string()),
+                keys: vec![KeyHint::new("Q", "Detach")],
+            };
+        }
+        if app.is_locked() {
+            return Self {
+                status: Some("⌛ Time is up".to_string()),
+                keys: vec![KeyHint::new("Q", "Quit")],
+            };
+        }
+
+        let warm_up = *app.mode() == Mode::PreMeeting;
+        let mut keys = Vec::new();
+
+        if *app.mode() == Mode::Paused {
+            // the clock is stopped, so resetting or reshuffling would only confuse
+            keys.push(KeyHint::new("Space", "Resume"));
+            keys.push(KeyHint::new("Tab/↓", "Next"));
+            keys.push(KeyHint::new("↑", "Previous"));
+            for (key, hint, _) in PAUSE_REASONS {
+                keys.push(KeyHint::new(key, hint));
+            }
+            keys.push(KeyHint::new("Q", "Quit"));
+            let status = match app.pause_reason() {
+                Some(reason) => format!("⏸ Paused ({})", reason),
+                None => "⏸ Paused".to_string(),
+            };
+            return Self {
+                status: Some(status),
+                keys,
+            };
+        }
+
+        if !app.config().hide_timer {
+            keys.push(KeyHint::new("Ctrl+R", "Reset timer"));
+        }
+        if app.is_order_locked() {
+            keys.push(KeyHint::new("L", "Unlock order"));
+        } else {
+            keys.push(KeyHint::new("Ctrl+N", "Reshuffle names"));
+        }
+        if warm_up {
+            keys.push(KeyHint::new("Tab/↓", "Start turns"));
+        } else {
+            keys.push(KeyHint::new("Tab/↓", "Next"));
+            keys.push(KeyHint::new("↑", "Previous"));
+        }
+        keys.push(KeyHint::new("Space", "Pause"));
+        if !warm_up {
+            keys.push(KeyHint::new("X", "Mark absent"));
+        }
+        if app.last_marked_absent().is_some() {
+            keys.push(KeyHint::new("Shift+X", "Bring back"));
+        }
+        if !warm_up && app.topics_for(current).any(|topic| !topic.done) {
+            keys.push(KeyHint::new("Enter", "Check off topic"));
+        }
+        if !warm_up && !app.config().widget {
+            keys.push(KeyHint::new("T", "Add topic"));
+        }
+        if app.has_teams() && !app.is_wheel() {
+            keys.push(KeyHint::new("←/→", "Collapse/expand teams"));
+        }
+        if app.is_wheel() {
+            keys.push(KeyHint::new("W", "List view"));
+        }
+        keys.push(KeyHint::new("Q", "Quit"));
+
+        // a hello while everyone's joining, then a nudge for whoever's used most of their week
+        let status = if warm_up {
+            Some(greeting(Local::now()).to_string())
+        } else {
+            app.airtime_used(app.current_person_index())
+                .map(|used| format!("💬 {} has used {}% of this week's airtime", current, used))
+        };
+        Self { status, keys }
+    }
+
+    fn notice(status: String) -> Self {
+        Self {
+            status: Some(status),
+            keys: Vec::new(),
+        }
+    }
+}
+
+impl fmt::Display for Help {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = self
+            .status
+            .iter()
+            .cloned()
+            .chain(
+                self.keys
+                    .iter()
+                    .map(|hint| format!("<{}> {}", hint.keys, hint.action)),
+            )
+            .collect();
+        write!(f, "{}", parts.join(" | "))
+    }
+}
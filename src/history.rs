@@ -0,0 +1,537 @@
+use chrono::NaiveDate;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::mem;
+use std::path::PathBuf;
+
+use crate::error::{AppError, Result};
+use crate::fair::Ledger;
+use crate::health::Health;
+use crate::pairs::PAIRING_MEMORY;
+use crate::privacy::Pseudonyms;
+use crate::roster::Member;
+use crate::series::Series;
+use crate::topics::Topic;
+
+// How many recent questions are remembered per person
+const QUESTION_MEMORY: usize = 20;
+
+/// Persistent state carried between meetings of a series
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct History {
+    // Questions asked per person, most recent last
+    #[serde(default)]
+    questions: HashMap<String, Vec<String>>,
+
+    // Past facilitators, most recent last
+    #[serde(default)]
+    facilitators: Vec<String>,
+
+    // Past meetings, oldest first
+    #[serde(default)]
+    meetings: Vec<MeetingRecord>,
+
+    // Topics left over from meetings, to follow up on afterwards
+    #[serde(default)]
+    parking_lot: Vec<ParkedTopic>,
+
+    // Groups made by `nextup pairs`, oldest first
+    #[serde(default)]
+    pairings: Vec<Pairing>,
+
+    // Slots staffed by `nextup assign`, oldest first
+    #[serde(default)]
+    assignments: Vec<Assignment>,
+
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    // set when names are hashed for the series: they're saved as identifiers
+    #[serde(skip)]
+    pseudonyms: Option<Pseudonyms>,
+}
+
+/// Summary of one past meeting
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MeetingRecord {
+    pub date: NaiveDate,
+    // the sprint the meeting was in, when sprints were set up
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sprint: Option<i64>,
+    pub score: u8,
+    pub elapsed_secs: u64,
+    pub overtime_secs: u64,
+    pub absent: usize,
+    // how long each person who spoke had the floor, over all rounds
+    #[serde(default)]
+    pub turns: Vec<Turn>,
+    // everyone on the team who didn't speak; not known for meetings recorded before this was kept
+    #[serde(default)]
+    pub absentees: Vec<String>,
+    // time the meeting was paused, left out of `elapsed_secs`
+    #[serde(default)]
+    pub paused_secs: u64,
+    // people whose turn in a hybrid standup was their written update rather than speaking
+    #[serde(default)]
+    pub written: Vec<String>,
+    // the facilitator's checklist, as it was ticked off at the end
+    #[serde(default)]
+    pub checklist: Vec<ChecklistItem>,
+}
+
+impl MeetingRecord {
+    /// A meeting's record with its health, before who spoke and the rest are filled in
+    pub fn new(date: NaiveDate, health: &Health) -> Self {
+        Self {
+            date,
+            sprint: None,
+            score: health.score,
+            elapsed_secs: health.elapsed.as_secs(),
+            overtime_secs: health.overtime.as_secs(),
+            absent: health.absent,
+            turns: Vec::new(),
+            absentees: Vec::new(),
+            paused_secs: health.paused.as_secs(),
+            written: Vec::new(),
+            checklist: Vec::new(),
+        }
+    }
+
+    /// Whether someone spoke, didn't, or wasn't on the team (as far as the record knows)
+    pub fn attended(&self, name: &str) -> Option<bool> {
+        if self.turns.iter().any(|turn| turn.name == name) {
+            Some(true)
+        } else if self.absentees.iter().any(|absentee| absentee == name) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Whether someone spoke live, rather than only sending a written update or not being there
+    pub fn spoke(&self, name: &str) -> Option<bool> {
+        self.attended(name)
+            .map(|attended| attended && !self.written.iter().any(|w| w == name))
+    }
+}
+
+/// A facilitator's checklist item, and whether it was done in a past meeting
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ChecklistItem {
+    pub item: String,
+    pub done: bool,
+}
+
+/// One person's time in a past meeting
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Turn {
+    pub name: String,
+    pub secs: u64,
+}
+
+/// A topic someone didn't get to during their turn
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ParkedTopic {
+    pub date: NaiveDate,
+    pub name: String,
+    pub topic: String,
+}
+
+/// Groups made for one pairing round
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Pairing {
+    pub date: NaiveDate,
+    pub groups: Vec<Vec<String>>,
+}
+
+/// People given an interview slot or review
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Assignment {
+    pub date: NaiveDate,
+    pub slot: String,
+    pub names: Vec<String>,
+}
+
+impl History {
+    /// Location of a series' history file
+    /// (e.g. `~/.local/state/nextup/series/team-daily-standup/history.json`)
+    pub fn path(series: &Series) -> Option<PathBuf> {
+        series.dir().map(|dir| dir.join("history.json"))
+    }
+
+    /// Load a series' history, starting fresh if it doesn't exist yet
+    pub fn load(series: &Series) -> Result<Self> {
+        let Some(path) = Self::path(series) else {
+            return Ok(Self::default());
+        };
+
+        let mut history: Self = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| AppError::HistoryError(format!("{}: {}", path.display(), e)))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(e) => {
+                return Err(AppError::HistoryError(format!("{}: {}", path.display(), e)).into());
+            }
+        };
+
+        history.path = Some(path);
+        history.pseudonyms = Pseudonyms::load(series)?;
+        Ok(history)
+    }
+
+    /// Write the history file, creating its directory if needed
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = match &self.pseudonyms {
+            Some(pseudonyms) => {
+                let mut hashed = self.clone();
+                hashed.rename(&self.names(), |name| pseudonyms.id(name));
+                hashed.to_json()?
+            }
+            None => self.to_json()?,
+        };
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Questions recently asked to a person, most recent last
+    pub fn recent_questions(&self, name: &str) -> &[String] {
+        self.questions.get(name).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Remember that a person was asked a question
+    pub fn record_question(&mut self, name: &str, question: &str) {
+        let asked = self.questions.entry(name.to_string()).or_default();
+        asked.retain(|q| q != question);
+        asked.push(question.to_string());
+
+        if asked.len() > QUESTION_MEMORY {
+            asked.drain(..asked.len() - QUESTION_MEMORY);
+        }
+    }
+
+    /// Pick whoever facilitated least recently (or never) as today's facilitator
+    pub fn next_facilitator(&self, members: &[Member]) -> Option<String> {
+        let mut ledger = Ledger::new(members.iter().map(|m| m.name.clone()));
+        for facilitator in &self.facilitators {
+            ledger.record(facilitator);
+        }
+        ledger.pick(1).first().map(|name| name.to_string())
+    }
+
+    /// Remember who facilitated a meeting
+    pub fn record_facilitator(&mut self, name: &str) {
+        self.facilitators.retain(|f| f != name);
+        self.facilitators.push(name.to_string());
+    }
+
+    /// Remember how a meeting went
+    pub fn record_meeting(&mut self, record: MeetingRecord) {
+        self.meetings.push(record);
+    }
+
+    /// How many of the latest meetings in a row someone missed
+    pub fn absence_streak(&self, name: &str) -> usize {
+        self.meetings
+            .iter()
+            .rev()
+            .take_while(|meeting| meeting.attended(name) == Some(false))
+            .count()
+    }
+
+    /// How many of the meetings someone was on the team for have gone by since they last spoke
+    /// live; all of them if they never have. None when no meeting knows them.
+    pub fn standups_since_spoke(&self, name: &str) -> Option<usize> {
+        let known: Vec<bool> = self
+            .meetings
+            .iter()
+            .filter_map(|meeting| meeting.spoke(name))
+            .collect();
+        if known.is_empty() {
+            return None;
+        }
+        Some(known.iter().rev().take_while(|spoke| !**spoke).count())
+    }
+
+    /// How late in the order each person has spoken over the last `meetings`, averaged: 0 when
+    /// they always went first, 1 when they always went last. People who haven't spoken in any
+    /// of them aren't included.
+    pub fn lateness(&self, meetings: usize) -> HashMap<String, f64> {
+        let mut positions: HashMap<String, Vec<f64>> = HashMap::new();
+        let recent = &self.meetings[self.meetings.len().saturating_sub(meetings)..];
+        for meeting in recent.iter().filter(|m| m.turns.len() > 1) {
+            let last = (meeting.turns.len() - 1) as f64;
+            for (i, turn) in meeting.turns.iter().enumerate() {
+                positions
+                    .entry(turn.name.clone())
+                    .or_default()
+                    .push(i as f64 / last);
+            }
+        }
+        positions
+            .into_iter()
+            .map(|(name, positions)| {
+                let average = positions.iter().sum::<f64>() / positions.len() as f64;
+                (name, average)
+            })
+            .collect()
+    }
+
+    /// Past meetings, oldest first
+    pub fn meetings(&self) -> &[MeetingRecord] {
+        &self.meetings
+    }
+
+    /// Move a topic nobody got to into the parking lot
+    pub fn park(&mut self, date: NaiveDate, topic: &Topic) {
+        self.parking_lot.push(ParkedTopic {
+            date,
+            name: topic.name.clone(),
+            topic: topic.text.clone(),
+        });
+    }
+
+    /// Past pairing rounds, oldest first
+    pub fn pairings(&self) -> &[Pairing] {
+        &self.pairings
+    }
+
+    /// Remember who was grouped together
+    pub fn record_pairing(&mut self, date: NaiveDate, groups: Vec<Vec<String>>) {
+        self.pairings.push(Pairing { date, groups });
+    }
+
+    /// Past assignments, oldest first
+    pub fn assignments(&self) -> &[Assignment] {
+        &self.assignments
+    }
+
+    /// Remember who was given a slot
+    pub fn record_assignment(&mut self, date: NaiveDate, slot: String, names: Vec<String>) {
+        self.assignments.push(Assignment { date, slot, names });
+    }
+
+    /// Forget meetings, parked topics, pairings and assignments from before `cutoff`; how many
+    /// entries went
+    pub fn prune(&mut self, cutoff: NaiveDate) -> usize {
+        let before = self.entries();
+        self.meetings.retain(|meeting| meeting.date >= cutoff);
+        self.parking_lot.retain(|topic| topic.date >= cutoff);
+        self.pairings.retain(|pairing| pairing.date >= cutoff);
+        self.assignments
+            .retain(|assignment| assignment.date >= cutoff);
+        before - self.entries()
+    }
+
+    /// Drop what nothing reads any more: questions and facilitator turns of people who've left the
+    /// team, pairings too old to be avoided, assignments older than `assignments_since`, and topics
+    /// parked more than once; how many entries went
+    pub fn compact(&mut self, members: &[Member], assignments_since: NaiveDate) -> usize {
+        let on_team = |name: &str| members.iter().any(|member| member.name == name);
+        let before = self.entries();
+
+        self.questions.retain(|name, _| on_team(name));
+        self.facilitators.retain(|name| on_team(name));
+        self.pairings
+            .drain(..self.pairings.len().saturating_sub(PAIRING_MEMORY));
+        self.assignments
+            .retain(|assignment| assignment.date >= assignments_since);
+        // the latest time a topic was parked is the one to follow up on
+        let mut seen = HashSet::new();
+        self.parking_lot.reverse();
+        self.parking_lot
+            .retain(|topic| seen.insert((topic.name.clone(), topic.topic.clone())));
+        self.parking_lot.reverse();
+
+        before - self.entries()
+    }
+
+    /// Replace everyone's name with "Person 1", "Person 2" and so on, the same person always
+    /// getting the same number, including where names come up in topics and slots
+    pub fn anonymize(&mut self) {
+        let names = self.names();
+        let alias = |name: &str| match names.iter().position(|n| n == name) {
+            Some(i) => format!("Person {}", i + 1),
+            None => name.to_string(),
+        };
+        self.rename(&names, alias);
+    }
+
+    /// Whether names are hashed for this series
+    pub fn is_private(&self) -> bool {
+        self.pseudonyms.is_some()
+    }
+
+    /// Put back the names of the team's members where the history has their identifiers, when
+    /// names are hashed for this series
+    pub fn identify(&mut self, members: &[Member]) {
+        let Some(pseudonyms) = &self.pseudonyms else {
+            return;
+        };
+        let names: HashMap<String, String> = members
+            .iter()
+            .map(|member| (pseudonyms.id(&member.name), member.name.clone()))
+            .collect();
+        let ids: Vec<String> = names.keys().cloned().collect();
+        self.rename(&ids, |id| {
+            names.get(id).cloned().unwrap_or_else(|| id.to_string())
+        });
+    }
+
+    /// Leave out what people wrote, for exports when names are hashed
+    pub fn omit_notes(&mut self) {
+        for topic in &mut self.parking_lot {
+            topic.topic.clear();
+        }
+    }
+
+    /// Remove every mention of someone, by any of the names or identifiers in `who`; how many
+    /// entries mentioned them
+    pub fn forget(&mut self, who: &[String]) -> usize {
+        let is = |name: &String| who.contains(name);
+        let drop_from = |names: &mut Vec<String>| {
+            let before = names.len();
+            names.retain(|name| !is(name));
+            before - names.len()
+        };
+        let mut forgotten = 0;
+
+        for meeting in &mut self.meetings {
+            let before = meeting.turns.len();
+            meeting.turns.retain(|turn| !is(&turn.name));
+            forgotten += before - meeting.turns.len();
+            forgotten += drop_from(&mut meeting.absentees) + drop_from(&mut meeting.written);
+        }
+        forgotten += drop_from(&mut self.facilitators);
+        for pairing in &mut self.pairings {
+            forgotten += pairing.groups.iter_mut().map(drop_from).sum::<usize>();
+            pairing.groups.retain(|group| !group.is_empty());
+        }
+        for assignment in &mut self.assignments {
+            forgotten += drop_from(&mut assignment.names);
+        }
+        self.pairings.retain(|pairing| !pairing.groups.is_empty());
+        self.assignments
+            .retain(|assignment| !assignment.names.is_empty());
+
+        let before = self.questions.len() + self.parking_lot.len();
+        self.questions.retain(|name, _| !is(name));
+        self.parking_lot.retain(|topic| !is(&topic.name));
+        forgotten + before - self.questions.len() - self.parking_lot.len()
+    }
+
+    /// The history as pretty-printed JSON, as it's saved
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Everything kept, counted for what pruning and compacting took out
+    fn entries(&self) -> usize {
+        self.questions.len()
+            + self.facilitators.len()
+            + self.meetings.len()
+            + self.parking_lot.len()
+            + self.pairings.len()
+            + self.assignments.len()
+    }
+
+    /// Everyone the history names, in the order they first come up
+    fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        let mut learn = |name: &String| {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        };
+        for meeting in &self.meetings {
+            meeting.turns.iter().for_each(|turn| learn(&turn.name));
+            meeting.absentees.iter().for_each(&mut learn);
+            meeting.written.iter().for_each(&mut learn);
+        }
+        self.facilitators.iter().for_each(&mut learn);
+        let mut asked: Vec<&String> = self.questions.keys().collect();
+        asked.sort();
+        asked.into_iter().for_each(&mut learn);
+        self.parking_lot.iter().for_each(|topic| learn(&topic.name));
+        for pairing in &self.pairings {
+            pairing.groups.iter().flatten().for_each(&mut learn);
+        }
+        for assignment in &self.assignments {
+            assignment.names.iter().for_each(&mut learn);
+        }
+        names
+    }
+
+    /// Give everyone in `names` the name `alias` has for them, including where they come up in
+    /// topics and slots
+    fn rename(&mut self, names: &[String], alias: impl Fn(&str) -> String) {
+        // longest first, so a name inside a longer one doesn't break it up
+        let mut by_length: Vec<&String> = names.iter().collect();
+        by_length.sort_by_key(|name| std::cmp::Reverse(name.len()));
+        let scrub = |text: &str| {
+            by_length.iter().fold(text.to_string(), |text, name| {
+                replace_word(&text, name, &alias(name))
+            })
+        };
+
+        for meeting in &mut self.meetings {
+            for turn in &mut meeting.turns {
+                turn.name = alias(&turn.name);
+            }
+            for name in meeting.absentees.iter_mut().chain(&mut meeting.written) {
+                *name = alias(name);
+            }
+        }
+        for name in &mut self.facilitators {
+            *name = alias(name);
+        }
+        self.questions = mem::take(&mut self.questions)
+            .into_iter()
+            .map(|(name, asked)| (alias(&name), asked))
+            .collect();
+        for topic in &mut self.parking_lot {
+            topic.name = alias(&topic.name);
+            topic.topic = scrub(&topic.topic);
+        }
+        for name in self
+            .pairings
+            .iter_mut()
+            .flat_map(|p| p.groups.iter_mut().flatten())
+        {
+            *name = alias(name);
+        }
+        for assignment in &mut self.assignments {
+            assignment.slot = scrub(&assignment.slot);
+            for name in &mut assignment.names {
+                *name = alias(name);
+            }
+        }
+    }
+}
+
+/// Replace `word` where it stands on its own in `text`, not where it's part of a longer word
+fn replace_word(text: &str, word: &str, with: &str) -> String {
+    let is_word = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+    let mut replaced = String::new();
+    let mut rest = text;
+    while let Some(at) = rest.find(word) {
+        let (before, after) = (&rest[..at], &rest[at + word.len()..]);
+        replaced.push_str(before);
+        if is_word(before.chars().next_back()) || is_word(after.chars().next()) {
+            replaced.push_str(word);
+        } else {
+            replaced.push_str(with);
+        }
+        rest = after;
+    }
+    replaced.push_str(rest);
+    replaced
+}
@@ -0,0 +1,278 @@
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode},
+};
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use crate::command::{AppCommand, MeetingCommand};
+use crate::error::Result;
+
+// Seconds added or taken away by the manual adjustment keys
+const ADJUST_MEETING_SECS: i64 = 60;
+const ADJUST_SPEAKER_SECS: i64 = 30;
+
+// The letter keys of a QWERTY keyboard, row by row; the layouts below list what the same keys type
+const QWERTY: &str = "qwertyuiopasdfghjklzxcvbnm";
+const AZERTY: &str = "azertyuiopqsdfghjklwxcvbn,";
+const QWERTZ: &str = "qwertzuiopasdfghjklyxcvbnm";
+const DVORAK: &str = "',.pyfgcrlaoeuidhtn;qjkxbm";
+const COLEMAK: &str = "qwfpgjluy;arstdhneizxcvbkm";
+// non-Latin layouts, whose keys are printed with the QWERTY letters too
+const RUSSIAN: &str = "йцукенгшщзфывапролдячсмить";
+const UKRAINIAN: &str = "йцукенгшщзфівапролдячсмить";
+const GREEK: &str = ";ςερτυθιοπασδφγηξκλζχψωβνμ";
+
+/// Which keyboard layout shortcuts are read for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeyboardLayout {
+    // shortcuts follow the letter typed; Cyrillic and Greek letters count as the Latin letter
+    // printed on the same key
+    Letters,
+    // shortcuts follow where the key is, as if the keyboard were QWERTY
+    Azerty,
+    Qwertz,
+    Dvorak,
+    Colemak,
+}
+
+impl FromStr for KeyboardLayout {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "letters" | "qwerty" => Ok(Self::Letters),
+            "azerty" => Ok(Self::Azerty),
+            "qwertz" => Ok(Self::Qwertz),
+            "dvorak" => Ok(Self::Dvorak),
+            "colemak" => Ok(Self::Colemak),
+            _ => Err(format!(
+                "unknown keyboard layout `{}`, expected letters, azerty, qwertz, dvorak or colemak",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for KeyboardLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Letters => "letters",
+            Self::Azerty => "azerty",
+            Self::Qwertz => "qwertz",
+            Self::Dvorak => "dvorak",
+            Self::Colemak => "colemak",
+        };
+        f.write_str(name)
+    }
+}
+
+impl KeyboardLayout {
+    fn tables(&self) -> &'static [&'static str] {
+        match self {
+            Self::Letters => &[RUSSIAN, UKRAINIAN, GREEK],
+            Self::Azerty => &[AZERTY],
+            Self::Qwertz => &[QWERTZ],
+            Self::Dvorak => &[DVORAK],
+            Self::Colemak => &[COLEMAK],
+        }
+    }
+
+    /// The key as the shortcuts expect it: the QWERTY letter for the key that was pressed
+    pub fn normalize(&self, mut key: KeyEvent) -> KeyEvent {
+        let KeyCode::Char(c) = key.code else {
+            return key;
+        };
+        let lower = c.to_lowercase().next().unwrap_or(c);
+        // the Greek Q key types `;`, which is only that key on a Greek keyboard
+        if *self == Self::Letters && !lower.is_alphabetic() {
+            return key;
+        }
+
+        let qwerty = self.tables().iter().find_map(|table| {
+            let position = table.chars().position(|k| k == lower)?;
+            QWERTY.chars().nth(position)
+        });
+        if let Some(qwerty) = qwerty {
+            key.code = KeyCode::Char(if c == lower {
+                qwerty
+            } else {
+                qwerty.to_ascii_uppercase()
+            });
+        }
+        key
+    }
+}
+
+/// What a key does while the meeting is under way
+pub fn command_for(key: KeyEvent) -> Option<AppCommand> {
+    let command = match (key.code, key.modifiers) {
+        // Ctrl+R -- Reset timer and per-person timers
+        (KeyCode::Char('r'), KeyModifiers::CONTROL) => MeetingCommand::Reset,
+
+        // Ctrl+N -- Reshuffle names
+        (KeyCode::Char('n'), KeyModifiers::CONTROL) => MeetingCommand::Shuffle,
+
+        // Alt+N -- Reshuffle only the people who haven't spoken yet
+        (KeyCode::Char('n'), KeyModifiers::ALT) => MeetingCommand::ShuffleRemaining,
+
+        // 'l' -- Lock the order so it can't be reshuffled by accident
+        (KeyCode::Char('l'), KeyModifiers::NONE) => MeetingCommand::ToggleOrderLock,
+
+        // 'q' -- End the meeting and show the closing screen
+        (KeyCode::Char('q'), KeyModifiers::NONE) => return Some(AppCommand::Close),
+
+        // Ctrl+C -- Quit
+        (KeyCode::Char('c'), KeyModifiers::CONTROL) => return Some(AppCommand::Quit),
+
+        // 't' -- Add a topic for the current speaker
+        (KeyCode::Char('t'), KeyModifiers::NONE) => return Some(AppCommand::EditTopic),
+
+        // Ctrl+= / Ctrl+- -- Add or take a minute off the meeting
+        (KeyCode::Char('=') | KeyCode::Char('+'), m) if m.contains(KeyModifiers::CONTROL) => {
+            MeetingCommand::AdjustRemaining(ADJUST_MEETING_SECS)
+        }
+        // without the kitty keyboard protocol, Ctrl+- arrives as Ctrl+7
+        (KeyCode::Char('-') | KeyCode::Char('7'), KeyModifiers::CONTROL) => {
+            MeetingCommand::AdjustRemaining(-ADJUST_MEETING_SECS)
+        }
+
+        // Alt+= / Alt+- -- Credit or deduct time for the current speaker
+        (KeyCode::Char('=') | KeyCode::Char('+'), m) if m.contains(KeyModifiers::ALT) => {
+            MeetingCommand::AdjustSpeaker(ADJUST_SPEAKER_SECS)
+        }
+        (KeyCode::Char('-'), KeyModifiers::ALT) => {
+            MeetingCommand::AdjustSpeaker(-ADJUST_SPEAKER_SECS)
+        }
+
+        // Enter -- Check off the current speaker's next topic
+        (KeyCode::Enter, KeyModifiers::NONE) => MeetingCommand::CheckTopic,
+
+        // Space -- Pause or resume the meeting clock and the speaker's timer
+        (KeyCode::Char(' '), KeyModifiers::NONE) => MeetingCommand::Pause,
+
+        // 'x' -- Mark the current speaker absent, skipping them but keeping them in the order
+        (KeyCode::Char('x'), KeyModifiers::NONE) => MeetingCommand::MarkAbsent,
+
+        // Shift+X -- Bring back whoever was last marked absent
+        (KeyCode::Char('X'), _) => MeetingCommand::UnmarkAbsent,
+
+        // 'm' -- Mark where the current speaker starts in the recording
+        (KeyCode::Char('m'), KeyModifiers::NONE) => MeetingCommand::Mark,
+
+        // Left Arrow -- Collapse every team but the current speaker's
+        (KeyCode::Left, KeyModifiers::NONE) => return Some(AppCommand::CollapseTeams),
+
+        // Right Arrow -- Expand all teams again
+        (KeyCode::Right, KeyModifiers::NONE) => return Some(AppCommand::ExpandTeams),
+
+        // 'w' -- Show the order as a wheel, or as a list again
+        (KeyCode::Char('w'), KeyModifiers::NONE) => return Some(AppCommand::ToggleWheel),
+
+        // Tab or Down Arrow -- Next person
+        (KeyCode::Tab, KeyModifiers::NONE) | (KeyCode::Down, KeyModifiers::NONE) => {
+            MeetingCommand::Next
+        }
+
+        // Shift+Tab or Up Arrow -- Previous person
+        (KeyCode::BackTab, KeyModifiers::NONE) | (KeyCode::Up, KeyModifiers::NONE) => {
+            MeetingCommand::Prev
+        }
+
+        _ => return None,
+    };
+    Some(AppCommand::Meeting(command))
+}
+
+/// e.g. "Ctrl+Alt+n" or "Tab"
+fn describe(key: &KeyEvent) -> String {
+    let mut name = String::new();
+    for (modifier, label) in [
+        (KeyModifiers::CONTROL, "Ctrl+"),
+        (KeyModifiers::ALT, "Alt+"),
+        (KeyModifiers::SHIFT, "Shift+"),
+        (KeyModifiers::SUPER, "Super+"),
+    ] {
+        if key.modifiers.contains(modifier) {
+            name.push_str(label);
+        }
+    }
+    match key.code {
+        KeyCode::Char(' ') => name.push_str("Space"),
+        KeyCode::Char(c) => name.push(c),
+        code => name.push_str(&code.to_string()),
+    }
+    name
+}
+
+/// What a shortcut does, in the help bar's words
+fn action(command: &AppCommand) -> &'static str {
+    match command {
+        AppCommand::Meeting(command) => match command {
+            MeetingCommand::Next => "Next",
+            MeetingCommand::Prev => "Previous",
+            MeetingCommand::Shuffle => "Reshuffle names",
+            MeetingCommand::ShuffleRemaining => "Reshuffle who's left",
+            MeetingCommand::ToggleOrderLock => "Lock/unlock order",
+            MeetingCommand::Reset => "Reset timer",
+            MeetingCommand::AdjustRemaining(secs) if *secs > 0 => "Add a minute",
+            MeetingCommand::AdjustRemaining(_) => "Take a minute off",
+            MeetingCommand::AdjustSpeaker(secs) if *secs > 0 => "Credit the speaker",
+            MeetingCommand::AdjustSpeaker(_) => "Deduct from the speaker",
+            MeetingCommand::CheckTopic => "Check off topic",
+            MeetingCommand::Mark => "Mark the recording",
+            MeetingCommand::Pause => "Pause/resume",
+            MeetingCommand::MarkAbsent => "Mark absent",
+            MeetingCommand::UnmarkAbsent => "Bring back",
+            _ => "",
+        },
+        AppCommand::Close => "Quit",
+        AppCommand::Quit => "Quit without the closing screen",
+        AppCommand::EditTopic => "Add topic",
+        AppCommand::CollapseTeams => "Collapse teams",
+        AppCommand::ExpandTeams => "Expand teams",
+        AppCommand::ToggleWheel => "Wheel/list view",
+    }
+}
+
+/// `nextup keys test`: echo each key as nextup sees it and the shortcut it triggers, until Esc
+pub fn test(layout: KeyboardLayout) -> Result<()> {
+    println!(
+        "Press keys to see what nextup makes of them ({:?} layout); Esc to stop.",
+        layout
+    );
+    enable_raw_mode()?;
+    let res = echo_keys(layout);
+    disable_raw_mode()?;
+    res
+}
+
+fn echo_keys(layout: KeyboardLayout) -> Result<()> {
+    let mut stdout = io::stdout();
+    loop {
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        if key.code == KeyCode::Esc {
+            return Ok(());
+        }
+
+        let normalized = layout.normalize(key);
+        let mut line = describe(&key);
+        if normalized != key {
+            line.push_str(&format!(" (as {})", describe(&normalized)));
+        }
+        match command_for(normalized) {
+            Some(command) => line.push_str(&format!(" → {}", action(&command))),
+            None => line.push_str(" → nothing"),
+        }
+        // raw mode needs the carriage return
+        execute!(stdout, crossterm::style::Print(format!("{}\r\n", line)))?;
+        stdout.flush()?;
+    }
+}
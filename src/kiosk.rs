@@ -0,0 +1,187 @@
+use chrono::{DateTime, Local};
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute,
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+};
+use ratatui::{
+    Frame, Terminal,
+    backend::CrosstermBackend,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+use std::io;
+use std::time::Duration;
+
+use crate::app::App;
+use crate::config::Config;
+use crate::error::Result;
+use crate::format::format_duration;
+use crate::preflight::MeetingWindow;
+use crate::session::TimeUp;
+use crate::title;
+use crate::ui::big_text;
+
+// How long to wait before bringing the display back after the terminal failed
+const RECOVERY_DELAY: Duration = Duration::from_secs(5);
+
+/// Run meetings on a wall display, every day at the start of the meeting window, without anyone
+/// touching a key: turns move on by themselves, the meeting closes itself when time is up, and
+/// the display goes back to waiting for the next one
+pub async fn run(config: Config) -> Result<()> {
+    let window = config
+        .window
+        .ok_or_else(|| anyhow::Error::msg("--kiosk needs a --window to start meetings at"))?;
+
+    // nobody is at the keyboard to move things on or close the meeting
+    let mut meeting_config = Config {
+        auto_advance: true,
+        time_up: TimeUp::Exit,
+        ..config
+    };
+
+    let mut held_today = false;
+    loop {
+        let start = window.next_start(Local::now(), held_today, false);
+        match wait_until(&meeting_config, start, start) {
+            Ok(true) => {}
+            Ok(false) => return Ok(()),
+            Err(e) => {
+                eprintln!("Kiosk display failed, retrying: {}", e);
+                tokio::time::sleep(RECOVERY_DELAY).await;
+                continue;
+            }
+        }
+
+        if let Err(e) = title::refresh(&mut meeting_config) {
+            eprintln!("Couldn't name today's meeting: {}", e);
+        }
+        run_meeting(meeting_config.clone(), window).await;
+        held_today = true;
+    }
+}
+
+/// Run one meeting, bringing it back from its autosave if the terminal goes away mid-meeting
+async fn run_meeting(mut config: Config, window: MeetingWindow) {
+    loop {
+        let result = match App::new(config.clone()).await {
+            Ok(mut app) => app.run().await,
+            Err(e) => Err(e),
+        };
+        let Err(e) = result else {
+            return;
+        };
+
+        // once the window is over there's no meeting left to get back to
+        if Local::now().time() >= window.end {
+            eprintln!("Kiosk meeting failed: {}", e);
+            return;
+        }
+        eprintln!("Kiosk meeting failed, resuming: {}", e);
+        tokio::time::sleep(RECOVERY_DELAY).await;
+        config.take_over = true;
+    }
+}
+
+/// Show the time and when the meeting starts until `wake`, which is usually the start itself.
+/// Returns false if someone quit with `Q` or `Ctrl+C` instead.
+pub fn wait_until(config: &Config, wake: DateTime<Local>, start: DateTime<Local>) -> Result<bool> {
+    if Local::now() >= wake {
+        return Ok(true);
+    }
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+    // whatever the last meeting left behind
+    terminal.clear()?;
+
+    let res = (|| -> Result<bool> {
+        while Local::now() < wake {
+            terminal.draw(|f| render_waiting(f, config, start))?;
+
+            if event::poll(Duration::from_secs(1))?
+                && let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+                && matches!(
+                    (key.code, key.modifiers),
+                    (KeyCode::Char('q'), KeyModifiers::NONE)
+                        | (KeyCode::Char('c'), KeyModifiers::CONTROL)
+                )
+            {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    })();
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+
+    res
+}
+
+/// The clock, big, with when the meeting starts below it
+fn render_waiting(f: &mut Frame, config: &Config, start: DateTime<Local>) {
+    let now = Local::now();
+    let until = (start - now).to_std().unwrap_or_default();
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            config.title.clone(),
+            Style::default().fg(Color::Cyan),
+        )),
+        Line::from(""),
+    ];
+    lines.extend(
+        big_text(&now.format("%H:%M").to_string())
+            .into_iter()
+            .map(Line::from),
+    );
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!(
+            "Next standup {} at {}, in {}",
+            day_name(start, now),
+            start.format("%H:%M"),
+            format_wait(until)
+        ),
+        Style::default().fg(Color::Gray),
+    )));
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Fill(1),
+            Constraint::Length(lines.len() as u16),
+            Constraint::Fill(1),
+        ])
+        .split(f.area());
+    f.render_widget(
+        Paragraph::new(lines).alignment(Alignment::Center),
+        chunks[1],
+    );
+}
+
+/// e.g. "3h 5m", or the seconds too once it's close
+fn format_wait(until: Duration) -> String {
+    let hours = until.as_secs() / 3600;
+    if hours > 0 {
+        format!("{}h {}m", hours, until.as_secs() % 3600 / 60)
+    } else {
+        format_duration(until)
+    }
+}
+
+/// "today", "tomorrow" or the weekday
+pub fn day_name(start: DateTime<Local>, now: DateTime<Local>) -> String {
+    match (start.date_naive() - now.date_naive()).num_days() {
+        0 => "today".to_string(),
+        1 => "tomorrow".to_string(),
+        _ => format!("on {}", start.format("%A")),
+    }
+}
@@ -0,0 +1,182 @@
+use std::io::{self, Read};
+use std::net::{SocketAddr, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::autosave::Autosave;
+use crate::error::{AppError, Result};
+use crate::series::Series;
+
+// The mDNS service type meetings are advertised under
+#[cfg(feature = "lan")]
+const SERVICE_TYPE: &str = "_nextup._tcp.local.";
+
+// How long to look for a meeting already running on the network before leading one
+#[cfg(feature = "lan")]
+const DISCOVERY_TIMEOUT: Duration = Duration::from_secs(2);
+
+// How long a follower waits on the leader for each snapshot
+const FETCH_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// This instance's part in a meeting shared with others on the network
+pub enum Lan {
+    // runs the meeting and serves it to followers
+    Leader(Leader),
+    // mirrors the meeting the leader at this address runs
+    Follower(SocketAddr),
+}
+
+/// Serves the latest autosave of the meeting to followers, and advertises it over mDNS so
+/// they find it without being told where
+pub struct Leader {
+    // the autosave as JSON, empty until the first one and `null` once the meeting is over
+    snapshot: Arc<Mutex<String>>,
+    #[cfg(feature = "lan")]
+    daemon: mdns_sd::ServiceDaemon,
+    #[cfg(feature = "lan")]
+    fullname: String,
+}
+
+impl Leader {
+    /// Start serving the series' meeting on a free port and advertise it
+    #[cfg(feature = "lan")]
+    pub fn start(series: &Series) -> Result<Self> {
+        use std::io::Write;
+        use std::net::TcpListener;
+        use std::thread;
+
+        let listener = TcpListener::bind("0.0.0.0:0")?;
+        let port = listener.local_addr()?.port();
+        let snapshot = Arc::new(Mutex::new(String::new()));
+
+        // each follower connects, reads the latest autosave and hangs up
+        let serving = snapshot.clone();
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let json = serving
+                    .lock()
+                    .map(|snapshot| snapshot.clone())
+                    .unwrap_or_default();
+                let _ = stream.write_all(json.as_bytes());
+            }
+        });
+
+        let error = |e: mdns_sd::Error| AppError::LanError(e.to_string());
+        let daemon = mdns_sd::ServiceDaemon::new().map_err(error)?;
+        let instance = format!("{}-{}", series.id(), std::process::id());
+        let service = mdns_sd::ServiceInfo::new(
+            SERVICE_TYPE,
+            &instance,
+            &format!("{}.local.", instance),
+            "",
+            port,
+            &[("series", series.id())][..],
+        )
+        .map_err(error)?
+        .enable_addr_auto();
+        let fullname = service.get_fullname().to_string();
+        daemon.register(service).map_err(error)?;
+
+        Ok(Self {
+            snapshot,
+            daemon,
+            fullname,
+        })
+    }
+
+    // built without the `lan` feature, there's no network to share the meeting on
+    #[cfg(not(feature = "lan"))]
+    pub fn start(_series: &Series) -> Result<Self> {
+        Err(not_built_in())
+    }
+
+    /// Hand followers the latest autosave
+    pub fn publish(&self, autosave: &Autosave) {
+        if let (Ok(json), Ok(mut snapshot)) =
+            (serde_json::to_string(autosave), self.snapshot.lock())
+        {
+            *snapshot = json;
+        }
+    }
+}
+
+impl Drop for Leader {
+    /// Stop advertising, and let followers know the meeting is over
+    fn drop(&mut self) {
+        if let Ok(mut snapshot) = self.snapshot.lock() {
+            *snapshot = "null".to_string();
+        }
+        #[cfg(feature = "lan")]
+        {
+            let _ = self.daemon.unregister(&self.fullname);
+            let _ = self.daemon.shutdown();
+        }
+    }
+}
+
+/// Look for an instance on the network running the series' meeting, and where to follow it
+#[cfg(feature = "lan")]
+pub fn discover(series: &Series) -> Result<Option<SocketAddr>> {
+    use std::time::Instant;
+
+    let error = |e: mdns_sd::Error| AppError::LanError(e.to_string());
+    let daemon = mdns_sd::ServiceDaemon::new().map_err(error)?;
+    let events = daemon.browse(SERVICE_TYPE).map_err(error)?;
+
+    let deadline = Instant::now() + DISCOVERY_TIMEOUT;
+    let mut found = None;
+    while found.is_none() {
+        let Ok(event) = events.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+        else {
+            break;
+        };
+        if let mdns_sd::ServiceEvent::ServiceResolved(service) = event
+            && service.get_property_val_str("series") == Some(series.id())
+        {
+            // IPv4 first, as link-local IPv6 addresses need a scope to connect to
+            let mut addresses: Vec<_> = service.get_addresses().iter().copied().collect();
+            addresses.sort_by_key(|address| address.is_ipv6());
+            found = addresses
+                .first()
+                .map(|address| SocketAddr::new(*address, service.get_port()));
+        }
+    }
+
+    let _ = daemon.shutdown();
+    Ok(found)
+}
+
+#[cfg(not(feature = "lan"))]
+pub fn discover(_series: &Series) -> Result<Option<SocketAddr>> {
+    Err(not_built_in())
+}
+
+/// The leader's latest autosave; none once its meeting is over. It's an error to ask before the
+/// leader has saved one.
+pub fn fetch(leader: SocketAddr) -> Result<Option<Autosave>> {
+    let mut stream = match TcpStream::connect_timeout(&leader, FETCH_TIMEOUT) {
+        Ok(stream) => stream,
+        // nobody listening: the leader has quit
+        Err(e) if e.kind() == io::ErrorKind::ConnectionRefused => return Ok(None),
+        Err(e) => return Err(AppError::LanError(format!("{}: {}", leader, e)).into()),
+    };
+    stream.set_read_timeout(Some(FETCH_TIMEOUT))?;
+
+    let mut json = String::new();
+    stream
+        .read_to_string(&mut json)
+        .map_err(|e| AppError::LanError(format!("{}: {}", leader, e)))?;
+    if json.is_empty() {
+        return Err(AppError::LanError(format!("{}: the meeting hasn't started", leader)).into());
+    }
+    serde_json::from_str(&json).map_err(|e| AppError::LanError(format!("{}: {}", leader, e)).into())
+}
+
+#[cfg(not(feature = "lan"))]
+fn not_built_in() -> anyhow::Error {
+    AppError::NotBuiltIn {
+        what: "following meetings on the network",
+        feature: "lan",
+    }
+    .into()
+}
@@ -0,0 +1,113 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::error::{AppError, Result};
+use crate::series::Series;
+
+/// Outcome of trying to lock a series
+pub enum Acquired {
+    Locked(Lock),
+    HeldBy(u32),
+}
+
+/// Marks a series as having a running meeting on this host; released when dropped
+pub struct Lock {
+    path: Option<PathBuf>,
+}
+
+impl Lock {
+    /// Location of a series' lock file
+    fn path(series: &Series) -> Option<PathBuf> {
+        series.dir().map(|dir| dir.join("lock"))
+    }
+
+    /// Lock the series unless another running nextup holds it.
+    /// Locks left behind by a process that has exited are replaced.
+    pub fn acquire(series: &Series) -> Result<Acquired> {
+        let Some(path) = Self::path(series) else {
+            return Ok(Acquired::Locked(Self { path: None }));
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        loop {
+            match fs::File::create_new(&path) {
+                Ok(mut file) => {
+                    write!(file, "{}", std::process::id())?;
+                    return Ok(Acquired::Locked(Self { path: Some(path) }));
+                }
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => match owner(&path) {
+                    Some(pid) if is_running(pid) => return Ok(Acquired::HeldBy(pid)),
+                    // stale: remove it and try again
+                    _ => remove(&path)?,
+                },
+                Err(e) => {
+                    return Err(AppError::LockError(format!("{}: {}", path.display(), e)).into());
+                }
+            }
+        }
+    }
+
+    /// Take the lock from the running instance, which stops once it notices
+    pub fn take_over(series: &Series) -> Result<Self> {
+        let path = Self::path(series);
+        if let Some(path) = &path {
+            fs::write(path, std::process::id().to_string())?;
+        }
+        Ok(Self { path })
+    }
+
+    /// Whether another instance has taken the lock from us
+    pub fn is_lost(series: &Series) -> bool {
+        Self::path(series)
+            .and_then(|path| owner(&path))
+            .is_some_and(|pid| pid != std::process::id())
+    }
+}
+
+impl Drop for Lock {
+    fn drop(&mut self) {
+        // leave the lock alone if someone took it over
+        if let Some(path) = &self.path
+            && owner(path) == Some(std::process::id())
+        {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// Process ID recorded in a lock file
+fn owner(path: &Path) -> Option<u32> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+fn remove(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => {
+            Err(AppError::LockError(format!("{}: {}", path.display(), e)).into())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Whether a process is still alive
+#[cfg(unix)]
+fn is_running(pid: u32) -> bool {
+    Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Whether a process is still alive
+#[cfg(not(unix))]
+fn is_running(pid: u32) -> bool {
+    Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/NH"])
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()))
+}
@@ -1,51 +1,1423 @@
-use clap::Parser;
+use chrono::{NaiveDate, NaiveTime};
+use clap::{Parser, Subcommand};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::process::ExitCode;
 use std::time::Duration;
 
+mod airtime;
 mod app;
+mod assign;
+mod attendance;
+mod audit;
+mod autopilot;
+mod autosave;
+mod avatar;
+#[cfg(feature = "slack")]
+mod bot;
+mod breakout;
+mod bundle;
+mod burndown;
+mod calendar;
+mod chapters;
+mod checkin;
+mod collate;
+mod command;
 mod config;
+mod daemon;
+mod dashboard;
+mod demo;
+#[cfg(feature = "desktop")]
+mod desktop;
+mod dnd;
+mod editor;
 mod error;
+mod escalation;
+mod events;
+mod export;
+mod fair;
+mod format;
+mod handoff;
+mod health;
+mod help;
+mod history;
+mod keys;
+mod kiosk;
+mod lan;
+mod lock;
+mod meeting;
+mod notify;
+mod order;
+mod pairs;
+mod per_person;
+mod preflight;
+mod privacy;
+mod questions;
+mod queue;
+mod remind;
+mod report;
+mod retention;
+mod roster;
+mod santa;
+mod schema;
+mod series;
+#[cfg(feature = "web")]
+mod server;
+#[cfg(not(feature = "web"))]
+#[path = "server_disabled.rs"]
+mod server;
+mod session;
+mod setup;
+#[cfg(feature = "web")]
+mod share;
+// parts of the client are only used by the bot
+#[cfg_attr(not(feature = "slack"), allow(dead_code))]
+mod slack;
+mod sources;
+mod sprint;
+mod stats;
+mod sync;
+mod templates;
+mod theme;
+mod title;
+mod topics;
+mod trace;
+mod transcript;
 mod ui;
+#[cfg(feature = "self-update")]
+mod update;
 
 use app::App;
-use config::Config;
+use avatar::ImageProtocol;
+#[cfg(feature = "slack")]
+use bot::Bot;
+use chapters::ChapterFormat;
+use checkin::CheckInOptions;
+use config::{Config, DEFAULT_CHECKLIST};
+use dnd::DoNotDisturb;
 use error::Result;
+use escalation::EscalationRule;
+use format::TimeFormat;
+use keys::KeyboardLayout;
+use lock::{Acquired, Lock};
+#[cfg(feature = "tts")]
+use notify::SpeechPattern;
+use notify::{BellPattern, OscProtocol};
+use order::OrderRule;
+use preflight::{Conflict, MeetingWindow};
+use privacy::Pseudonyms;
+use queue::RateLimit;
+use report::ReportFormat;
+use retention::Retention;
+use santa::{Delivery, Exclusion};
+use schema::SchemaKind;
+use series::{Series, Template};
+use session::{Clock, TimeUp};
+use slack::SlackClient;
+use sprint::SprintArg;
+use stats::{DateRange, StatsFormat};
+use theme::{TimerStyle, TimerTheme};
+use transcript::TranscriptFormat;
 
 #[derive(Parser)]
 #[command(name = "nextup")]
 #[command(version)]
 #[command(about = "A simple tool that randomizes a list of names for daily standups.")]
 struct Args {
-    // Window title
-    #[arg(long, default_value = "Team daily standup")]
-    title: String,
+    #[command(subcommand)]
+    command: Option<Command>,
 
-    // Path to file with team member names
-    #[arg(long, default_value = "team.txt")]
-    names: String,
+    // Window title (default: from the `title.txt` template, e.g. "Standup — Tue, Sprint 42")
+    #[arg(long, global = true)]
+    title: Option<String>,
 
-    // Meeting duration in minutes
-    #[arg(long, default_value_t = 15)]
-    duration: u64,
+    // The day sprint 1 started, for the sprint number in the generated title
+    #[arg(long, global = true, value_name = "DATE", value_parser = date_arg)]
+    sprint_epoch: Option<NaiveDate>,
+
+    // How many weeks a sprint lasts (default: 2)
+    #[arg(long, global = true, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    sprint_weeks: Option<u32>,
+
+    // Path to file with team member names (default: team.txt)
+    #[arg(long, global = true)]
+    names: Option<String>,
+
+    // Meeting duration in minutes (default: 15)
+    #[arg(long, global = true)]
+    duration: Option<u64>,
+
+    // When the meeting is scheduled, e.g. 09:30-09:45; starting far outside it asks first
+    #[arg(long, global = true)]
+    window: Option<MeetingWindow>,
+
+    // Go through the order once per round, e.g. --rounds "Yesterday/today,Blockers"
+    #[arg(long, global = true, value_delimiter = ',')]
+    rounds: Vec<String>,
+
+    // Time before the first turn for the intro and latecomers, e.g. 2m (the first Tab starts early)
+    #[arg(long, global = true, value_parser = duration_arg)]
+    warm_up: Option<Duration>,
+
+    // Time kept at the end of the meeting for wrap-up, e.g. 3m
+    #[arg(long, global = true, value_parser = duration_arg)]
+    cool_down: Option<Duration>,
+
+    // Speaking slot for anyone without a role or slot in the team file, e.g. 2m or 90s
+    // (default: the time left over, split evenly)
+    #[arg(long, global = true, value_parser = duration_arg)]
+    slot: Option<Duration>,
+
+    // Move on to the next speaker when the current one's slot runs out
+    #[arg(long, global = true, default_value_t = false)]
+    auto_advance: bool,
+
+    // Per-person limits: show the current speaker's slot under the timer and flash their row as it
+    // nearly runs out
+    #[arg(long, global = true, default_value_t = false)]
+    per_person: bool,
+
+    // Ring the bell (or --bell-sound) too when a slot is nearly up; implies --per-person
+    #[arg(long, global = true, default_value_t = false)]
+    per_person_bell: bool,
+
+    // Hard mode: flash when a slot runs out and cut the speaker off after this grace period (default 15s)
+    #[arg(long, global = true, value_name = "GRACE", num_args = 0..=1, default_missing_value = "15s", value_parser = duration_arg)]
+    hard_cutoff: Option<Duration>,
+
+    // What happens when time is up: overtime (keep counting), fast-forward (30s turns for whoever is left), lock (only quitting works) or exit (save, export and quit)
+    #[arg(
+        long,
+        global = true,
+        value_name = "BEHAVIOR",
+        default_value = "overtime"
+    )]
+    at_time_up: TimeUp,
+
+    // How the meeting clock runs: wall (time with the laptop asleep still counts) or monotonic (only time awake)
+    #[arg(long, global = true, value_name = "CLOCK", default_value = "wall")]
+    clock: Clock,
+
+    // Keyboard layout for shortcuts: letters (follow the letter typed, with Cyrillic and Greek read as the Latin letter on the key) or azerty, qwertz, dvorak or colemak (follow where the QWERTY key would be); default letters
+    #[arg(long, global = true, value_name = "LAYOUT")]
+    keyboard: Option<KeyboardLayout>,
 
     // Hide timer
-    #[arg(long, default_value_t = false)]
+    #[arg(long, global = true, default_value_t = false)]
     hide_timer: bool,
+
+    // How each speaker's time is written in the order: words (3m 5s), precise (3m 5.4s),
+    // clock (03:05), compact (3m) or percent (of their slot)
+    #[arg(long, global = true, value_name = "FORMAT", default_value = "words")]
+    time_format: TimeFormat,
+
+    // The same for the closing screen; default: as --time-format
+    #[arg(long, global = true, value_name = "FORMAT")]
+    summary_time_format: Option<TimeFormat>,
+
+    // How the countdown is drawn: bar, or analog for a clock face with a shrinking pie of time
+    #[arg(long, global = true, value_name = "STYLE", default_value = "bar")]
+    timer_style: TimerStyle,
+
+    // Timer icons and labels: default, plain (no emoji), traffic, urgent, words, or a JSON theme
+    // file
+    #[arg(long, global = true, value_name = "THEME")]
+    timer_theme: Option<String>,
+
+    // What to do as time runs out, e.g. "2m=theme:urgent", "1m=blink", "0=notify" or
+    // "30s=run:say wrap it up"; repeatable
+    #[arg(long = "escalate", global = true, value_name = "RULE")]
+    escalation: Vec<EscalationRule>,
+
+    // Beep as time runs out, e.g. --bells "5m=1,1m=off,0=3" (default: 5m=1,1m=2,0=3)
+    #[arg(long, global = true, value_name = "PATTERN", num_args = 0..=1, default_missing_value = notify::DEFAULT_BELLS)]
+    bells: Option<BellPattern>,
+
+    // Read out the time left at these thresholds and who's up next, e.g. --speak "2m,30s,0" (default: 5m,2m,1m,0)
+    #[cfg(feature = "tts")]
+    #[arg(long, global = true, value_name = "THRESHOLDS", num_args = 0..=1, default_missing_value = notify::DEFAULT_SPEECH)]
+    speak: Option<SpeechPattern>,
+
+    // Play this sound file for each beep instead of the terminal bell
+    #[arg(long, global = true, value_name = "FILE")]
+    bell_sound: Option<PathBuf>,
+
+    // Raise native terminal notifications (OSC 9 / OSC 777) as time runs out and when a
+    // speaker's slot is up; the protocol is detected unless given
+    #[arg(long, global = true, value_name = "PROTOCOL", num_args = 0..=1)]
+    terminal_notify: Option<Option<OscProtocol>>,
+
+    // Raise the same notifications through the system, with Next speaker and Pause buttons that
+    // control the meeting
+    #[cfg(feature = "desktop")]
+    #[arg(long, global = true, default_value_t = false)]
+    desktop_notify: bool,
+
+    // Show an avatar next to each name (`avatar=` in the team file, or Gravatar) in terminals that
+    // draw images, and initials elsewhere; the protocol (kitty, iterm2, sixel) is detected unless given
+    #[arg(long, global = true, value_name = "PROTOCOL", num_args = 0..=1)]
+    avatars: Option<Option<ImageProtocol>>,
+
+    // Compact borderless view (current speaker + countdown) for a small tmux pane
+    #[arg(long, global = true, default_value_t = false)]
+    widget: bool,
+
+    // Show the order as a wheel with a pointer at the speaker instead of a list; W switches
+    #[arg(long, global = true, default_value_t = false)]
+    wheel: bool,
+
+    // Give each row of the order a small bar filling up with the speaker's time against their slot
+    #[arg(long, global = true, default_value_t = false)]
+    row_bars: bool,
+
+    // Chart the turns left against the clock, with the planned pace and where it's heading
+    #[arg(long, global = true, default_value_t = false)]
+    burndown: bool,
+
+    // After the shuffle, put each team's members (or everyone, without teams) in alphabetical
+    // order by the rules of this language, e.g. `sv`; the user's locale when none is given
+    #[arg(long, global = true, value_name = "LOCALE", num_args = 0..=1)]
+    sort_names: Option<Option<String>>,
+
+    // Ask the facilitator to tick off a checklist before the closing screen: these items,
+    // comma-separated, or "started on time, parking lot reviewed, action items assigned"
+    #[arg(long, global = true, value_name = "ITEMS", num_args = 0..=1)]
+    checklist: Option<Option<String>>,
+
+    // Wall display mode: start every day at the --window start, big countdown, turns move on
+    // by themselves, and back to waiting once the meeting closes itself
+    #[arg(long, global = true, default_value_t = false)]
+    kiosk: bool,
+
+    // Run the whole meeting with nobody facilitating: start at the --window start, move on when a
+    // slot runs out, close when time is up, write the report and exit. Posts the meeting to this
+    // Slack channel with the bot token when one is given.
+    #[arg(long, global = true, value_name = "SLACK_CHANNEL", num_args = 0..=1)]
+    autopilot: Option<Option<String>>,
+
+    // Serve a live web view of the order, e.g. --serve 0.0.0.0:8080 (default)
+    #[arg(long, global = true, value_name = "ADDR", num_args = 0..=1, default_missing_value = "0.0.0.0:8080")]
+    serve: Option<SocketAddr>,
+
+    // Mirror the meeting between instances on this network: follow one already running it, or
+    // lead it and let others find it over mDNS
+    #[arg(long, global = true, default_value_t = false)]
+    lan: bool,
+
+    // Token that enables the web view's control API (POST /next, /prev, /pause, /shuffle, /absent/NAME)
+    // and check-ins
+    #[arg(long, global = true, env = "NEXTUP_API_TOKEN", hide_env_values = true)]
+    api_token: Option<String>,
+
+    // How long the web view's link works before it has to be shared again, e.g. 90m (default: 8 hours)
+    #[arg(long, global = true, value_name = "DURATION", value_parser = duration_arg)]
+    share_ttl: Option<Duration>,
+
+    // Clock time the recording started, e.g. 09:29:40 (default: when the meeting starts);
+    // press M to mark where each speaker starts in it
+    #[arg(long, global = true, value_name = "HH:MM[:SS]", value_parser = clock_arg)]
+    recording_start: Option<NaiveTime>,
+
+    // Write the marked speakers as a chapter list to this file instead of printing it on exit
+    #[arg(long, global = true, value_name = "FILE")]
+    chapters: Option<PathBuf>,
+
+    // Chapter list format: youtube (description timestamps) or ffmpeg (FFMETADATA1)
+    #[arg(long, global = true, value_name = "FORMAT", default_value = "youtube")]
+    chapter_format: ChapterFormat,
+
+    // Write a transcript skeleton to this file when the meeting ends: a heading per speaker with
+    // when they spoke, their update and topics, ready for notes
+    #[arg(long, global = true, value_name = "FILE")]
+    transcript: Option<PathBuf>,
+
+    // Fill in the transcript from this template instead of the built-in Markdown one
+    #[arg(long, global = true, value_name = "FILE")]
+    transcript_template: Option<PathBuf>,
+
+    // Write the transcript as markdown or org (org-mode, with clock lines and TODO entries);
+    // by default `.org` files are org and anything else Markdown
+    #[arg(long, global = true, value_name = "FORMAT")]
+    transcript_format: Option<TranscriptFormat>,
+
+    // Write a summary to this file when the meeting ends: attendees, the order, everyone's
+    // speaking time, the meeting's length and who ran over
+    #[arg(long, global = true, value_name = "FILE")]
+    report_path: Option<PathBuf>,
+
+    // Write the summary as md, json or csv; by default from the report file's extension
+    #[arg(long, global = true, value_name = "FORMAT")]
+    report_format: Option<ReportFormat>,
+
+    // Run headless: read commands from stdin and print newline-delimited JSON events
+    #[arg(long, global = true, default_value_t = false)]
+    json_events: bool,
+
+    // Show a question of the day for each speaker, optionally from a custom file
+    #[arg(long, global = true, value_name = "FILE", num_args = 0..=1)]
+    questions: Option<Option<String>>,
+
+    // Suggest who hands over to whom at every handover, e.g. "Alice, please hand off to Bob",
+    // optionally from a file of phrases with {from} and {to}
+    #[arg(long, global = true, value_name = "FILE", num_args = 0..=1)]
+    handoffs: Option<Option<String>>,
+
+    // Topics people want to raise, as `Name: topic` lines; unfinished ones go to the parking lot
+    #[arg(long, global = true, value_name = "FILE")]
+    topics: Option<String>,
+
+    // Silence notifications and set a Slack status while the meeting runs
+    #[arg(long, global = true, default_value_t = false)]
+    dnd: bool,
+
+    // Slack user token for --dnd status updates (needs users.profile:read and users.profile:write)
+    #[arg(long, global = true, env = "SLACK_USER_TOKEN", hide_env_values = true)]
+    slack_user_token: Option<String>,
+
+    // Slack bot token for reading check-in reactions when the meeting starts (needs reactions:read)
+    #[arg(long, global = true, env = "SLACK_BOT_TOKEN", hide_env_values = true)]
+    slack_bot_token: Option<String>,
+
+    // Slack channel to remind that the standup is coming up, with its order (`nextup remind`, or the daemon)
+    #[arg(long, global = true, value_name = "CHANNEL")]
+    remind_channel: Option<String>,
+
+    // How long before the --window start the daemon posts the reminder, e.g. 10m (default: 5m)
+    #[arg(long, global = true, value_parser = duration_arg)]
+    remind_before: Option<Duration>,
+
+    // Lock the order the reminder announces, so the meeting can't reshuffle it
+    #[arg(long, global = true, default_value_t = false)]
+    lock_order: bool,
+
+    // Fair rotation: shuffle people who've gone late in the series' last 10 meetings towards
+    // the front, so nobody keeps ending up last
+    #[arg(long, global = true, default_value_t = false)]
+    fair: bool,
+
+    // Hybrid standup: people who sent a written update keep their turn, which shows the update,
+    // and everyone else speaks live (default: async people are skipped)
+    #[arg(long, global = true, default_value_t = false)]
+    hybrid: bool,
+
+    // Read written updates from this directory, one `<name>.txt` or `.md` file per person
+    #[arg(long, global = true, value_name = "DIR")]
+    updates_dir: Option<PathBuf>,
+
+    // Scrum of scrums: one randomly picked member speaks for each `[Team]` in the team file
+    #[arg(long, global = true, default_value_t = false)]
+    representatives: bool,
+
+    // Send a trace of the meeting (a span per turn) to this OTLP/HTTP collector, e.g. http://localhost:4318
+    #[arg(long, global = true, env = "OTEL_EXPORTER_OTLP_ENDPOINT")]
+    otlp_endpoint: Option<String>,
+
+    // Headers for the collector, e.g. "x-honeycomb-team=KEY"
+    #[arg(long, global = true, env = "OTEL_EXPORTER_OTLP_HEADERS", hide_env_values = true,
+          value_delimiter = ',', value_parser = header_arg)]
+    otlp_headers: Vec<(String, String)>,
+
+    // Post Slack updates once speaker changes have settled for this long, only the last of a
+    // burst (default: 2s; 0 posts every change)
+    #[arg(long, global = true, value_parser = duration_arg)]
+    coalesce: Option<Duration>,
+
+    // At most this many calls to slack or otlp in a period, e.g. slack=20/1m; repeatable
+    #[arg(long = "rate-limit", global = true, value_name = "DEST=N/PERIOD")]
+    rate_limits: Vec<RateLimit>,
+
+    // Write the turn timings to this file as a Chrome trace, for Perfetto or chrome://tracing
+    #[arg(long, global = true, value_name = "FILE")]
+    chrome_trace: Option<PathBuf>,
+
+    // Meeting series with its own history and template (default: derived from the title)
+    #[arg(long, global = true)]
+    series: Option<String>,
+
+    // Forget meetings, parked topics, pairs and assignments older than this after each meeting,
+    // e.g. 6mo, 26w, 180d or 1y (default: keep everything)
+    #[arg(long, global = true, value_name = "AGE")]
+    retention: Option<Retention>,
+
+    // Keep only hashed identifiers for people in the series history, and leave notes out of
+    // exports; once on, it stays on for the series
+    #[arg(long, global = true, default_value_t = false)]
+    hash_names: bool,
+
+    // Save this meeting's title, names file, duration, window, rounds, keyboard layout, timer
+    // theme, escalation rules and order rules as the series template
+    #[arg(long, global = true, default_value_t = false)]
+    save_template: bool,
+
+    // Rotate the facilitator role across meetings of the series
+    #[arg(long, global = true, default_value_t = false)]
+    rotate_facilitator: bool,
+
+    // A rule the speaking order keeps to, e.g. "Alice before Bob", "Carol not first" or
+    // "Dave last on Fridays"; repeatable
+    #[arg(long = "order", global = true, value_name = "RULE")]
+    order_rules: Vec<OrderRule>,
+
+    // Merge more team sources into the team file, e.g. exported calendar attendees or a GitHub
+    // team, one name, email or handle per line; repeatable
+    #[arg(long = "source", global = true, value_name = "FILE")]
+    sources: Vec<String>,
+
+    // Let people only the other --source files know about join without asking
+    #[arg(long, global = true, default_value_t = false)]
+    add_unknown: bool,
+
+    // Someone joining this meeting only, without being added to the team file or the series'
+    // history; repeatable
+    #[arg(long = "guest", global = true, value_name = "NAME")]
+    guests: Vec<String>,
+
+    // An exported calendar (.ics); the footer counts down to the next event after the meeting
+    // and warns when the meeting looks set to run into it
+    #[arg(long, global = true, value_name = "FILE", env = "NEXTUP_CALENDAR")]
+    calendar: Option<PathBuf>,
+
+    // Offer to mark people absent before the meeting once they've missed this many in a row (0: never)
+    #[arg(long, global = true, value_name = "N")]
+    absence_streak: Option<usize>,
+
+    // Flag people in the order who haven't spoken live in this many standups (0: never)
+    #[arg(long, global = true, value_name = "N")]
+    silent_after: Option<usize>,
+
+    // Talk time each person has across the series' meetings in a week (Monday to Sunday), e.g.
+    // 15m; the order points out who has used 80% of it or more
+    #[arg(long, global = true, value_name = "DURATION", value_parser = duration_arg)]
+    weekly_budget: Option<Duration>,
+
+    // Settings file for every series, overridden by the series template and flags (default:
+    // ~/.config/nextup/config.toml)
+    #[arg(
+        long = "config",
+        global = true,
+        value_name = "FILE",
+        env = "NEXTUP_CONFIG"
+    )]
+    config_file: Option<PathBuf>,
+
+    // Directory for history and templates; sync it by making it a git checkout
+    #[arg(long, global = true, env = "NEXTUP_STATE_DIR")]
+    state_dir: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the standup headless from a Slack channel
+    #[cfg(feature = "slack")]
+    Bot {
+        // Slack channel ID to post the order to
+        #[arg(long)]
+        channel: String,
+
+        // Slack bot token (needs chat:write, channels:history and reactions:read)
+        #[arg(long, env = "SLACK_BOT_TOKEN", hide_env_values = true)]
+        token: String,
+    },
+
+    /// Run the meeting in the terminal, as a bare `nextup` does
+    Run,
+
+    /// Print a random order, one name per line, without starting the meeting
+    Shuffle {
+        // Print the order as a JSON array instead
+        #[arg(long, default_value_t = false)]
+        json: bool,
+
+        // Keep the order for today, so the meeting opens with it
+        #[arg(long, default_value_t = false)]
+        keep: bool,
+    },
+
+    /// Print the summary of the series' last meeting again
+    Report {
+        // Output format: md, json or csv (default: --report-format, or from the file's extension)
+        #[arg(long, value_name = "FORMAT")]
+        format: Option<ReportFormat>,
+
+        // Save it to this file instead of printing it
+        #[arg(value_name = "FILE")]
+        file: Option<PathBuf>,
+    },
+
+    /// Show the meeting health trend and turn lengths for the series
+    Stats {
+        // Output format: table or json
+        #[arg(long, value_name = "FORMAT", default_value = "table")]
+        format: StatsFormat,
+
+        // Browse the stats in an interactive dashboard instead
+        #[arg(long, default_value_t = false, conflicts_with = "format")]
+        tui: bool,
+
+        // Only include meetings on or after this date, e.g. 2025-11-01
+        #[arg(long, value_name = "DATE", value_parser = date_arg)]
+        since: Option<NaiveDate>,
+
+        // Only include meetings on or before this date
+        #[arg(long, value_name = "DATE", value_parser = date_arg)]
+        until: Option<NaiveDate>,
+
+        // Only include one sprint's meetings: `current` or a sprint number
+        #[arg(long, value_name = "SPRINT", conflicts_with_all = ["since", "until"])]
+        sprint: Option<SprintArg>,
+    },
+
+    /// Show how often each member made it to the meetings of the series
+    Attendance {
+        // Output format: table or json
+        #[arg(long, value_name = "FORMAT", default_value = "table")]
+        format: StatsFormat,
+
+        // Only include meetings on or after this date, e.g. 2025-11-01
+        #[arg(long, value_name = "DATE", value_parser = date_arg)]
+        since: Option<NaiveDate>,
+
+        // Only include meetings on or before this date
+        #[arg(long, value_name = "DATE", value_parser = date_arg)]
+        until: Option<NaiveDate>,
+    },
+
+    /// Split the team into random pairs (or bigger groups), avoiding recent repeats
+    Pairs {
+        // People per group, e.g. 3 for triads
+        #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u64).range(2..=20))]
+        size: u64,
+
+        // Also write the groups to this file as CSV, one group per row
+        #[arg(long, value_name = "FILE")]
+        export: Option<PathBuf>,
+
+        // Print the groups without remembering them
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// Split the team into break-out rooms of near-equal size, avoiding recent groupings
+    Breakout {
+        // How many rooms
+        #[arg(long, value_parser = clap::value_parser!(u64).range(2..=50))]
+        rooms: u64,
+
+        // Two people who mustn't share a room, e.g. --apart "Stan Marsh=Wendy Testaburger"
+        #[arg(long, value_name = "A=B")]
+        apart: Vec<Exclusion>,
+
+        // Two people who must share a room
+        #[arg(long, value_name = "A=B")]
+        together: Vec<Exclusion>,
+
+        // Also write the rooms to this file as CSV, one room per row
+        #[arg(long, value_name = "FILE")]
+        export: Option<PathBuf>,
+
+        // Post the rooms to this Slack channel (needs --slack-bot-token)
+        #[arg(long, value_name = "CHANNEL")]
+        announce: Option<String>,
+
+        // Print the rooms without remembering them
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// Spread interview slots or reviews across the team, lightest recent load first
+    Assign {
+        // Slots to staff, one per line, optionally with `| panel=N`
+        #[arg(value_name = "FILE")]
+        slots: String,
+
+        // People per slot, unless the slot sets its own
+        #[arg(long, default_value_t = 1)]
+        panel: usize,
+
+        // Also write the table to this file as CSV
+        #[arg(long, value_name = "FILE")]
+        export: Option<PathBuf>,
+
+        // Print the table without remembering it
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+
+    /// Share the meeting setup with a colleague as a single file, or set one up from such a file
+    Bundle {
+        #[command(subcommand)]
+        command: BundleCommand,
+    },
+
+    /// Let people check in as absent or async before the meeting, or list today's check-ins
+    Checkin {
+        // Post a check-in message to this Slack channel; reactions are read when the meeting starts
+        #[arg(long, value_name = "CHANNEL")]
+        slack_channel: Option<String>,
+        // Check-ins are also taken through the web view until Ctrl+C with the global --serve
+    },
+
+    /// Post to the --remind-channel that the standup is coming up, with today's order
+    Remind,
+
+    /// Manage the series history: forget old entries, compact it, or export it
+    History {
+        #[command(subcommand)]
+        command: HistoryCommand,
+    },
+
+    /// Remove someone's data from the series
+    Privacy {
+        #[command(subcommand)]
+        command: PrivacyCommand,
+    },
+
+    /// Send again the Slack posts and traces that kept failing during a meeting
+    RetryQueue {
+        // Drop the failed calls instead of sending them
+        #[arg(long, default_value_t = false)]
+        discard: bool,
+    },
+
+    /// Wait for the --window start each workday and run the meeting then
+    Daemon {
+        #[command(subcommand)]
+        command: Option<DaemonCommand>,
+    },
+
+    /// Manage the settings file shared by every series
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+
+    /// List the message and export templates and which of them are your own
+    Templates {
+        #[command(subcommand)]
+        command: Option<TemplatesCommand>,
+    },
+
+    /// Print the JSON Schema of a series' template.json (config), a team.json (team) or a series'
+    /// history.json (state), for editor completion and validation
+    Schema {
+        // Which file: config, team or state
+        #[arg(value_name = "FILE")]
+        kind: SchemaKind,
+    },
+
+    /// Check how shortcuts come through on this keyboard
+    Keys {
+        #[command(subcommand)]
+        command: KeysCommand,
+    },
+
+    /// Learn the shortcuts from a practice meeting that runs itself, with a made-up team
+    Demo,
+
+    /// Edit the team file interactively: add, remove, reorder and set roles, slots and who's active
+    EditTeam,
+
+    /// Replace this binary with the latest release
+    #[cfg(feature = "self-update")]
+    SelfUpdate,
+
+    /// Draw names for a gift exchange; nobody draws themselves
+    Santa {
+        // Two people who mustn't draw each other, e.g. --exclude "Stan Marsh=Wendy Testaburger"
+        #[arg(long, value_name = "A=B")]
+        exclude: Vec<Exclusion>,
+
+        // DM everyone their own draw on Slack (needs `slack=` member IDs in the team file)
+        #[arg(long, default_value_t = false, conflicts_with = "email_from")]
+        slack: bool,
+
+        // Slack bot token for --slack (needs chat:write)
+        #[arg(long, env = "SLACK_BOT_TOKEN", hide_env_values = true)]
+        token: Option<String>,
+
+        // Email everyone their own draw from this address through sendmail (needs `email=` in the team file)
+        #[arg(long, value_name = "ADDRESS")]
+        email_from: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum BundleCommand {
+    /// Write the series' settings, team and timer theme to a bundle, leaving out tokens
+    Export {
+        // Where to write the bundle (default: print it)
+        #[arg(value_name = "FILE")]
+        file: Option<PathBuf>,
+    },
+    /// Set up a series from a bundle; it keeps the bundle's series name unless --series is given
+    Import {
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+
+        // Where to put the team file (default: the series' state directory)
+        #[arg(long, value_name = "FILE")]
+        team_file: Option<PathBuf>,
+
+        // Replace the series template and team file if they already exist
+        #[arg(long, default_value_t = false)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum HistoryCommand {
+    /// Forget meetings, parked topics, pairs and assignments older than --older-than (default: --retention)
+    Prune {
+        // e.g. 6mo, 26w, 180d or 1y
+        #[arg(long, value_name = "AGE")]
+        older_than: Option<Retention>,
+
+        // Say how much would go without forgetting it
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+    /// Drop what nothing reads any more: questions and facilitator turns of people who've left,
+    /// old pairings and assignments, and topics parked twice
+    Compact,
+    /// Write the history as JSON
+    Export {
+        // Where to write it (default: print it)
+        #[arg(value_name = "FILE")]
+        file: Option<PathBuf>,
+
+        // Replace everyone's name with "Person 1", "Person 2" and so on
+        #[arg(long, default_value_t = false)]
+        anonymize: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum PrivacyCommand {
+    /// Remove everything kept about someone: their history, check-in and audit trail entries
+    Purge {
+        // Their name, or any identity the team file has for them
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum DaemonCommand {
+    /// Write a systemd user unit that runs the daemon with these options
+    Install,
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Write the settings file with every setting commented out, ready to fill in
+    Init {
+        // Replace the file if it already exists
+        #[arg(long, default_value_t = false)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum TemplatesCommand {
+    /// Copy the built-in templates into the templates directory to edit, keeping any already there
+    Init,
+}
+
+#[derive(Subcommand)]
+enum KeysCommand {
+    /// Echo each key pressed and the shortcut it triggers, until Esc
+    Test,
+}
+
+fn duration_arg(value: &str) -> std::result::Result<Duration, String> {
+    roster::parse_slot(value)
+        .ok_or_else(|| format!("invalid duration `{}`, expected e.g. 2m or 90s", value))
+}
+
+fn header_arg(value: &str) -> std::result::Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!(
+            "invalid header `{}`, expected e.g. api-key=SECRET",
+            value
+        )),
+    }
+}
+
+fn date_arg(value: &str) -> std::result::Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("invalid date `{}`, expected e.g. 2025-11-01", value))
+}
+
+fn clock_arg(value: &str) -> std::result::Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(value, "%H:%M"))
+        .map_err(|_| format!("invalid time `{}`, expected e.g. 09:29:40", value))
 }
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    let args = Args::parse();
+async fn main() -> ExitCode {
+    match run(Args::parse()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            error::report(&e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(mut args: Args) -> Result<()> {
+    // `nextup run` is a bare `nextup` spelled out
+    if matches!(args.command, Some(Command::Run)) {
+        args.command = None;
+    }
+
+    let config_file = args.config_file.clone().or_else(config::file_path);
+    // before reading it, so a broken file can be started over
+    if let Some(Command::Config {
+        command: ConfigCommand::Init { force },
+    }) = &args.command
+    {
+        let path =
+            config_file.ok_or_else(|| anyhow::Error::msg("couldn't find the config directory"))?;
+        return config::init(&path, *force);
+    }
+
+    // Flags take precedence over the series template, then the config file, then defaults
+    let defaults = Config::default();
+    let settings = match &config_file {
+        Some(path) => Config::load(path)?,
+        None => Template::default(),
+    };
+    // the config file's title picks the series too, as --title does
+    let mut series = Series::new(
+        args.series
+            .as_deref()
+            .or(args.title.as_deref())
+            .or(settings.title.as_deref())
+            .unwrap_or(&defaults.title),
+    );
+    if let Some(dir) = args.state_dir {
+        series = series.in_dir(dir);
+    }
+
+    // Pick up state shared by other hosts before reading any of it
+    if let Some(root) = series.root()
+        && let Err(e) = sync::pull(root)
+    {
+        eprintln!("{}", e);
+    }
+
+    let mut template = series.load_template()?.or(settings);
+
+    // A bare `nextup` with nothing set up yet walks through making a team
+    if args.command.is_none()
+        && args.names.is_none()
+        && !args.json_events
+        && setup::needed(
+            &series,
+            template
+                .names_file
+                .as_deref()
+                .unwrap_or(&defaults.names_file),
+        )
+        && let Some(saved) = setup::run(&series, &defaults)?
+    {
+        template = saved;
+        if let Some(root) = series.root()
+            && let Err(e) = sync::push(root, &format!("nextup: {} setup", series.id()))
+        {
+            eprintln!("{}", e);
+        }
+    }
 
     // Convert CLI args to our config struct
-    let config = Config {
-        title: args.title,
-        names_file: args.names,
-        duration: Duration::from_secs(args.duration * 60), // convert minutes to seconds
+    let timer_theme = args.timer_theme.or(template.timer_theme);
+    let mut config = Config {
+        auto_title: args.title.is_none() && template.title.is_none(),
+        title: args.title.or(template.title).unwrap_or(defaults.title),
+        sprint_epoch: args.sprint_epoch.or(template.sprint_epoch),
+        sprint_weeks: args
+            .sprint_weeks
+            .or(template.sprint_weeks)
+            .unwrap_or(defaults.sprint_weeks),
+        names_file: args
+            .names
+            .or(template.names_file)
+            .unwrap_or(defaults.names_file),
+        duration: args
+            .duration
+            .or(template.duration_minutes)
+            .map(|minutes| Duration::from_secs(minutes * 60)) // convert minutes to seconds
+            .unwrap_or(defaults.duration),
+        window: match args.window {
+            Some(window) => Some(window),
+            None => template
+                .window
+                .as_deref()
+                .map(str::parse)
+                .transpose()
+                .map_err(anyhow::Error::msg)?,
+        },
+        rounds: if args.rounds.is_empty() {
+            template.rounds
+        } else {
+            args.rounds.iter().map(|r| r.trim().to_string()).collect()
+        },
+        warm_up: args.warm_up.unwrap_or(defaults.warm_up),
+        cool_down: args.cool_down.unwrap_or(defaults.cool_down),
+        slot: args.slot,
+        auto_advance: args.auto_advance,
+        per_person: args.per_person || args.per_person_bell,
+        per_person_bell: args.per_person_bell,
+        hard_cutoff: args.hard_cutoff,
+        time_up: args.at_time_up,
+        clock: args.clock,
+        keyboard: match args.keyboard {
+            Some(keyboard) => keyboard,
+            None => template
+                .keyboard
+                .as_deref()
+                .map(str::parse)
+                .transpose()
+                .map_err(anyhow::Error::msg)?
+                .unwrap_or(defaults.keyboard),
+        },
         hide_timer: args.hide_timer,
+        timer_style: args.timer_style,
+        time_format: args.time_format,
+        summary_time_format: args.summary_time_format.unwrap_or(args.time_format),
+        timer_theme: timer_theme
+            .as_deref()
+            .map(TimerTheme::load)
+            .transpose()?
+            .unwrap_or_default(),
+        escalation: if args.escalation.is_empty() {
+            template
+                .escalation
+                .iter()
+                .map(|rule| rule.parse())
+                .collect::<std::result::Result<_, _>>()
+                .map_err(anyhow::Error::msg)?
+        } else {
+            args.escalation
+        },
+        bells: args.bells,
+        bell_sound: args.bell_sound,
+        #[cfg(feature = "tts")]
+        speak: args.speak,
+        terminal_notify: args.terminal_notify.is_some(),
+        osc_protocol: args.terminal_notify.flatten(),
+        #[cfg(feature = "desktop")]
+        desktop_notify: args.desktop_notify,
+        avatars: args.avatars.is_some(),
+        image_protocol: args.avatars.flatten(),
+        widget: args.widget,
+        wheel: args.wheel,
+        row_bars: args.row_bars,
+        burndown: args.burndown,
+        sort_names: args.sort_names.is_some(),
+        collation_locale: args.sort_names.flatten(),
+        kiosk: args.kiosk,
+        autopilot: args.autopilot.is_some(),
+        autopilot_channel: args.autopilot.flatten(),
+        serve: args.serve,
+        api_token: args.api_token,
+        share_ttl: args.share_ttl.unwrap_or(defaults.share_ttl),
+        recording_start: args.recording_start,
+        chapters_file: args.chapters,
+        chapter_format: args.chapter_format,
+        transcript: args.transcript,
+        transcript_template: args.transcript_template,
+        transcript_format: args.transcript_format,
+        report_path: args.report_path,
+        report_format: args.report_format,
+        questions: args.questions.is_some(),
+        questions_file: args.questions.flatten(),
+        handoffs: args.handoffs.is_some(),
+        handoffs_file: args.handoffs.flatten(),
+        topics_file: args.topics,
+        dnd: args.dnd,
+        slack_user_token: args.slack_user_token,
+        slack_bot_token: args.slack_bot_token,
+        remind_channel: args.remind_channel,
+        remind_before: args.remind_before.unwrap_or(defaults.remind_before),
+        lock_order: args.lock_order,
+        fair: args.fair,
+        hybrid: args.hybrid,
+        updates_dir: args.updates_dir,
+        representatives: args.representatives,
+        otlp_endpoint: args.otlp_endpoint,
+        otlp_headers: args.otlp_headers,
+        chrome_trace: args.chrome_trace,
+        coalesce: args.coalesce.unwrap_or(defaults.coalesce),
+        rate_limits: args.rate_limits,
+        series,
+        retention: args.retention,
+        hash_names: args.hash_names,
+        rotate_facilitator: args.rotate_facilitator,
+        order_rules: if args.order_rules.is_empty() {
+            template
+                .order_rules
+                .iter()
+                .map(|rule| rule.parse())
+                .collect::<std::result::Result<_, _>>()
+                .map_err(anyhow::Error::msg)?
+        } else {
+            args.order_rules
+        },
+        sources: args.sources,
+        add_unknown: args.add_unknown,
+        guests: args.guests,
+        calendar: args.calendar,
+        absence_streak: args.absence_streak.unwrap_or(defaults.absence_streak),
+        silent_after: args.silent_after.unwrap_or(defaults.silent_after),
+        weekly_budget: args.weekly_budget,
+        checklist: match args.checklist {
+            Some(Some(items)) => items
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(str::to_string)
+                .collect(),
+            Some(None) => DEFAULT_CHECKLIST.map(str::to_string).to_vec(),
+            None => Vec::new(),
+        },
+        lan: args.lan,
+        ..defaults
     };
 
-    // Initialize + Run the app
-    let mut app = App::new(config).await?;
-    app.run().await?;
+    // global flags can't conflict with each other in clap, so this is checked here
+    if config.kiosk && config.autopilot {
+        return Err(anyhow::Error::msg(
+            "--kiosk and --autopilot can't be used together",
+        ));
+    }
+    for (set, flag) in [(config.wheel, "--wheel"), (config.kiosk, "--kiosk")] {
+        if set && config.widget {
+            return Err(anyhow::Error::msg(format!(
+                "{} and --widget can't be used together",
+                flag
+            )));
+        }
+    }
+
+    // without a title of its own, the meeting is named for the day
+    title::refresh(&mut config)?;
+
+    if args.save_template {
+        config.series.save_template(&Template {
+            // a generated title is generated again next time
+            title: (!config.auto_title).then(|| config.title.clone()),
+            names_file: Some(config.names_file.clone()),
+            duration_minutes: Some(config.duration.as_secs() / 60),
+            window: config.window.map(|w| w.to_string()),
+            rounds: config.rounds.clone(),
+            keyboard: Some(config.keyboard.to_string()),
+            timer_theme,
+            order_rules: config.order_rules.iter().map(|r| r.to_string()).collect(),
+            escalation: config.escalation.iter().map(|r| r.to_string()).collect(),
+            sprint_epoch: config.sprint_epoch,
+            sprint_weeks: Some(config.sprint_weeks),
+        })?;
+
+        if let Some(root) = config.series.root()
+            && let Err(e) = sync::push(root, &format!("nextup: {} template", config.series.id()))
+        {
+            eprintln!("{}", e);
+        }
+    }
+
+    if config.hash_names && Pseudonyms::enable(&config.series)? {
+        eprintln!(
+            "People in the {} history go by hashed identifiers from now on.",
+            config.series.id()
+        );
+        if let Some(root) = config.series.root()
+            && let Err(e) = sync::push(root, &format!("nextup: {} hash names", config.series.id()))
+        {
+            eprintln!("{}", e);
+        }
+    }
+
+    match &args.command {
+        Some(Command::Stats {
+            format,
+            tui,
+            since,
+            until,
+            sprint,
+        }) => {
+            let range = match sprint {
+                Some(sprint) => sprint::range(&config, *sprint)?,
+                None => DateRange::new(*since, *until),
+            };
+            if *tui {
+                return dashboard::run(&config, range);
+            }
+            return stats::run(&config, *format, range);
+        }
+        Some(Command::Attendance {
+            format,
+            since,
+            until,
+        }) => {
+            return attendance::run(&config, *format, DateRange::new(*since, *until));
+        }
+        Some(Command::Pairs {
+            size,
+            export,
+            dry_run,
+        }) => {
+            return pairs::run(
+                &config,
+                &pairs::PairsOptions {
+                    size: *size as usize,
+                    export: export.as_deref(),
+                    dry_run: *dry_run,
+                },
+            );
+        }
+        Some(Command::Assign {
+            slots,
+            panel,
+            export,
+            dry_run,
+        }) => {
+            return assign::run(
+                &config,
+                &assign::AssignOptions {
+                    slots_file: slots,
+                    panel: *panel,
+                    export: export.as_deref(),
+                    dry_run: *dry_run,
+                },
+            );
+        }
+        Some(Command::Breakout {
+            rooms,
+            apart,
+            together,
+            export,
+            announce,
+            dry_run,
+        }) => {
+            return breakout::run(
+                &config,
+                &breakout::BreakoutOptions {
+                    rooms: *rooms as usize,
+                    apart,
+                    together,
+                    export: export.as_deref(),
+                    announce: announce.as_deref(),
+                    dry_run: *dry_run,
+                },
+            );
+        }
+        Some(Command::Santa {
+            exclude,
+            slack,
+            token,
+            email_from,
+        }) => {
+            let delivery = match (slack, email_from) {
+                (true, _) => {
+                    let token = token.clone().ok_or_else(|| {
+                        anyhow::Error::msg("--slack needs a bot token (--token or SLACK_BOT_TOKEN)")
+                    })?;
+                    Delivery::Slack(SlackClient::new(token))
+                }
+                (false, Some(from)) => Delivery::Email { from: from.clone() },
+                (false, None) => Delivery::Print,
+            };
+            return santa::run(&config, exclude, &delivery);
+        }
+        Some(Command::Bundle {
+            command: BundleCommand::Export { file },
+        }) => return bundle::export(&config, file.as_deref()),
+        Some(Command::Bundle {
+            command:
+                BundleCommand::Import {
+                    file,
+                    team_file,
+                    force,
+                },
+        }) => {
+            return bundle::import(
+                &config,
+                &bundle::ImportOptions {
+                    file,
+                    team_file: team_file.as_deref(),
+                    force: *force,
+                    // otherwise the series is named after the bundle's
+                    series: args.series.is_some().then(|| config.series.clone()),
+                },
+            );
+        }
+        Some(Command::Demo) => return demo::run(&config).await,
+        Some(Command::EditTeam) => return editor::run(&config),
+        Some(Command::Daemon { command: None }) => return daemon::run(config).await,
+        Some(Command::Daemon {
+            command: Some(DaemonCommand::Install),
+        }) => return daemon::install(),
+        Some(Command::Remind) => return remind::post(&config),
+        Some(Command::Shuffle { json, keep }) => return order::print(&config, *json, *keep),
+        Some(Command::Report { format, file }) => {
+            return report::print(&config, *format, file.as_deref());
+        }
+        Some(Command::History {
+            command:
+                HistoryCommand::Prune {
+                    older_than,
+                    dry_run,
+                },
+        }) => return retention::prune(&config, *older_than, *dry_run),
+        Some(Command::History {
+            command: HistoryCommand::Compact,
+        }) => return retention::compact(&config),
+        Some(Command::History {
+            command: HistoryCommand::Export { file, anonymize },
+        }) => return retention::export(&config, file.as_deref(), *anonymize),
+        Some(Command::Privacy {
+            command: PrivacyCommand::Purge { name },
+        }) => return privacy::purge(&config, name),
+        Some(Command::RetryQueue { discard }) => return queue::retry(&config, *discard),
+        Some(Command::Templates { command: None }) => return templates::list(),
+        Some(Command::Templates {
+            command: Some(TemplatesCommand::Init),
+        }) => return templates::init(),
+        Some(Command::Schema { kind }) => return schema::print(*kind),
+        Some(Command::Keys {
+            command: KeysCommand::Test,
+        }) => return keys::test(config.keyboard),
+        #[cfg(feature = "self-update")]
+        Some(Command::SelfUpdate) => return update::run(),
+        Some(Command::Checkin { slack_channel }) => {
+            let slack = match slack_channel {
+                Some(channel) => {
+                    let token = config.slack_bot_token.clone().ok_or_else(|| {
+                        anyhow::Error::msg(
+                            "--slack-channel needs a bot token (--slack-bot-token or SLACK_BOT_TOKEN)",
+                        )
+                    })?;
+                    Some((SlackClient::new(token), channel.as_str()))
+                }
+                None => None,
+            };
+            return checkin::run(
+                &config,
+                CheckInOptions {
+                    slack,
+                    serve: config.serve,
+                },
+            )
+            .await;
+        }
+        _ => {}
+    }
+
+    // Look over what the other team sources say, and offer to check in people who keep missing
+    // the meeting as absent, before it starts
+    if args.command.is_none() && !args.json_events && !config.kiosk && !config.autopilot {
+        sources::review(&mut config)?;
+        attendance::suggest_absences(&config, &sources::load_members(&config)?)?;
+    }
+
+    // Follow the meeting when another instance on the network is already running it
+    if config.lan
+        && let Some(leader) = lan::discover(&config.series)?
+    {
+        config.attach = true;
+        config.lan_leader = Some(leader);
+    }
+
+    // Guard against starting at the wrong time or alongside a running meeting; a kiosk or
+    // autopilot waits for its window instead
+    if !config.kiosk
+        && !config.autopilot
+        && config.lan_leader.is_none()
+        && !preflight::confirm(&preflight::check(&config)?)?
+    {
+        return Ok(());
+    }
+
+    // Only one instance runs a series at a time; held until the meeting ends. A follower on the
+    // network doesn't run it here.
+    let _lock = if config.lan_leader.is_some() {
+        None
+    } else {
+        match Lock::acquire(&config.series)? {
+            Acquired::Locked(lock) => Some(lock),
+            Acquired::HeldBy(pid) => {
+                // following along needs the terminal UI
+                let can_attach = args.command.is_none() && !args.json_events;
+                match preflight::resolve_conflict(&config, pid, can_attach)? {
+                    Conflict::Attach => {
+                        config.attach = true;
+                        None
+                    }
+                    Conflict::TakeOver => {
+                        config.take_over = true;
+                        Some(Lock::take_over(&config.series)?)
+                    }
+                    Conflict::Abort => return Ok(()),
+                }
+            }
+        }
+    };
+
+    // Held until the meeting ends, then restores notifications
+    let _dnd = config.dnd.then(|| DoNotDisturb::enable(&config));
+
+    match args.command {
+        #[cfg(feature = "slack")]
+        Some(Command::Bot { channel, token }) => {
+            let mut bot = Bot::new(config, token, channel)?;
+            bot.run().await?;
+        }
+        Some(
+            Command::Stats { .. }
+            | Command::Attendance { .. }
+            | Command::Pairs { .. }
+            | Command::Breakout { .. }
+            | Command::Assign { .. }
+            | Command::Santa { .. }
+            | Command::Bundle { .. }
+            | Command::Checkin { .. }
+            | Command::Demo
+            | Command::EditTeam
+            | Command::Templates { .. }
+            | Command::Schema { .. }
+            | Command::Remind
+            | Command::Shuffle { .. }
+            | Command::Report { .. }
+            | Command::Config { .. }
+            | Command::History { .. }
+            | Command::Privacy { .. }
+            | Command::RetryQueue { .. }
+            | Command::Keys { .. }
+            | Command::Daemon { .. },
+        ) => {
+            unreachable!("handled before the meeting starts")
+        }
+        #[cfg(feature = "self-update")]
+        Some(Command::SelfUpdate) => unreachable!("handled before the meeting starts"),
+        Some(Command::Run) => unreachable!("the same as no command"),
+        None if config.kiosk => {
+            kiosk::run(config).await?;
+        }
+        None if config.autopilot => {
+            autopilot::run(config).await?;
+        }
+        None if args.json_events => {
+            events::run(config).await?;
+        }
+        None => {
+            // Initialize + Run the app
+            let mut app = App::new(config).await?;
+            app.run().await?;
+        }
+    }
     Ok(())
 }
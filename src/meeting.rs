@@ -0,0 +1,691 @@
+use chrono::{DateTime, Datelike, Local};
+use std::collections::HashMap;
+use std::mem;
+use std::time::{Duration, Instant};
+
+use crate::airtime::Airtime;
+use crate::audit;
+use crate::autosave::Autosave;
+use crate::calendar::{self, Event};
+use crate::chapters::{Chapter, Chapters};
+use crate::checkin::{Attendance, CheckIn, CheckIns};
+use crate::collate::Collation;
+use crate::command::MeetingCommand;
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::fair::Weights;
+use crate::health::{ABSENT_THRESHOLD, Health};
+use crate::history::{ChecklistItem, History, MeetingRecord, Turn};
+use crate::lan::{self, Lan, Leader};
+use crate::lock::Lock;
+use crate::order::{self, OrderRule};
+use crate::questions::{self, QuestionOfTheDay};
+use crate::remind::PlannedOrder;
+use crate::retention::Retention;
+use crate::roster::{self, Celebration, Member};
+use crate::series::Series;
+use crate::session::{Session, SessionEvent, Speaker};
+use crate::sources;
+use crate::sprint::{self, Sprint};
+use crate::sync;
+use crate::topics::{Topic, Topics};
+
+// Meetings shorter than this (e.g. opened by accident) aren't recorded
+const MIN_RECORDED: Duration = Duration::from_secs(60);
+
+// How often the running meeting is autosaved (and attached viewers refresh)
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A session plus the per-meeting extras shared by the TUI and headless modes
+pub struct Meeting {
+    pub session: Session,
+    history: History,
+    // how long the history is kept, pruned as each meeting is recorded
+    retention: Option<Retention>,
+    // this week's talk time, with --weekly-budget
+    airtime: Option<Airtime>,
+    questions: Option<QuestionOfTheDay>,
+    topics: Topics,
+    // who said ahead of time they won't be speaking live
+    check_ins: CheckIns,
+    hybrid: bool,
+    // members per team, including those a representative speaks for
+    team_sizes: HashMap<String, usize>,
+    celebrations: Vec<(String, Celebration)>,
+    facilitator: Option<String>,
+    // what else is on the calendar today, not counting this meeting
+    calendar: Vec<Event>,
+    health: Option<Health>,
+    // how the meeting compares with recent ones, worked out when it's closed
+    trends: Vec<String>,
+    // the sprint the meeting falls in, when sprints are set up
+    sprint: Option<Sprint>,
+    // what the facilitator ticked off once it was over
+    checklist: Vec<ChecklistItem>,
+    // reshuffles are ignored while the order is locked
+    order_locked: bool,
+    // people marked absent from the keyboard, most recent last, to bring back in turn
+    marked_absent: Vec<String>,
+    // the order today's reminder announced, to open with instead of a shuffle
+    planned_order: Option<PlannedOrder>,
+    chapters: Chapters,
+    series: Series,
+    started_at: DateTime<Local>,
+    last_autosave: Option<Instant>,
+    read_only: bool,
+    lan: Option<Lan>,
+    resumed: bool,
+    taken_over: bool,
+}
+
+impl Meeting {
+    /// Load the roster and the series history
+    pub fn load(config: &Config) -> Result<Self> {
+        let mut members = sources::load_members(config)?;
+        let celebrations = roster::celebrations(&members, Local::now().date_naive());
+        let mut history = History::load(&config.series)?;
+        history.identify(&members);
+        let airtime = config
+            .weekly_budget
+            .map(|budget| Airtime::new(budget, &history, Local::now().date_naive()));
+
+        let questions = if config.questions {
+            let bank = questions::load_bank(config.questions_file.as_deref())?;
+            Some(QuestionOfTheDay::assign(&bank, &members, &history))
+        } else {
+            None
+        };
+
+        let topics = match &config.topics_file {
+            Some(filename) => Topics::load(filename, &members)?,
+            None => Topics::default(),
+        };
+
+        let check_ins = CheckIns::collect(config, &members)?;
+
+        let facilitator = if config.rotate_facilitator {
+            history.next_facilitator(&members)
+        } else {
+            None
+        };
+
+        // guests join after everything worked out from the team and its history
+        for name in &config.guests {
+            if roster::find(&members, name).is_some() {
+                return Err(AppError::GuestError(format!(
+                    "`{}` is already in the team file",
+                    name
+                ))
+                .into());
+            }
+            members.push(Member::guest(name.as_str()));
+        }
+
+        // the rules can name people by alias, and today's are the ones that count
+        let mut order_rules = config.order_rules.clone();
+        for name in order_rules.iter_mut().flat_map(OrderRule::names_mut) {
+            let member = roster::find(&members, name).ok_or_else(|| {
+                AppError::OrderError(format!("`{}` isn't in the team file", name))
+            })?;
+            *name = member.name.clone();
+        }
+        let names: Vec<&str> = members.iter().map(|m| m.name.as_str()).collect();
+        order::check(&order_rules, &names)?;
+        let today = Local::now().weekday();
+        order_rules.retain(|rule| rule.applies_on(today));
+
+        let mut events = match &config.calendar {
+            Some(path) => calendar::load(path, Local::now().date_naive())?,
+            None => Vec::new(),
+        };
+        // the meeting's own event doesn't count as the next one
+        events.retain(|event| {
+            !event.summary.eq_ignore_ascii_case(&config.title)
+                && config
+                    .window
+                    .is_none_or(|window| event.start.time() != window.start)
+        });
+
+        let team_sizes = roster::team_sizes(&members);
+        // a scrum of scrums hears from one person per team, preferably someone who's here
+        let members = if config.representatives {
+            roster::representatives(members, |m| check_ins.get(&m.name).is_none())
+        } else {
+            members
+        };
+
+        let mut session = Session::new(members, config.duration, config.rounds.clone())
+            .with_buffers(config.warm_up, config.cool_down)
+            .with_default_slot(config.slot)
+            .with_auto_advance(config.auto_advance)
+            .with_hard_cutoff(config.hard_cutoff)
+            .with_time_up(config.time_up)
+            .with_clock(config.clock)
+            .with_order_rules(order_rules)
+            .with_fair(config.fair.then(|| Weights::new(&history)))
+            .with_collation(
+                config
+                    .sort_names
+                    .then(|| Collation::new(config.collation_locale.as_deref()))
+                    .transpose()?,
+            )
+            .with_absent(
+                check_ins
+                    .entries()
+                    .iter()
+                    .filter(|c| skips_turn(c.attendance, config.hybrid))
+                    .map(|c| c.name.clone()),
+            );
+        let mut started_at = Local::now();
+        let mut resumed = false;
+
+        // pick up where the running instance is, here or on the network
+        let autosave = match config.lan_leader {
+            // the leader may not have saved yet; following catches up
+            Some(leader) => lan::fetch(leader).ok().flatten(),
+            None if config.attach || config.take_over => Autosave::load(&config.series)?,
+            None => None,
+        };
+        if let Some(autosave) = autosave {
+            session.restore(&autosave.session);
+            started_at = autosave.started_at;
+            resumed = true;
+        }
+
+        let recording_start = config
+            .recording_start
+            .and_then(|time| {
+                Local::now()
+                    .date_naive()
+                    .and_time(time)
+                    .and_local_timezone(Local)
+                    .single()
+            })
+            .unwrap_or(started_at);
+
+        let lan = match config.lan_leader {
+            Some(leader) => Some(Lan::Follower(leader)),
+            None if config.lan && !config.attach => {
+                Some(Lan::Leader(Leader::start(&config.series)?))
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            session,
+            history,
+            airtime,
+            retention: config.retention,
+            questions,
+            topics,
+            check_ins,
+            hybrid: config.hybrid,
+            team_sizes,
+            celebrations,
+            facilitator,
+            calendar: events,
+            health: None,
+            trends: Vec::new(),
+            sprint: sprint::on(config, started_at.date_naive()),
+            checklist: Vec::new(),
+            order_locked: false,
+            marked_absent: Vec::new(),
+            planned_order: PlannedOrder::load(&config.series)?,
+            chapters: Chapters::new(recording_start),
+            series: config.series.clone(),
+            started_at,
+            last_autosave: None,
+            read_only: config.attach,
+            lan,
+            resumed,
+            taken_over: false,
+        })
+    }
+
+    /// The next event on the calendar, once it's given
+    pub fn next_event(&self) -> Option<&Event> {
+        let now = Local::now();
+        self.calendar.iter().find(|event| event.start > now)
+    }
+
+    /// Whether the meeting looks set to run into the next event: its planned end, or where the
+    /// remaining slots take it, is past the event's start
+    pub fn runs_into_next_event(&self) -> bool {
+        let Some(event) = self.next_event() else {
+            return false;
+        };
+        let left = self
+            .session
+            .projected_length()
+            .max(self.session.duration())
+            .saturating_sub(self.session.elapsed());
+        Local::now() + chrono::Duration::from_std(left).unwrap_or_default() > event.start
+    }
+
+    /// Deal the opening order: the one today's reminder announced, locked if it said so,
+    /// or a fresh shuffle
+    pub fn open_order(&mut self) {
+        match &self.planned_order {
+            Some(planned) => {
+                self.session.set_order(&planned.order);
+                self.order_locked = planned.locked;
+            }
+            None => {
+                self.session.shuffle();
+            }
+        }
+    }
+
+    /// Note that the current speaker's turn came up
+    pub fn mark_current_asked(&mut self) {
+        if let Some(questions) = &mut self.questions {
+            questions.mark_asked(self.session.current_name());
+        }
+    }
+
+    /// Carry out a command from the keyboard or the control API
+    pub fn apply(&mut self, command: MeetingCommand) -> Option<SessionEvent> {
+        match command {
+            MeetingCommand::Next => self.session.next(),
+            MeetingCommand::Prev => self.session.prev(),
+            MeetingCommand::Shuffle | MeetingCommand::ShuffleRemaining if self.order_locked => None,
+            MeetingCommand::Shuffle => Some(self.session.shuffle()),
+            MeetingCommand::ShuffleRemaining => Some(self.session.shuffle_remaining()),
+            MeetingCommand::ToggleOrderLock => {
+                self.order_locked = !self.order_locked;
+                None
+            }
+            MeetingCommand::Reset => {
+                self.session.reset_per_person_timers();
+                self.session.reset_timer();
+                None
+            }
+            MeetingCommand::Pause => {
+                self.session.toggle_pause();
+                None
+            }
+            MeetingCommand::PauseReason(reason) => {
+                self.session.set_pause_reason(&reason);
+                None
+            }
+            MeetingCommand::Absent(name) => {
+                let name = self.preferred_name(name);
+                self.session.set_absent(&name, true).flatten()
+            }
+            MeetingCommand::MarkAbsent => {
+                let name = self.session.current_name().to_string();
+                if self.session.is_absent(self.session.current_person_index()) {
+                    return None;
+                }
+                self.marked_absent.push(name.clone());
+                self.session.set_absent(&name, true).flatten()
+            }
+            MeetingCommand::UnmarkAbsent => {
+                let name = self.marked_absent.pop()?;
+                self.session.set_absent(&name, false).flatten()
+            }
+            MeetingCommand::CheckIn {
+                name,
+                attendance,
+                update,
+            } => {
+                let name = self.preferred_name(name);
+                let skip = attendance.is_some_and(|a| skips_turn(a, self.hybrid));
+                let event = self.session.set_absent(&name, skip)?;
+                self.check_ins.set(&name, attendance, update);
+                if !self.read_only {
+                    // best effort, like the autosave
+                    let _ = self.check_ins.save();
+                }
+                event
+            }
+            MeetingCommand::AdjustRemaining(secs) => {
+                let adjustment = self.session.adjust_remaining(secs);
+                // best effort, like the autosave
+                let _ = audit::record(&self.series, adjustment);
+                None
+            }
+            MeetingCommand::AdjustSpeaker(secs) => {
+                let adjustment = self.session.adjust_speaker(secs);
+                let _ = audit::record(&self.series, adjustment);
+                None
+            }
+            MeetingCommand::AddTopic { name, text } => {
+                let name = self.preferred_name(name);
+                if self.session.speakers().iter().any(|s| s.name() == name) {
+                    self.topics.add(&name, &text);
+                }
+                None
+            }
+            MeetingCommand::CheckTopic => {
+                self.topics.check_off(self.session.current_name());
+                None
+            }
+            MeetingCommand::ResolveGap(choice) => {
+                self.session.resolve_gap(choice);
+                None
+            }
+            MeetingCommand::Mark => {
+                let name = self.session.current_name().to_string();
+                self.chapters.mark(&name);
+                None
+            }
+        }
+    }
+
+    /// The name a speaker goes by, for a command naming them by an alias, handle or id
+    fn preferred_name(&self, identity: String) -> String {
+        let members = self.session.speakers().iter().map(|s| &s.member);
+        roster::find(members, &identity).map_or(identity, |m| m.name.clone())
+    }
+
+    /// Refresh the autosave so other launches can follow or take over this meeting
+    pub fn autosave(&mut self) {
+        if self.read_only || self.taken_over || !self.autosave_due() {
+            return;
+        }
+
+        // another instance took over; it owns the autosave now
+        if Lock::is_lost(&self.series) {
+            self.taken_over = true;
+            return;
+        }
+
+        let autosave = Autosave {
+            pid: std::process::id(),
+            started_at: self.started_at,
+            updated_at: Local::now(),
+            session: self.session.snapshot(),
+        };
+        // best effort: a failed autosave shouldn't interrupt the meeting
+        let _ = autosave.save(&self.series);
+        if let Some(Lan::Leader(leader)) = &self.lan {
+            leader.publish(&autosave);
+        }
+    }
+
+    /// Mirror the instance running the meeting. Returns false once that meeting has ended.
+    pub fn follow(&mut self) -> Result<bool> {
+        if !self.autosave_due() {
+            return Ok(true);
+        }
+
+        let autosave = match &self.lan {
+            Some(Lan::Follower(leader)) => match lan::fetch(*leader) {
+                Ok(autosave) => autosave,
+                // a hiccup on the network; the next try may get through
+                Err(_) => return Ok(true),
+            },
+            _ => match Autosave::load(&self.series) {
+                Ok(autosave) => autosave,
+                // unreadable for a moment, e.g. half-written by an older nextup; the next try may do
+                Err(_) => return Ok(true),
+            },
+        };
+        match autosave {
+            Some(autosave) => {
+                self.session.restore(&autosave.session);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    fn autosave_due(&mut self) -> bool {
+        if self
+            .last_autosave
+            .is_some_and(|last| last.elapsed() < AUTOSAVE_INTERVAL)
+        {
+            return false;
+        }
+        self.last_autosave = Some(Instant::now());
+        true
+    }
+
+    /// Whoever was last marked absent from the keyboard, to bring back
+    pub fn last_marked_absent(&self) -> Option<&str> {
+        self.marked_absent.last().map(String::as_str)
+    }
+
+    /// Whether another instance took this meeting over
+    pub fn is_taken_over(&self) -> bool {
+        self.taken_over
+    }
+
+    /// Whether the meeting continues from another instance's autosave,
+    /// so its order must not be reshuffled
+    pub fn is_resumed(&self) -> bool {
+        self.resumed
+    }
+
+    pub fn is_order_locked(&self) -> bool {
+        self.order_locked
+    }
+
+    /// Whether this is only following a meeting run elsewhere
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Score the meeting as it stands now; later calls return the same result
+    pub fn close(&mut self) -> &Health {
+        let (history, trends) = (&self.history, &mut self.trends);
+        self.health.get_or_insert_with(|| {
+            let health = Health::compute(&self.session);
+            // a meeting too short to be recorded isn't worth comparing either
+            if health.elapsed >= MIN_RECORDED {
+                *trends = health.trends(history.meetings());
+            }
+            health
+        })
+    }
+
+    /// Notable differences from the series' recent meetings, once it has been closed
+    pub fn trends(&self) -> &[String] {
+        &self.trends
+    }
+
+    /// The meeting's score, once it has been closed
+    pub fn health(&self) -> Option<&Health> {
+        self.health.as_ref()
+    }
+
+    /// Keep the facilitator's checklist, to be recorded with the meeting
+    pub fn set_checklist(&mut self, checklist: Vec<ChecklistItem>) {
+        self.checklist = checklist;
+    }
+
+    /// Record what happened in this meeting to the series history
+    pub fn finish(&mut self) -> Result<()> {
+        // whoever runs the meeting records it
+        if self.read_only || self.taken_over {
+            return Ok(());
+        }
+
+        Autosave::clear(&self.series)?;
+
+        let health = self.close().clone();
+        let history = &mut self.history;
+        let recorded = health.elapsed >= MIN_RECORDED;
+
+        if recorded {
+            let (spoke, absent): (Vec<&Speaker>, Vec<&Speaker>) = self
+                .session
+                .speakers()
+                .iter()
+                .filter(|speaker| !speaker.member.guest)
+                .partition(|speaker| speaker.total() >= ABSENT_THRESHOLD);
+            let turns = spoke
+                .into_iter()
+                .map(|speaker| Turn {
+                    name: speaker.name().to_string(),
+                    secs: speaker.total().as_secs(),
+                })
+                .collect();
+            let absentees = absent
+                .into_iter()
+                .map(|speaker| speaker.name().to_string())
+                .collect();
+            // in a hybrid standup, async people's turns were their written updates
+            let written = self
+                .session
+                .speakers()
+                .iter()
+                .map(|speaker| speaker.name())
+                .filter(|name| {
+                    self.hybrid
+                        && self
+                            .check_ins
+                            .get(name)
+                            .is_some_and(|c| c.attendance == Attendance::Async)
+                })
+                .map(str::to_string)
+                .collect();
+            history.record_meeting(MeetingRecord {
+                sprint: self.sprint.map(|sprint| sprint.number),
+                turns,
+                absentees,
+                written,
+                checklist: mem::take(&mut self.checklist),
+                ..MeetingRecord::new(Local::now().date_naive(), &health)
+            });
+        }
+        if let Some(questions) = &self.questions {
+            questions.record(history);
+        }
+        if let Some(facilitator) = &self.facilitator {
+            history.record_facilitator(facilitator);
+        }
+        let mut parked = false;
+        for topic in self.topics.unfinished() {
+            history.park(Local::now().date_naive(), topic);
+            parked = true;
+        }
+
+        // nothing to save from a meeting that was opened and closed again
+        if !recorded && !parked && self.questions.is_none() && self.facilitator.is_none() {
+            return Ok(());
+        }
+
+        if let Some(retention) = self.retention {
+            history.prune(retention.cutoff(Local::now().date_naive()));
+        }
+        history.save()?;
+
+        // share the updated history when the state directory is a git checkout
+        if let Some(root) = self.series.root()
+            && let Err(e) = sync::push(root, &format!("nextup: {} meeting", self.series.id()))
+        {
+            eprintln!("{}", e);
+        }
+
+        Ok(())
+    }
+
+    /// Topics someone has queued for their turn
+    pub fn topics_for<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Topic> {
+        self.topics.for_name(name)
+    }
+
+    /// Topics that will go to the parking lot if the meeting ends now
+    pub fn unfinished_topics(&self) -> impl Iterator<Item = &Topic> {
+        self.topics.unfinished()
+    }
+
+    /// How someone checked in today, if they won't be speaking live
+    pub fn check_in(&self, name: &str) -> Option<&CheckIn> {
+        self.check_ins.get(name)
+    }
+
+    /// The current speaker's written update, shown in place of speaking in a hybrid standup
+    pub fn current_update(&self) -> Option<&str> {
+        if !self.hybrid {
+            return None;
+        }
+        self.check_in(self.session.current_name())?
+            .update
+            .as_deref()
+    }
+
+    /// How many standups have gone by since someone last spoke live, as of the series history
+    pub fn standups_since_spoke(&self, name: &str) -> Option<usize> {
+        self.history.standups_since_spoke(name)
+    }
+
+    /// How much of their weekly budget someone in the order has used, counting this meeting, once
+    /// it's worth pointing out
+    pub fn airtime_used(&self, index: usize) -> Option<u32> {
+        let speaker = &self.session.speakers()[index];
+        self.airtime.as_ref()?.used(speaker.name(), speaker.total())
+    }
+
+    /// Whether async people keep their turn to show their written update
+    pub fn is_hybrid(&self) -> bool {
+        self.hybrid
+    }
+
+    /// Written updates from people who checked in as async
+    pub fn async_updates(&self) -> impl Iterator<Item = &CheckIn> {
+        self.check_ins
+            .entries()
+            .iter()
+            .filter(|c| c.attendance == Attendance::Async)
+    }
+
+    /// How many members a team has, whether or not they all speak
+    pub fn team_size(&self, team: &str) -> usize {
+        self.team_sizes.get(team).copied().unwrap_or_default()
+    }
+
+    /// The most recent recording marker
+    pub fn last_chapter(&self) -> Option<&Chapter> {
+        self.chapters.last()
+    }
+
+    /// Save the recording markers as a chapter list, or print it when no file was given
+    pub fn export_chapters(&self, config: &Config) -> Result<()> {
+        if self.chapters.is_empty() {
+            return Ok(());
+        }
+
+        match &config.chapters_file {
+            Some(path) => {
+                self.chapters.export(path, config.chapter_format)?;
+                println!("Chapters saved to {}", path.display());
+            }
+            None => print!("{}", self.chapters.render(config.chapter_format)),
+        }
+        Ok(())
+    }
+
+    /// The current speaker's question of the day, if enabled
+    pub fn current_question(&self) -> Option<&str> {
+        self.questions
+            .as_ref()
+            .and_then(|q| q.for_name(self.session.current_name()))
+    }
+
+    pub fn celebrations(&self) -> &[(String, Celebration)] {
+        &self.celebrations
+    }
+
+    /// Whether the current speaker has something to celebrate today
+    pub fn is_celebrating_now(&self) -> bool {
+        let current = self.session.current_name();
+        self.celebrations.iter().any(|(name, _)| name == current)
+    }
+
+    pub fn facilitator(&self) -> Option<&str> {
+        self.facilitator.as_deref()
+    }
+
+    /// The sprint the meeting falls in, when sprints are set up
+    pub fn sprint(&self) -> Option<Sprint> {
+        self.sprint
+    }
+}
+
+/// Whether someone who checked in this way is skipped; in a hybrid standup only those who are out
+fn skips_turn(attendance: Attendance, hybrid: bool) -> bool {
+    attendance == Attendance::Absent || !hybrid
+}
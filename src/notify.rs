@@ -0,0 +1,585 @@
+use serde::Serialize;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::command::MeetingCommand;
+use crate::config::Config;
+#[cfg(feature = "desktop")]
+use crate::desktop::Desktop;
+use crate::format::format_duration;
+use crate::roster::parse_slot;
+#[cfg(feature = "tts")]
+use crate::session::Phase;
+use crate::session::Session;
+use crate::templates::Templates;
+
+// Used when --bells is given without a pattern
+pub const DEFAULT_BELLS: &str = "5m=1,1m=2,0=3";
+
+// Used when --speak is given without thresholds
+#[cfg(feature = "tts")]
+pub const DEFAULT_SPEECH: &str = "5m,2m,1m,0";
+
+// Remaining time at which terminal notifications are raised
+const TERMINAL_THRESHOLDS: [Duration; 3] = [
+    Duration::from_secs(5 * 60),
+    Duration::from_secs(60),
+    Duration::ZERO,
+];
+
+// Minimum gap between beeps of the same announcement
+const BEEP_GAP: Duration = Duration::from_millis(300);
+
+/// How many beeps to sound when the remaining time reaches `at`; zero disables the threshold
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BellRule {
+    pub at: Duration,
+    pub beeps: u32,
+}
+
+/// Beeps per remaining-time threshold, e.g. `5m=1,1m=2,0=3` or `5m=1,1m=off,0=3`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BellPattern(pub Vec<BellRule>);
+
+impl FromStr for BellPattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = |rule: &str| {
+            format!(
+                "invalid bell rule `{}`, expected e.g. 5m=1, 1m=off or 0=3",
+                rule
+            )
+        };
+
+        s.split(',')
+            .map(str::trim)
+            .filter(|rule| !rule.is_empty())
+            .map(|rule| {
+                let (at, beeps) = rule.split_once('=').ok_or_else(|| invalid(rule))?;
+                let at = match at.trim() {
+                    "0" | "0s" | "end" => Duration::ZERO,
+                    at => parse_slot(at).ok_or_else(|| invalid(rule))?,
+                };
+                let beeps = match beeps.trim() {
+                    "off" => 0,
+                    beeps => beeps.parse().map_err(|_| invalid(rule))?,
+                };
+                Ok(BellRule { at, beeps })
+            })
+            .collect::<std::result::Result<_, _>>()
+            .map(Self)
+    }
+}
+
+/// Remaining-time thresholds to announce out loud, e.g. `5m,2m,30s,0`
+#[cfg(feature = "tts")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeechPattern(pub Vec<Duration>);
+
+#[cfg(feature = "tts")]
+impl FromStr for SpeechPattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        s.split(',')
+            .map(str::trim)
+            .filter(|at| !at.is_empty())
+            .map(|at| match at {
+                "0" | "0s" | "end" => Ok(Duration::ZERO),
+                at => parse_slot(at).ok_or_else(|| {
+                    format!(
+                        "invalid speech threshold `{}`, expected e.g. 2m, 30s or 0",
+                        at
+                    )
+                }),
+            })
+            .collect::<std::result::Result<_, _>>()
+            .map(Self)
+    }
+}
+
+/// Where beeps are sounded
+pub enum Backend {
+    TerminalBell,
+    // player command and the sound file it plays
+    Sound { player: &'static str, file: PathBuf },
+}
+
+impl Backend {
+    /// The `--bell-sound` file, or the terminal bell
+    pub fn new(config: &Config) -> Self {
+        match &config.bell_sound {
+            Some(file) => match sound_player() {
+                Some(player) => Backend::Sound {
+                    player,
+                    file: file.clone(),
+                },
+                None => {
+                    eprintln!("No audio player found, using the terminal bell");
+                    Backend::TerminalBell
+                }
+            },
+            None => Backend::TerminalBell,
+        }
+    }
+
+    pub fn beep(&self) {
+        match self {
+            Backend::TerminalBell => {
+                let mut stdout = io::stdout();
+                let _ = stdout.write_all(b"\x07").and_then(|_| stdout.flush());
+            }
+            Backend::Sound { player, file } => {
+                // fire and forget; a missing sound shouldn't disturb the meeting, and a played one
+                // is waited on out of the way so it doesn't linger as a zombie
+                let child = Command::new(player)
+                    .arg(file)
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .spawn();
+                if let Ok(mut child) = child {
+                    thread::spawn(move || child.wait());
+                }
+            }
+        }
+    }
+}
+
+/// Terminal escape sequences that raise a native notification
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OscProtocol {
+    // `ESC ] 9 ; body BEL` -- iTerm2, kitty, WezTerm, Ghostty
+    Osc9,
+    // `ESC ] 777 ; notify ; title ; body BEL` -- VTE-based terminals, foot, WezTerm
+    Osc777,
+}
+
+impl FromStr for OscProtocol {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "osc9" | "9" => Ok(Self::Osc9),
+            "osc777" | "777" => Ok(Self::Osc777),
+            _ => Err(format!(
+                "unknown notification protocol `{}`, expected osc9 or osc777",
+                s
+            )),
+        }
+    }
+}
+
+impl OscProtocol {
+    /// Guess what the terminal supports from the environment it sets
+    pub fn detect() -> Option<Self> {
+        let var = |name: &str| std::env::var(name).unwrap_or_default();
+        let term = var("TERM");
+        let program = var("TERM_PROGRAM");
+
+        if std::env::var_os("VTE_VERSION").is_some()
+            || term.starts_with("foot")
+            || program == "WezTerm"
+        {
+            Some(Self::Osc777)
+        } else if std::env::var_os("KITTY_WINDOW_ID").is_some()
+            || term == "xterm-kitty"
+            || program == "iTerm.app"
+            || program == "ghostty"
+        {
+            Some(Self::Osc9)
+        } else {
+            None
+        }
+    }
+
+    pub fn notify(self, title: &str, body: &str) {
+        let sequence = match self {
+            Self::Osc9 => format!("\x1b]9;{}: {}\x07", title, body),
+            Self::Osc777 => format!("\x1b]777;notify;{};{}\x07", title, body),
+        };
+
+        // tmux only forwards escape sequences wrapped in its passthrough
+        let sequence = if std::env::var_os("TMUX").is_some() {
+            format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+        } else {
+            sequence
+        };
+
+        let mut stdout = io::stdout();
+        let _ = stdout
+            .write_all(sequence.as_bytes())
+            .and_then(|_| stdout.flush());
+    }
+}
+
+/// Announcements read out by the system's text-to-speech command
+#[cfg(feature = "tts")]
+struct Speech {
+    command: &'static str,
+    templates: Templates,
+    at: Vec<Duration>,
+    thresholds: Thresholds,
+    // (round, speaker) last announced as next up
+    speaker: Option<(usize, usize)>,
+}
+
+#[cfg(feature = "tts")]
+impl Speech {
+    fn new(pattern: &SpeechPattern, templates: &Templates) -> Option<Self> {
+        let candidates: &[&'static str] = if cfg!(target_os = "macos") {
+            &["say"]
+        } else {
+            &["espeak-ng", "espeak", "spd-say"]
+        };
+        let Some(command) = find_command(candidates) else {
+            eprintln!("No text-to-speech command found (say, espeak-ng, espeak or spd-say)");
+            return None;
+        };
+
+        Some(Self {
+            command,
+            templates: templates.clone(),
+            at: pattern.0.clone(),
+            thresholds: Thresholds::new(pattern.0.clone()),
+            speaker: None,
+        })
+    }
+
+    fn update(&mut self, session: &Session) {
+        let mut phrases = Vec::new();
+
+        // nobody is up during the warm-up
+        let speaker = (session.current_round(), session.current_person_index());
+        if session.phase() != Phase::WarmUp && self.speaker != Some(speaker) {
+            phrases.push(Notification {
+                speaker: Some(session.current_name().to_string()),
+                ..Notification::new("next")
+            });
+            self.speaker = Some(speaker);
+        }
+
+        for i in self.thresholds.update(session.remaining_time()) {
+            let at = self.at[i];
+            phrases.push(if at.is_zero() {
+                Notification::new("time_up")
+            } else {
+                Notification {
+                    remaining: Some(spoken_duration(at)),
+                    ..Notification::new("left")
+                }
+            });
+        }
+        let phrases: Vec<String> = phrases
+            .into_iter()
+            .filter_map(|phrase| self.templates.render("notify/speech.txt", phrase).ok())
+            .filter(|phrase| !phrase.is_empty())
+            .collect();
+
+        if !phrases.is_empty() {
+            // fire and forget, like the sound backend
+            let child = Command::new(self.command)
+                .arg(phrases.join(". "))
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn();
+            if let Ok(mut child) = child {
+                thread::spawn(move || child.wait());
+            }
+        }
+    }
+}
+
+/// A duration as it reads out loud, e.g. "2 minutes" or "1 minute 30 seconds"
+#[cfg(feature = "tts")]
+fn spoken_duration(duration: Duration) -> String {
+    let unit = |n: u64, name: &str| {
+        if n == 1 {
+            format!("1 {}", name)
+        } else {
+            format!("{} {}s", n, name)
+        }
+    };
+    let (minutes, seconds) = (duration.as_secs() / 60, duration.as_secs() % 60);
+
+    match (minutes, seconds) {
+        (0, seconds) => unit(seconds, "second"),
+        (minutes, 0) => unit(minutes, "minute"),
+        (minutes, seconds) => format!("{} {}", unit(minutes, "minute"), unit(seconds, "second")),
+    }
+}
+
+/// What the notification and speech templates see; `event` says which announcement it is
+#[derive(Serialize, Default)]
+struct Notification {
+    event: &'static str,
+    speaker: Option<String>,
+    remaining: Option<String>,
+    slot: Option<String>,
+}
+
+impl Notification {
+    fn new(event: &'static str) -> Self {
+        Self {
+            event,
+            ..Self::default()
+        }
+    }
+
+    /// The meeting's remaining time reaching `at`, zero being time up
+    fn reached(at: Duration) -> Self {
+        if at.is_zero() {
+            Self::new("time_up")
+        } else {
+            Self {
+                remaining: Some(format_duration(at)),
+                ..Self::new("left")
+            }
+        }
+    }
+}
+
+/// The terminal notification for the meeting's remaining time reaching `at`, e.g. "2m left";
+/// None when the template leaves it out or can't be rendered
+pub fn reached_text(templates: &Templates, at: Duration) -> Option<String> {
+    templates
+        .render("notify/notification.txt", Notification::reached(at))
+        .ok()
+        .filter(|text| !text.is_empty())
+}
+
+/// Tracks which remaining-time thresholds have been reached
+pub struct Thresholds {
+    at: Vec<Duration>,
+    // whether each threshold had been reached at the last update; None until the first one
+    reached: Option<Vec<bool>>,
+}
+
+impl Thresholds {
+    pub fn new(at: Vec<Duration>) -> Self {
+        Self { at, reached: None }
+    }
+
+    /// Indexes of thresholds reached since the last update.
+    /// Thresholds already behind us at the first update (or after a reset) aren't reported.
+    pub fn update(&mut self, remaining: Duration) -> Vec<usize> {
+        let reached: Vec<bool> = self.at.iter().map(|at| remaining <= *at).collect();
+
+        let crossed = match &self.reached {
+            Some(previous) => (0..reached.len())
+                .filter(|&i| reached[i] && !previous[i])
+                .collect(),
+            None => Vec::new(),
+        };
+        self.reached = Some(reached);
+        crossed
+    }
+}
+
+/// Announces remaining-time thresholds as the meeting counts down
+pub struct Notifier {
+    bells: Option<(Vec<u32>, Thresholds)>,
+    backend: Backend,
+    pending_beeps: u32,
+    last_beep: Option<Instant>,
+    terminal: Option<(OscProtocol, Thresholds)>,
+    // the same notifications raised by the system, with buttons that control the meeting
+    #[cfg(feature = "desktop")]
+    desktop: Option<(Desktop, Thresholds)>,
+    templates: Templates,
+    // beep when a speaker's slot runs out (hard mode with a sound configured)
+    slot_beep: bool,
+    // (round, speaker) whose slot has already been announced
+    slot_announced: Option<(usize, usize)>,
+    #[cfg(feature = "tts")]
+    speech: Option<Speech>,
+}
+
+impl Notifier {
+    /// Set up notifications from the config, or None when they're turned off
+    pub fn new(config: &Config, templates: &Templates) -> Option<Self> {
+        let bells = config.bells.as_ref().map(|pattern| {
+            (
+                pattern.0.iter().map(|rule| rule.beeps).collect(),
+                Thresholds::new(pattern.0.iter().map(|rule| rule.at).collect()),
+            )
+        });
+
+        let protocol = match (config.terminal_notify, config.osc_protocol) {
+            (false, _) => None,
+            (true, Some(protocol)) => Some(protocol),
+            (true, None) => {
+                let detected = OscProtocol::detect();
+                if detected.is_none() {
+                    eprintln!(
+                        "This terminal doesn't seem to support notifications; \
+                         force one with --terminal-notify osc9 or osc777"
+                    );
+                }
+                detected
+            }
+        };
+        let terminal =
+            protocol.map(|protocol| (protocol, Thresholds::new(TERMINAL_THRESHOLDS.to_vec())));
+
+        #[cfg(feature = "desktop")]
+        let desktop = config
+            .desktop_notify
+            .then(Desktop::new)
+            .flatten()
+            .map(|desktop| (desktop, Thresholds::new(TERMINAL_THRESHOLDS.to_vec())));
+        #[cfg(feature = "desktop")]
+        let raises = desktop.is_some();
+        #[cfg(not(feature = "desktop"))]
+        let raises = false;
+
+        let slot_beep =
+            config.hard_cutoff.is_some() && (config.bells.is_some() || config.bell_sound.is_some());
+
+        #[cfg(feature = "tts")]
+        let speech = config
+            .speak
+            .as_ref()
+            .and_then(|pattern| Speech::new(pattern, templates));
+        #[cfg(feature = "tts")]
+        let speaks = speech.is_some();
+        #[cfg(not(feature = "tts"))]
+        let speaks = false;
+
+        if bells.is_none() && terminal.is_none() && !raises && !slot_beep && !speaks {
+            return None;
+        }
+
+        let backend = Backend::new(config);
+
+        Some(Self {
+            bells,
+            backend,
+            pending_beeps: 0,
+            last_beep: None,
+            terminal,
+            #[cfg(feature = "desktop")]
+            desktop,
+            templates: templates.clone(),
+            slot_beep,
+            slot_announced: None,
+            #[cfg(feature = "tts")]
+            speech,
+        })
+    }
+
+    /// Announce any thresholds the meeting has just reached
+    pub fn update(&mut self, session: &Session) {
+        let remaining = session.remaining_time();
+
+        if let Some((beeps, thresholds)) = &mut self.bells {
+            for i in thresholds.update(remaining) {
+                self.pending_beeps += beeps[i];
+            }
+        }
+
+        // let the speaker know their own slot ran out
+        let index = session.current_person_index();
+        let speaker = (session.current_round(), index);
+        let slot_up = session.current_time() >= session.slot(index)
+            && self.slot_announced.as_ref() != Some(&speaker);
+        if slot_up {
+            self.slot_announced = Some(speaker);
+            if self.slot_beep {
+                self.pending_beeps += 1;
+            }
+        }
+
+        // one beep per update, spaced out so they can be counted
+        if self.pending_beeps > 0 && self.last_beep.is_none_or(|last| last.elapsed() >= BEEP_GAP) {
+            self.backend.beep();
+            self.pending_beeps -= 1;
+            self.last_beep = Some(Instant::now());
+        }
+
+        #[cfg(feature = "tts")]
+        if let Some(speech) = &mut self.speech {
+            speech.update(session);
+        }
+
+        // what's announced when a speaker's slot runs out
+        let slot_up = slot_up.then(|| Notification {
+            speaker: Some(session.current_name().to_string()),
+            slot: Some(format_duration(session.slot(index))),
+            ..Notification::new("slot_up")
+        });
+
+        if let Some((protocol, thresholds)) = &mut self.terminal {
+            let crossed = thresholds.update(remaining);
+            for text in milestone_texts(&self.templates, crossed, slot_up.as_ref()) {
+                protocol.notify("nextup", &text);
+            }
+        }
+
+        #[cfg(feature = "desktop")]
+        if let Some((desktop, thresholds)) = &mut self.desktop {
+            let crossed = thresholds.update(remaining);
+            for text in milestone_texts(&self.templates, crossed, slot_up.as_ref()) {
+                desktop.notify("nextup", &text);
+            }
+        }
+    }
+
+    /// The next command from a desktop notification's button, if any were pressed
+    pub fn next_command(&mut self) -> Option<MeetingCommand> {
+        #[cfg(feature = "desktop")]
+        if let Some((desktop, _)) = &mut self.desktop {
+            return desktop.next_command();
+        }
+        None
+    }
+}
+
+/// The notifications for remaining-time thresholds just crossed, and a slot running out;
+/// a broken template can't be reported mid-meeting, so it just stays quiet
+fn milestone_texts(
+    templates: &Templates,
+    crossed: Vec<usize>,
+    slot_up: Option<&Notification>,
+) -> Vec<String> {
+    crossed
+        .into_iter()
+        .map(|i| {
+            templates.render(
+                "notify/notification.txt",
+                Notification::reached(TERMINAL_THRESHOLDS[i]),
+            )
+        })
+        .chain(
+            slot_up.map(|notification| templates.render("notify/notification.txt", notification)),
+        )
+        .filter_map(|text| text.ok())
+        .filter(|text| !text.is_empty())
+        .collect()
+}
+
+/// A command-line audio player available on this system
+fn sound_player() -> Option<&'static str> {
+    let candidates: &[&'static str] = if cfg!(target_os = "macos") {
+        &["afplay"]
+    } else {
+        &["paplay", "aplay"]
+    };
+    find_command(candidates)
+}
+
+/// The first of these commands installed on this system
+pub fn find_command(candidates: &[&'static str]) -> Option<&'static str> {
+    candidates.iter().copied().find(|command| {
+        Command::new("which")
+            .arg(command)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    })
+}
@@ -0,0 +1,349 @@
+use chrono::Weekday;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::export;
+use crate::meeting::Meeting;
+use crate::remind::PlannedOrder;
+
+// How many placements the search tries before giving up on finding an order
+const SEARCH_LIMIT: usize = 100_000;
+
+/// What a rule asks of the speaking order
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rule {
+    // the first person speaks at some point before the second
+    Before(String, String),
+    First(String),
+    Last(String),
+    NotFirst(String),
+    NotLast(String),
+}
+
+/// A speaking order rule such as `Alice before Bob`, `Carol not first` or `Dave last on Fridays`
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderRule {
+    pub rule: Rule,
+    // only on this day of the week
+    pub day: Option<Weekday>,
+}
+
+impl FromStr for OrderRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "can't read the order rule `{}`, expected e.g. `Alice before Bob`, `Carol not first` or `Dave last on Fridays`",
+                s
+            )
+        };
+        let mut text = s.trim();
+
+        // keywords are ASCII, so positions found in the lowercased text hold in the original
+        let lower = text.to_ascii_lowercase();
+        let mut day = None;
+        if let Some(at) = lower.rfind(" on ") {
+            let name = lower[at + 4..].trim();
+            let name = name.strip_suffix('s').unwrap_or(name);
+            day = Some(name.parse::<Weekday>().map_err(|_| invalid())?);
+            text = text[..at].trim();
+        }
+        let lower = text.to_ascii_lowercase();
+
+        let name = |name: &str| {
+            let name = name.trim();
+            (!name.is_empty())
+                .then(|| name.to_string())
+                .ok_or_else(invalid)
+        };
+        let rule = if let Some(at) = lower.find(" before ") {
+            Rule::Before(name(&text[..at])?, name(&text[at + 8..])?)
+        } else if let Some(at) = lower.find(" after ") {
+            Rule::Before(name(&text[at + 7..])?, name(&text[..at])?)
+        } else if let Some(who) = lower.strip_suffix(" not first") {
+            Rule::NotFirst(name(&text[..who.len()])?)
+        } else if let Some(who) = lower.strip_suffix(" not last") {
+            Rule::NotLast(name(&text[..who.len()])?)
+        } else if let Some(who) = lower.strip_suffix(" first") {
+            Rule::First(name(&text[..who.len()])?)
+        } else if let Some(who) = lower.strip_suffix(" last") {
+            Rule::Last(name(&text[..who.len()])?)
+        } else {
+            return Err(invalid());
+        };
+
+        Ok(Self { rule, day })
+    }
+}
+
+impl fmt::Display for OrderRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.rule {
+            Rule::Before(a, b) => write!(f, "{} before {}", a, b)?,
+            Rule::First(name) => write!(f, "{} first", name)?,
+            Rule::Last(name) => write!(f, "{} last", name)?,
+            Rule::NotFirst(name) => write!(f, "{} not first", name)?,
+            Rule::NotLast(name) => write!(f, "{} not last", name)?,
+        }
+        if let Some(day) = self.day {
+            write!(f, " on {}s", weekday_name(day))?;
+        }
+        Ok(())
+    }
+}
+
+impl OrderRule {
+    /// Whether the rule is in force on this day
+    pub fn applies_on(&self, day: Weekday) -> bool {
+        self.day.is_none_or(|d| d == day)
+    }
+
+    /// The names the rule mentions, to match them to the team
+    pub fn names_mut(&mut self) -> Vec<&mut String> {
+        match &mut self.rule {
+            Rule::Before(a, b) => vec![a, b],
+            Rule::First(name) | Rule::Last(name) | Rule::NotFirst(name) | Rule::NotLast(name) => {
+                vec![name]
+            }
+        }
+    }
+
+    /// Whether `name` can speak at `position` of `total`, with `placed` already speaking before
+    /// them; `present` is everyone in the order
+    fn allows(
+        &self,
+        name: &str,
+        position: usize,
+        total: usize,
+        placed: &HashSet<&str>,
+        present: &HashSet<&str>,
+    ) -> bool {
+        match &self.rule {
+            Rule::First(who) if present.contains(who.as_str()) => (name == who) == (position == 0),
+            Rule::Last(who) if present.contains(who.as_str()) => {
+                (name == who) == (position + 1 == total)
+            }
+            Rule::NotFirst(who) => !(name == who && position == 0),
+            Rule::NotLast(who) => !(name == who && position + 1 == total),
+            Rule::Before(a, b) => {
+                name != b || !present.contains(a.as_str()) || placed.contains(a.as_str())
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Arrange `candidates` to follow `placed` so every rule holds, keeping as close to the given
+/// order as the rules allow. Returns positions in `candidates`, or None when no order fits.
+pub fn arrange(candidates: &[&str], placed: &[&str], rules: &[OrderRule]) -> Option<Vec<usize>> {
+    let present: HashSet<&str> = placed.iter().chain(candidates).copied().collect();
+    let mut search = Search {
+        candidates,
+        rules,
+        present,
+        total: placed.len() + candidates.len(),
+        placed: placed.iter().copied().collect(),
+        order: Vec::new(),
+        used: vec![false; candidates.len()],
+        steps: 0,
+    };
+    search.next().then_some(search.order)
+}
+
+struct Search<'a> {
+    candidates: &'a [&'a str],
+    rules: &'a [OrderRule],
+    present: HashSet<&'a str>,
+    total: usize,
+    placed: HashSet<&'a str>,
+    order: Vec<usize>,
+    used: Vec<bool>,
+    steps: usize,
+}
+
+impl Search<'_> {
+    /// Place the next speaker, trying candidates in their given order and backing up on dead ends
+    fn next(&mut self) -> bool {
+        if self.order.len() == self.candidates.len() {
+            return true;
+        }
+        let position = self.total - self.candidates.len() + self.order.len();
+
+        for i in 0..self.candidates.len() {
+            self.steps += 1;
+            if self.used[i] || self.steps > SEARCH_LIMIT {
+                continue;
+            }
+            let name = self.candidates[i];
+            if !self
+                .rules
+                .iter()
+                .all(|rule| rule.allows(name, position, self.total, &self.placed, &self.present))
+            {
+                continue;
+            }
+
+            self.used[i] = true;
+            self.placed.insert(name);
+            self.order.push(i);
+            if self.next() {
+                return true;
+            }
+            self.order.pop();
+            self.placed.remove(name);
+            self.used[i] = false;
+        }
+        false
+    }
+}
+
+/// Make sure the rules can all hold for these people, on every day of the week, explaining
+/// the clash when they can't
+pub fn check(rules: &[OrderRule], names: &[&str]) -> Result<()> {
+    for day in [
+        Weekday::Mon,
+        Weekday::Tue,
+        Weekday::Wed,
+        Weekday::Thu,
+        Weekday::Fri,
+        Weekday::Sat,
+        Weekday::Sun,
+    ] {
+        let today: Vec<OrderRule> = rules
+            .iter()
+            .filter(|rule| rule.applies_on(day))
+            .cloned()
+            .collect();
+        if let Some(conflict) = conflict(&today, names) {
+            return Err(AppError::OrderError(conflict).into());
+        }
+    }
+    Ok(())
+}
+
+/// Why rules that apply on the same day can't all hold, if they can't
+fn conflict(rules: &[OrderRule], names: &[&str]) -> Option<String> {
+    for (i, a) in rules.iter().enumerate() {
+        for b in &rules[i + 1..] {
+            let clash = match (&a.rule, &b.rule) {
+                (Rule::First(x), Rule::First(y)) | (Rule::Last(x), Rule::Last(y)) => x != y,
+                (Rule::First(x), Rule::NotFirst(y))
+                | (Rule::NotFirst(x), Rule::First(y))
+                | (Rule::Last(x), Rule::NotLast(y))
+                | (Rule::NotLast(x), Rule::Last(y)) => x == y,
+                (Rule::First(x), Rule::Last(y)) | (Rule::Last(x), Rule::First(y)) => {
+                    x == y && names.len() > 1
+                }
+                (Rule::First(x), Rule::Before(_, y))
+                | (Rule::Before(_, y), Rule::First(x))
+                | (Rule::Last(x), Rule::Before(y, _))
+                | (Rule::Before(y, _), Rule::Last(x)) => x == y,
+                _ => false,
+            };
+            if clash {
+                return Some(format!("`{}` and `{}` can't both hold", a, b));
+            }
+        }
+    }
+
+    if let Some(cycle) = before_cycle(rules) {
+        return Some(format!("{} goes round in a circle", cycle.join(" before ")));
+    }
+
+    // anything subtler, e.g. everyone but one person not first and that one after someone
+    if arrange(names, &[], rules).is_none() {
+        let days: HashSet<_> = rules.iter().filter_map(|rule| rule.day).collect();
+        let when = match days.iter().next() {
+            Some(day) if days.len() == 1 => format!(" on {}s", weekday_name(*day)),
+            _ => String::new(),
+        };
+        return Some(format!(
+            "no order of the {} people fits them all{}",
+            names.len(),
+            when
+        ));
+    }
+    None
+}
+
+/// People who'd each have to speak before the next and the last before the first, if any
+fn before_cycle(rules: &[OrderRule]) -> Option<Vec<String>> {
+    let edges: Vec<(&str, &str)> = rules
+        .iter()
+        .filter_map(|rule| match &rule.rule {
+            Rule::Before(a, b) => Some((a.as_str(), b.as_str())),
+            _ => None,
+        })
+        .collect();
+
+    fn visit<'a>(
+        name: &'a str,
+        edges: &[(&'a str, &'a str)],
+        path: &mut Vec<&'a str>,
+        done: &mut HashSet<&'a str>,
+    ) -> Option<Vec<String>> {
+        if let Some(start) = path.iter().position(|n| *n == name) {
+            let mut cycle: Vec<String> = path[start..].iter().map(|n| n.to_string()).collect();
+            cycle.push(name.to_string());
+            return Some(cycle);
+        }
+        if !done.insert(name) {
+            return None;
+        }
+        path.push(name);
+        for (_, next) in edges.iter().filter(|(from, _)| *from == name) {
+            if let Some(cycle) = visit(next, edges, path, done) {
+                return Some(cycle);
+            }
+        }
+        path.pop();
+        None
+    }
+
+    let mut done = HashSet::new();
+    edges
+        .iter()
+        .find_map(|(from, _)| visit(from, &edges, &mut Vec::new(), &mut done))
+}
+
+/// Print a random order, one name per line or as a JSON array, for `nextup shuffle`; the one a
+/// reminder planned for today when there is one. With `keep`, the meeting opens with it later
+/// today, as it does after a reminder. People who checked in as absent are left out.
+pub fn print(config: &Config, json: bool, keep: bool) -> Result<()> {
+    let mut meeting = Meeting::load(config)?;
+    meeting.open_order();
+    if keep {
+        PlannedOrder::keep(config, &meeting)?;
+    }
+    let session = &meeting.session;
+    let order: Vec<&str> = session
+        .speakers()
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !session.is_absent(*i))
+        .map(|(_, s)| s.name())
+        .collect();
+
+    let text = if json {
+        serde_json::to_string(&order)?
+    } else {
+        order.join("\n")
+    };
+    export::to_stdout(&(text + "\n"))
+}
+
+fn weekday_name(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
@@ -0,0 +1,153 @@
+use chrono::{Local, NaiveDate};
+use rand::seq::SliceRandom;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::export;
+use crate::history::{History, Pairing};
+use crate::sources;
+use crate::sync;
+
+// How many past pairings are taken into account; older ones are fair game again
+pub const PAIRING_MEMORY: usize = 8;
+
+// Random groupings tried before settling on the one with the fewest recent repeats
+const ATTEMPTS: usize = 500;
+
+/// Options for `nextup pairs`
+pub struct PairsOptions<'a> {
+    // people per group, 2 for pairs or 3 for triads
+    pub size: usize,
+    pub export: Option<&'a Path>,
+    // print the groups without remembering them
+    pub dry_run: bool,
+}
+
+/// Split the team into random groups, avoiding people who were grouped recently
+pub fn run(config: &Config, options: &PairsOptions) -> Result<()> {
+    let members = sources::load_members(config)?;
+    let names: Vec<String> = members.iter().map(|m| m.name.clone()).collect();
+    let size = options.size.max(2);
+
+    if names.len() < 2 {
+        println!(
+            "Pairing needs at least two people in {}.",
+            config.names_file
+        );
+        return Ok(());
+    }
+
+    let mut history = History::load(&config.series)?;
+    history.identify(&members);
+    let recent = &history.pairings()[history.pairings().len().saturating_sub(PAIRING_MEMORY)..];
+
+    let mut best = group(&names, size);
+    let mut best_penalty = penalty(&best, recent);
+    for _ in 1..ATTEMPTS {
+        if best_penalty == 0 {
+            break;
+        }
+        let groups = group(&names, size);
+        let penalty = penalty(&groups, recent);
+        if penalty < best_penalty {
+            best = groups;
+            best_penalty = penalty;
+        }
+    }
+
+    let today = Local::now().date_naive();
+    println!("Groups for {}, {}\n", config.series.id(), today);
+    for members in &best {
+        let mut line = format!("  {}", members.join(" & "));
+        let repeats = repeats(members, recent);
+        if !repeats.is_empty() {
+            line.push_str(&format!(" (again: {})", repeats.join(", ")));
+        }
+        println!("{}", line);
+    }
+
+    if let Some(path) = options.export {
+        // one group per row
+        export::write_csv(path, None, &best)?;
+        println!("\nSaved to {}", path.display());
+    }
+
+    if options.dry_run {
+        return Ok(());
+    }
+
+    history.record_pairing(today, best);
+    history.save()?;
+
+    // share the updated history when the state directory is a git checkout
+    if let Some(root) = config.series.root()
+        && let Err(e) = sync::push(root, &format!("nextup: {} pairs", config.series.id()))
+    {
+        eprintln!("{}", e);
+    }
+
+    Ok(())
+}
+
+/// Shuffle everyone into groups of `size`; leftovers join the first groups rather than being left alone
+fn group(names: &[String], size: usize) -> Vec<Vec<String>> {
+    let mut names = names.to_vec();
+    names.shuffle(&mut rand::rng());
+
+    let mut groups: Vec<Vec<String>> = names.chunks(size).map(<[String]>::to_vec).collect();
+    if groups.len() > 1 && groups[groups.len() - 1].len() < size {
+        let leftovers = groups.pop().unwrap_or_default();
+        for (i, name) in leftovers.into_iter().enumerate() {
+            let target = i % groups.len();
+            groups[target].push(name);
+        }
+    }
+    groups
+}
+
+/// Every two people sharing a group
+pub fn pairs(group: &[String]) -> impl Iterator<Item = (&String, &String)> {
+    group
+        .iter()
+        .enumerate()
+        .flat_map(move |(i, a)| group[i + 1..].iter().map(move |b| (a, b)))
+}
+
+fn grouped_together(pairing: &Pairing, a: &str, b: &str) -> bool {
+    pairing
+        .groups
+        .iter()
+        .any(|group| group.iter().any(|name| name == a) && group.iter().any(|name| name == b))
+}
+
+/// How much the groups repeat recent pairings; the more recent a repeat, the more it counts
+pub fn penalty(groups: &[Vec<String>], recent: &[Pairing]) -> usize {
+    groups
+        .iter()
+        .flat_map(|group| pairs(group))
+        .map(|(a, b)| {
+            recent
+                .iter()
+                .enumerate()
+                .filter(|(_, pairing)| grouped_together(pairing, a, b))
+                // oldest first, so later pairings weigh more
+                .map(|(i, _)| i + 1)
+                .sum::<usize>()
+        })
+        .sum()
+}
+
+/// Pairs within a group that have been together recently, e.g. "Ann & Bob on 2025-12-08"
+pub fn repeats(group: &[String], recent: &[Pairing]) -> Vec<String> {
+    pairs(group)
+        .filter_map(|(a, b)| {
+            let last: NaiveDate = recent
+                .iter()
+                .rev()
+                .find(|pairing| grouped_together(pairing, a, b))?
+                .date;
+            Some(format!("{} & {} on {}", a, b, last))
+        })
+        .collect()
+}
@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::notify::Backend;
+use crate::session::Session;
+
+// How long before a slot runs out the speaker is warned, at most; short slots warn in their
+// last quarter instead
+const WARNING: Duration = Duration::from_secs(15);
+
+/// Per-person limits: the current speaker's slot shown beside the meeting clock, with a
+/// warning as it runs out
+pub struct PerPerson {
+    // sounded once per turn as the slot nearly runs out, with --per-person-bell
+    bell: Option<Backend>,
+    // (round, speaker) already warned, so going back to someone doesn't ring again
+    warned: Option<(usize, usize)>,
+}
+
+/// Where the current speaker stands against their slot
+pub struct SlotStatus {
+    pub name: String,
+    pub slot: Duration,
+    pub spoken: Duration,
+    // how far past their slot they've run over the whole meeting
+    pub overage: Duration,
+}
+
+impl PerPerson {
+    /// Keep track of slots when the meeting is run with per-person limits
+    pub fn new(config: &Config) -> Option<Self> {
+        config.per_person.then(|| Self {
+            bell: config.per_person_bell.then(|| Backend::new(config)),
+            warned: None,
+        })
+    }
+
+    /// Ring the bell when the current speaker's slot is nearly up
+    pub fn update(&mut self, session: &Session) {
+        let turn = (session.current_round(), session.current_person_index());
+        if nearly_up(session) && self.warned != Some(turn) {
+            self.warned = Some(turn);
+            if let Some(bell) = &self.bell {
+                bell.beep();
+            }
+        }
+    }
+
+    /// The current speaker's slot as it stands
+    pub fn status(&self, session: &Session) -> SlotStatus {
+        let index = session.current_person_index();
+        SlotStatus {
+            name: session.speakers()[index].name().to_string(),
+            slot: session.slot(index),
+            spoken: session.current_time(),
+            overage: session.overage(index),
+        }
+    }
+}
+
+impl SlotStatus {
+    pub fn left(&self) -> Duration {
+        self.slot.saturating_sub(self.spoken)
+    }
+
+    /// Fraction of the slot still to go, for the gauge
+    pub fn ratio(&self) -> f64 {
+        if self.slot.is_zero() {
+            0.0
+        } else {
+            self.left().as_secs_f64() / self.slot.as_secs_f64()
+        }
+    }
+}
+
+/// Whether the current speaker is in the last stretch of their slot, or past it
+pub fn nearly_up(session: &Session) -> bool {
+    let slot = session.slot(session.current_person_index());
+    !slot.is_zero() && slot.saturating_sub(session.current_time()) <= WARNING.min(slot / 4)
+}
@@ -0,0 +1,197 @@
+use chrono::{DateTime, Datelike, Local, NaiveTime, TimeDelta, Weekday};
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::str::FromStr;
+
+use crate::autosave::Autosave;
+use crate::config::Config;
+use crate::error::{AppError, Result};
+
+// Starting this far before or after the meeting window gets a warning
+const WINDOW_TOLERANCE: TimeDelta = TimeDelta::minutes(30);
+
+/// When a meeting is supposed to happen, e.g. `09:30-09:45`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeetingWindow {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl FromStr for MeetingWindow {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || format!("invalid meeting window `{}`, expected e.g. 09:30-09:45", s);
+
+        let (start, end) = s.split_once('-').ok_or_else(invalid)?;
+        let start = NaiveTime::parse_from_str(start.trim(), "%H:%M").map_err(|_| invalid())?;
+        let end = NaiveTime::parse_from_str(end.trim(), "%H:%M").map_err(|_| invalid())?;
+        if end <= start {
+            return Err(invalid());
+        }
+
+        Ok(Self { start, end })
+    }
+}
+
+impl std::fmt::Display for MeetingWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}-{}",
+            self.start.format("%H:%M"),
+            self.end.format("%H:%M")
+        )
+    }
+}
+
+impl MeetingWindow {
+    /// How far outside the window `now` is, if further than the tolerance
+    fn distance(&self, now: NaiveTime) -> Option<TimeDelta> {
+        let distance = if now < self.start {
+            self.start - now
+        } else if now > self.end {
+            now - self.end
+        } else {
+            TimeDelta::zero()
+        };
+
+        (distance > WINDOW_TOLERANCE).then_some(distance)
+    }
+
+    /// When the next meeting starts: now if we're inside today's window and today's meeting
+    /// hasn't been held yet, otherwise the window's next start, skipping weekends if asked
+    pub fn next_start(
+        &self,
+        now: DateTime<Local>,
+        held_today: bool,
+        workdays_only: bool,
+    ) -> DateTime<Local> {
+        let today = now.date_naive();
+        for day in today.iter_days().take(8) {
+            if workdays_only && matches!(day.weekday(), Weekday::Sat | Weekday::Sun) {
+                continue;
+            }
+            if day == today {
+                if held_today || now.time() >= self.end {
+                    continue;
+                }
+                if now.time() >= self.start {
+                    return now;
+                }
+            }
+            if let Some(start) = day
+                .and_time(self.start)
+                .and_local_timezone(Local)
+                .earliest()
+            {
+                return start;
+            }
+        }
+        now
+    }
+}
+
+/// Reasons to double-check before starting a meeting
+pub fn check(config: &Config) -> Result<Vec<String>> {
+    let now = Local::now();
+    let mut warnings = Vec::new();
+
+    if let Some(window) = config.window
+        && let Some(distance) = window.distance(now.time())
+    {
+        warnings.push(format!(
+            "It's {}, {} outside this meeting's {} window.",
+            now.format("%H:%M"),
+            format_delta(distance),
+            window
+        ));
+    }
+
+    Ok(warnings)
+}
+
+/// Print any warnings and, when someone is at the terminal, ask whether to go ahead.
+/// Headless runs carry on after printing the warnings.
+pub fn confirm(warnings: &[String]) -> Result<bool> {
+    if warnings.is_empty() {
+        return Ok(true);
+    }
+
+    for warning in warnings {
+        eprintln!("Warning: {}", warning);
+    }
+
+    if !io::stdin().is_terminal() {
+        return Ok(true);
+    }
+
+    eprint!("Start anyway? [y/N] ");
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// What to do when another nextup is already running the series
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Conflict {
+    Attach,
+    TakeOver,
+    Abort,
+}
+
+/// Describe the running meeting and ask how to proceed.
+/// Without a terminal there's nobody to ask, so the launch fails instead.
+pub fn resolve_conflict(config: &Config, pid: u32, can_attach: bool) -> Result<Conflict> {
+    if !io::stdin().is_terminal() {
+        return Err(AppError::AlreadyRunning { pid }.into());
+    }
+
+    let mut running = format!("pid {}", pid);
+    if let Some(autosave) = Autosave::load(&config.series)? {
+        running.push_str(&format!(
+            ", started {}",
+            autosave.started_at.format("%H:%M")
+        ));
+        if let Some(speaker) = autosave.current_speaker() {
+            running.push_str(&format!(", {} is speaking", speaker));
+        }
+    }
+    eprintln!(
+        "A {} meeting is already running ({}).",
+        config.series.id(),
+        running
+    );
+
+    loop {
+        if can_attach {
+            eprint!("[a]ttach read-only, [t]ake over, or a[b]ort? ");
+        } else {
+            eprint!("[t]ake over or a[b]ort? ");
+        }
+        io::stderr().flush()?;
+
+        let mut answer = String::new();
+        if io::stdin().lock().read_line(&mut answer)? == 0 {
+            return Ok(Conflict::Abort);
+        }
+
+        match answer.trim().to_lowercase().as_str() {
+            "a" | "attach" if can_attach => return Ok(Conflict::Attach),
+            "t" | "take over" => return Ok(Conflict::TakeOver),
+            "b" | "abort" | "" => return Ok(Conflict::Abort),
+            _ => {}
+        }
+    }
+}
+
+/// e.g. "2h 5m" or "45m"
+fn format_delta(delta: TimeDelta) -> String {
+    let (hours, minutes) = (delta.num_hours(), delta.num_minutes() % 60);
+    if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
@@ -0,0 +1,177 @@
+use hmac_sha256::HMAC;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::audit;
+use crate::checkin::CheckIns;
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::history::History;
+use crate::remind::PlannedOrder;
+use crate::report;
+use crate::roster;
+use crate::series::Series;
+use crate::sources;
+use crate::sync;
+
+// What every stored identifier starts with, so one is never taken for a name and hashed again
+const ID_PREFIX: &str = "member-";
+
+// Bytes of the hash kept in an identifier; plenty to tell a team apart
+const ID_LEN: usize = 6;
+
+/// Turns names into identifiers for a series' history, keyed by a secret kept next to it, so
+/// the history says nothing about who someone is to anyone without the key and the team file
+#[derive(Debug, Clone)]
+pub struct Pseudonyms {
+    key: [u8; 32],
+}
+
+impl Pseudonyms {
+    /// Location of a series' key (e.g. `~/.local/state/nextup/series/team-daily-standup/privacy.key`)
+    pub fn path(series: &Series) -> Option<PathBuf> {
+        series.dir().map(|dir| dir.join("privacy.key"))
+    }
+
+    /// The series' key, if names are hashed for it
+    pub fn load(series: &Series) -> Result<Option<Self>> {
+        let Some(path) = Self::path(series) else {
+            return Ok(None);
+        };
+
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(AppError::HistoryError(format!("{}: {}", path.display(), e)).into());
+            }
+        };
+        let invalid = || AppError::HistoryError(format!("{}: not a privacy key", path.display()));
+        let bytes = content
+            .trim()
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| {
+                std::str::from_utf8(pair)
+                    .ok()
+                    .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+            })
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(invalid)?;
+        let key = bytes.try_into().map_err(|_| invalid())?;
+        Ok(Some(Self { key }))
+    }
+
+    /// Start hashing names for the series, rewriting the history it has so far; false when they
+    /// already were
+    pub fn enable(series: &Series) -> Result<bool> {
+        let Some(path) = Self::path(series) else {
+            return Ok(false);
+        };
+        if path.exists() {
+            return Ok(false);
+        }
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let key: [u8; 32] = rand::random();
+        fs::write(&path, hex(&key) + "\n")?;
+
+        // the history picks the key up when it's loaded, and saves with it
+        if History::path(series).is_some_and(|path| path.exists()) {
+            History::load(series)?.save()?;
+        }
+        Ok(true)
+    }
+
+    /// The identifier stored for someone; identifiers stay as they are
+    pub fn id(&self, name: &str) -> String {
+        if is_id(name) {
+            return name.to_string();
+        }
+        let mac = HMAC::mac(name.as_bytes(), self.key);
+        format!("{}{}", ID_PREFIX, hex(&mac[..ID_LEN]))
+    }
+}
+
+/// Whether a name in the history is really a hashed identifier
+pub fn is_id(name: &str) -> bool {
+    name.strip_prefix(ID_PREFIX)
+        .is_some_and(|hash| hash.len() == ID_LEN * 2 && hash.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Put names back in a hashed history for everyone on the team, for reports. People who've
+/// left, or everyone when the team can't be read, keep their identifiers.
+pub fn identify(config: &Config, history: &mut History) {
+    if history.is_private()
+        && let Ok(members) = sources::load_members(config)
+    {
+        history.identify(&members);
+    }
+}
+
+/// Remove everything kept about someone in the series: their history, today's check-in, their
+/// place in today's planned order and the last meeting's report, and their lines in the audit
+/// trail, for `nextup privacy purge`
+pub fn purge(config: &Config, name: &str) -> Result<()> {
+    // the team file can name them by any identity they have; someone who's left goes by the
+    // name they were recorded under
+    let members = sources::load_members(config).unwrap_or_default();
+    let name = roster::find(&members, name)
+        .map(|member| member.name.clone())
+        .unwrap_or_else(|| name.to_string());
+
+    let mut who = vec![name.clone()];
+    if let Some(pseudonyms) = Pseudonyms::load(&config.series)? {
+        who.push(pseudonyms.id(&name));
+    }
+
+    let mut history = History::load(&config.series)?;
+    let mut removed = history.forget(&who);
+    if removed > 0 {
+        history.save()?;
+    }
+
+    let mut check_ins = CheckIns::load(&config.series)?;
+    if check_ins.get(&name).is_some() {
+        check_ins.set(&name, None, None);
+        check_ins.save()?;
+        removed += 1;
+    }
+    if PlannedOrder::forget(&config.series, &name)? {
+        removed += 1;
+    }
+    if report::forget(&config.series, &who)? {
+        removed += 1;
+    }
+    removed += audit::forget(&config.series, &name)?;
+
+    if removed == 0 {
+        println!("Nothing is kept about {} in {}.", name, config.series.id());
+        return Ok(());
+    }
+
+    // share the purge when the state directory is a git checkout, so it reaches every copy
+    if let Some(root) = config.series.root()
+        && let Err(e) = sync::push(
+            root,
+            &format!("nextup: {} privacy purge", config.series.id()),
+        )
+    {
+        eprintln!("{}", e);
+    }
+    println!(
+        "Removed {} {} about {} from {}.",
+        removed,
+        if removed == 1 { "entry" } else { "entries" },
+        name,
+        config.series.id()
+    );
+    Ok(())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
@@ -0,0 +1,90 @@
+use rand::seq::SliceRandom;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+use crate::error::{AppError, Result};
+use crate::history::History;
+use crate::roster::Member;
+
+// Embed the default question bank at compile time
+const DEFAULT_QUESTIONS_CONTENT: &str = include_str!("../questions.txt");
+
+/// Load the question bank from a file, or the embedded default when none is given
+pub fn load_bank(filename: Option<&str>) -> Result<Vec<String>> {
+    let content = match filename {
+        Some(filename) => fs::read_to_string(filename).map_err(AppError::QuestionsFileError)?,
+        None => DEFAULT_QUESTIONS_CONTENT.to_string(),
+    };
+
+    let questions: Vec<String> = content
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    if questions.is_empty() {
+        return Err(AppError::NoQuestionsFound.into());
+    }
+
+    Ok(questions)
+}
+
+/// Today's question for each person
+pub struct QuestionOfTheDay {
+    assigned: HashMap<String, String>,
+    asked: HashSet<String>,
+}
+
+impl QuestionOfTheDay {
+    /// Give everyone a different question, avoiding ones they were asked recently
+    pub fn assign(bank: &[String], members: &[Member], history: &History) -> Self {
+        let mut rng = rand::rng();
+        let mut pool: Vec<&String> = bank.iter().collect();
+        pool.shuffle(&mut rng);
+
+        let mut assigned = HashMap::new();
+        let mut used_today = HashSet::new();
+
+        for Member { name, .. } in members {
+            let recent = history.recent_questions(name);
+
+            // prefer fresh questions, then anything not used today, then anything
+            let question = pool
+                .iter()
+                .find(|q| !used_today.contains(*q) && !recent.contains(q))
+                .or_else(|| pool.iter().find(|q| !used_today.contains(*q)))
+                .or_else(|| pool.first());
+
+            if let Some(&question) = question {
+                used_today.insert(question);
+                assigned.insert(name.clone(), question.clone());
+            }
+        }
+
+        Self {
+            assigned,
+            asked: HashSet::new(),
+        }
+    }
+
+    /// The question assigned to a person
+    pub fn for_name(&self, name: &str) -> Option<&str> {
+        self.assigned.get(name).map(String::as_str)
+    }
+
+    /// Note that a person's turn came up and their question was shown
+    pub fn mark_asked(&mut self, name: &str) {
+        if !self.asked.contains(name) {
+            self.asked.insert(name.to_string());
+        }
+    }
+
+    /// Store the questions that were actually asked
+    pub fn record(&self, history: &mut History) {
+        for name in &self.asked {
+            if let Some(question) = self.assigned.get(name) {
+                history.record_question(name, question);
+            }
+        }
+    }
+}
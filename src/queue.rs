@@ -0,0 +1,517 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::roster::parse_slot;
+use crate::series::Series;
+use crate::slack::SlackClient;
+use crate::trace;
+
+// Tries per job before it goes to the dead-letter file
+const ATTEMPTS: u32 = 4;
+
+// Wait after the first failed try, doubled after each one after it
+const FIRST_BACKOFF: Duration = Duration::from_secs(1);
+
+// Used when --coalesce isn't given
+pub const DEFAULT_COALESCE: Duration = Duration::from_secs(2);
+
+/// Where a job goes, for `--rate-limit`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Destination {
+    Slack,
+    Otlp,
+}
+
+impl FromStr for Destination {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "slack" => Ok(Self::Slack),
+            "otlp" => Ok(Self::Otlp),
+            _ => Err(format!(
+                "unknown destination `{}`, expected slack or otlp",
+                s
+            )),
+        }
+    }
+}
+
+/// At most `calls` calls to a destination in any `per`, e.g. `slack=20/1m`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    pub destination: Destination,
+    pub calls: usize,
+    pub per: Duration,
+}
+
+impl FromStr for RateLimit {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "invalid rate limit `{}`, expected e.g. slack=20/1m or slack=1/s",
+                s
+            )
+        };
+
+        let (destination, rate) = s.split_once('=').ok_or_else(invalid)?;
+        let (calls, per) = rate.split_once('/').ok_or_else(invalid)?;
+        let per = match per.trim() {
+            // a bare unit is one of it
+            unit @ ("s" | "m") => format!("1{}", unit),
+            per => per.to_string(),
+        };
+        Ok(Self {
+            destination: destination.parse()?,
+            calls: calls
+                .trim()
+                .parse()
+                .ok()
+                .filter(|calls| *calls > 0)
+                .ok_or_else(invalid)?,
+            per: parse_slot(&per)
+                .filter(|per| !per.is_zero())
+                .ok_or_else(invalid)?,
+        })
+    }
+}
+
+/// A call to Slack or a trace collector, kept as data so it can be sent again later
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Job {
+    // a message to a channel, or a reply in a thread
+    SlackPost {
+        channel: String,
+        text: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        thread_ts: Option<String>,
+    },
+    // an edit to a message already posted
+    SlackUpdate {
+        channel: String,
+        ts: String,
+        text: String,
+    },
+    // a trace in OTLP's JSON encoding
+    Trace {
+        endpoint: String,
+        body: Value,
+    },
+}
+
+impl Job {
+    fn destination(&self) -> Destination {
+        match self {
+            Job::SlackPost { .. } | Job::SlackUpdate { .. } => Destination::Slack,
+            Job::Trace { .. } => Destination::Otlp,
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Job::SlackPost {
+                channel,
+                thread_ts: None,
+                ..
+            } => format!("Slack post to {}", channel),
+            Job::SlackPost { channel, .. } => format!("Slack reply in {}", channel),
+            Job::SlackUpdate { channel, .. } => format!("Slack edit in {}", channel),
+            Job::Trace { endpoint, .. } => format!("trace to {}", endpoint),
+        }
+    }
+
+    fn run(&self, targets: &Targets) -> Result<()> {
+        match self {
+            Job::SlackPost {
+                channel,
+                text,
+                thread_ts,
+            } => {
+                targets
+                    .slack()?
+                    .post_message(channel, text, thread_ts.as_deref())?;
+            }
+            Job::SlackUpdate { channel, ts, text } => {
+                targets.slack()?.update_message(channel, ts, text)?;
+            }
+            Job::Trace { endpoint, body } => {
+                trace::send(endpoint, &targets.otlp_headers, body.clone())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Where jobs go. Tokens and headers come from the config, so none end up in the dead-letter
+/// file.
+struct Targets {
+    slack: Option<SlackClient>,
+    otlp_headers: Vec<(String, String)>,
+    // each limit with when the calls it counts were made
+    limits: Vec<(RateLimit, VecDeque<Instant>)>,
+}
+
+impl Targets {
+    fn new(config: &Config, slack: Option<SlackClient>) -> Self {
+        Self {
+            slack,
+            otlp_headers: config.otlp_headers.clone(),
+            limits: config
+                .rate_limits
+                .iter()
+                .map(|limit| (*limit, VecDeque::new()))
+                .collect(),
+        }
+    }
+
+    /// Wait until a call to `destination` is within its rate limits, and count it
+    fn throttle(&mut self, destination: Destination) {
+        for (limit, calls) in &mut self.limits {
+            if limit.destination != destination {
+                continue;
+            }
+            while calls
+                .front()
+                .is_some_and(|call| call.elapsed() >= limit.per)
+            {
+                calls.pop_front();
+            }
+            if calls.len() >= limit.calls
+                && let Some(oldest) = calls.pop_front()
+            {
+                thread::sleep(limit.per.saturating_sub(oldest.elapsed()));
+            }
+            calls.push_back(Instant::now());
+        }
+    }
+
+    fn slack(&self) -> Result<&SlackClient> {
+        self.slack.as_ref().ok_or_else(|| {
+            anyhow::Error::msg(
+                "sending to Slack needs a bot token (--slack-bot-token or SLACK_BOT_TOKEN)",
+            )
+        })
+    }
+}
+
+/// A job that failed every try, as a line of the dead-letter file
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub failed_at: DateTime<Local>,
+    pub attempts: u32,
+    pub error: String,
+    pub job: Job,
+}
+
+/// What the queue is handed
+enum Message {
+    Send(Job),
+    // held until nothing with the same key has come for the quiet period, so only the last of a
+    // burst is sent
+    Coalesce(&'static str, Job),
+}
+
+/// A coalesced job waiting out its quiet period
+struct Held {
+    key: &'static str,
+    job: Job,
+    due: Instant,
+}
+
+/// Sends jobs on a thread of its own, in the order they came, so a slow or flaky network never
+/// holds up the meeting. Bursts of the same update are coalesced and calls kept within
+/// `--rate-limit`. Jobs that keep failing are kept in the series' dead-letter file for
+/// `nextup retry-queue`.
+pub struct Queue {
+    sender: Option<Sender<Message>>,
+    worker: Option<JoinHandle<()>>,
+    // once the meeting is over, jobs get one more try each rather than backing off
+    closing: Arc<AtomicBool>,
+    failed: Arc<AtomicUsize>,
+    // only the Slack bot coalesces
+    #[cfg_attr(not(feature = "slack"), allow(dead_code))]
+    quiet: Duration,
+    series: Series,
+}
+
+impl Queue {
+    /// Start the queue, posting to Slack with `slack` when given
+    pub fn new(config: &Config, slack: Option<SlackClient>) -> Self {
+        let (sender, receiver) = mpsc::channel::<Message>();
+        let closing = Arc::new(AtomicBool::new(false));
+        let failed = Arc::new(AtomicUsize::new(0));
+        let mut targets = Targets::new(config, slack);
+        let quiet = config.coalesce;
+
+        let worker = {
+            let closing = closing.clone();
+            let failed = failed.clone();
+            let series = config.series.clone();
+            thread::spawn(move || {
+                let mut deliver = |job: Job| {
+                    if let Err((attempts, e)) = send(&job, &mut targets, &closing) {
+                        let letter = DeadLetter {
+                            failed_at: Local::now(),
+                            attempts,
+                            error: e.to_string(),
+                            job,
+                        };
+                        // with the terminal taken over there's nowhere to report it now
+                        if append(&series, &letter).is_ok() {
+                            failed.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                };
+
+                let mut held: Vec<Held> = Vec::new();
+                loop {
+                    let message = match held.iter().map(|h| h.due).min() {
+                        Some(due) => {
+                            receiver.recv_timeout(due.saturating_duration_since(Instant::now()))
+                        }
+                        None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                    };
+                    match message {
+                        // whatever is held goes first, so nothing is posted out of order
+                        Ok(Message::Send(job)) => {
+                            held.sort_by_key(|h| h.due);
+                            for h in held.drain(..) {
+                                deliver(h.job);
+                            }
+                            deliver(job);
+                        }
+                        Ok(Message::Coalesce(key, job)) => {
+                            held.retain(|h| h.key != key);
+                            held.push(Held {
+                                key,
+                                job,
+                                due: Instant::now() + quiet,
+                            });
+                        }
+                        Err(RecvTimeoutError::Timeout) => {}
+                        // the meeting is over, so nothing newer is coming
+                        Err(RecvTimeoutError::Disconnected) => {
+                            held.sort_by_key(|h| h.due);
+                            for h in held {
+                                deliver(h.job);
+                            }
+                            break;
+                        }
+                    }
+
+                    let now = Instant::now();
+                    held.sort_by_key(|h| h.due);
+                    while held.first().is_some_and(|h| h.due <= now) {
+                        deliver(held.remove(0).job);
+                    }
+                }
+            })
+        };
+
+        Self {
+            sender: Some(sender),
+            worker: Some(worker),
+            closing,
+            failed,
+            quiet,
+            series: config.series.clone(),
+        }
+    }
+
+    pub fn push(&self, job: Job) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(Message::Send(job));
+        }
+    }
+
+    /// Send `job` once `--coalesce` has passed with no newer job under the same key, which
+    /// replaces it; e.g. the order after the facilitator skips through several speakers
+    #[cfg_attr(not(feature = "slack"), allow(dead_code))]
+    pub fn push_coalesced(&self, key: &'static str, job: Job) {
+        if let Some(sender) = &self.sender {
+            let message = if self.quiet.is_zero() {
+                Message::Send(job)
+            } else {
+                Message::Coalesce(key, job)
+            };
+            let _ = sender.send(message);
+        }
+    }
+
+    /// Wait for the jobs still queued, giving each one more try, and say how many were kept for
+    /// `nextup retry-queue`; call once the meeting is over
+    pub fn finish(&mut self) {
+        self.closing.store(true, Ordering::Relaxed);
+        self.sender = None;
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+
+        let failed = self.failed.swap(0, Ordering::Relaxed);
+        if failed > 0
+            && let Some(path) = path(&self.series)
+        {
+            eprintln!(
+                "{} call{} to Slack or the trace collector failed and {} kept in {}; send {} again with `nextup retry-queue`",
+                failed,
+                if failed == 1 { "" } else { "s" },
+                if failed == 1 { "was" } else { "were" },
+                path.display(),
+                if failed == 1 { "it" } else { "them" },
+            );
+        }
+    }
+}
+
+impl Drop for Queue {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+/// Run a job, backing off between tries. Gives up at once on a feature left out of the build,
+/// which no retry fixes, and on the last try; returns how many tries it had.
+fn send(
+    job: &Job,
+    targets: &mut Targets,
+    closing: &AtomicBool,
+) -> std::result::Result<(), (u32, anyhow::Error)> {
+    let mut backoff = FIRST_BACKOFF;
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        targets.throttle(job.destination());
+        let Err(e) = job.run(targets) else {
+            return Ok(());
+        };
+        let not_built_in = matches!(
+            e.downcast_ref::<AppError>(),
+            Some(AppError::NotBuiltIn { .. })
+        );
+        if attempts >= ATTEMPTS || not_built_in || closing.load(Ordering::Relaxed) {
+            return Err((attempts, e));
+        }
+        thread::sleep(backoff);
+        backoff *= 2;
+    }
+}
+
+/// Where a series keeps the calls that failed
+pub fn path(series: &Series) -> Option<PathBuf> {
+    series.dir().map(|dir| dir.join("dead-letter.jsonl"))
+}
+
+fn append(series: &Series, letter: &DeadLetter) -> Result<()> {
+    let Some(path) = path(series) else {
+        return Ok(());
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(letter)?)?;
+    Ok(())
+}
+
+fn load(series: &Series) -> Result<Vec<DeadLetter>> {
+    let Some(path) = path(series) else {
+        return Ok(Vec::new());
+    };
+    match fs::read_to_string(&path) {
+        Ok(content) => content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                serde_json::from_str(line).map_err(|e| {
+                    AppError::HistoryError(format!("{}: {}", path.display(), e)).into()
+                })
+            })
+            .collect(),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(AppError::HistoryError(format!("{}: {}", path.display(), e)).into()),
+    }
+}
+
+fn save(series: &Series, letters: &[DeadLetter]) -> Result<()> {
+    let Some(path) = path(series) else {
+        return Ok(());
+    };
+    if letters.is_empty() {
+        return match fs::remove_file(&path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        };
+    }
+    let lines: Vec<String> = letters
+        .iter()
+        .map(serde_json::to_string)
+        .collect::<std::result::Result<_, _>>()?;
+    fs::write(path, lines.join("\n") + "\n")?;
+    Ok(())
+}
+
+/// Send the failed calls again for `nextup retry-queue`, keeping the ones that still fail, or
+/// drop them all with `discard`
+pub fn retry(config: &Config, discard: bool) -> Result<()> {
+    let letters = load(&config.series)?;
+    if letters.is_empty() {
+        println!("Nothing to retry.");
+        return Ok(());
+    }
+    if discard {
+        save(&config.series, &[])?;
+        println!(
+            "Dropped {} failed call{}.",
+            letters.len(),
+            if letters.len() == 1 { "" } else { "s" }
+        );
+        return Ok(());
+    }
+
+    let mut targets = Targets::new(config, config.slack_bot_token.clone().map(SlackClient::new));
+    let total = letters.len();
+    let mut kept = Vec::new();
+    for letter in letters {
+        match send(&letter.job, &mut targets, &AtomicBool::new(false)) {
+            Ok(()) => println!(
+                "Sent {} (failed {})",
+                letter.job.describe(),
+                letter.failed_at.format("%Y-%m-%d %H:%M")
+            ),
+            Err((attempts, e)) => {
+                println!("Still failing: {}: {}", letter.job.describe(), e);
+                kept.push(DeadLetter {
+                    failed_at: Local::now(),
+                    attempts: letter.attempts + attempts,
+                    error: e.to_string(),
+                    job: letter.job,
+                });
+            }
+        }
+    }
+    save(&config.series, &kept)?;
+
+    println!("Sent {} of {}.", total - kept.len(), total);
+    if !kept.is_empty() {
+        println!(
+            "The rest stay queued; run `nextup retry-queue` again later, or drop them with --discard."
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,148 @@
+use chrono::{Local, NaiveDate};
+use minijinja::context;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::meeting::Meeting;
+use crate::series::Series;
+use crate::slack::SlackClient;
+use crate::sync;
+use crate::templates::Templates;
+
+/// The order a reminder announced, which the meeting opens with on the same day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlannedOrder {
+    date: NaiveDate,
+    pub order: Vec<String>,
+    // reshuffles are ignored, as with `L`
+    pub locked: bool,
+}
+
+impl PlannedOrder {
+    fn path(series: &Series) -> Option<PathBuf> {
+        series.dir().map(|dir| dir.join("order.json"))
+    }
+
+    /// Today's planned order, if a reminder was posted today
+    pub fn load(series: &Series) -> Result<Option<Self>> {
+        let Some(path) = Self::path(series) else {
+            return Ok(None);
+        };
+
+        let planned: Self = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| AppError::HistoryError(format!("{}: {}", path.display(), e)))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(AppError::HistoryError(format!("{}: {}", path.display(), e)).into());
+            }
+        };
+        Ok((planned.date == Local::now().date_naive()).then_some(planned))
+    }
+
+    /// Keep the order `meeting` opened with for the rest of today, locked with `--lock-order`,
+    /// and share it with whoever runs the meeting
+    pub fn keep(config: &Config, meeting: &Meeting) -> Result<Self> {
+        let planned = Self {
+            date: Local::now().date_naive(),
+            order: meeting
+                .session
+                .speakers()
+                .iter()
+                .map(|s| s.name().to_string())
+                .collect(),
+            locked: config.lock_order || meeting.is_order_locked(),
+        };
+        planned.save(&config.series)?;
+
+        // share the order when the state directory is a git checkout
+        if let Some(root) = config.series.root()
+            && let Err(e) = sync::push(root, &format!("nextup: {} order", config.series.id()))
+        {
+            eprintln!("{}", e);
+        }
+        Ok(planned)
+    }
+
+    fn save(&self, series: &Series) -> Result<()> {
+        let Some(path) = Self::path(series) else {
+            return Ok(());
+        };
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Take someone out of today's planned order; false when they weren't in it
+    pub fn forget(series: &Series, name: &str) -> Result<bool> {
+        let Some(mut planned) = Self::load(series)? else {
+            return Ok(false);
+        };
+        let before = planned.order.len();
+        planned.order.retain(|n| n != name);
+        if planned.order.len() == before {
+            return Ok(false);
+        }
+        planned.save(series)?;
+        Ok(true)
+    }
+}
+
+/// Post the reminder that the standup is coming up, with the order it'll open with, to
+/// `--remind-channel`. The order is dealt now and kept for today's meeting, locked with
+/// `--lock-order`; a second reminder on the same day repeats the same order.
+pub fn post(config: &Config) -> Result<()> {
+    let channel = config
+        .remind_channel
+        .as_deref()
+        .ok_or_else(|| anyhow::Error::msg("reminders need a --remind-channel to post to"))?;
+    let token = config.slack_bot_token.clone().ok_or_else(|| {
+        anyhow::Error::msg("reminders need a bot token (--slack-bot-token or SLACK_BOT_TOKEN)")
+    })?;
+
+    let mut meeting = Meeting::load(config)?;
+    meeting.open_order();
+    let planned = PlannedOrder::keep(config, &meeting)?;
+    let session = &meeting.session;
+
+    // the meeting's start, when its window says; otherwise it's `--remind-before` away
+    let now = Local::now();
+    let start = config
+        .window
+        .map(|window| window.next_start(now, false, true))
+        .filter(|start| start.date_naive() == now.date_naive());
+    let minutes = match start {
+        Some(start) => (start - now).num_minutes().max(0) as u64,
+        None => config.remind_before.as_secs() / 60,
+    };
+    let speakers: Vec<&str> = session
+        .speakers()
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !session.is_absent(*i))
+        .map(|(_, s)| s.name())
+        .collect();
+
+    let text = Templates::load(config)?.render(
+        "slack/reminder.txt",
+        context! {
+            title => config.title,
+            start => start.map(|start| start.format("%H:%M").to_string()),
+            minutes => minutes,
+            speakers => speakers,
+            locked => planned.locked,
+        },
+    )?;
+    if text.is_empty() {
+        return Ok(());
+    }
+    SlackClient::new(token).post_message(channel, &text, None)?;
+    Ok(())
+}
@@ -0,0 +1,347 @@
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::export;
+use crate::format::format_duration;
+use crate::health::{ABSENT_THRESHOLD, Health};
+use crate::meeting::Meeting;
+use crate::privacy::Pseudonyms;
+use crate::series::Series;
+use crate::sources;
+
+// The CSV's columns
+const CSV_HEADER: [&str; 10] = [
+    "date",
+    "title",
+    "position",
+    "name",
+    "attended",
+    "spoken_secs",
+    "budget_secs",
+    "overrun_secs",
+    "meeting_secs",
+    "meeting_overtime_secs",
+];
+
+/// What kind of file the summary report is written as
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportFormat {
+    Markdown,
+    Json,
+    // one row per person, with the meeting's totals repeated on each
+    Csv,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "markdown" | "md" => Ok(Self::Markdown),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => Err(format!(
+                "unknown report format `{}`, expected md, json or csv",
+                s
+            )),
+        }
+    }
+}
+
+impl ReportFormat {
+    /// The format a report file's name asks for: JSON for `.json`, CSV for `.csv`, Markdown
+    /// otherwise
+    fn for_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::Json,
+            Some("csv") => Self::Csv,
+            _ => Self::Markdown,
+        }
+    }
+}
+
+/// How the meeting went, as written to `--report-path` when it ends. The series keeps the
+/// latest one for `nextup report`.
+#[derive(Clone, Serialize, Deserialize)]
+struct Report {
+    title: String,
+    date: String,
+    planned_secs: u64,
+    // meeting time, not counting pauses
+    elapsed_secs: u64,
+    overtime_secs: u64,
+    paused_secs: u64,
+    attendees: Vec<String>,
+    // everyone in the order they were called, whether or not they came
+    speakers: Vec<SpeakerReport>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct SpeakerReport {
+    name: String,
+    attended: bool,
+    spoken_secs: u64,
+    // their slot in every round
+    budget_secs: u64,
+    // how far past their slot they ran, summed over the rounds
+    overrun_secs: u64,
+}
+
+impl Report {
+    fn new(config: &Config, meeting: &Meeting) -> Self {
+        let session = &meeting.session;
+        // a meeting followed from elsewhere isn't closed here
+        let health = meeting
+            .health()
+            .cloned()
+            .unwrap_or_else(|| Health::compute(session));
+
+        let speakers: Vec<SpeakerReport> = session
+            .speakers()
+            .iter()
+            .enumerate()
+            .map(|(index, speaker)| SpeakerReport {
+                name: speaker.name().to_string(),
+                attended: !session.is_absent(index) && speaker.total() >= ABSENT_THRESHOLD,
+                spoken_secs: speaker.total().as_secs(),
+                budget_secs: session.budget(index).as_secs(),
+                overrun_secs: session.overage(index).as_secs(),
+            })
+            .collect();
+
+        Self {
+            title: config.title.clone(),
+            date: Local::now().format("%Y-%m-%d").to_string(),
+            planned_secs: session.duration().as_secs(),
+            elapsed_secs: health.elapsed.as_secs(),
+            overtime_secs: health.overtime.as_secs(),
+            paused_secs: health.paused.as_secs(),
+            attendees: speakers
+                .iter()
+                .filter(|s| s.attended)
+                .map(|s| s.name.clone())
+                .collect(),
+            speakers,
+        }
+    }
+
+    /// Location of the series' latest report (e.g. `~/.local/state/nextup/series/team-daily-standup/report.json`)
+    fn path(series: &Series) -> Option<PathBuf> {
+        series.dir().map(|dir| dir.join("report.json"))
+    }
+
+    /// The series' latest report, if a meeting has been run
+    fn load(series: &Series) -> Result<Option<Self>> {
+        let Some(path) = Self::path(series) else {
+            return Ok(None);
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map(Some)
+                .map_err(|e| AppError::HistoryError(format!("{}: {}", path.display(), e)).into()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(AppError::HistoryError(format!("{}: {}", path.display(), e)).into()),
+        }
+    }
+
+    /// Keep this as the series' latest report, with names hashed when its history is
+    fn save(&self, series: &Series) -> Result<()> {
+        let Some(path) = Self::path(series) else {
+            return Ok(());
+        };
+
+        let json = match Pseudonyms::load(series)? {
+            Some(pseudonyms) => {
+                let mut hashed = self.clone();
+                hashed.rename(|name| pseudonyms.id(name));
+                serde_json::to_string_pretty(&hashed)?
+            }
+            None => serde_json::to_string_pretty(self)?,
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, json + "\n")?;
+        Ok(())
+    }
+
+    fn rename(&mut self, rename: impl Fn(&str) -> String) {
+        for name in self
+            .attendees
+            .iter_mut()
+            .chain(self.speakers.iter_mut().map(|s| &mut s.name))
+        {
+            *name = rename(name);
+        }
+    }
+
+    fn render(&self, format: ReportFormat) -> Result<String> {
+        Ok(match format {
+            ReportFormat::Markdown => self.markdown() + "\n",
+            ReportFormat::Json => serde_json::to_string_pretty(self)? + "\n",
+            ReportFormat::Csv => export::to_csv(Some(&CSV_HEADER), &self.csv_rows()),
+        })
+    }
+
+    fn markdown(&self) -> String {
+        let secs = |secs: u64| format_duration(Duration::from_secs(secs));
+        let mut lines = vec![
+            format!("# {}", self.title),
+            String::new(),
+            format!("- Date: {}", self.date),
+            format!(
+                "- Duration: {} of {}{}",
+                secs(self.elapsed_secs),
+                secs(self.planned_secs),
+                if self.overtime_secs > 0 {
+                    format!(" ({} over)", secs(self.overtime_secs))
+                } else {
+                    String::new()
+                }
+            ),
+        ];
+        if self.paused_secs > 0 {
+            lines.push(format!("- Paused: {}", secs(self.paused_secs)));
+        }
+        lines.push(format!(
+            "- Attendees: {} of {}",
+            self.attendees.len(),
+            self.speakers.len()
+        ));
+        lines.extend([
+            String::new(),
+            "| # | Name | Spoke | Budget | Overrun |".to_string(),
+            "|---|------|-------|--------|---------|".to_string(),
+        ]);
+        for (i, speaker) in self.speakers.iter().enumerate() {
+            lines.push(format!(
+                "| {} | {} | {} | {} | {} |",
+                i + 1,
+                speaker.name,
+                if speaker.attended {
+                    secs(speaker.spoken_secs)
+                } else {
+                    "absent".to_string()
+                },
+                secs(speaker.budget_secs),
+                if speaker.overrun_secs > 0 {
+                    secs(speaker.overrun_secs)
+                } else {
+                    "–".to_string()
+                }
+            ));
+        }
+
+        let overruns: Vec<&SpeakerReport> = self
+            .speakers
+            .iter()
+            .filter(|s| s.overrun_secs > 0)
+            .collect();
+        if !overruns.is_empty() {
+            lines.extend([String::new(), "## Overruns".to_string(), String::new()]);
+            lines.extend(
+                overruns
+                    .iter()
+                    .map(|s| format!("- {}: {} past their slot", s.name, secs(s.overrun_secs))),
+            );
+        }
+        lines.join("\n")
+    }
+
+    fn csv_rows(&self) -> Vec<Vec<String>> {
+        self.speakers
+            .iter()
+            .enumerate()
+            .map(|(i, speaker)| {
+                vec![
+                    self.date.clone(),
+                    self.title.clone(),
+                    (i + 1).to_string(),
+                    speaker.name.clone(),
+                    speaker.attended.to_string(),
+                    speaker.spoken_secs.to_string(),
+                    speaker.budget_secs.to_string(),
+                    speaker.overrun_secs.to_string(),
+                    self.elapsed_secs.to_string(),
+                    self.overtime_secs.to_string(),
+                ]
+            })
+            .collect()
+    }
+}
+
+/// Keep the summary of the meeting for `nextup report`, and write it to `--report-path` if one
+/// was given, when it ends
+pub fn write(config: &Config, meeting: &Meeting) -> Result<()> {
+    let report = Report::new(config, meeting);
+    // whoever runs the meeting keeps its report
+    if !meeting.is_read_only() && !meeting.is_taken_over() {
+        report.save(&config.series)?;
+    }
+
+    let Some(path) = &config.report_path else {
+        return Ok(());
+    };
+    let format = config
+        .report_format
+        .unwrap_or_else(|| ReportFormat::for_path(path));
+    fs::write(path, report.render(format)?)?;
+    println!("Report saved to {}", path.display());
+    Ok(())
+}
+
+/// Take anyone in `who` out of the series' latest report, for `nextup privacy purge`; false when
+/// they weren't in it
+pub fn forget(series: &Series, who: &[String]) -> Result<bool> {
+    let Some(mut report) = Report::load(series)? else {
+        return Ok(false);
+    };
+    let before = report.speakers.len();
+    report.speakers.retain(|s| !who.contains(&s.name));
+    report.attendees.retain(|name| !who.contains(name));
+    if report.speakers.len() == before {
+        return Ok(false);
+    }
+    report.save(series)?;
+    Ok(true)
+}
+
+/// Print the series' latest report again, or save it to `file`, for `nextup report`
+pub fn print(config: &Config, format: Option<ReportFormat>, file: Option<&Path>) -> Result<()> {
+    let Some(mut report) = Report::load(&config.series)? else {
+        println!("No meeting of {} has been run yet.", config.series.id());
+        return Ok(());
+    };
+    // hashed names are put back for everyone on the team
+    if let Some(pseudonyms) = Pseudonyms::load(&config.series)?
+        && let Ok(members) = sources::load_members(config)
+    {
+        let names: HashMap<String, String> = members
+            .iter()
+            .map(|member| (pseudonyms.id(&member.name), member.name.clone()))
+            .collect();
+        report.rename(|name| names.get(name).cloned().unwrap_or_else(|| name.to_string()));
+    }
+
+    let format = format
+        .or(config.report_format)
+        .unwrap_or_else(|| file.map_or(ReportFormat::Markdown, ReportFormat::for_path));
+    let text = report.render(format)?;
+    match file {
+        Some(path) => {
+            fs::write(path, text)?;
+            println!("Saved to {}", path.display());
+        }
+        None => export::to_stdout(&text)?,
+    }
+    Ok(())
+}
@@ -0,0 +1,150 @@
+use chrono::{Days, Local, Months, NaiveDate};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::assign::LOAD_WINDOW_DAYS;
+use crate::config::Config;
+use crate::error::Result;
+use crate::export;
+use crate::history::History;
+use crate::sources;
+use crate::sync;
+
+/// How long a series' history is kept, e.g. `6mo`, `26w`, `180d` or `1y`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Retention {
+    Days(u64),
+    Months(u32),
+}
+
+impl Retention {
+    /// The first day still kept, as of `today`
+    pub fn cutoff(self, today: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Days(days) => today.checked_sub_days(Days::new(days)),
+            Self::Months(months) => today.checked_sub_months(Months::new(months)),
+        }
+        .unwrap_or(NaiveDate::MIN)
+    }
+}
+
+impl FromStr for Retention {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "invalid retention `{}`, expected e.g. 180d, 26w, 6mo or 1y",
+                s
+            )
+        };
+
+        let s = s.trim();
+        let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let (count, unit) = s.split_at(split);
+        let count: u32 = count.parse().map_err(|_| invalid())?;
+        match unit {
+            "d" => Ok(Self::Days(count.into())),
+            "w" => Ok(Self::Days(u64::from(count) * 7)),
+            "mo" => Ok(Self::Months(count)),
+            "y" => Ok(Self::Months(count * 12)),
+            _ => Err(invalid()),
+        }
+        .and_then(|retention| match retention {
+            Self::Days(0) | Self::Months(0) => Err(invalid()),
+            retention => Ok(retention),
+        })
+    }
+}
+
+/// Forget everything in the history older than `older_than`, or the `--retention` setting, for
+/// `nextup history prune`
+pub fn prune(config: &Config, older_than: Option<Retention>, dry_run: bool) -> Result<()> {
+    let retention = older_than.or(config.retention).ok_or_else(|| {
+        anyhow::Error::msg("say how much history to keep with --older-than or --retention")
+    })?;
+
+    let mut history = History::load(&config.series)?;
+    let cutoff = retention.cutoff(Local::now().date_naive());
+    let pruned = history.prune(cutoff);
+    if pruned == 0 {
+        println!("Nothing in the history is from before {}.", cutoff);
+        return Ok(());
+    }
+    if dry_run {
+        println!("Would forget {} from before {}.", entries(pruned), cutoff);
+        return Ok(());
+    }
+
+    save(config, &history, "history prune")?;
+    println!("Forgot {} from before {}.", entries(pruned), cutoff);
+    Ok(())
+}
+
+/// Drop what nothing reads from the history any more, for `nextup history compact`
+pub fn compact(config: &Config) -> Result<()> {
+    let members = sources::load_members(config)?;
+    let mut history = History::load(&config.series)?;
+    history.identify(&members);
+    let size = history.to_json()?.len();
+
+    let since = Local::now().date_naive() - chrono::Duration::days(LOAD_WINDOW_DAYS);
+    let dropped = history.compact(&members, since);
+    if dropped == 0 {
+        println!("The history is already compact.");
+        return Ok(());
+    }
+
+    save(config, &history, "history compact")?;
+    println!(
+        "Dropped {}; the history went from {} to {}.",
+        entries(dropped),
+        kilobytes(size),
+        kilobytes(history.to_json()?.len())
+    );
+    Ok(())
+}
+
+/// Write the history as JSON, with everyone's name replaced when asked, for
+/// `nextup history export`
+pub fn export(config: &Config, file: Option<&Path>, anonymize: bool) -> Result<()> {
+    let mut history = History::load(&config.series)?;
+    if anonymize {
+        history.anonymize();
+    }
+    // hashed names are what's exported, and nothing anyone wrote
+    if history.is_private() {
+        history.omit_notes();
+    }
+
+    let json = history.to_json()?;
+    match file {
+        Some(path) => {
+            fs::write(path, json + "\n")?;
+            println!("Saved to {}", path.display());
+        }
+        None => export::to_stdout(&(json + "\n"))?,
+    }
+    Ok(())
+}
+
+fn save(config: &Config, history: &History, what: &str) -> Result<()> {
+    history.save()?;
+
+    // share the updated history when the state directory is a git checkout
+    if let Some(root) = config.series.root()
+        && let Err(e) = sync::push(root, &format!("nextup: {} {}", config.series.id(), what))
+    {
+        eprintln!("{}", e);
+    }
+    Ok(())
+}
+
+fn entries(count: usize) -> String {
+    format!("{} {}", count, if count == 1 { "entry" } else { "entries" })
+}
+
+fn kilobytes(bytes: usize) -> String {
+    format!("{:.1} KB", bytes as f64 / 1024.0)
+}
@@ -0,0 +1,509 @@
+use chrono::{Datelike, NaiveDate};
+use rand::seq::IndexedRandom;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::time::Duration;
+
+use crate::error::{AppError, Result};
+
+// Embed the default team.txt file at compile time
+const DEFAULT_TEAM_CONTENT: &str = include_str!("../team.txt");
+
+/// A team member and their optional metadata
+///
+/// Each line of the team file is a name, optionally followed by `|`-separated
+/// `key=value` fields, e.g. `Kyle Broflovski | birthday=05-26 | start=2021-03-01 | role=lead`.
+/// A `[Team]` heading puts the members below it in that team.
+///
+/// The name is the one people go by and the one shown everywhere. Integrations can know
+/// someone by other identities: `id=` (stable across renames), `github=`, `slack=`, `email=`
+/// and `aka=` (comma-separated, e.g. a legal name), all of which find the same member.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Member {
+    pub name: String,
+    // canonical identity; the name unless given
+    pub id: String,
+    // (month, day); the year is not needed
+    pub birthday: Option<(u32, u32)>,
+    pub start_date: Option<NaiveDate>,
+    pub role: Option<String>,
+    // speaking slot per round, from `slot=` or the member's role
+    pub slot: Option<Duration>,
+    // where to reach them privately: a Slack member ID and an email address
+    pub slack_id: Option<String>,
+    pub email: Option<String>,
+    pub github: Option<String>,
+    // an image shown next to their name with `--avatars`, relative to the team file
+    pub avatar: Option<String>,
+    // other names they're known by
+    pub aliases: Vec<String>,
+    // the team they're on, for a scrum of scrums
+    pub team: Option<String>,
+    // `active=no` keeps someone in the file without them taking part
+    pub active: bool,
+    // here for one meeting with `--guest`, and left out of everything the series keeps
+    pub guest: bool,
+}
+
+/// Something worth celebrating today
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Celebration {
+    Birthday,
+    Anniversary { years: i32 },
+}
+
+impl Member {
+    pub fn new(name: impl Into<String>) -> Self {
+        let name = name.into();
+        Self {
+            id: name.clone(),
+            name,
+            birthday: None,
+            start_date: None,
+            role: None,
+            slot: None,
+            slack_id: None,
+            email: None,
+            github: None,
+            avatar: None,
+            aliases: Vec::new(),
+            team: None,
+            active: true,
+            guest: false,
+        }
+    }
+
+    /// Someone joining this meeting only, with `--guest`
+    pub fn guest(name: impl Into<String>) -> Self {
+        Self {
+            guest: true,
+            ..Self::new(name)
+        }
+    }
+
+    /// Parse one line of the team file
+    fn parse(line: &str, filename: &str, line_number: usize) -> Result<Self> {
+        let mut fields = line.split('|').map(str::trim);
+        let mut member = Self::new(fields.next().unwrap_or_default());
+
+        for field in fields.filter(|f| !f.is_empty()) {
+            let invalid = |reason: &str| AppError::RosterError {
+                path: filename.to_string(),
+                line: line_number,
+                message: format!("{} in `{}`", reason, field),
+            };
+
+            let (key, value) = field
+                .split_once('=')
+                .ok_or_else(|| invalid("expected key=value"))?;
+            member.set(key.trim(), value.trim()).map_err(invalid)?;
+        }
+
+        Ok(member)
+    }
+
+    /// Set one of the team file's `key=value` fields, or say what's wrong with it
+    fn set(&mut self, key: &str, value: &str) -> std::result::Result<(), &'static str> {
+        match key {
+            "birthday" => self.birthday = Some(parse_month_day(value).ok_or("invalid date")?),
+            "start" => {
+                self.start_date =
+                    Some(NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| "invalid date")?)
+            }
+            "role" => self.role = Some(value.to_string()),
+            "slot" => self.slot = Some(parse_slot(value).ok_or("invalid slot")?),
+            "slack" => self.slack_id = Some(value.to_string()),
+            "email" => self.email = Some(value.to_string()),
+            "id" => self.id = value.to_string(),
+            "github" => self.github = Some(value.trim_start_matches('@').to_string()),
+            "avatar" => self.avatar = Some(value.to_string()),
+            "aka" => self.aliases.extend(
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|alias| !alias.is_empty())
+                    .map(str::to_string),
+            ),
+            "active" => {
+                self.active = match value {
+                    "yes" | "true" => true,
+                    "no" | "false" => false,
+                    _ => return Err("expected yes or no"),
+                }
+            }
+            _ => return Err("unknown field"),
+        }
+        Ok(())
+    }
+
+    /// Whether an identity from an integration (a name, alias, id, handle or address) is
+    /// this member; case doesn't matter, nor a leading `@`
+    pub fn answers_to(&self, identity: &str) -> bool {
+        let identity = identity.trim().trim_start_matches('@');
+        std::iter::once(&self.name)
+            .chain(std::iter::once(&self.id))
+            .chain(&self.aliases)
+            .chain(&self.github)
+            .chain(&self.slack_id)
+            .chain(&self.email)
+            .any(|known| known.eq_ignore_ascii_case(identity))
+    }
+
+    /// What this person is celebrating on the given day, if anything
+    pub fn celebration(&self, today: NaiveDate) -> Option<Celebration> {
+        if let Some((month, day)) = self.birthday
+            && falls_on(today, month, day)
+        {
+            return Some(Celebration::Birthday);
+        }
+
+        if let Some(start) = self.start_date
+            && falls_on(today, start.month(), start.day())
+        {
+            let years = today.year() - start.year();
+            if years > 0 {
+                return Some(Celebration::Anniversary { years });
+            }
+        }
+
+        None
+    }
+}
+
+impl Celebration {
+    /// Short message for banners and chat posts
+    pub fn message(&self, name: &str) -> String {
+        match self {
+            Celebration::Birthday => format!("🎂 Happy birthday, {}!", name),
+            Celebration::Anniversary { years: 1 } => {
+                format!("🎉 {} has been on the team for a year!", name)
+            }
+            Celebration::Anniversary { years } => {
+                format!("🎉 {} has been on the team for {} years!", name, years)
+            }
+        }
+    }
+}
+
+/// Parse `MM-DD` or `YYYY-MM-DD` into (month, day)
+fn parse_month_day(value: &str) -> Option<(u32, u32)> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Some((date.month(), date.day()));
+    }
+
+    let (month, day) = value.split_once('-')?;
+    let (month, day) = (month.parse().ok()?, day.parse().ok()?);
+
+    // validate against a leap year so Feb 29 is accepted
+    NaiveDate::from_ymd_opt(2000, month, day).map(|_| (month, day))
+}
+
+/// Whether a yearly date lands on `today`; Feb 29 is observed on Feb 28 in other years
+fn falls_on(today: NaiveDate, month: u32, day: u32) -> bool {
+    if month == 2 && day == 29 && !today.leap_year() {
+        return today.month() == 2 && today.day() == 28;
+    }
+    today.month() == month && today.day() == day
+}
+
+/// Parse a slot length such as `3m`, `90s` or `2m30s`; a bare number is minutes
+pub fn parse_slot(value: &str) -> Option<Duration> {
+    if let Ok(minutes) = value.parse::<u64>() {
+        return Some(Duration::from_secs(minutes * 60));
+    }
+
+    let (minutes, seconds) = match value.split_once('m') {
+        Some((minutes, rest)) => (minutes, rest.strip_suffix('s').unwrap_or(rest)),
+        None => ("0", value.strip_suffix('s')?),
+    };
+    let minutes: u64 = minutes.parse().ok()?;
+    let seconds: u64 = if seconds.is_empty() {
+        0
+    } else {
+        seconds.parse().ok()?
+    };
+
+    let slot = Duration::from_secs(minutes * 60 + seconds);
+    (!slot.is_zero()).then_some(slot)
+}
+
+/// Parse a role line such as `@role lead=3m` into the role name and its slot length
+fn parse_role(line: &str, filename: &str, line_number: usize) -> Result<(String, Duration)> {
+    let invalid = || AppError::RosterError {
+        path: filename.to_string(),
+        line: line_number,
+        message: format!("expected `@role name=slot` in `{}`", line),
+    };
+
+    let (role, slot) = line
+        .trim_start_matches("@role")
+        .split_once('=')
+        .ok_or_else(invalid)?;
+    let slot = parse_slot(slot.trim()).ok_or_else(invalid)?;
+
+    Ok((role.trim().to_string(), slot))
+}
+
+/// Load members from a file, falling back to embedded default if file not found
+pub fn load_members(filename: &str) -> Result<Vec<Member>> {
+    parse_members(&read(filename)?, filename)
+}
+
+/// The team file's contents, or the embedded default team for a missing `team.txt`
+pub fn read(filename: &str) -> Result<String> {
+    // Try to read from file first
+    let content = match fs::read_to_string(filename) {
+        Ok(content) => content,
+        Err(e) => {
+            // If the file doesn't exist, and we're using the default filename,
+            // fall back to the embedded content
+            if filename == "team.txt" && e.kind() == io::ErrorKind::NotFound {
+                DEFAULT_TEAM_CONTENT.to_string()
+            } else {
+                // For other errors or custom filenames, propagate the error
+                return Err(AppError::NamesFileError {
+                    path: filename.to_string(),
+                    source: e,
+                }
+                .into());
+            }
+        }
+    };
+
+    Ok(content)
+}
+
+/// Members from the contents of a team file; `filename` is for error messages, and a name
+/// ending in `.json` says it's a [`TeamFile`] rather than the text format
+pub fn parse_members(content: &str, filename: &str) -> Result<Vec<Member>> {
+    let (members, role_slots) = if filename.ends_with(".json") {
+        parse_json(content, filename)?
+    } else {
+        parse_text(content, filename)?
+    };
+    let mut members: Vec<Member> = members.into_iter().filter(|m| m.active).collect();
+
+    if members.is_empty() {
+        return Err(AppError::NoNamesFound.into());
+    }
+
+    // an explicit slot wins over the member's role
+    for member in &mut members {
+        if member.slot.is_none() {
+            member.slot = member
+                .role
+                .as_ref()
+                .and_then(|role| role_slots.get(role).copied());
+        }
+    }
+
+    Ok(members)
+}
+
+/// Everyone in a text team file, active or not, and the slot lengths its `@role` lines set
+fn parse_text(content: &str, filename: &str) -> Result<(Vec<Member>, HashMap<String, Duration>)> {
+    // blank lines and `#` comments are skipped; `@role` lines set slot lengths per role,
+    // and `[Team]` headings group the members that follow
+    let mut members = Vec::new();
+    let mut role_slots = HashMap::new();
+    let mut team = None;
+    // integrations match people by id, so each one has to pick out one person
+    let mut ids: HashMap<String, String> = HashMap::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line.starts_with("@role") {
+            let (role, slot) = parse_role(line, filename, i + 1)?;
+            role_slots.insert(role, slot);
+        } else if let Some(heading) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            // an empty `[]` ends the last team
+            team = Some(heading.trim().to_string()).filter(|t| !t.is_empty());
+        } else {
+            let mut member = Member::parse(line, filename, i + 1)?;
+            if let Some(other) = ids.insert(member.id.clone(), member.name.clone()) {
+                return Err(AppError::RosterError {
+                    path: filename.to_string(),
+                    line: i + 1,
+                    message: format!("id `{}` is already {}'s", member.id, other),
+                }
+                .into());
+            }
+            member.team = team.clone();
+            members.push(member);
+        }
+    }
+
+    Ok((members, role_slots))
+}
+
+/// A team file in JSON, e.g. `team.json`: the text format's fields, one object per person
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TeamFile {
+    /// The schema editors check the file against, from `nextup schema team`
+    #[serde(default, rename = "$schema")]
+    #[allow(dead_code)]
+    pub schema: Option<String>,
+    /// Speaking slot lengths per role, e.g. `{"lead": "3m"}`
+    #[serde(default)]
+    pub roles: BTreeMap<String, String>,
+    pub members: Vec<TeamEntry>,
+}
+
+/// One person in a JSON team file
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TeamEntry {
+    /// The name they go by, shown everywhere
+    pub name: String,
+    /// A canonical ID that stays the same if they change their name (default: the name)
+    pub id: Option<String>,
+    /// `MM-DD` or `YYYY-MM-DD`
+    pub birthday: Option<String>,
+    /// The day they joined the team, `YYYY-MM-DD`
+    pub start: Option<String>,
+    pub role: Option<String>,
+    /// Their speaking slot per round, e.g. `90s` or `2m30s`; a bare number is minutes
+    pub slot: Option<String>,
+    /// Their Slack member ID
+    pub slack: Option<String>,
+    pub email: Option<String>,
+    /// Their GitHub handle
+    pub github: Option<String>,
+    /// An image shown next to their name with `--avatars`, relative to the team file
+    pub avatar: Option<String>,
+    /// Other names they're known by, e.g. a legal name
+    #[serde(default)]
+    pub aka: Vec<String>,
+    /// The team they're on, for a scrum of scrums
+    pub team: Option<String>,
+    /// `false` keeps them in the file without them taking part
+    pub active: Option<bool>,
+}
+
+/// Everyone in a JSON team file, active or not, and its slot lengths per role
+fn parse_json(content: &str, filename: &str) -> Result<(Vec<Member>, HashMap<String, Duration>)> {
+    let invalid = |message: String| AppError::TeamFileError {
+        path: filename.to_string(),
+        message,
+    };
+    let file: TeamFile = serde_json::from_str(content).map_err(|e| invalid(e.to_string()))?;
+
+    let mut role_slots = HashMap::new();
+    for (role, slot) in &file.roles {
+        let slot = parse_slot(slot.trim())
+            .ok_or_else(|| invalid(format!("invalid slot `{}` for role {}", slot, role)))?;
+        role_slots.insert(role.clone(), slot);
+    }
+
+    let mut members = Vec::new();
+    let mut ids: HashMap<String, String> = HashMap::new();
+    for entry in file.members {
+        let mut member = Member::new(entry.name.trim());
+        let fields = [
+            ("id", entry.id),
+            ("birthday", entry.birthday),
+            ("start", entry.start),
+            ("role", entry.role),
+            ("slot", entry.slot),
+            ("slack", entry.slack),
+            ("email", entry.email),
+            ("github", entry.github),
+            ("avatar", entry.avatar),
+        ];
+        for (key, value) in fields {
+            if let Some(value) = value {
+                member.set(key, value.trim()).map_err(|reason| {
+                    invalid(format!("{} in {}'s `{}`", reason, member.name, key))
+                })?;
+            }
+        }
+        member.aliases.extend(entry.aka);
+        member.team = entry.team;
+        member.active = entry.active.unwrap_or(true);
+
+        if let Some(other) = ids.insert(member.id.clone(), member.name.clone()) {
+            return Err(invalid(format!("id `{}` is already {}'s", member.id, other)).into());
+        }
+        members.push(member);
+    }
+
+    Ok((members, role_slots))
+}
+
+/// The member an identity from an integration belongs to: whoever goes by that name, otherwise
+/// whoever answers to it
+pub fn find<'a, I>(members: I, identity: &str) -> Option<&'a Member>
+where
+    I: IntoIterator<Item = &'a Member>,
+    I::IntoIter: Clone,
+{
+    let members = members.into_iter();
+    members
+        .clone()
+        .find(|m| m.name == identity)
+        .or_else(|| members.clone().find(|m| m.answers_to(identity)))
+}
+
+/// One randomly picked member per team, preferring those who are `available`, in roster order.
+/// Members without a team all stay.
+pub fn representatives(members: Vec<Member>, available: impl Fn(&Member) -> bool) -> Vec<Member> {
+    let mut picked: HashMap<String, String> = HashMap::new();
+    for member in &members {
+        let Some(team) = &member.team else {
+            continue;
+        };
+        if picked.contains_key(team) {
+            continue;
+        }
+
+        let team_members: Vec<&Member> = members
+            .iter()
+            .filter(|m| m.team.as_ref() == Some(team))
+            .collect();
+        let candidates: Vec<&Member> = team_members
+            .iter()
+            .copied()
+            .filter(|m| available(m))
+            .collect();
+        let pool = if candidates.is_empty() {
+            &team_members
+        } else {
+            &candidates
+        };
+        if let Some(representative) = pool.choose(&mut rand::rng()) {
+            picked.insert(team.clone(), representative.name.clone());
+        }
+    }
+
+    members
+        .into_iter()
+        .filter(|m| match &m.team {
+            Some(team) => picked.get(team) == Some(&m.name),
+            None => true,
+        })
+        .collect()
+}
+
+/// How many members each team has
+pub fn team_sizes(members: &[Member]) -> HashMap<String, usize> {
+    let mut sizes = HashMap::new();
+    for team in members.iter().filter_map(|m| m.team.clone()) {
+        *sizes.entry(team).or_insert(0) += 1;
+    }
+    sizes
+}
+
+/// Today's celebrations across the team
+pub fn celebrations(members: &[Member], today: NaiveDate) -> Vec<(String, Celebration)> {
+    members
+        .iter()
+        .filter_map(|m| m.celebration(today).map(|c| (m.name.clone(), c)))
+        .collect()
+}
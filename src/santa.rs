@@ -0,0 +1,207 @@
+use minijinja::context;
+use rand::seq::SliceRandom;
+use std::collections::HashSet;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::roster::{self, Member};
+use crate::slack::SlackClient;
+use crate::sources;
+use crate::templates::Templates;
+
+// Subject of the emails and heading of the printed list
+const SUBJECT: &str = "Secret Santa";
+
+/// Two people who mustn't draw each other, e.g. `Stan Marsh=Wendy Testaburger`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exclusion(pub String, pub String);
+
+impl FromStr for Exclusion {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.split_once('=') {
+            Some((a, b)) if !a.trim().is_empty() && !b.trim().is_empty() => {
+                Ok(Self(a.trim().to_string(), b.trim().to_string()))
+            }
+            _ => Err(format!("invalid exclusion `{}`, expected e.g. Ann=Bob", s)),
+        }
+    }
+}
+
+impl Exclusion {
+    fn forbids(&self, giver: &str, receiver: &str) -> bool {
+        (self.0 == giver && self.1 == receiver) || (self.0 == receiver && self.1 == giver)
+    }
+}
+
+/// How each person learns who they drew
+pub enum Delivery {
+    // print the whole list, for whoever is organizing
+    Print,
+    // a Slack DM to each person's `slack=` member ID
+    Slack(SlackClient),
+    // an email to each person's `email=` address, sent through the local `sendmail`
+    Email { from: String },
+}
+
+impl Delivery {
+    /// Make sure everyone can be reached before anything is sent
+    fn check(&self, members: &[Member]) -> Result<()> {
+        let missing: Vec<&str> = members
+            .iter()
+            .filter(|m| match self {
+                Delivery::Print => false,
+                Delivery::Slack(_) => m.slack_id.is_none(),
+                Delivery::Email { .. } => m.email.is_none(),
+            })
+            .map(|m| m.name.as_str())
+            .collect();
+
+        if missing.is_empty() {
+            return Ok(());
+        }
+        let field = match self {
+            Delivery::Slack(_) => "slack=",
+            _ => "email=",
+        };
+        Err(AppError::AssignmentError(format!(
+            "no {} in the team file for {}",
+            field,
+            missing.join(", ")
+        ))
+        .into())
+    }
+
+    /// Tell `giver` who they drew, and nobody else
+    fn send(&self, templates: &Templates, giver: &Member, receiver: &Member) -> Result<()> {
+        let text = templates.render(
+            "santa.txt",
+            context! { subject => SUBJECT, giver => giver.name, receiver => receiver.name },
+        )?;
+        match self {
+            Delivery::Print => println!("  {} → {}", giver.name, receiver.name),
+            Delivery::Slack(client) => {
+                // posting to a member ID lands in the bot's DM with them
+                client.post_message(giver.slack_id.as_deref().unwrap_or_default(), &text, None)?;
+            }
+            Delivery::Email { from } => {
+                let to = giver.email.as_deref().unwrap_or_default();
+                sendmail(from, to, SUBJECT, &text)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Draw names for a gift exchange and let everyone know who they're buying for
+pub fn run(config: &Config, exclusions: &[Exclusion], delivery: &Delivery) -> Result<()> {
+    let members = sources::load_members(config)?;
+
+    // exclusions can name people by any identity they have
+    let preferred = |name: &str| {
+        roster::find(&members, name)
+            .map(|m| m.name.clone())
+            .ok_or_else(|| AppError::AssignmentError(format!("`{}` isn't in the team file", name)))
+    };
+    let exclusions = exclusions
+        .iter()
+        .map(|Exclusion(a, b)| Ok(Exclusion(preferred(a)?, preferred(b)?)))
+        .collect::<Result<Vec<_>>>()?;
+    if members.len() < 2 {
+        return Err(AppError::AssignmentError("it takes at least two people".to_string()).into());
+    }
+    delivery.check(&members)?;
+    let templates = Templates::load(config)?;
+
+    let draw = draw(&members, &exclusions).ok_or_else(|| {
+        AppError::AssignmentError("the exclusions leave no way to draw names".to_string())
+    })?;
+
+    if let Delivery::Print = delivery {
+        println!("{}\n", SUBJECT);
+    }
+    for (giver, receiver) in members.iter().zip(&draw) {
+        delivery.send(&templates, giver, &members[*receiver])?;
+    }
+    if !matches!(delivery, Delivery::Print) {
+        println!("Sent {} people who they drew.", members.len());
+    }
+
+    Ok(())
+}
+
+/// Who each member gives to, by index: nobody draws themselves or someone they're excluded from.
+/// None when the exclusions make that impossible.
+fn draw(members: &[Member], exclusions: &[Exclusion]) -> Option<Vec<usize>> {
+    let allowed = |giver: usize, receiver: usize| {
+        giver != receiver
+            && !exclusions
+                .iter()
+                .any(|e| e.forbids(&members[giver].name, &members[receiver].name))
+    };
+
+    let mut draw = Vec::with_capacity(members.len());
+    let mut taken = HashSet::new();
+    search(members.len(), &allowed, &mut draw, &mut taken).then_some(draw)
+}
+
+/// Give the next giver a random receiver that's still free, backtracking when someone is left
+/// with nobody, so a valid draw is found whenever there is one
+fn search(
+    n: usize,
+    allowed: &impl Fn(usize, usize) -> bool,
+    draw: &mut Vec<usize>,
+    taken: &mut HashSet<usize>,
+) -> bool {
+    let giver = draw.len();
+    if giver == n {
+        return true;
+    }
+
+    let mut candidates: Vec<usize> = (0..n).collect();
+    candidates.shuffle(&mut rand::rng());
+    for receiver in candidates {
+        if taken.contains(&receiver) || !allowed(giver, receiver) {
+            continue;
+        }
+        draw.push(receiver);
+        taken.insert(receiver);
+        if search(n, allowed, draw, taken) {
+            return true;
+        }
+        draw.pop();
+        taken.remove(&receiver);
+    }
+    false
+}
+
+/// Hand a plain-text message to the system's `sendmail`
+fn sendmail(from: &str, to: &str, subject: &str, body: &str) -> Result<()> {
+    let mut child = Command::new("sendmail")
+        .arg("-t")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .map_err(|e| AppError::MailError(format!("sendmail: {}", e)))?;
+
+    // dropping stdin at the end of the block closes it, so sendmail sees the end of the message
+    if let Some(mut stdin) = child.stdin.take() {
+        write!(
+            stdin,
+            "From: {}\nTo: {}\nSubject: {}\nContent-Type: text/plain; charset=utf-8\n\n{}\n",
+            from, to, subject, body
+        )?;
+    }
+
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(
+            AppError::MailError(format!("sendmail exited with {} for {}", status, to)).into(),
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use schemars::schema_for;
+use std::str::FromStr;
+
+use crate::error::Result;
+use crate::export;
+use crate::history::History;
+use crate::roster::TeamFile;
+use crate::series::Template;
+
+/// Which of nextup's files `nextup schema` describes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SchemaKind {
+    // a series' saved settings, `template.json`
+    Config,
+    // a JSON team file, e.g. `team.json`
+    Team,
+    // a series' history, `history.json` in the state directory
+    State,
+}
+
+impl FromStr for SchemaKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "config" => Ok(Self::Config),
+            "team" => Ok(Self::Team),
+            "state" => Ok(Self::State),
+            _ => Err(format!(
+                "unknown schema `{}`, expected config, team or state",
+                s
+            )),
+        }
+    }
+}
+
+/// Print the JSON Schema of one of nextup's files, generated from the types it's read into, for
+/// editors to complete and check the file with
+pub fn print(kind: SchemaKind) -> Result<()> {
+    let schema = match kind {
+        SchemaKind::Config => schema_for!(Template),
+        SchemaKind::Team => schema_for!(TeamFile),
+        SchemaKind::State => schema_for!(History),
+    };
+    export::to_stdout(&(serde_json::to_string_pretty(&schema)? + "\n"))
+}
@@ -0,0 +1,159 @@
+use chrono::NaiveDate;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::{AppError, Result};
+
+/// Root directory for nextup's persistent state (e.g. `~/.local/state/nextup`)
+pub fn state_root() -> Option<PathBuf> {
+    dirs::state_dir()
+        .or_else(dirs::data_local_dir)
+        .map(|dir| dir.join("nextup"))
+}
+
+/// A recurring meeting with its own history, rotation, and template
+#[derive(Debug, Clone, PartialEq)]
+pub struct Series {
+    id: String,
+    root: Option<PathBuf>,
+}
+
+/// Saved settings a series starts from when no flag overrides them
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct Template {
+    pub title: Option<String>,
+    pub names_file: Option<String>,
+    pub duration_minutes: Option<u64>,
+    // e.g. "09:30-09:45"
+    #[serde(default)]
+    pub window: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rounds: Vec<String>,
+    // e.g. "azerty"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keyboard: Option<String>,
+    // a built-in theme name or a theme file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timer_theme: Option<String>,
+    // e.g. "Alice before Bob"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub order_rules: Vec<String>,
+    // e.g. "2m=theme:urgent"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub escalation: Vec<String>,
+    // the day sprint 1 started, and how many weeks a sprint lasts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sprint_epoch: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sprint_weeks: Option<u32>,
+}
+
+impl Template {
+    /// These settings, with any left out taken from `fallback`
+    pub fn or(self, fallback: Template) -> Template {
+        let or_list = |list: Vec<String>, fallback: Vec<String>| {
+            if list.is_empty() { fallback } else { list }
+        };
+        Template {
+            title: self.title.or(fallback.title),
+            names_file: self.names_file.or(fallback.names_file),
+            duration_minutes: self.duration_minutes.or(fallback.duration_minutes),
+            window: self.window.or(fallback.window),
+            rounds: or_list(self.rounds, fallback.rounds),
+            keyboard: self.keyboard.or(fallback.keyboard),
+            timer_theme: self.timer_theme.or(fallback.timer_theme),
+            order_rules: or_list(self.order_rules, fallback.order_rules),
+            escalation: or_list(self.escalation, fallback.escalation),
+            sprint_epoch: self.sprint_epoch.or(fallback.sprint_epoch),
+            sprint_weeks: self.sprint_weeks.or(fallback.sprint_weeks),
+        }
+    }
+}
+
+impl Series {
+    /// Create a series from a name such as "Backend standup" (ID `backend-standup`)
+    pub fn new(name: &str) -> Self {
+        let mut id = String::new();
+        for c in name.trim().chars() {
+            if c.is_alphanumeric() {
+                id.extend(c.to_lowercase());
+            } else if !id.ends_with('-') {
+                id.push('-');
+            }
+        }
+
+        let id = id.trim_matches('-');
+        Self {
+            id: if id.is_empty() { "default" } else { id }.to_string(),
+            root: state_root(),
+        }
+    }
+
+    /// Keep state under a different root, such as a shared git checkout
+    pub fn in_dir(mut self, root: PathBuf) -> Self {
+        self.root = Some(root);
+        self
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Root directory shared by all series
+    pub fn root(&self) -> Option<&Path> {
+        self.root.as_deref()
+    }
+
+    /// Directory holding this series' state
+    pub fn dir(&self) -> Option<PathBuf> {
+        self.root
+            .as_ref()
+            .map(|root| root.join("series").join(&self.id))
+    }
+
+    /// Whether a template was saved, even one with every setting left out
+    pub fn has_template(&self) -> bool {
+        self.template_path().is_some_and(|path| path.exists())
+    }
+
+    fn template_path(&self) -> Option<PathBuf> {
+        self.dir().map(|dir| dir.join("template.json"))
+    }
+
+    /// Load the series template, if one was saved
+    pub fn load_template(&self) -> Result<Template> {
+        let Some(path) = self.template_path() else {
+            return Ok(Template::default());
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).map_err(|e| {
+                // the location is reported separately
+                let location = format!(" at line {} column {}", e.line(), e.column());
+                AppError::ConfigError {
+                    line: e.line(),
+                    column: e.column(),
+                    message: e.to_string().trim_end_matches(&location).to_string(),
+                    path,
+                }
+                .into()
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Template::default()),
+            Err(e) => Err(AppError::HistoryError(format!("{}: {}", path.display(), e)).into()),
+        }
+    }
+
+    /// Save the settings future meetings of this series start from
+    pub fn save_template(&self, template: &Template) -> Result<()> {
+        let (Some(dir), Some(path)) = (self.dir(), self.template_path()) else {
+            return Ok(());
+        };
+
+        fs::create_dir_all(&dir)?;
+        fs::write(path, serde_json::to_string_pretty(template)?)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,504 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode, Uri, header},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use include_dir::{Dir, include_dir};
+use qrcode::QrCode;
+use qrcode::render::unicode::Dense1x2;
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, SocketAddr, UdpSocket};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+
+use crate::checkin::{Attendance, CheckIns};
+use crate::command::MeetingCommand;
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::meeting::Meeting;
+use crate::roster::Member;
+use crate::share::{self, Rejection, ShareKey};
+use crate::theme::TimerTheme;
+use crate::ui::gauge_color;
+
+// Web UI bundled into the binary
+static ASSETS: Dir = include_dir!("$CARGO_MANIFEST_DIR/web");
+
+/// What the web view shows, published by the running meeting
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WebState {
+    pub title: String,
+    pub round: Option<String>,
+    pub speakers: Vec<WebSpeaker>,
+    pub current: usize,
+    pub paused: bool,
+    pub remaining_secs: u64,
+    pub duration_secs: u64,
+    // remaining fraction of the meeting, with the terminal gauge's color and icon for it
+    pub progress: f64,
+    pub color: String,
+    pub icon: String,
+    // taking check-ins before the meeting rather than showing one
+    pub check_in: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WebSpeaker {
+    pub name: String,
+    // time spoken in the current round
+    pub secs: u64,
+    pub absent: bool,
+    pub topics: Vec<WebTopic>,
+    // how they checked in, when they won't be speaking live
+    pub attendance: Option<Attendance>,
+    pub update: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WebTopic {
+    pub text: String,
+    pub done: bool,
+}
+
+/// HTTP server with a live view of the meeting for people in the room
+pub struct Server {
+    url: String,
+    qr_code: String,
+    state: watch::Sender<WebState>,
+    commands: mpsc::UnboundedReceiver<MeetingCommand>,
+}
+
+/// What request handlers share
+#[derive(Clone)]
+struct Shared {
+    state: watch::Receiver<WebState>,
+    commands: mpsc::UnboundedSender<MeetingCommand>,
+    // the control API is only enabled with a token
+    api_token: Option<String>,
+    // the web view itself needs a signed link, or the API token
+    share_key: Arc<ShareKey>,
+    // before the meeting the link is enough to check in; once it runs, marking someone out
+    // moves the meeting along, so it takes the API token
+    open_check_ins: bool,
+}
+
+impl Server {
+    /// Start serving in the background, behind a link that works for `share_ttl`; with an API
+    /// token, the meeting can also be driven over HTTP
+    pub async fn start(
+        addr: SocketAddr,
+        api_token: Option<String>,
+        share_ttl: Duration,
+    ) -> Result<Self> {
+        Self::serve(addr, api_token, share_ttl, false).await
+    }
+
+    /// Start serving check-ins before the meeting, for `nextup checkin --serve`: anyone with the
+    /// link can check themselves in
+    pub async fn start_check_ins(
+        addr: SocketAddr,
+        api_token: Option<String>,
+        share_ttl: Duration,
+    ) -> Result<Self> {
+        Self::serve(addr, api_token, share_ttl, true).await
+    }
+
+    async fn serve(
+        addr: SocketAddr,
+        api_token: Option<String>,
+        share_ttl: Duration,
+        open_check_ins: bool,
+    ) -> Result<Self> {
+        let listener = tokio::net::TcpListener::bind(addr)
+            .await
+            .map_err(|e| AppError::ServerError(format!("{}: {}", addr, e)))?;
+        let addr = listener.local_addr()?;
+
+        let (state, receiver) = watch::channel(WebState::default());
+        let (sender, commands) = mpsc::unbounded_channel();
+        let share_key = Arc::new(ShareKey::new(share_ttl));
+        let token = share_key.issue();
+        let shared = Shared {
+            state: receiver,
+            commands: sender,
+            api_token,
+            share_key,
+            open_check_ins,
+        };
+
+        let app = Router::new()
+            .route("/state.json", get(state_json))
+            .route("/next", post(|s, h| command(s, h, MeetingCommand::Next)))
+            .route("/prev", post(|s, h| command(s, h, MeetingCommand::Prev)))
+            .route("/pause", post(pause))
+            .route(
+                "/shuffle",
+                post(|s, h| command(s, h, MeetingCommand::Shuffle)),
+            )
+            .route("/absent/{name}", post(absent))
+            .route("/topics/{name}", post(add_topic))
+            .route("/checkin/{name}", post(check_in))
+            .fallback(asset)
+            .with_state(shared);
+        tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, app).await {
+                eprintln!("Web view stopped: {}", e);
+            }
+        });
+
+        let url = format!("http://{}/?token={}", reachable_addr(addr), token);
+        let qr_code = QrCode::new(url.as_bytes())
+            .map_err(|e| AppError::ServerError(e.to_string()))?
+            .render::<Dense1x2>()
+            // light modules drawn solid so the code reads on a dark background
+            .dark_color(Dense1x2::Light)
+            .light_color(Dense1x2::Dark)
+            .build();
+
+        Ok(Self {
+            url,
+            qr_code,
+            state,
+            commands,
+        })
+    }
+
+    /// Read-only link people in the room can open, until it expires
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The URL as a QR code drawn with half-block characters
+    pub fn qr_code(&self) -> &str {
+        &self.qr_code
+    }
+
+    /// The next command sent through the control API, if any are waiting
+    pub fn next_command(&mut self) -> Option<MeetingCommand> {
+        self.commands.try_recv().ok()
+    }
+
+    /// Share the meeting's current state with the web view, with the timer in `theme`
+    pub fn publish(&self, config: &Config, meeting: &Meeting, theme: &TimerTheme) {
+        let session = &meeting.session;
+        let remaining = session.remaining_time();
+        let progress = if session.duration().is_zero() {
+            0.0
+        } else {
+            remaining.as_secs_f64() / session.duration().as_secs_f64()
+        };
+        let (r, g, b) = gauge_color(progress);
+
+        self.state.send_replace(WebState {
+            title: config.title.clone(),
+            round: session.round_label(),
+            speakers: session
+                .speakers()
+                .iter()
+                .enumerate()
+                .map(|(i, speaker)| {
+                    let check_in = meeting.check_in(speaker.name());
+                    WebSpeaker {
+                        name: speaker.name().to_string(),
+                        secs: speaker.round_times[session.current_round()].as_secs(),
+                        absent: session.is_absent(i),
+                        topics: meeting
+                            .topics_for(speaker.name())
+                            .map(|topic| WebTopic {
+                                text: topic.text.clone(),
+                                done: topic.done,
+                            })
+                            .collect(),
+                        attendance: check_in.map(|c| c.attendance),
+                        update: check_in.and_then(|c| c.update.clone()),
+                    }
+                })
+                .collect(),
+            current: session.current_person_index(),
+            paused: session.is_paused(),
+            remaining_secs: remaining.as_secs(),
+            duration_secs: session.duration().as_secs(),
+            progress,
+            color: format!("#{:02x}{:02x}{:02x}", r, g, b),
+            icon: theme.icon(remaining, session.is_time_up()).to_string(),
+            check_in: false,
+        });
+    }
+
+    /// Share the team and today's check-ins while waiting for the meeting to start
+    pub fn publish_check_ins(&self, config: &Config, members: &[Member], check_ins: &CheckIns) {
+        self.state.send_replace(WebState {
+            title: config.title.clone(),
+            speakers: members
+                .iter()
+                .map(|member| {
+                    let check_in = check_ins.get(&member.name);
+                    WebSpeaker {
+                        name: member.name.clone(),
+                        secs: 0,
+                        absent: check_in.is_some_and(|c| c.attendance == Attendance::Absent),
+                        topics: Vec::new(),
+                        attendance: check_in.map(|c| c.attendance),
+                        update: check_in.and_then(|c| c.update.clone()),
+                    }
+                })
+                .collect(),
+            duration_secs: config.duration.as_secs(),
+            check_in: true,
+            ..WebState::default()
+        });
+    }
+}
+
+/// `?token=` from the share link
+#[derive(Deserialize)]
+struct ViewQuery {
+    token: Option<String>,
+}
+
+async fn state_json(
+    State(shared): State<Shared>,
+    headers: HeaderMap,
+    Query(query): Query<ViewQuery>,
+) -> Response {
+    if let Err(message) = shared.authorize_view(&headers, query.token.as_deref()) {
+        return (StatusCode::UNAUTHORIZED, message).into_response();
+    }
+    Json(shared.state.borrow().clone()).into_response()
+}
+
+/// Queue a command for the meeting
+async fn command(
+    State(shared): State<Shared>,
+    headers: HeaderMap,
+    command: MeetingCommand,
+) -> StatusCode {
+    if let Err(status) = shared.authorize(&headers) {
+        return status;
+    }
+    shared.submit(command)
+}
+
+/// `?reason=` for a pause, e.g. `technical issue`
+#[derive(Deserialize)]
+struct PauseQuery {
+    reason: Option<String>,
+}
+
+/// Pause or resume the meeting, noting why it was paused when a reason is given
+async fn pause(
+    State(shared): State<Shared>,
+    headers: HeaderMap,
+    Query(query): Query<PauseQuery>,
+) -> StatusCode {
+    if let Err(status) = shared.authorize(&headers) {
+        return status;
+    }
+
+    let status = shared.submit(MeetingCommand::Pause);
+    match query.reason.filter(|reason| !reason.trim().is_empty()) {
+        // only sticks if this paused the meeting rather than resuming it
+        Some(reason) if status == StatusCode::ACCEPTED => {
+            shared.submit(MeetingCommand::PauseReason(reason.trim().to_string()))
+        }
+        _ => status,
+    }
+}
+
+/// Mark someone in the meeting as absent
+async fn absent(
+    State(shared): State<Shared>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+) -> StatusCode {
+    if let Err(status) = shared.authorize(&headers) {
+        return status;
+    }
+
+    if !shared.is_speaker(&name) {
+        return StatusCode::NOT_FOUND;
+    }
+    shared.submit(MeetingCommand::Absent(name))
+}
+
+/// Queue a topic, sent as the request body, for someone's turn; anyone with the share link can
+async fn add_topic(
+    State(shared): State<Shared>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+    Query(query): Query<ViewQuery>,
+    text: String,
+) -> StatusCode {
+    if shared
+        .authorize_view(&headers, query.token.as_deref())
+        .is_err()
+    {
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    let text = text.trim();
+    if text.is_empty() {
+        return StatusCode::BAD_REQUEST;
+    }
+    if !shared.is_speaker(&name) {
+        return StatusCode::NOT_FOUND;
+    }
+    shared.submit(MeetingCommand::AddTopic {
+        name,
+        text: text.to_string(),
+    })
+}
+
+/// `?status=` for a check-in: absent, async, or here to take it back
+#[derive(Deserialize)]
+struct CheckInQuery {
+    status: String,
+    // the share link's, so teammates can check themselves in before the meeting
+    token: Option<String>,
+}
+
+/// Check someone in as absent or async for today, with any written update as the request body
+async fn check_in(
+    State(shared): State<Shared>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+    Query(query): Query<CheckInQuery>,
+    update: String,
+) -> StatusCode {
+    let authorized = if shared.open_check_ins {
+        shared
+            .authorize_view(&headers, query.token.as_deref())
+            .map_err(|_| StatusCode::UNAUTHORIZED)
+    } else {
+        shared.authorize(&headers)
+    };
+    if let Err(status) = authorized {
+        return status;
+    }
+
+    let attendance = match query.status.as_str() {
+        "here" => None,
+        status => match status.parse::<Attendance>() {
+            Ok(attendance) => Some(attendance),
+            Err(_) => return StatusCode::BAD_REQUEST,
+        },
+    };
+    if !shared.is_speaker(&name) {
+        return StatusCode::NOT_FOUND;
+    }
+    shared.submit(MeetingCommand::CheckIn {
+        name,
+        attendance,
+        update: Some(update.trim().to_string()),
+    })
+}
+
+impl Shared {
+    fn is_speaker(&self, name: &str) -> bool {
+        self.state
+            .borrow()
+            .speakers
+            .iter()
+            .any(|speaker| speaker.name == name)
+    }
+
+    /// Check the request's `Authorization: Bearer` token
+    fn authorize(&self, headers: &HeaderMap) -> std::result::Result<(), StatusCode> {
+        let Some(api_token) = &self.api_token else {
+            return Err(StatusCode::FORBIDDEN);
+        };
+
+        let given = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        match given {
+            Some(given) if share::constant_time_eq(given.as_bytes(), api_token.as_bytes()) => {
+                Ok(())
+            }
+            _ => Err(StatusCode::UNAUTHORIZED),
+        }
+    }
+
+    /// Check for a share link's token, or the API token, before showing the meeting; what to
+    /// say instead when there's neither
+    fn authorize_view(
+        &self,
+        headers: &HeaderMap,
+        token: Option<&str>,
+    ) -> std::result::Result<(), &'static str> {
+        if self.authorize(headers).is_ok() {
+            return Ok(());
+        }
+
+        let message = match token.map(|token| self.share_key.verify(token)) {
+            Some(Ok(())) => return Ok(()),
+            Some(Err(Rejection::Expired)) => "This link has expired; ask for a new one",
+            Some(Err(Rejection::Invalid)) => "This link isn't valid",
+            None => "Open the link shown in the terminal, or scan its QR code",
+        };
+        Err(message)
+    }
+
+    fn submit(&self, command: MeetingCommand) -> StatusCode {
+        match self.commands.send(command) {
+            Ok(()) => StatusCode::ACCEPTED,
+            // the meeting is over
+            Err(_) => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+}
+
+/// Serve a bundled file, with `/` mapping to the index page; the page needs a share link
+async fn asset(
+    State(shared): State<Shared>,
+    headers: HeaderMap,
+    Query(query): Query<ViewQuery>,
+    uri: Uri,
+) -> Response {
+    let path = match uri.path().trim_start_matches('/') {
+        "" => "index.html",
+        path => path,
+    };
+    if path == "index.html"
+        && let Err(message) = shared.authorize_view(&headers, query.token.as_deref())
+    {
+        return (StatusCode::UNAUTHORIZED, message).into_response();
+    }
+
+    let Some(file) = ASSETS.get_file(path) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let content_type = match path.rsplit_once('.').map(|(_, ext)| ext) {
+        Some("html") => "text/html; charset=utf-8",
+        Some("css") => "text/css; charset=utf-8",
+        Some("js") => "text/javascript; charset=utf-8",
+        Some("svg") => "image/svg+xml",
+        Some("png") => "image/png",
+        _ => "application/octet-stream",
+    };
+
+    ([(header::CONTENT_TYPE, content_type)], file.contents()).into_response()
+}
+
+/// An address others on the network can reach; a wildcard bind is replaced by this host's LAN address
+fn reachable_addr(addr: SocketAddr) -> SocketAddr {
+    if !addr.ip().is_unspecified() {
+        return addr;
+    }
+
+    // connecting a UDP socket picks the outgoing interface without sending anything
+    let lan_ip = UdpSocket::bind("0.0.0.0:0")
+        .and_then(|socket| {
+            socket.connect("8.8.8.8:80")?;
+            socket.local_addr()
+        })
+        .map(|local| local.ip())
+        .unwrap_or(IpAddr::from([127, 0, 0, 1]));
+
+    SocketAddr::new(lan_ip, addr.port())
+}
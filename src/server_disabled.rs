@@ -0,0 +1,60 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use crate::checkin::CheckIns;
+use crate::command::MeetingCommand;
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::meeting::Meeting;
+use crate::roster::Member;
+use crate::theme::TimerTheme;
+
+/// Stand-in for the web view when nextup is built without the `web` feature: starting it fails
+/// with a note on how to get it, so the rest of nextup builds the same way either way
+pub enum Server {}
+
+impl Server {
+    pub async fn start(
+        _addr: SocketAddr,
+        _api_token: Option<String>,
+        _share_ttl: Duration,
+    ) -> Result<Self> {
+        Err(AppError::NotBuiltIn {
+            what: "the web view",
+            feature: "web",
+        }
+        .into())
+    }
+
+    pub async fn start_check_ins(
+        _addr: SocketAddr,
+        _api_token: Option<String>,
+        _share_ttl: Duration,
+    ) -> Result<Self> {
+        Err(AppError::NotBuiltIn {
+            what: "the web view",
+            feature: "web",
+        }
+        .into())
+    }
+
+    pub fn url(&self) -> &str {
+        match *self {}
+    }
+
+    pub fn qr_code(&self) -> &str {
+        match *self {}
+    }
+
+    pub fn next_command(&mut self) -> Option<MeetingCommand> {
+        match *self {}
+    }
+
+    pub fn publish(&self, _config: &Config, _meeting: &Meeting, _theme: &TimerTheme) {
+        match *self {}
+    }
+
+    pub fn publish_check_ins(&self, _config: &Config, _members: &[Member], _check_ins: &CheckIns) {
+        match *self {}
+    }
+}
@@ -0,0 +1,1205 @@
+use chrono::{DateTime, Local};
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::collate::Collation;
+use crate::fair::{self, Weights};
+use crate::order::{self, OrderRule};
+use crate::roster::Member;
+
+// Remaining-time thresholds that produce a warning event
+const WARNING_THRESHOLDS: [Duration; 2] = [Duration::from_secs(5 * 60), Duration::from_secs(60)];
+
+// A pause between ticks this long means the laptop slept or the terminal was frozen
+const GAP_THRESHOLD: Duration = Duration::from_secs(30);
+
+// Turn length for whoever is left once time is up, when fast-forwarding
+const FAST_FORWARD_SLOT: Duration = Duration::from_secs(30);
+
+// Reasons a pause can be given with a single key: the key, its hint, and the reason noted
+pub const PAUSE_REASONS: [(&str, &str, &str); 3] = [
+    ("1", "Waiting for join", "waiting for someone to join"),
+    ("2", "Technical issue", "technical issue"),
+    ("3", "Break", "break"),
+];
+
+/// Events produced by the meeting engine
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionEvent {
+    Shuffled,
+    SpeakerChanged { index: usize },
+    RoundChanged { round: usize },
+    Warning { remaining: Duration },
+    // the speaker at `index` ran past their slot and grace period in hard mode
+    CutOff { index: usize },
+    // nothing ticked for this long, e.g. the laptop slept; see `resolve_gap`
+    Gap { length: Duration },
+    Ended,
+}
+
+/// Part of the meeting we're in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Phase {
+    // intro and waiting for people to join, before anyone's turn
+    WarmUp,
+    Rounds,
+    // time kept at the end for wrap-up
+    WrapUp,
+}
+
+/// What happens once the meeting's time is up
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeUp {
+    // keep going and count the time over
+    Overtime,
+    // give everyone left a short turn, moving on by itself
+    FastForward,
+    // ignore everything but quitting
+    Lock,
+    // close the meeting and exit, saving and exporting as usual
+    Exit,
+}
+
+impl FromStr for TimeUp {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "overtime" | "continue" => Ok(Self::Overtime),
+            "fast-forward" | "fast" => Ok(Self::FastForward),
+            "lock" => Ok(Self::Lock),
+            "exit" | "quit" => Ok(Self::Exit),
+            _ => Err(format!(
+                "unknown time-up behavior `{}`, expected overtime, fast-forward, lock or exit",
+                s
+            )),
+        }
+    }
+}
+
+/// How the meeting clock measures time passing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Clock {
+    // follow the wall clock, so time spent with the laptop asleep still counts
+    Wall,
+    // count only time the machine was awake, immune to the system clock being changed
+    Monotonic,
+}
+
+impl FromStr for Clock {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "wall" | "wall-clock" => Ok(Self::Wall),
+            "monotonic" => Ok(Self::Monotonic),
+            _ => Err(format!("unknown clock `{}`, expected wall or monotonic", s)),
+        }
+    }
+}
+
+/// What to make of time the meeting wasn't ticking through
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GapChoice {
+    // as if it never happened
+    Discard,
+    // part of the meeting and the current turn
+    Count,
+    // part of the meeting, but nobody's turn
+    Break,
+}
+
+impl FromStr for GapChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "discard" => Ok(Self::Discard),
+            "count" => Ok(Self::Count),
+            "break" => Ok(Self::Break),
+            _ => Err(format!(
+                "unknown gap choice `{}`, expected discard, count or break",
+                s
+            )),
+        }
+    }
+}
+
+/// A stretch without ticks, waiting for someone to say what it was
+#[derive(Debug, Clone, Copy)]
+struct Gap {
+    // how long it really was, and how much of that the meeting clock and the speaker got
+    length: Duration,
+    counted: Duration,
+    credited: Duration,
+    speaker: usize,
+    round: usize,
+}
+
+/// A point in time on both clocks, so either can measure from it
+#[derive(Debug, Clone, Copy)]
+struct Moment {
+    instant: Instant,
+    wall: SystemTime,
+}
+
+impl Moment {
+    fn now() -> Self {
+        Self {
+            instant: Instant::now(),
+            wall: SystemTime::now(),
+        }
+    }
+
+    fn checked_sub(self, duration: Duration) -> Option<Self> {
+        Some(Self {
+            instant: self.instant.checked_sub(duration)?,
+            wall: self.wall.checked_sub(duration)?,
+        })
+    }
+
+    /// Time from `earlier` to this moment; a wall clock set backwards counts as none
+    fn since(&self, earlier: Moment, clock: Clock) -> Duration {
+        match clock {
+            Clock::Wall => self.wall.duration_since(earlier.wall).unwrap_or_default(),
+            Clock::Monotonic => self.instant.duration_since(earlier.instant),
+        }
+    }
+}
+
+/// A manual change to the meeting length or a speaker's time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Adjustment {
+    // meeting time when it was made
+    pub elapsed_ms: u64,
+    // speaker credited or deducted; None for the meeting's remaining time
+    pub speaker: Option<String>,
+    pub secs: i64,
+}
+
+impl std::fmt::Display for Adjustment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.secs < 0 { "-" } else { "+" };
+        write!(
+            f,
+            "{} {}{}s",
+            self.speaker.as_deref().unwrap_or("meeting"),
+            sign,
+            self.secs.unsigned_abs()
+        )
+    }
+}
+
+/// One pause of the meeting clock, for the ledger the summary reports from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pause {
+    pub start: DateTime<Local>,
+    // None while the meeting is still paused
+    pub end: Option<DateTime<Local>>,
+    pub reason: Option<String>,
+}
+
+impl Pause {
+    /// How long the pause lasted, or has so far
+    pub fn length(&self) -> Duration {
+        (self.end.unwrap_or_else(Local::now) - self.start)
+            .to_std()
+            .unwrap_or_default()
+    }
+}
+
+/// Serializable state of a running session, used to resume or mirror it elsewhere
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    pub order: Vec<String>,
+    pub current_round: usize,
+    pub current_person_index: usize,
+    // round_timers[round][person] in milliseconds
+    pub round_timers_ms: Vec<Vec<u64>>,
+    pub elapsed_ms: u64,
+    #[serde(default)]
+    pub paused: bool,
+    #[serde(default)]
+    pub absent: Vec<String>,
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
+    #[serde(default)]
+    pub warm_up_ms: Option<u64>,
+    #[serde(default)]
+    pub adjustments: Vec<Adjustment>,
+    #[serde(default)]
+    pub cut_off: Vec<String>,
+    #[serde(default)]
+    pub pauses: Vec<Pause>,
+}
+
+/// A member in the meeting's order, with the time they've spoken so far
+#[derive(Debug, Clone)]
+pub struct Speaker {
+    pub member: Member,
+    // time spoken in each round
+    pub round_times: Vec<Duration>,
+    // whether hard mode had to cut them off
+    pub cut_off: bool,
+}
+
+impl Speaker {
+    fn new(member: Member, rounds: usize) -> Self {
+        Self {
+            member,
+            round_times: vec![Duration::ZERO; rounds],
+            cut_off: false,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.member.name
+    }
+
+    /// Time spoken over all rounds
+    pub fn total(&self) -> Duration {
+        self.round_times.iter().sum()
+    }
+}
+
+/// Meeting engine shared by the TUI and the headless modes
+pub struct Session {
+    // the order, each member with their own timers so they move together
+    speakers: Vec<Speaker>,
+    rounds: Vec<String>,
+    current_round: usize,
+    current_person_index: usize,
+    duration: Duration,
+    // meeting time before the first turn (cut short when the rounds start early) and kept for wrap-up
+    warm_up: Duration,
+    cool_down: Duration,
+    // slot for members without their own, per round
+    default_slot: Duration,
+    auto_advance: bool,
+    // grace period after a slot before hard mode cuts the speaker off
+    hard_cutoff: Option<Duration>,
+    time_up: TimeUp,
+    // when fast-forwarding, the position in the order whose short turn is under way and
+    // what they had spoken when it began
+    fast_turn: Option<(usize, Duration)>,
+    // names of people skipped in this meeting
+    absent: HashSet<String>,
+    // rules every shuffle keeps to, e.g. `Alice before Bob`
+    order_rules: Vec<OrderRule>,
+    // with --sort-names, each team is put in alphabetical order after the shuffle
+    collation: Option<Collation>,
+    // with --fair, people who've gone late recently are likelier to be shuffled near the front
+    fair: Option<Weights>,
+    adjustments: Vec<Adjustment>,
+    clock: Clock,
+    timer_start: Moment,
+    last_ppt_update: Moment,
+    // when the current pause began, and how long earlier pauses lasted
+    paused_at: Option<Moment>,
+    paused_total: Duration,
+    // every pause so far, with its reason if one was given
+    pauses: Vec<Pause>,
+    // when the session last ticked, to notice the laptop sleeping
+    last_tick: Moment,
+    pending_gap: Option<Gap>,
+    warnings_sent: usize,
+    ended: bool,
+}
+
+impl Session {
+    /// Create a new session for the given members and meeting duration.
+    /// The order is traversed once per round; with no rounds given there is a single unnamed round.
+    pub fn new(members: Vec<Member>, duration: Duration, mut rounds: Vec<String>) -> Self {
+        if rounds.is_empty() {
+            rounds.push(String::new());
+        }
+        let mut session = Self {
+            speakers: members
+                .into_iter()
+                .map(|member| Speaker::new(member, rounds.len()))
+                .collect(),
+            rounds,
+            current_round: 0,
+            current_person_index: 0,
+            duration,
+            warm_up: Duration::ZERO,
+            cool_down: Duration::ZERO,
+            default_slot: Duration::ZERO,
+            auto_advance: false,
+            hard_cutoff: None,
+            time_up: TimeUp::Overtime,
+            fast_turn: None,
+            absent: HashSet::new(),
+            order_rules: Vec::new(),
+            collation: None,
+            fair: None,
+            adjustments: Vec::new(),
+            clock: Clock::Wall,
+            timer_start: Moment::now(),
+            last_ppt_update: Moment::now(),
+            paused_at: None,
+            paused_total: Duration::ZERO,
+            pauses: Vec::new(),
+            last_tick: Moment::now(),
+            pending_gap: None,
+            warnings_sent: 0,
+            ended: false,
+        };
+        session.default_slot = session.even_slot();
+        session
+    }
+
+    /// Keep time free before the first turn and at the end; turns share what's left.
+    /// Set before a fixed slot, since the even split is worked out again.
+    pub fn with_buffers(mut self, warm_up: Duration, cool_down: Duration) -> Self {
+        self.warm_up = warm_up;
+        self.cool_down = cool_down;
+        self.default_slot = self.even_slot();
+        self
+    }
+
+    /// Whatever time in a round isn't claimed by explicit slots, split evenly among everyone else
+    fn even_slot(&self) -> Duration {
+        let budget = self.duration.saturating_sub(self.warm_up + self.cool_down);
+        let per_round = budget / self.rounds.len() as u32;
+        let claimed: Duration = self.speakers.iter().filter_map(|s| s.member.slot).sum();
+        let unassigned = self
+            .speakers
+            .iter()
+            .filter(|s| s.member.slot.is_none())
+            .count()
+            .max(1);
+        per_round.saturating_sub(claimed) / unassigned as u32
+    }
+
+    /// Use a fixed slot for members without their own instead of an even split
+    pub fn with_default_slot(mut self, slot: Option<Duration>) -> Self {
+        if let Some(slot) = slot {
+            self.default_slot = slot;
+        }
+        self
+    }
+
+    /// Move on automatically once the current speaker has used up their slot
+    pub fn with_auto_advance(mut self, auto_advance: bool) -> Self {
+        self.auto_advance = auto_advance;
+        self
+    }
+
+    /// Cut speakers off this long after their slot runs out
+    pub fn with_hard_cutoff(mut self, grace: Option<Duration>) -> Self {
+        self.hard_cutoff = grace;
+        self
+    }
+
+    /// Choose what happens once the meeting's time is up
+    pub fn with_time_up(mut self, time_up: TimeUp) -> Self {
+        self.time_up = time_up;
+        self
+    }
+
+    /// Measure the meeting on the wall clock or only while the machine is awake
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Skip these people from the start, e.g. those who checked in as away
+    pub fn with_absent(mut self, names: impl IntoIterator<Item = String>) -> Self {
+        self.absent.extend(names);
+        self
+    }
+
+    /// Sort the names within each team after every shuffle, so only the teams' order is random
+    pub fn with_collation(mut self, collation: Option<Collation>) -> Self {
+        self.collation = collation;
+        self
+    }
+
+    /// Keep every shuffle to these rules, already checked to fit together
+    pub fn with_order_rules(mut self, rules: Vec<OrderRule>) -> Self {
+        self.order_rules = rules;
+        self
+    }
+
+    /// Weight every shuffle by how late people have gone in recent meetings
+    pub fn with_fair(mut self, weights: Option<Weights>) -> Self {
+        self.fair = weights;
+        self
+    }
+
+    /// Shuffle the names randomly and start again from the top.
+    /// Everyone keeps the time they've spoken so far; resetting it is a separate step.
+    pub fn shuffle(&mut self) -> SessionEvent {
+        self.update_per_person_timers();
+        let mut rng = rand::rng();
+        match &self.fair {
+            Some(weights) => {
+                fair::weighted_shuffle(&mut self.speakers, |s| weights.weight(s.name()), &mut rng)
+            }
+            None => self.speakers.shuffle(&mut rng),
+        }
+        // teams stay together, and absent people go last within them so the first speaker is
+        // someone who's here
+        let teams = self.team_order();
+        let absent = &self.absent;
+        let collation = &self.collation;
+        self.speakers.sort_by(|a, b| {
+            team_rank(&teams, a)
+                .cmp(&team_rank(&teams, b))
+                .then_with(|| absent.contains(a.name()).cmp(&absent.contains(b.name())))
+                .then_with(|| alphabetical(collation, a, b))
+        });
+        self.follow_order_rules(0);
+        self.current_round = 0;
+        self.current_person_index = 0;
+        SessionEvent::Shuffled
+    }
+
+    /// Put the speakers in this order by name, anyone it leaves out following in their current order
+    pub fn set_order(&mut self, names: &[String]) {
+        self.update_per_person_timers();
+        self.speakers.sort_by_key(|s| {
+            names
+                .iter()
+                .position(|name| name == s.name())
+                .unwrap_or(names.len())
+        });
+        self.current_round = 0;
+        self.current_person_index = 0;
+    }
+
+    /// Shuffle only the people after the current speaker
+    pub fn shuffle_remaining(&mut self) -> SessionEvent {
+        let start = self.current_person_index + 1;
+        self.speakers[start..].shuffle(&mut rand::rng());
+        let teams = self.team_order();
+        let collation = &self.collation;
+        self.speakers[start..].sort_by(|a, b| {
+            team_rank(&teams, a)
+                .cmp(&team_rank(&teams, b))
+                .then_with(|| alphabetical(collation, a, b))
+        });
+        self.follow_order_rules(start);
+        SessionEvent::Shuffled
+    }
+
+    /// Rearrange the order from `start` on as little as the order rules need. Positions count
+    /// among the people who are here, so absent people go to the end; if no order fits, which
+    /// checking the rules up front should rule out, the shuffle stands.
+    fn follow_order_rules(&mut self, start: usize) {
+        if self.order_rules.is_empty() {
+            return;
+        }
+
+        let (present, absent): (Vec<Speaker>, Vec<Speaker>) = self
+            .speakers
+            .drain(start..)
+            .partition(|s| !self.absent.contains(s.name()));
+        let placed: Vec<&str> = self
+            .speakers
+            .iter()
+            .filter(|s| !self.absent.contains(s.name()))
+            .map(Speaker::name)
+            .collect();
+        let candidates: Vec<&str> = present.iter().map(Speaker::name).collect();
+
+        match order::arrange(&candidates, &placed, &self.order_rules) {
+            Some(arranged) => {
+                let mut present: Vec<Option<Speaker>> = present.into_iter().map(Some).collect();
+                self.speakers
+                    .extend(arranged.into_iter().filter_map(|i| present[i].take()));
+            }
+            None => self.speakers.extend(present),
+        }
+        self.speakers.extend(absent);
+    }
+
+    /// Teams in the order they first come up; members without a team count as one group
+    fn team_order(&self) -> Vec<Option<String>> {
+        let mut teams: Vec<Option<String>> = Vec::new();
+        for speaker in &self.speakers {
+            if !teams.contains(&speaker.member.team) {
+                teams.push(speaker.member.team.clone());
+            }
+        }
+        teams
+    }
+
+    /// Whether the roster is split into teams, for a scrum of scrums
+    pub fn has_teams(&self) -> bool {
+        self.speakers.iter().any(|s| s.member.team.is_some())
+    }
+
+    /// Reset per-person timers for every round and start again from the first round
+    pub fn reset_per_person_timers(&mut self) {
+        for speaker in &mut self.speakers {
+            speaker.round_times.fill(Duration::ZERO);
+            speaker.cut_off = false;
+        }
+        self.current_round = 0;
+        self.current_person_index = 0;
+    }
+
+    /// Reset the main timer
+    pub fn reset_timer(&mut self) {
+        // once the turns are under way, starting over doesn't bring the warm-up back
+        if self.phase() != Phase::WarmUp {
+            self.warm_up = Duration::ZERO;
+        }
+        self.timer_start = Moment::now();
+        self.last_ppt_update = Moment::now();
+        self.paused_at = None;
+        self.paused_total = Duration::ZERO;
+        // the countdown starts over, but pauses already taken stay in the ledger; a reset
+        // while paused also resumes
+        if let Some(pause) = self.pauses.last_mut()
+            && pause.end.is_none()
+        {
+            pause.end = Some(Local::now());
+        }
+        self.pending_gap = None;
+        self.warnings_sent = 0;
+        self.ended = false;
+    }
+
+    /// Pause or resume both the meeting clock and the current speaker's timer
+    pub fn toggle_pause(&mut self) {
+        match self.paused_at.take() {
+            Some(paused_at) => {
+                self.paused_total += self.since(paused_at);
+                self.last_ppt_update = Moment::now();
+                if let Some(pause) = self.pauses.last_mut() {
+                    pause.end = Some(Local::now());
+                }
+            }
+            None => {
+                // credit the speaker with the time up to the pause
+                self.update_per_person_timers();
+                self.paused_at = Some(Moment::now());
+                self.pauses.push(Pause {
+                    start: Local::now(),
+                    end: None,
+                    reason: None,
+                });
+            }
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused_at.is_some()
+    }
+
+    /// Say why the meeting is paused; does nothing while it isn't
+    pub fn set_pause_reason(&mut self, reason: &str) {
+        if !self.is_paused() {
+            return;
+        }
+        if let Some(pause) = self.pauses.last_mut() {
+            pause.reason = Some(reason.to_string());
+        }
+    }
+
+    pub fn pauses(&self) -> &[Pause] {
+        &self.pauses
+    }
+
+    /// The reason given for the pause under way, if any
+    pub fn pause_reason(&self) -> Option<&str> {
+        self.pauses
+            .last()
+            .filter(|_| self.is_paused())
+            .and_then(|pause| pause.reason.as_deref())
+    }
+
+    /// Time spent paused over all pauses, including one still under way
+    pub fn paused_time(&self) -> Duration {
+        self.pauses.iter().map(Pause::length).sum()
+    }
+
+    /// Mark someone as absent (skipped by navigation) or present again.
+    /// Returns None when nobody by that name is in the meeting; if the current
+    /// speaker is marked absent, the turn moves on and that move is returned. During the warm-up
+    /// the first turn goes to the next present person, still starting when the warm-up ends.
+    pub fn set_absent(&mut self, name: &str, absent: bool) -> Option<Option<SessionEvent>> {
+        if !self.speakers.iter().any(|s| s.name() == name) {
+            return None;
+        }
+
+        if !absent {
+            self.absent.remove(name);
+            return Some(None);
+        }
+
+        self.absent.insert(name.to_string());
+        if self.current_name() != name {
+            return Some(None);
+        }
+
+        // not `next()`, which ends the warm-up rather than moving on from its first speaker
+        let n = self.speakers.len();
+        let position = self.current_round * n + self.current_person_index;
+        let target = (position + 1..self.rounds.len() * n)
+            .find(|p| !self.is_absent(p % n))
+            .or_else(|| (0..position).rev().find(|p| !self.is_absent(p % n)));
+        self.update_per_person_timers();
+        Some(target.map(|target| self.move_to(target)))
+    }
+
+    pub fn is_absent(&self, index: usize) -> bool {
+        self.absent.contains(self.speakers[index].name())
+    }
+
+    /// Add time to (or, when negative, take time from) what's left of the meeting
+    pub fn adjust_remaining(&mut self, secs: i64) -> &Adjustment {
+        self.duration = offset(self.duration, secs);
+        self.skip_past_warnings();
+        self.log_adjustment(None, secs)
+    }
+
+    /// Credit (or, when negative, deduct) time to the current speaker in this round
+    pub fn adjust_speaker(&mut self, secs: i64) -> &Adjustment {
+        self.update_per_person_timers();
+        let timer = &mut self.speakers[self.current_person_index].round_times[self.current_round];
+        *timer = offset(*timer, secs);
+        let speaker = self.current_name().to_string();
+        self.log_adjustment(Some(speaker), secs)
+    }
+
+    fn log_adjustment(&mut self, speaker: Option<String>, secs: i64) -> &Adjustment {
+        self.adjustments.push(Adjustment {
+            elapsed_ms: self.elapsed().as_millis() as u64,
+            speaker,
+            secs,
+        });
+        &self.adjustments[self.adjustments.len() - 1]
+    }
+
+    /// Manual adjustments made so far, oldest first
+    pub fn adjustments(&self) -> &[Adjustment] {
+        &self.adjustments
+    }
+
+    /// Move to the next present person, wrapping into the next round after the last one.
+    /// During the warm-up, this starts the first turn early instead.
+    pub fn next(&mut self) -> Option<SessionEvent> {
+        if self.phase() == Phase::WarmUp {
+            self.update_per_person_timers();
+            self.warm_up = self.elapsed();
+            return Some(SessionEvent::SpeakerChanged {
+                index: self.current_person_index,
+            });
+        }
+
+        let n = self.speakers.len();
+        let position = self.current_round * n + self.current_person_index;
+        let target = (position + 1..self.rounds.len() * n).find(|p| !self.is_absent(p % n))?;
+        Some(self.move_to(target))
+    }
+
+    /// Move to the previous present person, going back into the previous round from the first one
+    pub fn prev(&mut self) -> Option<SessionEvent> {
+        let n = self.speakers.len();
+        let position = self.current_round * n + self.current_person_index;
+        let target = (0..position).rev().find(|p| !self.is_absent(p % n))?;
+        Some(self.move_to(target))
+    }
+
+    /// Jump to a position counted across rounds (round * members + person)
+    fn move_to(&mut self, position: usize) -> SessionEvent {
+        let round = position / self.speakers.len();
+        self.current_person_index = position % self.speakers.len();
+
+        if round != self.current_round {
+            self.current_round = round;
+            SessionEvent::RoundChanged { round }
+        } else {
+            SessionEvent::SpeakerChanged {
+                index: self.current_person_index,
+            }
+        }
+    }
+
+    /// Capture the order, position and timers
+    pub fn snapshot(&self) -> SessionSnapshot {
+        SessionSnapshot {
+            order: self.speakers.iter().map(|s| s.name().to_string()).collect(),
+            current_round: self.current_round,
+            current_person_index: self.current_person_index,
+            round_timers_ms: (0..self.rounds.len())
+                .map(|round| {
+                    self.speakers
+                        .iter()
+                        .map(|s| s.round_times[round].as_millis() as u64)
+                        .collect()
+                })
+                .collect(),
+            elapsed_ms: self.elapsed().as_millis() as u64,
+            paused: self.is_paused(),
+            absent: self.absent.iter().cloned().collect(),
+            duration_ms: Some(self.duration.as_millis() as u64),
+            warm_up_ms: Some(self.warm_up.as_millis() as u64),
+            adjustments: self.adjustments.clone(),
+            cut_off: self
+                .speakers
+                .iter()
+                .filter(|s| s.cut_off)
+                .map(|s| s.name().to_string())
+                .collect(),
+            pauses: self.pauses.clone(),
+        }
+    }
+
+    /// Continue from a snapshot. Members are matched by name; anyone not in the
+    /// snapshot is appended with empty timers.
+    pub fn restore(&mut self, snapshot: &SessionSnapshot) {
+        let mut remaining = std::mem::take(&mut self.speakers);
+        for (i, name) in snapshot.order.iter().enumerate() {
+            if let Some(pos) = remaining.iter().position(|s| s.name() == name) {
+                let mut speaker = remaining.remove(pos);
+                for (round, time) in speaker.round_times.iter_mut().enumerate() {
+                    *time = snapshot
+                        .round_timers_ms
+                        .get(round)
+                        .and_then(|timers| timers.get(i))
+                        .map_or(Duration::ZERO, |ms| Duration::from_millis(*ms));
+                }
+                speaker.cut_off = snapshot.cut_off.iter().any(|n| n == name);
+                self.speakers.push(speaker);
+            }
+        }
+        for speaker in &mut remaining {
+            speaker.round_times.fill(Duration::ZERO);
+            speaker.cut_off = false;
+        }
+        self.speakers.append(&mut remaining);
+
+        self.current_round = snapshot.current_round.min(self.rounds.len() - 1);
+        self.current_person_index = snapshot
+            .current_person_index
+            .min(self.speakers.len().saturating_sub(1));
+
+        self.absent = snapshot.absent.iter().cloned().collect();
+
+        let now = Moment::now();
+        self.timer_start = now
+            .checked_sub(Duration::from_millis(snapshot.elapsed_ms))
+            .unwrap_or(now);
+        self.last_ppt_update = now;
+        self.last_tick = now;
+        self.paused_at = snapshot.paused.then_some(now);
+        self.paused_total = Duration::ZERO;
+        self.pauses = snapshot.pauses.clone();
+
+        // including any time added or taken away by hand
+        if let Some(duration_ms) = snapshot.duration_ms {
+            self.duration = Duration::from_millis(duration_ms);
+        }
+        if let Some(warm_up_ms) = snapshot.warm_up_ms {
+            self.warm_up = Duration::from_millis(warm_up_ms);
+        }
+        self.adjustments = snapshot.adjustments.clone();
+
+        // don't repeat warnings the original session already gave
+        self.skip_past_warnings();
+    }
+
+    /// Treat warnings for time already behind us as given, e.g. after restoring or adjusting
+    fn skip_past_warnings(&mut self) {
+        let remaining = self.remaining_time();
+        self.warnings_sent = WARNING_THRESHOLDS
+            .iter()
+            .take_while(|threshold| remaining <= **threshold)
+            .count();
+        self.ended = remaining.is_zero();
+    }
+
+    /// Advance timers and report any warnings or the end of the meeting
+    pub fn tick(&mut self) -> Vec<SessionEvent> {
+        let now = Moment::now();
+        let since_last = now
+            .since(self.last_tick, Clock::Wall)
+            .max(now.since(self.last_tick, Clock::Monotonic));
+        let counted = now.since(self.last_tick, self.clock);
+        self.last_tick = now;
+
+        let spoken = self.current_time();
+        self.update_per_person_timers();
+
+        let mut events = Vec::new();
+
+        // a paused clock didn't count the time anyway
+        if since_last >= GAP_THRESHOLD && !self.is_paused() {
+            self.pending_gap = Some(Gap {
+                length: since_last,
+                counted,
+                credited: self.current_time().saturating_sub(spoken),
+                speaker: self.current_person_index,
+                round: self.current_round,
+            });
+            events.push(SessionEvent::Gap { length: since_last });
+        }
+
+        // only when the slot runs out now, so going back to someone doesn't skip them again
+        let slot = self.slot(self.current_person_index);
+        if self.auto_advance
+            && spoken < slot
+            && self.current_time() >= slot
+            && let Some(event) = self.next()
+        {
+            events.push(event);
+        }
+
+        // hard mode: once the grace period is over too, the turn moves on regardless
+        if let Some(limit) = self.hard_cutoff.map(|grace| slot + grace)
+            && spoken < limit
+            && self.current_time() >= limit
+        {
+            let index = self.current_person_index;
+            self.speakers[index].cut_off = true;
+            events.push(SessionEvent::CutOff { index });
+            events.extend(self.next());
+        }
+
+        let remaining = self.remaining_time();
+
+        while let Some(&threshold) = WARNING_THRESHOLDS.get(self.warnings_sent) {
+            let applies = threshold < self.duration;
+            if applies && remaining > threshold {
+                break;
+            }
+            self.warnings_sent += 1;
+
+            // thresholds longer than the meeting itself are skipped silently
+            if applies && !remaining.is_zero() {
+                events.push(SessionEvent::Warning {
+                    remaining: threshold,
+                });
+            }
+        }
+
+        if remaining.is_zero() && !self.ended {
+            self.ended = true;
+            events.push(SessionEvent::Ended);
+        }
+
+        // once time is up, hurry through whoever is left
+        if self.time_up == TimeUp::FastForward
+            && remaining.is_zero()
+            && self.phase() != Phase::WarmUp
+        {
+            let position = self.current_round * self.speakers.len() + self.current_person_index;
+            match self.fast_turn {
+                Some((turn, start)) if turn == position => {
+                    if self.current_time().saturating_sub(start) >= FAST_FORWARD_SLOT {
+                        events.extend(self.next());
+                    }
+                }
+                _ => self.fast_turn = Some((position, self.current_time())),
+            }
+        }
+
+        events
+    }
+
+    /// How long the last gap between ticks was, until it's been resolved
+    pub fn pending_gap(&self) -> Option<Duration> {
+        self.pending_gap.map(|gap| gap.length)
+    }
+
+    /// Settle what the last gap between ticks counts toward. Until then it's counted however
+    /// the meeting's clock saw it: all of it on the wall clock, only time awake on the monotonic one.
+    pub fn resolve_gap(&mut self, choice: GapChoice) {
+        let Some(gap) = self.pending_gap.take() else {
+            return;
+        };
+        let uncounted = gap.length.saturating_sub(gap.counted);
+
+        // the meeting clock
+        match choice {
+            GapChoice::Discard => self.paused_total += gap.counted,
+            GapChoice::Count | GapChoice::Break => {
+                self.timer_start = self
+                    .timer_start
+                    .checked_sub(uncounted)
+                    .unwrap_or(self.timer_start)
+            }
+        }
+
+        // the turn it happened in
+        if let Some(time) = self
+            .speakers
+            .get_mut(gap.speaker)
+            .and_then(|speaker| speaker.round_times.get_mut(gap.round))
+        {
+            match choice {
+                GapChoice::Discard | GapChoice::Break => *time = time.saturating_sub(gap.credited),
+                // only if the turn was running at the time
+                GapChoice::Count if !gap.credited.is_zero() => *time += uncounted,
+                GapChoice::Count => {}
+            }
+        }
+
+        self.skip_past_warnings();
+    }
+
+    /// Update per-person timers
+    fn update_per_person_timers(&mut self) {
+        let now = Moment::now();
+        // nobody's turn has started during the warm-up
+        if self.is_paused() || self.phase() == Phase::WarmUp {
+            self.last_ppt_update = now;
+            return;
+        }
+
+        let elapsed = now.since(self.last_ppt_update, self.clock);
+
+        // Update the current person's timer for this round
+        if let Some(speaker) = self.speakers.get_mut(self.current_person_index) {
+            speaker.round_times[self.current_round] += elapsed;
+        }
+
+        self.last_ppt_update = now;
+    }
+
+    /// Planned meeting length
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Get remaining meeting time
+    pub fn remaining_time(&self) -> Duration {
+        self.duration.saturating_sub(self.elapsed())
+    }
+
+    /// Time the meeting has run past its planned length
+    pub fn overtime(&self) -> Duration {
+        self.elapsed().saturating_sub(self.duration)
+    }
+
+    /// Whether the meeting's time has run out
+    pub fn is_time_up(&self) -> bool {
+        self.remaining_time().is_zero()
+    }
+
+    pub fn time_up(&self) -> TimeUp {
+        self.time_up
+    }
+
+    /// Get elapsed meeting time, not counting pauses
+    pub fn elapsed(&self) -> Duration {
+        let paused = self.paused_total + self.paused_at.map_or(Duration::ZERO, |p| self.since(p));
+        self.since(self.timer_start).saturating_sub(paused)
+    }
+
+    /// Time from `moment` until now, on the meeting's clock
+    fn since(&self, moment: Moment) -> Duration {
+        Moment::now().since(moment, self.clock)
+    }
+
+    /// Which part of the meeting we're in
+    pub fn phase(&self) -> Phase {
+        if self.elapsed() < self.warm_up {
+            Phase::WarmUp
+        } else if !self.cool_down.is_zero() && self.remaining_time() <= self.cool_down {
+            Phase::WrapUp
+        } else {
+            Phase::Rounds
+        }
+    }
+
+    /// Time until the first turn starts, during the warm-up
+    pub fn warm_up_left(&self) -> Option<Duration> {
+        (self.phase() == Phase::WarmUp).then(|| self.warm_up.saturating_sub(self.elapsed()))
+    }
+
+    /// Meeting length if the rest of the warm-up, everyone from the current speaker on
+    /// using their full slot, and the wrap-up all run their course
+    pub fn projected_length(&self) -> Duration {
+        let upcoming: Duration = (self.current_round..self.rounds.len())
+            .flat_map(|round| {
+                let first = if round == self.current_round {
+                    self.current_person_index
+                } else {
+                    0
+                };
+                (first..self.speakers.len()).map(move |i| {
+                    self.slot(i)
+                        .saturating_sub(self.speakers[i].round_times[round])
+                })
+            })
+            .sum();
+
+        let elapsed = self.elapsed();
+        elapsed.max(self.warm_up) + upcoming + self.cool_down
+    }
+
+    /// Turns still to come across every round, counting the current one; everyone's during the
+    /// warm-up
+    pub fn turns_left(&self) -> usize {
+        let n = self.speakers.len();
+        let position = if self.phase() == Phase::WarmUp {
+            0
+        } else {
+            self.current_round * n + self.current_person_index
+        };
+        (position..self.rounds.len() * n)
+            .filter(|p| !self.is_absent(p % n))
+            .count()
+    }
+
+    /// Turns the whole meeting has, one per present person per round
+    pub fn turns_total(&self) -> usize {
+        let present = (0..self.speakers.len())
+            .filter(|i| !self.is_absent(*i))
+            .count();
+        present * self.rounds.len()
+    }
+
+    /// When the turns are planned to start and end: after the warm-up, and before the wrap-up
+    pub fn speaking_time(&self) -> (Duration, Duration) {
+        (
+            self.warm_up,
+            self.duration
+                .saturating_sub(self.cool_down)
+                .max(self.warm_up),
+        )
+    }
+
+    /// How far past the end the meeting is projected to run, if at all
+    pub fn projected_overrun(&self) -> Option<Duration> {
+        // ignore sub-second drift between the meeting clock and per-person timers
+        let overrun = self.projected_length().saturating_sub(self.duration);
+        (overrun.as_secs() > 0).then_some(overrun)
+    }
+
+    /// Speaking slot per round for the member at `index`
+    pub fn slot(&self, index: usize) -> Duration {
+        self.speakers[index]
+            .member
+            .slot
+            .unwrap_or(self.default_slot)
+    }
+
+    /// The time someone has over the whole meeting: their slot in every round
+    pub fn budget(&self, index: usize) -> Duration {
+        self.slot(index) * self.rounds.len() as u32
+    }
+
+    /// The order, with everyone's time so far
+    pub fn speakers(&self) -> &[Speaker] {
+        &self.speakers
+    }
+
+    /// Per-person timers for the current round
+    pub fn per_person_timers(&self) -> Vec<Duration> {
+        self.speakers
+            .iter()
+            .map(|s| s.round_times[self.current_round])
+            .collect()
+    }
+
+    /// Time the current speaker has spoken in this round
+    pub fn current_time(&self) -> Duration {
+        self.speakers[self.current_person_index].round_times[self.current_round]
+    }
+
+    /// How far past their slot someone has run, summed over the rounds
+    pub fn overage(&self, index: usize) -> Duration {
+        let slot = self.slot(index);
+        self.speakers[index]
+            .round_times
+            .iter()
+            .map(|time| time.saturating_sub(slot))
+            .sum()
+    }
+
+    /// In hard mode, how long until the current speaker is cut off, once their slot has run out
+    pub fn cutoff_in(&self) -> Option<Duration> {
+        let grace = self.hard_cutoff?;
+        let slot = self.slot(self.current_person_index);
+        let spoken = self.current_time();
+        (spoken >= slot).then(|| (slot + grace).saturating_sub(spoken))
+    }
+
+    /// Per-person time summed over all rounds
+    pub fn person_totals(&self) -> Vec<Duration> {
+        self.speakers.iter().map(Speaker::total).collect()
+    }
+
+    /// Total time spent in each round
+    pub fn round_totals(&self) -> Vec<(&str, Duration)> {
+        self.rounds
+            .iter()
+            .enumerate()
+            .map(|(round, name)| {
+                let total = self.speakers.iter().map(|s| s.round_times[round]).sum();
+                (name.as_str(), total)
+            })
+            .collect()
+    }
+
+    /// Label such as "Round 2/3: Blockers", or None for single-round meetings
+    pub fn round_label(&self) -> Option<String> {
+        if self.rounds.len() < 2 {
+            return None;
+        }
+
+        let name = &self.rounds[self.current_round];
+        let mut label = format!("Round {}/{}", self.current_round + 1, self.rounds.len());
+        if !name.is_empty() {
+            label.push_str(&format!(": {}", name));
+        }
+        Some(label)
+    }
+
+    /// Name of the current round (empty when rounds are unnamed)
+    pub fn round_name(&self) -> &str {
+        &self.rounds[self.current_round]
+    }
+
+    pub fn is_multi_round(&self) -> bool {
+        self.rounds.len() > 1
+    }
+
+    pub fn current_round(&self) -> usize {
+        self.current_round
+    }
+
+    pub fn current_person_index(&self) -> usize {
+        self.current_person_index
+    }
+
+    pub fn current_name(&self) -> &str {
+        self.speakers[self.current_person_index].name()
+    }
+}
+
+/// `duration` moved by `secs`, stopping at zero
+fn offset(duration: Duration, secs: i64) -> Duration {
+    let delta = Duration::from_secs(secs.unsigned_abs());
+    if secs < 0 {
+        duration.saturating_sub(delta)
+    } else {
+        duration + delta
+    }
+}
+
+/// Two speakers in alphabetical order with --sort-names; otherwise they stay as shuffled
+fn alphabetical(collation: &Option<Collation>, a: &Speaker, b: &Speaker) -> Ordering {
+    collation.as_ref().map_or(Ordering::Equal, |collation| {
+        collation.compare(a.name(), b.name())
+    })
+}
+
+/// Where a speaker's team comes in the order
+fn team_rank(teams: &[Option<String>], speaker: &Speaker) -> usize {
+    teams
+        .iter()
+        .position(|team| *team == speaker.member.team)
+        .unwrap_or(teams.len())
+}
@@ -0,0 +1,93 @@
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::Path;
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::roster;
+use crate::series::{Series, Template};
+
+/// Whether to offer the first-run wizard: nothing has been set up for the series,
+/// there's no team file to fall back on, and someone is at the terminal to answer
+pub fn needed(series: &Series, names_file: &str) -> bool {
+    io::stdin().is_terminal() && !series.has_template() && !Path::new(names_file).exists()
+}
+
+/// Ask for the team and meeting, then save them as the series template so later runs start
+/// from them. Returns the template, or None when no names were given and the example team
+/// should be used instead.
+pub fn run(series: &Series, defaults: &Config) -> Result<Option<Template>> {
+    let Some(dir) = series.dir() else {
+        return Ok(None);
+    };
+
+    eprintln!("Welcome to nextup! There's no team set up yet, so let's make one.");
+    eprintln!("Press Enter to accept the [default] answers.");
+    eprintln!();
+
+    let title = ask(&format!("Meeting title [{}]: ", defaults.title))?
+        .unwrap_or_else(|| defaults.title.clone());
+
+    let names_file = dir.join("team.txt");
+    let names = loop {
+        eprintln!("Paste or type the team's names, one per line, then an empty line:");
+        let mut names = Vec::new();
+        while let Some(name) = ask("  ")? {
+            names.push(name);
+        }
+        if names.is_empty() {
+            eprintln!("No names given, starting with the example team.");
+            return Ok(None);
+        }
+
+        // the same rules as a hand-written team file, so fields like `| role=lead` work too
+        let content = names.join("\n") + "\n";
+        match roster::parse_members(&content, &names_file.display().to_string()) {
+            Ok(_) => break content,
+            Err(e) => eprintln!("{}; let's try that again.", e),
+        }
+    };
+
+    let default_minutes = defaults.duration.as_secs() / 60;
+    let duration_minutes = loop {
+        let Some(answer) = ask(&format!(
+            "Meeting length in minutes [{}]: ",
+            default_minutes
+        ))?
+        else {
+            break default_minutes;
+        };
+        match answer.parse::<u64>() {
+            Ok(minutes) if minutes > 0 => break minutes,
+            _ => eprintln!("`{}` isn't a number of minutes.", answer),
+        }
+    };
+
+    fs::create_dir_all(&dir)?;
+    fs::write(&names_file, names)?;
+    let template = Template {
+        title: Some(title),
+        names_file: Some(names_file.display().to_string()),
+        duration_minutes: Some(duration_minutes),
+        ..Template::default()
+    };
+    series.save_template(&template)?;
+
+    eprintln!();
+    eprintln!(
+        "Saved the team to {}; `nextup edit-team` changes it later.",
+        names_file.display()
+    );
+    Ok(Some(template))
+}
+
+/// Prompt for one line; None for an empty answer or the end of input
+fn ask(prompt: &str) -> Result<Option<String>> {
+    eprint!("{}", prompt);
+    io::stderr().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    let answer = answer.trim();
+    Ok((!answer.is_empty()).then(|| answer.to_string()))
+}
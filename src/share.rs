@@ -0,0 +1,72 @@
+use hmac_sha256::HMAC;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// Bytes of the signature kept in a token; enough that it can't be guessed, short enough to
+// keep the QR code small
+const SIGNATURE_LEN: usize = 16;
+
+/// Signs read-only links to the web view that stop working after a while, so the view can be
+/// shared without handing out the control API's token
+pub struct ShareKey {
+    // made up for each run, so links from an earlier meeting never work again
+    secret: [u8; 32],
+    ttl: Duration,
+}
+
+/// Why a share token was turned away
+#[derive(Debug, PartialEq)]
+pub enum Rejection {
+    Invalid,
+    Expired,
+}
+
+impl ShareKey {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            secret: rand::random(),
+            ttl,
+        }
+    }
+
+    /// A token good for the key's lifetime from now: when it expires, and its signature
+    pub fn issue(&self) -> String {
+        let expires = unix_now() + self.ttl.as_secs();
+        format!("{}.{}", expires, hex(&self.sign(expires)))
+    }
+
+    /// Check a token was issued by this key and hasn't expired
+    pub fn verify(&self, token: &str) -> Result<(), Rejection> {
+        let (expires, signature) = token.split_once('.').ok_or(Rejection::Invalid)?;
+        let expires: u64 = expires.parse().map_err(|_| Rejection::Invalid)?;
+        if !constant_time_eq(signature.as_bytes(), hex(&self.sign(expires)).as_bytes()) {
+            return Err(Rejection::Invalid);
+        }
+        if unix_now() >= expires {
+            return Err(Rejection::Expired);
+        }
+        Ok(())
+    }
+
+    fn sign(&self, expires: u64) -> [u8; SIGNATURE_LEN] {
+        let mac = HMAC::mac(expires.to_string().as_bytes(), self.secret);
+        let mut signature = [0; SIGNATURE_LEN];
+        signature.copy_from_slice(&mac[..SIGNATURE_LEN]);
+        signature
+    }
+}
+
+/// Compare without stopping at the first difference, so timing doesn't leak the token
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
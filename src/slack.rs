@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::error::{AppError, Result};
+
+#[cfg(feature = "slack")]
+const API_BASE: &str = "https://slack.com/api";
+
+/// A message in a Slack conversation
+#[derive(Debug, Deserialize)]
+pub struct Message {
+    pub ts: String,
+    #[serde(default)]
+    pub text: String,
+    // member ID of whoever posted it
+    #[serde(default)]
+    pub user: Option<String>,
+    #[serde(default)]
+    pub bot_id: Option<String>,
+    #[serde(default)]
+    pub reactions: Vec<Reaction>,
+}
+
+/// An emoji reaction, how many people added it and who they are
+#[derive(Debug, Deserialize)]
+pub struct Reaction {
+    pub name: String,
+    pub count: u32,
+    // member IDs of the people who reacted
+    #[serde(default)]
+    pub users: Vec<String>,
+}
+
+/// A user's custom status
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Status {
+    #[serde(default)]
+    pub status_text: String,
+    #[serde(default)]
+    pub status_emoji: String,
+    // Unix timestamp when Slack clears the status, 0 for never
+    #[serde(default)]
+    pub status_expiration: i64,
+}
+
+/// Minimal Slack Web API client
+pub struct SlackClient {
+    #[cfg_attr(not(feature = "slack"), allow(dead_code))]
+    token: String,
+}
+
+impl SlackClient {
+    pub fn new(token: String) -> Self {
+        Self { token }
+    }
+
+    /// Post a message to a channel, optionally as a thread reply.
+    /// Returns the timestamp identifying the new message.
+    pub fn post_message(
+        &self,
+        channel: &str,
+        text: &str,
+        thread_ts: Option<&str>,
+    ) -> Result<String> {
+        let mut body = json!({ "channel": channel, "text": text });
+        if let Some(ts) = thread_ts {
+            body["thread_ts"] = json!(ts);
+        }
+
+        let response = self.post("chat.postMessage", body)?;
+        Ok(response["ts"].as_str().unwrap_or_default().to_string())
+    }
+
+    /// Replace the text of a previously posted message
+    pub fn update_message(&self, channel: &str, ts: &str, text: &str) -> Result<()> {
+        self.post(
+            "chat.update",
+            json!({ "channel": channel, "ts": ts, "text": text }),
+        )?;
+        Ok(())
+    }
+
+    /// Fetch thread replies posted after `oldest`
+    pub fn replies(&self, channel: &str, thread_ts: &str, oldest: &str) -> Result<Vec<Message>> {
+        let response = self.get(
+            "conversations.replies",
+            &[("channel", channel), ("ts", thread_ts), ("oldest", oldest)],
+        )?;
+
+        let messages: Vec<Message> = serde_json::from_value(response["messages"].clone())
+            .map_err(|e| AppError::Slack(e.to_string()))?;
+
+        // Slack always includes the parent message; drop it along with anything not newer
+        Ok(messages
+            .into_iter()
+            .filter(|m| m.ts.as_str() > oldest && m.ts != thread_ts)
+            .collect())
+    }
+
+    /// Fetch the reactions on a message
+    pub fn reactions(&self, channel: &str, ts: &str) -> Result<Vec<Reaction>> {
+        let response = self.get("reactions.get", &[("channel", channel), ("timestamp", ts)])?;
+
+        let message: Message = serde_json::from_value(response["message"].clone())
+            .map_err(|e| AppError::Slack(e.to_string()))?;
+        Ok(message.reactions)
+    }
+
+    /// Read the user's current status (requires a user token)
+    pub fn status(&self) -> Result<Status> {
+        let response = self.get("users.profile.get", &[])?;
+        serde_json::from_value(response["profile"].clone())
+            .map_err(|e| AppError::Slack(e.to_string()).into())
+    }
+
+    /// Set the user's status (requires a user token with `users.profile:write`)
+    pub fn set_status(&self, status: &Status) -> Result<()> {
+        self.post("users.profile.set", json!({ "profile": status }))?;
+        Ok(())
+    }
+
+    #[cfg(feature = "slack")]
+    fn post(&self, method: &str, body: Value) -> Result<Value> {
+        let response = ureq::post(&format!("{}/{}", API_BASE, method))
+            .set("Authorization", &format!("Bearer {}", self.token))
+            .send_json(body)
+            .map_err(|e| AppError::Slack(e.to_string()))?;
+        Self::check(response)
+    }
+
+    #[cfg(feature = "slack")]
+    fn get(&self, method: &str, query: &[(&str, &str)]) -> Result<Value> {
+        let response = ureq::get(&format!("{}/{}", API_BASE, method))
+            .set("Authorization", &format!("Bearer {}", self.token))
+            .query_pairs(query.iter().copied())
+            .call()
+            .map_err(|e| AppError::Slack(e.to_string()))?;
+        Self::check(response)
+    }
+
+    /// Slack reports failures with `ok: false` and an error code
+    #[cfg(feature = "slack")]
+    fn check(response: ureq::Response) -> Result<Value> {
+        let value: Value = response.into_json()?;
+        if value["ok"].as_bool() != Some(true) {
+            let error = value["error"].as_str().unwrap_or("unknown error");
+            return Err(AppError::Slack(error.to_string()).into());
+        }
+        Ok(value)
+    }
+
+    // built without the `slack` feature, nothing reaches Slack
+    #[cfg(not(feature = "slack"))]
+    fn post(&self, _method: &str, _body: Value) -> Result<Value> {
+        Err(Self::disabled())
+    }
+
+    #[cfg(not(feature = "slack"))]
+    fn get(&self, _method: &str, _query: &[(&str, &str)]) -> Result<Value> {
+        Err(Self::disabled())
+    }
+
+    #[cfg(not(feature = "slack"))]
+    fn disabled() -> anyhow::Error {
+        AppError::NotBuiltIn {
+            what: "Slack support",
+            feature: "slack",
+        }
+        .into()
+    }
+}
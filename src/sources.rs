@@ -0,0 +1,205 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::roster::{self, Member};
+
+/// A field two sources disagree about; the team file's (or the earlier source's) value is kept
+#[derive(Debug, Clone, PartialEq)]
+pub struct Disagreement {
+    pub name: String,
+    pub field: &'static str,
+    pub kept: String,
+    pub ignored: String,
+    pub source: String,
+}
+
+/// Someone a source lists who isn't in the team file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unknown {
+    pub member: Member,
+    pub source: String,
+}
+
+/// The team file merged with the other team sources
+#[derive(Debug, Default)]
+pub struct Merged {
+    pub members: Vec<Member>,
+    pub disagreements: Vec<Disagreement>,
+    pub unknown: Vec<Unknown>,
+}
+
+impl Merged {
+    /// Whether there's anything to look over before the meeting
+    pub fn needs_review(&self) -> bool {
+        !self.disagreements.is_empty() || !self.unknown.is_empty()
+    }
+}
+
+/// The team for the meeting: the team file plus anyone the other sources know about,
+/// with people they don't know only included when `--add-unknown` says so
+pub fn load_members(config: &Config) -> Result<Vec<Member>> {
+    if config.sources.is_empty() {
+        return roster::load_members(&config.names_file);
+    }
+
+    let merged = load(config)?;
+    let mut members = merged.members;
+    if config.add_unknown {
+        members.extend(merged.unknown.into_iter().map(|unknown| unknown.member));
+    }
+    Ok(members)
+}
+
+/// Read and merge every configured team source
+pub fn load(config: &Config) -> Result<Merged> {
+    let base = roster::load_members(&config.names_file)?;
+    let mut sources = Vec::new();
+    for filename in &config.sources {
+        sources.push((filename.clone(), roster::load_members(filename)?));
+    }
+    Ok(merge(base, sources))
+}
+
+/// Match each source's people to the team by any identity they share (name, id, alias, GitHub
+/// handle, Slack ID or email), filling in what the team doesn't say about them
+pub fn merge(base: Vec<Member>, sources: Vec<(String, Vec<Member>)>) -> Merged {
+    let mut merged = Merged {
+        members: base,
+        ..Merged::default()
+    };
+
+    for (source, members) in sources {
+        for member in members {
+            let known = merged.members.iter_mut().find(|m| same_person(m, &member));
+            let known = known.or_else(|| {
+                merged
+                    .unknown
+                    .iter_mut()
+                    .map(|unknown| &mut unknown.member)
+                    .find(|m| same_person(m, &member))
+            });
+
+            match known {
+                Some(known) => {
+                    let disagreements = fill_in(known, member);
+                    merged.disagreements.extend(disagreements.into_iter().map(
+                        |(field, kept, ignored)| Disagreement {
+                            name: known.name.clone(),
+                            field,
+                            kept,
+                            ignored,
+                            source: source.clone(),
+                        },
+                    ));
+                }
+                None => merged.unknown.push(Unknown {
+                    member,
+                    source: source.clone(),
+                }),
+            }
+        }
+    }
+
+    merged
+}
+
+/// Whether two entries from different sources are the same person
+fn same_person(known: &Member, other: &Member) -> bool {
+    std::iter::once(&other.name)
+        .chain(std::iter::once(&other.id))
+        .chain(&other.aliases)
+        .chain(&other.github)
+        .chain(&other.slack_id)
+        .chain(&other.email)
+        .any(|identity| known.answers_to(identity))
+}
+
+/// Copy over what `other` knows and `known` doesn't; returns the fields where both say
+/// something different, as (field, kept, ignored)
+fn fill_in(known: &mut Member, other: Member) -> Vec<(&'static str, String, String)> {
+    let mut disagreements = Vec::new();
+    for (field, known, other) in [
+        ("slack", &mut known.slack_id, other.slack_id),
+        ("email", &mut known.email, other.email),
+        ("github", &mut known.github, other.github),
+        ("avatar", &mut known.avatar, other.avatar),
+        ("role", &mut known.role, other.role),
+        ("team", &mut known.team, other.team),
+    ] {
+        match (known.as_ref(), other) {
+            (None, other) => *known = other,
+            (Some(kept), Some(other)) if !kept.eq_ignore_ascii_case(&other) => {
+                disagreements.push((field, kept.clone(), other))
+            }
+            _ => {}
+        }
+    }
+    known.birthday = known.birthday.or(other.birthday);
+    known.start_date = known.start_date.or(other.start_date);
+    known.slot = known.slot.or(other.slot);
+
+    // whatever the source calls them finds them from now on
+    for alias in std::iter::once(other.name).chain(other.aliases) {
+        if !known.answers_to(&alias) {
+            known.aliases.push(alias);
+        }
+    }
+
+    disagreements
+}
+
+/// Show what the other sources disagree about or add to the team before the meeting, and ask
+/// whether people the team file doesn't know should join. Without a terminal the findings are
+/// only printed, and `--add-unknown` decides.
+pub fn review(config: &mut Config) -> Result<()> {
+    if config.sources.is_empty() {
+        return Ok(());
+    }
+    let merged = load(config)?;
+    if !merged.needs_review() {
+        return Ok(());
+    }
+
+    eprintln!(
+        "Merged {} with {}: {} people",
+        config.names_file,
+        config.sources.join(", "),
+        merged.members.len()
+    );
+
+    if !merged.disagreements.is_empty() {
+        eprintln!("Conflicts, keeping the first value:");
+        for d in &merged.disagreements {
+            eprintln!(
+                "  {}: {} is {}, but {} says {}",
+                d.name, d.field, d.kept, d.source, d.ignored
+            );
+        }
+    }
+
+    if !merged.unknown.is_empty() {
+        eprintln!("Not in {}:", config.names_file);
+        for unknown in &merged.unknown {
+            eprintln!("  {} (from {})", unknown.member.name, unknown.source);
+        }
+    }
+
+    if !io::stdin().is_terminal() {
+        return Ok(());
+    }
+
+    // the meeting screen would cover all this straight away
+    if config.add_unknown || merged.unknown.is_empty() {
+        eprint!("Press Enter to start the meeting ");
+    } else {
+        eprint!("Add them to today's meeting? [y/N] ");
+    }
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    if matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+        config.add_unknown = true;
+    }
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use chrono::{Datelike, Duration, Local, NaiveDate, Weekday};
+use std::str::FromStr;
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::stats::DateRange;
+
+/// One sprint of the team's calendar, counted from the day sprint 1 started
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sprint {
+    pub number: i64,
+    pub start: NaiveDate,
+    weeks: u32,
+}
+
+impl Sprint {
+    /// The sprint a day falls in, counting the one starting on `epoch` as sprint 1; none before it
+    pub fn on(date: NaiveDate, epoch: NaiveDate, weeks: u32) -> Option<Self> {
+        let days = (date - epoch).num_days();
+        if days < 0 {
+            return None;
+        }
+        Self::numbered(days / Self::length(weeks) + 1, epoch, weeks)
+    }
+
+    /// Sprint `number`, counting the one starting on `epoch` as sprint 1
+    pub fn numbered(number: i64, epoch: NaiveDate, weeks: u32) -> Option<Self> {
+        (number >= 1).then(|| Self {
+            number,
+            start: epoch + Duration::days((number - 1) * Self::length(weeks)),
+            weeks: weeks.max(1),
+        })
+    }
+
+    fn length(weeks: u32) -> i64 {
+        i64::from(weeks.max(1)) * 7
+    }
+
+    /// The sprint's last day
+    pub fn end(&self) -> NaiveDate {
+        self.start + Duration::days(Self::length(self.weeks) - 1)
+    }
+
+    /// Which working day of the sprint `date` is, and how many it has, e.g. (6, 10). Weekends
+    /// count as the working day before them.
+    pub fn day(&self, date: NaiveDate) -> (usize, usize) {
+        let working_days = |until: NaiveDate| {
+            self.start
+                .iter_days()
+                .take_while(|day| *day <= until)
+                .filter(|day| !matches!(day.weekday(), Weekday::Sat | Weekday::Sun))
+                .count()
+        };
+        (working_days(date).max(1), working_days(self.end()))
+    }
+}
+
+/// The sprint the meeting on `date` belongs to, once `--sprint-epoch` says when they started
+pub fn on(config: &Config, date: NaiveDate) -> Option<Sprint> {
+    config
+        .sprint_epoch
+        .and_then(|epoch| Sprint::on(date, epoch, config.sprint_weeks))
+}
+
+/// Which sprint `nextup stats --sprint` is about
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SprintArg {
+    Current,
+    Number(i64),
+}
+
+impl FromStr for SprintArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "current" => Ok(Self::Current),
+            number => match number.parse() {
+                Ok(number) if number >= 1 => Ok(Self::Number(number)),
+                _ => Err(format!(
+                    "unknown sprint `{}`, expected current or a sprint number",
+                    s
+                )),
+            },
+        }
+    }
+}
+
+/// The meetings `--sprint` picks. The current sprint needs `--sprint-epoch`; a numbered one
+/// without it goes only by the sprint meetings were recorded in.
+pub fn range(config: &Config, arg: SprintArg) -> Result<DateRange> {
+    let number = match arg {
+        SprintArg::Number(number) => number,
+        SprintArg::Current => {
+            on(config, Local::now().date_naive())
+                .ok_or_else(|| {
+                    anyhow::Error::msg(
+                        "`--sprint current` needs the day sprint 1 started (--sprint-epoch)",
+                    )
+                })?
+                .number
+        }
+    };
+    let sprint = config
+        .sprint_epoch
+        .and_then(|epoch| Sprint::numbered(number, epoch, config.sprint_weeks));
+    Ok(DateRange::sprint(number, sprint))
+}
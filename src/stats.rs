@@ -0,0 +1,470 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::format::format_duration;
+use crate::history::{History, MeetingRecord};
+use crate::privacy;
+use crate::sprint::{self, Sprint};
+
+// How many recent meetings are listed, and compared against the ones before them
+const RECENT: usize = 10;
+const TREND_WINDOW: usize = 5;
+
+// Width of the score bar
+const BAR_WIDTH: usize = 20;
+
+// Buckets in each person's turn-length histogram, spread over everyone's longest turn
+const HISTOGRAM_BUCKETS: usize = 8;
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// How `nextup stats` prints
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatsFormat {
+    Table,
+    Json,
+}
+
+impl FromStr for StatsFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "table" => Ok(Self::Table),
+            "json" => Ok(Self::Json),
+            _ => Err(format!(
+                "unknown stats format `{}`, expected table or json",
+                s
+            )),
+        }
+    }
+}
+
+/// Which meetings to include by date; open ends are unbounded
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DateRange {
+    pub since: Option<NaiveDate>,
+    pub until: Option<NaiveDate>,
+    // for ranges counted back from today, which are labelled that way
+    days: Option<i64>,
+    // for one sprint's meetings, whose dates are the sprint's when they're known
+    sprint: Option<i64>,
+}
+
+impl DateRange {
+    pub fn new(since: Option<NaiveDate>, until: Option<NaiveDate>) -> Self {
+        Self {
+            since,
+            until,
+            days: None,
+            sprint: None,
+        }
+    }
+
+    /// Sprint `number`'s meetings: those recorded in it, and older ones between its dates
+    pub fn sprint(number: i64, sprint: Option<Sprint>) -> Self {
+        Self {
+            since: sprint.map(|sprint| sprint.start),
+            until: sprint.map(|sprint| sprint.end()),
+            days: None,
+            sprint: Some(number),
+        }
+    }
+
+    /// The last `days` days up to and including today
+    pub fn last_days(today: NaiveDate, days: i64) -> Self {
+        Self {
+            since: Some(today - chrono::Duration::days(days - 1)),
+            until: None,
+            days: Some(days),
+            sprint: None,
+        }
+    }
+
+    pub fn is_bounded(&self) -> bool {
+        self.since.is_some() || self.until.is_some() || self.sprint.is_some()
+    }
+
+    fn contains(&self, date: NaiveDate) -> bool {
+        self.since.is_none_or(|since| date >= since) && self.until.is_none_or(|until| date <= until)
+    }
+
+    /// Whether a meeting is in the range; one recorded in a sprint goes by its sprint
+    pub fn includes(&self, meeting: &MeetingRecord) -> bool {
+        match (self.sprint, meeting.sprint) {
+            (Some(number), Some(sprint)) => sprint == number,
+            (Some(_), None) => self.since.is_some() && self.contains(meeting.date),
+            (None, _) => self.contains(meeting.date),
+        }
+    }
+
+    /// e.g. "Last 30 days", "2025-11-01 to today" or "Sprint 42 (2025-12-08 to 2025-12-21)"
+    pub fn label(&self) -> String {
+        if let Some(number) = self.sprint {
+            return match (self.since, self.until) {
+                (Some(since), Some(until)) => format!("Sprint {} ({} to {})", number, since, until),
+                _ => format!("Sprint {}", number),
+            };
+        }
+        match (self.days, self.since, self.until) {
+            (Some(days), _, _) => format!("Last {} days", days),
+            (None, None, None) => "All time".to_string(),
+            (None, since, until) => format!(
+                "{} to {}",
+                since.map_or("the start".to_string(), |d| d.to_string()),
+                until.map_or("today".to_string(), |d| d.to_string())
+            ),
+        }
+    }
+}
+
+/// How long one person's turns have run across the series
+#[derive(Debug, Serialize)]
+pub struct TurnDurations {
+    pub name: String,
+    pub turns: usize,
+    pub min_secs: u64,
+    pub median_secs: u64,
+    pub max_secs: u64,
+    // turns per bucket of `bucket_secs`, starting from zero
+    pub histogram: Vec<usize>,
+}
+
+/// How often one of the facilitator's checklist items was done, over the meetings that asked it
+#[derive(Debug, Serialize)]
+pub struct ChecklistAdherence {
+    pub item: String,
+    pub meetings: usize,
+    pub done: usize,
+    pub percent: f64,
+}
+
+/// How the meetings of one sprint went
+#[derive(Debug, Serialize)]
+pub struct SprintSummary {
+    pub sprint: i64,
+    pub meetings: usize,
+    pub average_score: f64,
+    pub overtime_secs: u64,
+    pub absent: usize,
+}
+
+/// Everything `--format json` prints
+#[derive(Serialize)]
+struct Report<'a> {
+    series: &'a str,
+    meetings: &'a [MeetingRecord],
+    bucket_secs: u64,
+    speakers: Vec<TurnDurations>,
+    checklist: Vec<ChecklistAdherence>,
+    sprints: Vec<SprintSummary>,
+}
+
+/// Print the health score trend and turn lengths for the configured series
+pub fn run(config: &Config, format: StatsFormat, range: DateRange) -> Result<()> {
+    let mut history = History::load(&config.series)?;
+    privacy::identify(config, &mut history);
+    let meetings: Vec<MeetingRecord> = history
+        .meetings()
+        .iter()
+        .filter(|m| range.includes(m))
+        .cloned()
+        .collect();
+    let meetings = meetings.as_slice();
+
+    if format == StatsFormat::Json {
+        let (bucket, speakers) = turn_durations(meetings);
+        let report = Report {
+            series: config.series.id(),
+            meetings,
+            bucket_secs: bucket.as_secs(),
+            speakers,
+            checklist: checklist_adherence(meetings),
+            sprints: sprint_summaries(config, meetings),
+        };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    if meetings.is_empty() {
+        println!("No meetings recorded yet for {}.", config.series.id());
+        return Ok(());
+    }
+
+    if range.is_bounded() {
+        println!(
+            "Meeting health for {}, {}\n",
+            config.series.id(),
+            range.label()
+        );
+    } else {
+        println!("Meeting health for {}\n", config.series.id());
+    }
+    for meeting in &meetings[meetings.len().saturating_sub(RECENT)..] {
+        println!("{}", meeting_line(meeting));
+    }
+
+    println!(
+        "\nAverage: {:.0} over {} meetings",
+        average(meetings),
+        meetings.len()
+    );
+
+    // compare the latest meetings with the ones just before them
+    if meetings.len() >= TREND_WINDOW * 2 {
+        let (earlier, latest) =
+            meetings[meetings.len() - TREND_WINDOW * 2..].split_at(TREND_WINDOW);
+        let change = average(latest) - average(earlier);
+        let arrow = if change >= 0.5 {
+            "▲"
+        } else if change <= -0.5 {
+            "▼"
+        } else {
+            "="
+        };
+        println!(
+            "Last {}: {:.0} ({} {:.0} from the {} before)",
+            TREND_WINDOW,
+            average(latest),
+            arrow,
+            change.abs(),
+            TREND_WINDOW
+        );
+    }
+
+    let (bucket, speakers) = turn_durations(meetings);
+    if !speakers.is_empty() {
+        print_durations(bucket, &speakers);
+    }
+
+    let checklist = checklist_adherence(meetings);
+    if !checklist.is_empty() {
+        print_checklist(&checklist);
+    }
+
+    // one sprint's meetings are already the ones above
+    let sprints = sprint_summaries(config, meetings);
+    if sprints.len() > 1 {
+        print_sprints(&sprints);
+    }
+
+    Ok(())
+}
+
+/// The meetings grouped by the sprint they were in, oldest sprint first. Meetings recorded
+/// before sprints were set up go by their date, and are left out without `--sprint-epoch`.
+pub fn sprint_summaries(config: &Config, meetings: &[MeetingRecord]) -> Vec<SprintSummary> {
+    let mut by_sprint: BTreeMap<i64, Vec<&MeetingRecord>> = BTreeMap::new();
+    for meeting in meetings {
+        let number = meeting
+            .sprint
+            .or_else(|| sprint::on(config, meeting.date).map(|sprint| sprint.number));
+        if let Some(number) = number {
+            by_sprint.entry(number).or_default().push(meeting);
+        }
+    }
+
+    by_sprint
+        .into_iter()
+        .map(|(sprint, meetings)| SprintSummary {
+            sprint,
+            meetings: meetings.len(),
+            average_score: meetings.iter().map(|m| m.score as f64).sum::<f64>()
+                / meetings.len() as f64,
+            overtime_secs: meetings.iter().map(|m| m.overtime_secs).sum(),
+            absent: meetings.iter().map(|m| m.absent).sum(),
+        })
+        .collect()
+}
+
+/// e.g. `Sprint 42   9 meetings  avg  78  3m 10s over, 4 absent`
+fn print_sprints(sprints: &[SprintSummary]) {
+    println!("\nBy sprint\n");
+    for summary in sprints {
+        let mut line = format!(
+            "Sprint {:<4} {:>3} meetings  avg {:>3.0}",
+            summary.sprint, summary.meetings, summary.average_score
+        );
+        if summary.overtime_secs > 0 {
+            line.push_str(&format!(
+                "  {} over",
+                format_duration(Duration::from_secs(summary.overtime_secs))
+            ));
+        }
+        if summary.absent > 0 {
+            line.push_str(&format!(", {} absent", summary.absent));
+        }
+        println!("{}", line);
+    }
+}
+
+/// Each checklist item the meetings were asked about, in the order they were first asked
+pub fn checklist_adherence(meetings: &[MeetingRecord]) -> Vec<ChecklistAdherence> {
+    let mut items: Vec<ChecklistAdherence> = Vec::new();
+    for checked in meetings.iter().flat_map(|m| &m.checklist) {
+        let index = match items.iter().position(|a| a.item == checked.item) {
+            Some(index) => index,
+            None => {
+                items.push(ChecklistAdherence {
+                    item: checked.item.clone(),
+                    meetings: 0,
+                    done: 0,
+                    percent: 0.0,
+                });
+                items.len() - 1
+            }
+        };
+        let adherence = &mut items[index];
+        adherence.meetings += 1;
+        adherence.done += usize::from(checked.done);
+        adherence.percent = adherence.done as f64 * 100.0 / adherence.meetings as f64;
+    }
+    items
+}
+
+fn print_checklist(items: &[ChecklistAdherence]) {
+    let width = items
+        .iter()
+        .map(|a| a.item.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    println!("\nFacilitator checklist\n");
+    for adherence in items {
+        println!(
+            "{:<width$}  {:>3.0}%  {}/{}",
+            adherence.item,
+            adherence.percent,
+            adherence.done,
+            adherence.meetings,
+            width = width
+        );
+    }
+}
+
+/// Per-person turn lengths, with the histogram bucket width they share
+pub fn turn_durations(meetings: &[MeetingRecord]) -> (Duration, Vec<TurnDurations>) {
+    let mut by_name: BTreeMap<&str, Vec<u64>> = BTreeMap::new();
+    for turn in meetings.iter().flat_map(|m| &m.turns) {
+        by_name.entry(&turn.name).or_default().push(turn.secs);
+    }
+
+    // the same buckets for everyone, so histograms can be compared
+    let longest = by_name.values().flatten().copied().max().unwrap_or(0);
+    let bucket_secs = (longest / HISTOGRAM_BUCKETS as u64 + 1).max(1);
+
+    let speakers = by_name
+        .into_iter()
+        .map(|(name, mut secs)| {
+            secs.sort_unstable();
+            let mut histogram = vec![0; HISTOGRAM_BUCKETS];
+            for s in &secs {
+                histogram[(*s / bucket_secs) as usize] += 1;
+            }
+            TurnDurations {
+                name: name.to_string(),
+                turns: secs.len(),
+                min_secs: secs[0],
+                median_secs: secs[secs.len() / 2],
+                max_secs: secs[secs.len() - 1],
+                histogram,
+            }
+        })
+        .collect();
+
+    (Duration::from_secs(bucket_secs), speakers)
+}
+
+/// e.g. `Kyle Broflovski   12  1m 10s  2m 5s  3m 40s  ▁▃█▅▂▁▁▁`
+fn print_durations(bucket: Duration, speakers: &[TurnDurations]) {
+    let width = speakers
+        .iter()
+        .map(|s| s.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Name".len());
+    let secs = |s: u64| format_duration(Duration::from_secs(s));
+
+    println!(
+        "\nTurn lengths (each bar is {}, from 0s on the left)\n",
+        format_duration(bucket)
+    );
+    println!(
+        "{:<width$}  {:>5}  {:>7}  {:>7}  {:>7}  Histogram",
+        "Name",
+        "Turns",
+        "Min",
+        "Median",
+        "Max",
+        width = width
+    );
+    for speaker in speakers {
+        println!(
+            "{:<width$}  {:>5}  {:>7}  {:>7}  {:>7}  {}",
+            speaker.name,
+            speaker.turns,
+            secs(speaker.min_secs),
+            secs(speaker.median_secs),
+            secs(speaker.max_secs),
+            sparkline(&speaker.histogram),
+            width = width
+        );
+    }
+}
+
+/// Bucket counts as block characters scaled to the fullest bucket; empty buckets are blank
+pub fn sparkline(counts: &[usize]) -> String {
+    let max = counts.iter().copied().max().unwrap_or(0).max(1);
+    counts
+        .iter()
+        .map(|&count| {
+            if count == 0 {
+                ' '
+            } else {
+                SPARK_LEVELS[(count * SPARK_LEVELS.len()).div_ceil(max) - 1]
+            }
+        })
+        .collect()
+}
+
+/// e.g. `2025-12-15  ████████████████░░░░  82  16m 10s (1m 10s over), 2m paused, 1 absent`
+fn meeting_line(meeting: &MeetingRecord) -> String {
+    let filled = meeting.score as usize * BAR_WIDTH / 100;
+    let mut line = format!(
+        "{}  {}{}  {:>3}  {}",
+        meeting.date,
+        "█".repeat(filled),
+        "░".repeat(BAR_WIDTH - filled),
+        meeting.score,
+        format_duration(Duration::from_secs(meeting.elapsed_secs))
+    );
+
+    if meeting.overtime_secs > 0 {
+        line.push_str(&format!(
+            " ({} over)",
+            format_duration(Duration::from_secs(meeting.overtime_secs))
+        ));
+    }
+    if meeting.paused_secs > 0 {
+        line.push_str(&format!(
+            ", {} paused",
+            format_duration(Duration::from_secs(meeting.paused_secs))
+        ));
+    }
+    if meeting.absent > 0 {
+        line.push_str(&format!(", {} absent", meeting.absent));
+    }
+    if !meeting.checklist.is_empty() {
+        let done = meeting.checklist.iter().filter(|c| c.done).count();
+        line.push_str(&format!(", checklist {}/{}", done, meeting.checklist.len()));
+    }
+    line
+}
+
+fn average(meetings: &[MeetingRecord]) -> f64 {
+    meetings.iter().map(|m| m.score as f64).sum::<f64>() / meetings.len() as f64
+}
@@ -0,0 +1,73 @@
+use std::path::Path;
+use std::process::Command;
+
+use crate::error::{AppError, Result};
+
+/// Whether the state directory is a git checkout that should be synced
+pub fn is_git_store(root: &Path) -> bool {
+    root.join(".git").exists()
+}
+
+/// Pull the latest shared state before reading it
+pub fn pull(root: &Path) -> Result<()> {
+    if !is_git_store(root) {
+        return Ok(());
+    }
+
+    git(root, &["pull", "--rebase", "--autostash", "--quiet"])?;
+    Ok(())
+}
+
+/// Commit local state changes and push them so other hosts see them
+pub fn push(root: &Path, message: &str) -> Result<()> {
+    if !is_git_store(root) {
+        return Ok(());
+    }
+
+    // locks and autosaves describe meetings running on this host only
+    git(
+        root,
+        &[
+            "add",
+            "--all",
+            "--",
+            ".",
+            ":(exclude,glob)series/*/lock",
+            ":(exclude,glob)series/*/autosave.json*",
+        ],
+    )?;
+
+    // nothing staged means nothing to share
+    if git(root, &["diff", "--cached", "--quiet"]).is_ok() {
+        return Ok(());
+    }
+    git(root, &["commit", "--quiet", "-m", message])?;
+
+    // someone else may have pushed during the meeting; rebase once and retry
+    if git(root, &["push", "--quiet"]).is_err() {
+        if let Err(e) = git(root, &["pull", "--rebase", "--quiet"]) {
+            let _ = git(root, &["rebase", "--abort"]);
+            return Err(e);
+        }
+        git(root, &["push", "--quiet"])?;
+    }
+
+    Ok(())
+}
+
+/// Run a git command in the state directory
+fn git(root: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(args)
+        .output()
+        .map_err(|e| AppError::SyncError(format!("git: {}", e)))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::SyncError(format!("git {}: {}", args[0], stderr.trim())).into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use minijinja::Environment;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::export;
+
+/// Every template nextup renders, with its built-in text
+const BUILT_IN: &[(&str, &str)] = &[
+    ("transcript.md", include_str!("../templates/transcript.md")),
+    (
+        "transcript.org",
+        include_str!("../templates/transcript.org"),
+    ),
+    (
+        "slack/order.txt",
+        include_str!("../templates/slack/order.txt"),
+    ),
+    (
+        "slack/start.txt",
+        include_str!("../templates/slack/start.txt"),
+    ),
+    (
+        "slack/announcement.txt",
+        include_str!("../templates/slack/announcement.txt"),
+    ),
+    (
+        "slack/summary.txt",
+        include_str!("../templates/slack/summary.txt"),
+    ),
+    (
+        "slack/checkin.txt",
+        include_str!("../templates/slack/checkin.txt"),
+    ),
+    (
+        "slack/status.txt",
+        include_str!("../templates/slack/status.txt"),
+    ),
+    (
+        "slack/reminder.txt",
+        include_str!("../templates/slack/reminder.txt"),
+    ),
+    (
+        "slack/breakout.txt",
+        include_str!("../templates/slack/breakout.txt"),
+    ),
+    ("title.txt", include_str!("../templates/title.txt")),
+    ("santa.txt", include_str!("../templates/santa.txt")),
+    (
+        "notify/notification.txt",
+        include_str!("../templates/notify/notification.txt"),
+    ),
+    (
+        "notify/speech.txt",
+        include_str!("../templates/notify/speech.txt"),
+    ),
+];
+
+/// The Jinja templates messages and exports are rendered from. Each is the built-in one unless
+/// the templates directory has a file of the same name.
+#[derive(Clone)]
+pub struct Templates {
+    env: Environment<'static>,
+}
+
+impl Templates {
+    /// Load the templates, overridden from the templates directory, and `--transcript-template`
+    /// for the transcript in either format. A mistake in any of them is reported here rather than mid-meeting.
+    pub fn load(config: &Config) -> Result<Self> {
+        let mut env = Environment::new();
+        // block tags on lines of their own leave no blank lines behind
+        env.set_trim_blocks(true);
+        env.set_lstrip_blocks(true);
+
+        let dir = dir();
+        for (name, built_in) in BUILT_IN {
+            let file = match (*name, &config.transcript_template) {
+                (name, Some(file)) if name.starts_with("transcript.") => Some(file.clone()),
+                _ => dir
+                    .as_ref()
+                    .map(|dir| dir.join(name))
+                    .filter(|f| f.exists()),
+            };
+            let source = match &file {
+                Some(file) => read(file)?,
+                None => built_in.to_string(),
+            };
+            env.add_template_owned(*name, source).map_err(|e| {
+                let name = file.map_or(name.to_string(), |f| f.display().to_string());
+                AppError::TemplateError(format!("{}: {}", name, e))
+            })?;
+        }
+
+        Ok(Self { env })
+    }
+
+    /// Fill in a template. Trailing blank lines are dropped, so a template that renders to
+    /// nothing leaves its message out.
+    pub fn render<S: Serialize>(&self, name: &str, context: S) -> Result<String> {
+        let text = self
+            .env
+            .get_template(name)
+            .and_then(|template| template.render(context))
+            .map_err(|e| AppError::TemplateError(format!("{}: {}", name, e)))?;
+        Ok(text.trim_end().to_string())
+    }
+}
+
+/// Where templates are overridden: `nextup/templates` in the config directory
+pub fn dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("nextup").join("templates"))
+}
+
+fn read(file: &Path) -> Result<String> {
+    fs::read_to_string(file)
+        .map_err(|e| AppError::TemplateError(format!("{}: {}", file.display(), e)).into())
+}
+
+/// List the templates and which of them are overridden, for `nextup templates`
+pub fn list() -> Result<()> {
+    let dir = dir().ok_or_else(|| anyhow::Error::msg("couldn't find the config directory"))?;
+    let mut text = format!("Templates in {}:\n", dir.display());
+    for (name, _) in BUILT_IN {
+        let state = if dir.join(name).exists() {
+            "yours"
+        } else {
+            "built-in"
+        };
+        text.push_str(&format!("  {:<26} {}\n", name, state));
+    }
+    text.push_str("Copy the built-in ones there to edit with `nextup templates init`.\n");
+    export::to_stdout(&text)
+}
+
+/// Copy the built-in templates into the templates directory, leaving ones already there alone
+pub fn init() -> Result<()> {
+    let dir = dir().ok_or_else(|| anyhow::Error::msg("couldn't find the config directory"))?;
+    for (name, built_in) in BUILT_IN {
+        let path = dir.join(name);
+        if path.exists() {
+            println!("Kept {}", path.display());
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, built_in)?;
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
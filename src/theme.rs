@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::error::{AppError, Result};
+use crate::format::format_duration;
+
+/// How the meeting's countdown is drawn
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimerStyle {
+    // a bar across the screen that empties as time runs out
+    Bar,
+    // a clock face whose pie of remaining time shrinks, next to the time left
+    Analog,
+}
+
+impl FromStr for TimerStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "bar" => Ok(Self::Bar),
+            "analog" | "clock" => Ok(Self::Analog),
+            _ => Err(format!(
+                "unknown timer style `{}`, expected bar or analog",
+                s
+            )),
+        }
+    }
+}
+
+/// How one stretch of the meeting is shown on the timer: an icon and the word after the time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Step {
+    #[serde(default)]
+    pub icon: String,
+    #[serde(default)]
+    pub label: String,
+}
+
+impl Step {
+    fn new(icon: &str, label: &str) -> Self {
+        Self {
+            icon: icon.to_string(),
+            label: label.to_string(),
+        }
+    }
+}
+
+/// Icons and labels for the timer: while there's plenty of time, once it's time to wrap up, and
+/// once time is up. A theme is one of the built-in names or a JSON file such as
+/// `{"plenty": {"icon": "🟢", "label": "left"}, "wrap_up_secs": 120, "emoji": true}`;
+/// steps and settings the file leaves out come from the default theme.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimerTheme {
+    pub plenty: Step,
+    pub wrap_up: Step,
+    pub over: Step,
+    // the timer switches to the wrap-up step with this much of the meeting left
+    pub wrap_up_secs: u64,
+    // false leaves the icons out everywhere, e.g. for terminals without emoji fonts
+    pub emoji: bool,
+}
+
+impl Default for TimerTheme {
+    fn default() -> Self {
+        Self {
+            plenty: Step::new("⏳", "left"),
+            wrap_up: Step::new("⌛", "left"),
+            over: Step::new("⌛", "over"),
+            wrap_up_secs: 180,
+            emoji: true,
+        }
+    }
+}
+
+impl TimerTheme {
+    /// A built-in theme by name, or a theme file
+    pub fn load(name: &str) -> Result<Self> {
+        let theme = match name {
+            "default" => Self::default(),
+            "plain" => Self {
+                emoji: false,
+                ..Self::default()
+            },
+            "traffic" => Self {
+                plenty: Step::new("🟢", "left"),
+                wrap_up: Step::new("🟡", "left, wrap up"),
+                over: Step::new("🔴", "over"),
+                ..Self::default()
+            },
+            // for escalation rules to switch to as time runs short
+            "urgent" => Self {
+                plenty: Step::new("🔥", "left, wrap up now"),
+                wrap_up: Step::new("🔥", "left, wrap up now"),
+                over: Step::new("🚨", "over"),
+                ..Self::default()
+            },
+            "words" => Self {
+                plenty: Step::new("", "left, plenty of time"),
+                wrap_up: Step::new("", "left, wrap up"),
+                over: Step::new("", "over, time's up"),
+                emoji: false,
+                ..Self::default()
+            },
+            path => {
+                let content = fs::read_to_string(path).map_err(|e| {
+                    AppError::ThemeError(format!(
+                        "`{}` is neither a built-in theme nor a readable file: {}",
+                        path, e
+                    ))
+                })?;
+                serde_json::from_str(&content)
+                    .map_err(|e| AppError::ThemeError(format!("{}: {}", path, e)))?
+            }
+        };
+        Ok(theme)
+    }
+
+    /// Which step the meeting is at
+    fn step(&self, remaining: Duration, over: bool) -> &Step {
+        if over {
+            &self.over
+        } else if remaining.as_secs() > self.wrap_up_secs {
+            &self.plenty
+        } else {
+            &self.wrap_up
+        }
+    }
+
+    /// The icon for this point in the meeting, empty when the theme has none
+    pub fn icon(&self, remaining: Duration, over: bool) -> &str {
+        if self.emoji {
+            &self.step(remaining, over).icon
+        } else {
+            ""
+        }
+    }
+
+    /// e.g. "⏳ 14m 57s left" or "⌛ +1m 5s over"
+    pub fn timer_text(&self, remaining: Duration, overtime: Option<Duration>) -> String {
+        let step = self.step(remaining, overtime.is_some());
+        let time = match overtime {
+            Some(overtime) => format!("+{}", format_duration(overtime)),
+            None => format_duration(remaining),
+        };
+
+        let mut text = self.icon(remaining, overtime.is_some()).to_string();
+        for part in [time.as_str(), step.label.as_str()] {
+            if !part.is_empty() {
+                if !text.is_empty() {
+                    text.push(' ');
+                }
+                text.push_str(part);
+            }
+        }
+        text
+    }
+}
@@ -0,0 +1,40 @@
+use chrono::{DateTime, Local, Timelike};
+use minijinja::context;
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::sprint;
+use crate::templates::Templates;
+
+/// Hello for the time of day, e.g. "Good morning"
+pub fn greeting(now: DateTime<Local>) -> &'static str {
+    match now.hour() {
+        0..12 => "Good morning",
+        12..18 => "Good afternoon",
+        _ => "Good evening",
+    }
+}
+
+/// The meeting's title for today from the `title.txt` template, e.g. "Standup — Tue, Sprint 42"
+pub fn render(config: &Config, templates: &Templates) -> Result<String> {
+    let now = Local::now();
+    let today = now.date_naive();
+    templates.render(
+        "title.txt",
+        context! {
+            greeting => greeting(now),
+            weekday => today.format("%a").to_string(),
+            date => today.format("%Y-%m-%d").to_string(),
+            sprint => sprint::on(config, today).map(|sprint| sprint.number),
+        },
+    )
+}
+
+/// Bring a generated title up to date for a meeting on another day, e.g. in the daemon. A title
+/// given with `--title` or saved with the series stays as it is.
+pub fn refresh(config: &mut Config) -> Result<()> {
+    if config.auto_title {
+        config.title = render(config, &Templates::load(config)?)?;
+    }
+    Ok(())
+}
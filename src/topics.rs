@@ -0,0 +1,79 @@
+use std::fs;
+
+use crate::error::{AppError, Result};
+use crate::roster::{self, Member};
+
+/// Something a member wants to bring up during their turn
+#[derive(Debug, Clone)]
+pub struct Topic {
+    pub name: String,
+    pub text: String,
+    pub done: bool,
+}
+
+/// Topics submitted ahead of or during the meeting, in submission order
+#[derive(Debug, Default)]
+pub struct Topics {
+    topics: Vec<Topic>,
+}
+
+impl Topics {
+    /// Load `Name: topic` lines; blank lines and `#` comments are ignored
+    pub fn load(filename: &str, members: &[Member]) -> Result<Self> {
+        let content = fs::read_to_string(filename).map_err(AppError::TopicsFileError)?;
+        let mut topics = Self::default();
+
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = |message: String| AppError::TopicsError {
+                path: filename.to_string(),
+                line: i + 1,
+                message,
+            };
+            let (name, text) = line
+                .split_once(':')
+                .ok_or_else(|| invalid("expected `Name: topic`".to_string()))?;
+            let (name, text) = (name.trim(), text.trim());
+            let Some(member) = roster::find(members, name) else {
+                return Err(invalid(format!("`{}` isn't in the team file", name)).into());
+            };
+            if !text.is_empty() {
+                topics.add(&member.name, text);
+            }
+        }
+
+        Ok(topics)
+    }
+
+    pub fn add(&mut self, name: &str, text: &str) {
+        self.topics.push(Topic {
+            name: name.to_string(),
+            text: text.to_string(),
+            done: false,
+        });
+    }
+
+    /// Someone's topics, finished ones included
+    pub fn for_name<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Topic> {
+        self.topics.iter().filter(move |topic| topic.name == name)
+    }
+
+    /// Check off someone's first open topic
+    pub fn check_off(&mut self, name: &str) -> Option<&Topic> {
+        let topic = self
+            .topics
+            .iter_mut()
+            .find(|topic| topic.name == name && !topic.done)?;
+        topic.done = true;
+        Some(topic)
+    }
+
+    /// Topics nobody got to
+    pub fn unfinished(&self) -> impl Iterator<Item = &Topic> {
+        self.topics.iter().filter(|topic| !topic.done)
+    }
+}
@@ -0,0 +1,406 @@
+use serde_json::{Value, json};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::meeting::Meeting;
+use crate::queue::{Job, Queue};
+use crate::session::Phase;
+
+// Reported as the trace's `service.name` and instrumentation scope
+const SERVICE_NAME: &str = "nextup";
+
+/// A turn that has ended, ready to be exported as a child span
+struct TurnSpan {
+    span_id: String,
+    name: String,
+    round: usize,
+    start: SystemTime,
+    end: SystemTime,
+    spoken: Duration,
+    slot: Duration,
+    overtime: Duration,
+    topics: usize,
+    topics_done: usize,
+    cut_off: bool,
+}
+
+/// The turn under way
+struct OpenTurn {
+    span_id: String,
+    name: String,
+    round: usize,
+    start: SystemTime,
+    // what they had already spoken this round, e.g. when coming back to them
+    spoken_before: Duration,
+}
+
+/// Records the meeting as a trace -- the meeting is the root span and each turn a child span --
+/// and when the meeting ends sends it to an OTLP/HTTP collector, writes it as a Chrome trace
+/// file for Perfetto, or both
+pub struct Tracer {
+    endpoint: Option<String>,
+    chrome_trace: Option<PathBuf>,
+    trace_id: String,
+    root_span_id: String,
+    start: SystemTime,
+    // the last update, taken as the end of the meeting (not when it's exported after a closing screen)
+    last_update: SystemTime,
+    turns: Vec<TurnSpan>,
+    current: Option<OpenTurn>,
+}
+
+impl Tracer {
+    /// Start tracing when an OTLP endpoint or a Chrome trace file is configured
+    pub fn new(config: &Config) -> Option<Self> {
+        if config.otlp_endpoint.is_none() && config.chrome_trace.is_none() {
+            return None;
+        }
+
+        Some(Self {
+            endpoint: config
+                .otlp_endpoint
+                .as_ref()
+                .map(|endpoint| format!("{}/v1/traces", endpoint.trim_end_matches('/'))),
+            chrome_trace: config.chrome_trace.clone(),
+            trace_id: random_id(16),
+            root_span_id: random_id(8),
+            start: SystemTime::now(),
+            last_update: SystemTime::now(),
+            turns: Vec::new(),
+            current: None,
+        })
+    }
+
+    /// Close the current turn's span and open the next one when the speaker changes
+    pub fn update(&mut self, meeting: &Meeting) {
+        let session = &meeting.session;
+        let speaking = session.phase() != Phase::WarmUp;
+        let round = session.current_round();
+        let name = session.current_name();
+        self.last_update = SystemTime::now();
+
+        if let Some(turn) = &self.current
+            && speaking
+            && turn.round == round
+            && turn.name == name
+        {
+            return;
+        }
+        self.close_turn(meeting);
+
+        if speaking {
+            self.current = Some(OpenTurn {
+                span_id: random_id(8),
+                name: name.to_string(),
+                round,
+                start: self.last_update,
+                spoken_before: spoken(meeting, name, round),
+            });
+        }
+    }
+
+    fn close_turn(&mut self, meeting: &Meeting) {
+        let Some(turn) = self.current.take() else {
+            return;
+        };
+        let session = &meeting.session;
+        let Some(index) = session
+            .speakers()
+            .iter()
+            .position(|s| s.name() == turn.name)
+        else {
+            return;
+        };
+
+        let spoken_now = spoken(meeting, &turn.name, turn.round);
+        let slot = session.slot(index);
+        let topics: Vec<bool> = meeting.topics_for(&turn.name).map(|t| t.done).collect();
+        self.turns.push(TurnSpan {
+            span_id: turn.span_id,
+            round: turn.round,
+            start: turn.start,
+            end: self.last_update,
+            spoken: spoken_now.saturating_sub(turn.spoken_before),
+            slot,
+            // only what this turn added beyond the slot
+            overtime: spoken_now.saturating_sub(slot) - turn.spoken_before.saturating_sub(slot),
+            topics: topics.len(),
+            topics_done: topics.iter().filter(|done| **done).count(),
+            cut_off: session.speakers()[index].cut_off,
+            name: turn.name,
+        });
+    }
+
+    /// Write the trace file and queue the trace for the collector; call once the meeting has
+    /// been closed
+    pub fn export(&mut self, config: &Config, meeting: &Meeting, queue: &Queue) -> Result<()> {
+        // whoever runs the meeting reports it
+        if meeting.is_read_only() || meeting.is_taken_over() {
+            return Ok(());
+        }
+        self.close_turn(meeting);
+
+        if let Some(path) = &self.chrome_trace {
+            let trace = self.chrome_trace(config, meeting);
+            fs::write(path, serde_json::to_string_pretty(&trace)?)
+                .map_err(|e| AppError::TraceError(format!("{}: {}", path.display(), e)))?;
+        }
+        if let Some(endpoint) = &self.endpoint {
+            // a build without OTLP says so now, rather than leaving a trace that can't be sent
+            check_built_in()?;
+            queue.push(Job::Trace {
+                endpoint: endpoint.clone(),
+                body: self.otlp_trace(config, meeting),
+            });
+        }
+        Ok(())
+    }
+
+    /// The trace in OTLP's JSON encoding
+    fn otlp_trace(&self, config: &Config, meeting: &Meeting) -> Value {
+        let session = &meeting.session;
+        let mut attributes = vec![
+            attribute("nextup.series", json!(config.series.id())),
+            int_attribute("nextup.duration_secs", session.duration().as_secs()),
+            int_attribute("nextup.elapsed_secs", session.elapsed().as_secs()),
+            int_attribute("nextup.speakers", session.speakers().len() as u64),
+        ];
+        if let Some(health) = meeting.health() {
+            attributes.push(int_attribute("nextup.health_score", health.score.into()));
+            attributes.push(int_attribute(
+                "nextup.overtime_secs",
+                health.overtime.as_secs(),
+            ));
+            attributes.push(int_attribute("nextup.absent", health.absent as u64));
+        }
+
+        let mut spans = vec![json!({
+            "traceId": self.trace_id,
+            "spanId": self.root_span_id,
+            "name": config.title,
+            "kind": 1,
+            "startTimeUnixNano": unix_nanos(self.start),
+            "endTimeUnixNano": unix_nanos(self.last_update),
+            "attributes": attributes,
+        })];
+        for turn in &self.turns {
+            let mut attributes = vec![
+                attribute("nextup.speaker", json!(turn.name)),
+                int_attribute("nextup.round", turn.round as u64 + 1),
+                int_attribute("nextup.spoken_secs", turn.spoken.as_secs()),
+                int_attribute("nextup.slot_secs", turn.slot.as_secs()),
+                int_attribute("nextup.overtime_secs", turn.overtime.as_secs()),
+                int_attribute("nextup.topics", turn.topics as u64),
+                int_attribute("nextup.topics_done", turn.topics_done as u64),
+            ];
+            if turn.cut_off {
+                attributes.push(attribute("nextup.cut_off", json!({ "boolValue": true })));
+            }
+            if session.is_multi_round() {
+                attributes.push(attribute(
+                    "nextup.round_name",
+                    json!(round_name(meeting, turn.round)),
+                ));
+            }
+
+            spans.push(json!({
+                "traceId": self.trace_id,
+                "spanId": turn.span_id,
+                "parentSpanId": self.root_span_id,
+                "name": turn.name,
+                "kind": 1,
+                "startTimeUnixNano": unix_nanos(turn.start),
+                "endTimeUnixNano": unix_nanos(turn.end),
+                "attributes": attributes,
+            }));
+        }
+
+        json!({
+            "resourceSpans": [{
+                "resource": {
+                    "attributes": [attribute("service.name", json!(SERVICE_NAME))],
+                },
+                "scopeSpans": [{
+                    "scope": { "name": SERVICE_NAME, "version": env!("CARGO_PKG_VERSION") },
+                    "spans": spans,
+                }],
+            }],
+        })
+    }
+
+    /// The trace in Chrome's trace event format: the meeting on a track of its own and a track
+    /// per speaker, with each turn a complete event on its speaker's track. Times are in
+    /// microseconds from the start of the meeting.
+    fn chrome_trace(&self, config: &Config, meeting: &Meeting) -> Value {
+        let session = &meeting.session;
+        let micros = |time: SystemTime| {
+            time.duration_since(self.start)
+                .unwrap_or_default()
+                .as_micros() as u64
+        };
+        // a track per speaker, in the order, after the meeting's
+        let track = |name: &str| {
+            session
+                .speakers()
+                .iter()
+                .position(|s| s.name() == name)
+                .map_or(0, |i| i + 1)
+        };
+
+        let mut events = vec![json!({
+            "name": "thread_name",
+            "ph": "M",
+            "pid": 1,
+            "tid": 0,
+            "args": { "name": "Meeting" },
+        })];
+        for (i, speaker) in session.speakers().iter().enumerate() {
+            events.push(json!({
+                "name": "thread_name",
+                "ph": "M",
+                "pid": 1,
+                "tid": i + 1,
+                "args": { "name": speaker.name() },
+            }));
+        }
+
+        let mut args = json!({
+            "series": config.series.id(),
+            "duration_secs": session.duration().as_secs(),
+            "elapsed_secs": session.elapsed().as_secs(),
+        });
+        if let Some(health) = meeting.health() {
+            args["health_score"] = json!(health.score);
+            args["overtime_secs"] = json!(health.overtime.as_secs());
+            args["absent"] = json!(health.absent);
+        }
+        events.push(json!({
+            "name": config.title,
+            "cat": "meeting",
+            "ph": "X",
+            "pid": 1,
+            "tid": 0,
+            "ts": 0,
+            "dur": micros(self.last_update),
+            "args": args,
+        }));
+
+        for turn in &self.turns {
+            let mut args = json!({
+                "round": turn.round + 1,
+                "spoken_secs": turn.spoken.as_secs(),
+                "slot_secs": turn.slot.as_secs(),
+                "overtime_secs": turn.overtime.as_secs(),
+                "topics": turn.topics,
+                "topics_done": turn.topics_done,
+                "cut_off": turn.cut_off,
+            });
+            if session.is_multi_round() {
+                args["round_name"] = json!(round_name(meeting, turn.round));
+            }
+            events.push(json!({
+                "name": turn.name,
+                "cat": "turn",
+                "ph": "X",
+                "pid": 1,
+                "tid": track(&turn.name),
+                "ts": micros(turn.start),
+                "dur": micros(turn.end).saturating_sub(micros(turn.start)),
+                "args": args,
+            }));
+        }
+
+        json!({
+            "traceEvents": events,
+            "displayTimeUnit": "ms",
+            "otherData": { "service": SERVICE_NAME, "version": env!("CARGO_PKG_VERSION") },
+        })
+    }
+}
+
+/// Send a trace in OTLP's JSON encoding to a collector
+#[cfg(feature = "otlp")]
+pub fn send(endpoint: &str, headers: &[(String, String)], body: Value) -> Result<()> {
+    let mut request = ureq::post(endpoint);
+    for (name, value) in headers {
+        request = request.set(name, value);
+    }
+    request
+        .send_json(body)
+        .map_err(|e| AppError::TraceError(format!("{}: {}", endpoint, e)))?;
+    Ok(())
+}
+
+// built without the `otlp` feature, the trace has nowhere to go
+#[cfg(not(feature = "otlp"))]
+pub fn send(_endpoint: &str, _headers: &[(String, String)], _body: Value) -> Result<()> {
+    Err(not_built_in())
+}
+
+#[cfg(feature = "otlp")]
+fn check_built_in() -> Result<()> {
+    Ok(())
+}
+
+#[cfg(not(feature = "otlp"))]
+fn check_built_in() -> Result<()> {
+    Err(not_built_in())
+}
+
+#[cfg(not(feature = "otlp"))]
+fn not_built_in() -> anyhow::Error {
+    AppError::NotBuiltIn {
+        what: "OpenTelemetry export",
+        feature: "otlp",
+    }
+    .into()
+}
+
+/// Time spoken by someone in a round
+fn spoken(meeting: &Meeting, name: &str, round: usize) -> Duration {
+    meeting
+        .session
+        .speakers()
+        .iter()
+        .find(|s| s.name() == name)
+        .map_or(Duration::ZERO, |s| s.round_times[round])
+}
+
+fn round_name(meeting: &Meeting, round: usize) -> String {
+    meeting
+        .session
+        .round_totals()
+        .get(round)
+        .map_or_else(String::new, |(name, _)| name.to_string())
+}
+
+/// An OTLP key-value pair; plain strings become string values
+fn attribute(key: &str, value: Value) -> Value {
+    let value = match value {
+        Value::String(s) => json!({ "stringValue": s }),
+        value => value,
+    };
+    json!({ "key": key, "value": value })
+}
+
+fn int_attribute(key: &str, value: u64) -> Value {
+    // OTLP's JSON encoding carries 64-bit integers as strings
+    attribute(key, json!({ "intValue": value.to_string() }))
+}
+
+fn unix_nanos(time: SystemTime) -> String {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+        .to_string()
+}
+
+/// A random trace or span ID of `bytes` bytes, hex-encoded
+fn random_id(bytes: usize) -> String {
+    (0..bytes)
+        .map(|_| format!("{:02x}", rand::random::<u8>()))
+        .collect()
+}
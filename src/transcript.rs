@@ -0,0 +1,209 @@
+use chrono::{DateTime, Local, Timelike};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::format::format_duration;
+use crate::meeting::Meeting;
+use crate::session::Phase;
+use crate::templates::Templates;
+
+/// What kind of document the transcript is written as
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TranscriptFormat {
+    Markdown,
+    // Emacs org-mode, with the turns as clock lines and topics as TODO entries
+    Org,
+}
+
+impl FromStr for TranscriptFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "markdown" | "md" => Ok(Self::Markdown),
+            "org" => Ok(Self::Org),
+            _ => Err(format!(
+                "unknown transcript format `{}`, expected markdown or org",
+                s
+            )),
+        }
+    }
+}
+
+impl TranscriptFormat {
+    /// The format a transcript file's name asks for: org-mode for `.org`, Markdown otherwise
+    fn for_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("org") => Self::Org,
+            _ => Self::Markdown,
+        }
+    }
+
+    fn template(self) -> &'static str {
+        match self {
+            Self::Markdown => "transcript.md",
+            Self::Org => "transcript.org",
+        }
+    }
+}
+
+/// A stretch of one person speaking, by the clock
+struct Stretch {
+    name: String,
+    start: DateTime<Local>,
+    end: DateTime<Local>,
+}
+
+/// What the template sees
+#[derive(Serialize)]
+struct Context {
+    title: String,
+    date: String,
+    start: String,
+    end: String,
+    speakers: Vec<SpeakerContext>,
+}
+
+#[derive(Serialize)]
+struct SpeakerContext {
+    name: String,
+    // e.g. "09:31–09:33", one per time they had the floor
+    turns: Vec<String>,
+    // the same as org-mode clock lines, e.g. "[2025-11-03 Mon 09:31]--[2025-11-03 Mon 09:33] =>  0:02"
+    clocks: Vec<String>,
+    spoken: String,
+    absent: bool,
+    topics: Vec<TopicContext>,
+    update: Option<String>,
+}
+
+#[derive(Serialize)]
+struct TopicContext {
+    text: String,
+    done: bool,
+}
+
+/// Keeps track of who spoke when, to write a transcript skeleton when the meeting ends: a
+/// heading per speaker with the times they spoke, their written update and their topics, for
+/// notes to be filled in
+pub struct Transcript {
+    templates: Templates,
+    started: DateTime<Local>,
+    stretches: Vec<Stretch>,
+}
+
+impl Transcript {
+    /// Start keeping track when a transcript file is configured
+    pub fn new(config: &Config, templates: &Templates) -> Option<Self> {
+        config.transcript.as_ref()?;
+        Some(Self {
+            templates: templates.clone(),
+            started: Local::now(),
+            stretches: Vec::new(),
+        })
+    }
+
+    /// Extend the current speaker's stretch, or start a new one when the speaker changes
+    pub fn update(&mut self, meeting: &Meeting) {
+        let session = &meeting.session;
+        if session.phase() == Phase::WarmUp || session.is_paused() {
+            return;
+        }
+        let now = Local::now();
+        let name = session.current_name();
+
+        match self.stretches.last_mut() {
+            Some(stretch) if stretch.name == name => stretch.end = now,
+            _ => self.stretches.push(Stretch {
+                name: name.to_string(),
+                start: now,
+                end: now,
+            }),
+        }
+    }
+
+    /// Fill in the template and write the transcript
+    pub fn write(&self, config: &Config, meeting: &Meeting) -> Result<()> {
+        let Some(path) = &config.transcript else {
+            return Ok(());
+        };
+        let format = config
+            .transcript_format
+            .unwrap_or_else(|| TranscriptFormat::for_path(path));
+        let text = self
+            .templates
+            .render(format.template(), self.context(config, meeting))?;
+
+        fs::write(path, text + "\n")?;
+        println!("Transcript saved to {}", path.display());
+        Ok(())
+    }
+
+    fn context(&self, config: &Config, meeting: &Meeting) -> Context {
+        let time = |t: &DateTime<Local>| t.format("%H:%M").to_string();
+        let speakers = meeting
+            .session
+            .speakers()
+            .iter()
+            .enumerate()
+            .map(|(index, speaker)| SpeakerContext {
+                name: speaker.name().to_string(),
+                turns: self
+                    .stretches
+                    .iter()
+                    .filter(|s| s.name == speaker.name())
+                    .map(|s| format!("{}–{}", time(&s.start), time(&s.end)))
+                    .collect(),
+                clocks: self
+                    .stretches
+                    .iter()
+                    .filter(|s| s.name == speaker.name())
+                    .map(org_clock)
+                    .collect(),
+                spoken: format_duration(speaker.total()),
+                absent: meeting.session.is_absent(index),
+                topics: meeting
+                    .topics_for(speaker.name())
+                    .map(|topic| TopicContext {
+                        text: topic.text.clone(),
+                        done: topic.done,
+                    })
+                    .collect(),
+                update: meeting
+                    .check_in(speaker.name())
+                    .and_then(|check_in| check_in.update.clone()),
+            })
+            .collect();
+
+        let end = self.stretches.last().map_or(self.started, |s| s.end);
+        Context {
+            title: config.title.clone(),
+            date: self.started.format("%Y-%m-%d").to_string(),
+            start: time(&self.started),
+            end: time(&end),
+            speakers,
+        }
+    }
+}
+
+/// A stretch as org-mode clocks it, to the minute
+fn org_clock(stretch: &Stretch) -> String {
+    let minute = |t: &DateTime<Local>| t.with_second(0).and_then(|t| t.with_nanosecond(0));
+    let (start, end) = (
+        minute(&stretch.start).unwrap_or(stretch.start),
+        minute(&stretch.end).unwrap_or(stretch.end),
+    );
+    let minutes = (end - start).num_minutes();
+    let stamp = |t: &DateTime<Local>| t.format("[%Y-%m-%d %a %H:%M]").to_string();
+    format!(
+        "{}--{} => {:>2}:{:02}",
+        stamp(&start),
+        stamp(&end),
+        minutes / 60,
+        minutes % 60
+    )
+}
@@ -1,134 +1,1345 @@
-use crate::app::App;
-use ratatui::text::Span;
+use crate::app::{App, Mode};
+use crate::avatar::{KIOSK_AVATAR, LIST_AVATAR, Placement, badge};
+use crate::burndown::Lines;
+use crate::checkin::Attendance;
+use crate::format::{TimeFormat, clock_reading, format_duration, format_minutes};
+use crate::health::Health;
+use crate::help::Help;
+use crate::per_person::SlotStatus;
+use crate::session::{Pause, Phase, Speaker};
+use crate::theme::TimerStyle;
+use chrono::Local;
+use rand::Rng;
+use rand::seq::IndexedRandom;
+use ratatui::text::{Line, Span};
 use ratatui::{
     Frame,
+    buffer::Buffer,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Gauge, List, ListItem, ListState, Paragraph},
+    symbols::Marker,
+    widgets::{
+        Axis, Block, Borders, Chart, Clear, Dataset, Gauge, GraphType, LegendPosition, LineGauge,
+        List, ListItem, ListState, Paragraph, StatefulWidget, Wrap,
+        canvas::{Canvas, Circle, Line as CanvasLine},
+    },
 };
+use std::cell::RefCell;
+use std::f64::consts::{FRAC_PI_2, TAU};
 use std::time::Duration;
 
+// How long each on/off phase of the hard-mode flash lasts
+const FLASH_PERIOD_MS: u128 = 500;
+
+// Async updates shown below the names before the rest are cut off
+const MAX_ASYNC_LINES: usize = 5;
+
+// Lines of the current speaker's written update shown before it's cut off
+const MAX_UPDATE_LINES: usize = 8;
+
+// Layout breakpoints: below these the full layout gives way to a smaller one
+const COMPACT_BELOW_ROWS: u16 = 15;
+const COMPACT_BELOW_COLUMNS: u16 = 40;
+const FULL_FROM_ROWS: u16 = 24;
+
+// Rows the analog clock face takes in the full layout, borders included
+const ANALOG_TIMER_ROWS: u16 = 11;
+
+// Smallest wheel worth drawing, in columns from the hub; below it the order stays a list
+const WHEEL_MIN_RADIUS: f64 = 6.0;
+
+// Names on the wheel are cut short after this many characters
+const WHEEL_NAME_CHARS: usize = 16;
+
+// Width of the bar in each row of the order with --row-bars
+const ROW_BAR_CELLS: usize = 8;
+
+// Rows the burndown chart takes in the full layout, borders included
+const BURNDOWN_ROWS: u16 = 10;
+
+/// How much of the layout the terminal has room for
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Breakpoint {
+    // current speaker, next speaker and a one-line timer, like the widget
+    Compact,
+    // the names, a slimmer timer and help; banners and questions are left out
+    Reduced,
+    Full,
+}
+
+impl Breakpoint {
+    fn for_area(area: Rect) -> Self {
+        if area.height < COMPACT_BELOW_ROWS || area.width < COMPACT_BELOW_COLUMNS {
+            Self::Compact
+        } else if area.height < FULL_FROM_ROWS {
+            Self::Reduced
+        } else {
+            Self::Full
+        }
+    }
+}
+
 /// UI renderer
 pub struct UI<'a> {
     app: &'a App,
+    // where the last render left room for avatars, for the app to draw them in afterwards
+    placements: RefCell<Vec<Placement>>,
 }
 
 impl<'a> UI<'a> {
     pub fn new(app: &'a App) -> Self {
-        Self { app }
+        Self {
+            app,
+            placements: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Where avatar images go on the screen just rendered
+    pub fn placements(&self) -> Vec<Placement> {
+        self.placements.borrow().clone()
     }
 
     /// Main render function
     pub fn render(&self, f: &mut Frame) {
         let config = self.app.config();
+        self.placements.borrow_mut().clear();
 
-        // create the main layout
-        let chunks = if config.hide_timer {
-            // without timer: names and help
-            Layout::default()
-                .direction(Direction::Vertical)
-                .constraints(
-                    [
-                        Constraint::Min(3),    // Names widget (flexible)
-                        Constraint::Length(3), // Help widget (fixed)
-                    ]
-                    .as_ref(),
-                )
-                .split(f.area())
+        if *self.app.mode() == Mode::Summary
+            && let Some(health) = self.app.health()
+        {
+            self.render_closing_view(f, f.area(), health);
+            self.render_demo_tip(f);
+            return;
+        }
+
+        if config.kiosk {
+            self.render_kiosk_view(f, f.area());
+            return;
+        }
+
+        if config.widget {
+            self.render_widget_view(f, f.area());
+            self.render_dialog(f);
+            return;
+        }
+
+        // the layout follows the terminal's size, so resizing it brings the rest back
+        let breakpoint = Breakpoint::for_area(f.area());
+        if breakpoint == Breakpoint::Compact {
+            self.render_compact_view(f, f.area());
+            self.render_handoff_toast(f);
+            self.render_dialog(f);
+            self.render_demo_tip(f);
+            return;
+        }
+        let full = breakpoint == Breakpoint::Full;
+
+        let question = self.app.current_question().filter(|_| full);
+        let celebrating = full && !self.app.celebrations().is_empty();
+        // a hybrid standup shows each update on its owner's turn instead of all at once
+        let async_updates = if self.app.is_hybrid() {
+            0
         } else {
-            // with timer: names, timer, and help
-            Layout::default()
-                .direction(Direction::Vertical)
-                .constraints(
-                    [
-                        Constraint::Min(3),    // Names widget (flexible)
-                        Constraint::Length(5), // Timer widget (fixed)
-                        Constraint::Length(3), // Help widget (fixed)
-                    ]
-                    .as_ref(),
-                )
-                .split(f.area())
+            self.app.async_updates().count()
         };
+        let update = self.app.current_update();
+
+        // create the main layout: optional banner, names, optional updates and question,
+        // optional timer, and help
+        let mut constraints = Vec::new();
+        if celebrating {
+            constraints.push(Constraint::Length(3)); // Celebration banner (fixed)
+        }
+        constraints.push(Constraint::Min(3)); // Names widget (flexible)
+        if async_updates > 0 {
+            // Async updates widget, one line each up to a limit
+            constraints.push(Constraint::Length(
+                async_updates.min(MAX_ASYNC_LINES) as u16 + 2,
+            ));
+        }
+        if let Some(update) = update {
+            // Current speaker's written update, sized to fit up to a limit
+            let lines = update.lines().count().clamp(1, MAX_UPDATE_LINES);
+            constraints.push(Constraint::Length(lines as u16 + 2));
+        }
+        if question.is_some() {
+            constraints.push(Constraint::Length(3)); // Question widget (fixed)
+        }
+        let burndown = if full { self.app.burndown() } else { None };
+        if burndown.is_some() {
+            constraints.push(Constraint::Length(BURNDOWN_ROWS)); // Burndown chart (fixed)
+        }
+        // the clock face needs the room of the full layout
+        let analog = full && config.timer_style == TimerStyle::Analog;
+        if !config.hide_timer {
+            // Timer widget (fixed), a line thick when space is short
+            constraints.push(Constraint::Length(if analog {
+                ANALOG_TIMER_ROWS
+            } else if full {
+                5
+            } else {
+                3
+            }));
+        }
+        let slot_status = self.app.slot_status();
+        if slot_status.is_some() {
+            constraints.push(Constraint::Length(1)); // Current speaker's slot gauge (fixed)
+        }
+        constraints.push(Constraint::Length(3)); // Help widget (fixed)
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(constraints)
+            .split(f.area());
+        let mut areas = chunks.iter().copied();
+
+        // Render celebration banner if anyone has a birthday or anniversary today
+        if celebrating && let Some(area) = areas.next() {
+            self.render_celebration_banner(f, area);
+        }
+
+        // Render names widget, as a list or a wheel
+        if let Some(area) = areas.next() {
+            if self.app.is_wheel() {
+                self.render_wheel_widget(f, area);
+            } else {
+                self.render_names_widget(f, area);
+            }
+        }
+
+        // Render written updates from people who checked in as async
+        if async_updates > 0
+            && let Some(area) = areas.next()
+        {
+            self.render_async_updates_widget(f, area);
+        }
+
+        // Render the current speaker's written update in a hybrid standup
+        if let Some(update) = update
+            && let Some(area) = areas.next()
+        {
+            self.render_update_widget(f, area, update);
+        }
+
+        // Render question widget if enabled
+        if let Some(question) = question
+            && let Some(area) = areas.next()
+        {
+            self.render_question_widget(f, area, question);
+        }
 
-        // Render names widget
-        self.render_names_widget(f, chunks[0]);
+        // Render the burndown chart if enabled
+        if let Some(burndown) = burndown
+            && let Some(area) = areas.next()
+        {
+            self.render_burndown_widget(f, area, &burndown);
+        }
 
         // Render timer widget if not hidden
-        if !config.hide_timer {
-            self.render_timer_widget(f, chunks[1]);
-            self.render_help_widget(f, chunks[2]);
-        } else {
-            self.render_help_widget(f, chunks[1]);
+        if !config.hide_timer
+            && let Some(area) = areas.next()
+        {
+            if analog {
+                self.render_analog_timer_widget(f, area);
+            } else {
+                self.render_timer_widget(f, area);
+            }
         }
+
+        // Render the current speaker's slot with per-person limits
+        if let Some(status) = slot_status
+            && let Some(area) = areas.next()
+        {
+            self.render_slot_widget(f, area, &status);
+        }
+
+        if let Some(area) = areas.next() {
+            match self.app.mode() {
+                Mode::Editing { text } => self.render_topic_prompt(f, area, text),
+                _ => self.render_help_widget(f, area),
+            }
+        }
+
+        self.render_handoff_toast(f);
+        self.render_dialog(f);
+        self.render_demo_tip(f);
+    }
+
+    /// Suggest the handover that just happened in a box at the top right, for a few seconds
+    fn render_handoff_toast(&self, f: &mut Frame) {
+        let Some(handoff) = self.app.handoff() else {
+            return;
+        };
+
+        let area = f.area();
+        let width = (handoff.chars().count() as u16 + 4).min(area.width);
+        let height = (wrapped_lines(handoff, width.saturating_sub(2)) + 2).min(area.height);
+        let toast = Rect::new(
+            area.right().saturating_sub(width + 1).max(area.x),
+            area.y + 1.min(area.height.saturating_sub(height)),
+            width,
+            height,
+        );
+
+        f.render_widget(Clear, toast);
+        f.render_widget(
+            Paragraph::new(handoff)
+                .alignment(Alignment::Center)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(Color::Magenta)),
+                )
+                .wrap(Wrap { trim: true }),
+            toast,
+        );
+    }
+
+    /// Point out what the demo is showing, above the timer and help
+    fn render_demo_tip(&self, f: &mut Frame) {
+        let Some(tip) = self.app.demo_tip() else {
+            return;
+        };
+
+        let area = f.area();
+        let width = 64.min(area.width);
+        let height = (wrapped_lines(tip, width.saturating_sub(2)) + 2).min(area.height);
+        let dialog = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.bottom().saturating_sub(height + 9).max(area.y),
+            width,
+            height,
+        );
+
+        f.render_widget(Clear, dialog);
+        f.render_widget(
+            Paragraph::new(tip)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(Color::Cyan))
+                        .title("Demo"),
+                )
+                .wrap(Wrap { trim: true }),
+            dialog,
+        );
+    }
+
+    /// Ask what the time nextup wasn't running through was, or for the facilitator's checklist,
+    /// over whatever else is on screen
+    fn render_dialog(&self, f: &mut Frame) {
+        let (title, lines) = match self.app.mode() {
+            Mode::Resuming { gap } => (
+                "Welcome back",
+                vec![
+                    Line::from(Span::styled(
+                        format!("💤 Nothing happened for {}", format_duration(*gap)),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    )),
+                    Line::from("The laptop may have slept or the terminal frozen."),
+                    Line::from(""),
+                    Line::from("<D> Discard it, as if it never happened"),
+                    Line::from("<C> Count it toward the meeting and this turn"),
+                    Line::from("<B> Call it a break, counting toward the meeting only"),
+                ],
+            ),
+            Mode::Checklist { ticked } => {
+                let mut lines = vec![
+                    Line::from(Span::styled(
+                        "Before wrapping up, how did it go?",
+                        Style::default().add_modifier(Modifier::BOLD),
+                    )),
+                    Line::from(""),
+                ];
+                for (i, (item, done)) in self.app.config().checklist.iter().zip(ticked).enumerate()
+                {
+                    let check = if *done { "☑" } else { "☐" };
+                    lines.push(Line::from(format!("<{}> {} {}", i + 1, check, item)));
+                }
+                lines.push(Line::from(""));
+                lines.push(Line::from("<Enter> Done   <Esc> Skip"));
+                ("Facilitator checklist", lines)
+            }
+            _ => return,
+        };
+
+        let area = f.area();
+        let width = 58.min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let dialog = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+
+        f.render_widget(Clear, dialog);
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(Color::Yellow))
+                        .title(title),
+                )
+                .wrap(Wrap { trim: true }),
+            dialog,
+        );
+    }
+
+    /// Render the closing screen with the meeting's health score and talk times
+    fn render_closing_view(&self, f: &mut Frame, area: Rect, health: &Health) {
+        let score_color = match health.score {
+            75.. => Color::Green,
+            50..=74 => Color::Yellow,
+            _ => Color::Red,
+        };
+
+        let mut time = format_duration(health.elapsed);
+        if !health.overtime.is_zero() {
+            time.push_str(&format!(" ({} over)", format_duration(health.overtime)));
+        }
+
+        let mut lines = vec![
+            Line::from(Span::styled(
+                format!(
+                    "Meeting health: {}/100 · {}",
+                    health.score,
+                    health.verdict()
+                ),
+                Style::default()
+                    .fg(score_color)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(format!("Time: {}", time)),
+            Line::from(format!("Talk-time spread: {:.0}%", health.spread * 100.0)),
+            Line::from(format!("Absent: {}", health.absent)),
+        ];
+        if let (Some(sprint), Some(day)) = (self.app.sprint(), self.app.sprint_day()) {
+            lines.push(Line::from(format!("Sprint: {} · {}", sprint.number, day)));
+        }
+        for trend in self.app.trends() {
+            lines.push(Line::from(Span::styled(
+                format!("📈 {}", trend),
+                Style::default().fg(Color::Cyan),
+            )));
+        }
+        if !health.paused.is_zero() {
+            lines.push(Line::from(format!(
+                "Paused: {}",
+                pause_summary(health.paused, self.app.pauses())
+            )));
+        }
+        let adjustments = self.app.adjustments();
+        if !adjustments.is_empty() {
+            let adjustments: Vec<String> = adjustments.iter().map(|a| a.to_string()).collect();
+            lines.push(Line::from(format!(
+                "Adjusted by hand: {}",
+                adjustments.join(", ")
+            )));
+        }
+        lines.push(Line::from(""));
+        let format = self.app.config().summary_time_format;
+        for (i, speaker) in self.app.speakers().iter().enumerate() {
+            lines.push(Line::from(format!(
+                "{}: {}{}",
+                speaker.name(),
+                format.format(speaker.total(), self.app.budget(i)),
+                if speaker.cut_off { " (cut off)" } else { "" }
+            )));
+        }
+        let parked: Vec<_> = self.app.unfinished_topics().collect();
+        if !parked.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "Parking lot",
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+            for topic in parked {
+                lines.push(Line::from(format!("{}: {}", topic.name, topic.text)));
+            }
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Press any key to exit",
+            Style::default().fg(Color::Gray),
+        )));
+        if let Some(version) = self.app.newer_version() {
+            lines.push(Line::from(Span::styled(
+                format!("nextup {} is out: nextup self-update", version),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+
+        let paragraph = Paragraph::new(lines).alignment(Alignment::Center).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(self.app.config().title.clone()),
+        );
+
+        f.render_widget(paragraph, area);
+    }
+
+    /// Render the widget view for a terminal too small for more, with the help or the topic
+    /// being typed on the last line
+    fn render_compact_view(&self, f: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Current speaker, next speaker, countdown
+                Constraint::Fill(1),
+                Constraint::Length(1), // Help
+            ])
+            .split(area);
+        self.render_widget_view(f, chunks[0]);
+
+        let line = match self.app.mode() {
+            Mode::Editing { text } => {
+                let name = self.app.speakers()[self.app.current_person_index()].name();
+                Line::from(format!("Topic for {}: {}█", name, text))
+            }
+            _ => Line::from(Help::for_app(self.app).to_string())
+                .style(Style::default().fg(Color::Gray)),
+        };
+        f.render_widget(Paragraph::new(line), chunks[2]);
+    }
+
+    /// Render the borderless three-line view: current speaker, next speaker, countdown
+    fn render_widget_view(&self, f: &mut Frame, area: Rect) {
+        let speakers = self.app.speakers();
+        let current_idx = self.app.current_person_index();
+        let timers = self.app.per_person_timers();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Current speaker
+                Constraint::Length(1), // Next speaker
+                Constraint::Length(1), // Countdown
+            ])
+            .split(area);
+
+        let name = speakers[current_idx].name();
+        let mut current = vec![Span::styled(
+            "▶ ",
+            Style::default().add_modifier(Modifier::BOLD),
+        )];
+        if self.app.avatars().is_enabled() {
+            current.extend([badge(name), Span::raw(" ")]);
+        }
+        current.extend([
+            Span::styled(
+                name.to_string(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(
+                    "  {}/{} · {}",
+                    current_idx + 1,
+                    speakers.len(),
+                    format_duration(timers[current_idx])
+                ),
+                Style::default().fg(Color::Gray),
+            ),
+        ]);
+        f.render_widget(Paragraph::new(Line::from(current)), chunks[0]);
+
+        let mut next = match speakers.get(current_idx + 1) {
+            Some(speaker) => format!("  next: {}", speaker.name()),
+            None => "  last speaker".to_string(),
+        };
+        if let Some(round) = self.app.round_label() {
+            next.push_str(&format!(" · {}", round));
+        }
+        f.render_widget(
+            Paragraph::new(next).style(Style::default().fg(Color::Gray)),
+            chunks[1],
+        );
+
+        if !self.app.config().hide_timer {
+            let remaining = self.app.remaining_time();
+            let progress = self.progress();
+            let overtime = self.app.overtime();
+            let time = match overtime {
+                Some(overtime) => format!("+{}", format_duration(overtime)),
+                None => format_duration(remaining),
+            };
+            let icon = self.app.timer_theme().icon(remaining, overtime.is_some());
+            let icon = if self.app.is_paused() {
+                "⏸ PAUSED"
+            } else {
+                icon
+            };
+            let gauge = LineGauge::default()
+                .filled_style(gauge_style(progress))
+                .ratio(progress.clamp(0.0, 1.0))
+                .label(format!("{} {} ", icon, time).trim_start().to_string());
+            f.render_widget(gauge, chunks[2]);
+        }
+    }
+
+    /// Render the wall display: who's speaking, a big countdown and who's next, readable across a room
+    fn render_kiosk_view(&self, f: &mut Frame, area: Rect) {
+        let speakers = self.app.speakers();
+        let current_idx = self.app.current_person_index();
+        let progress = self.progress();
+        let (r, g, b) = gauge_color(progress);
+
+        let clock = match self.app.overtime() {
+            Some(overtime) => format!("+{}", clock_reading(overtime)),
+            None => clock_reading(self.app.remaining_time()),
+        };
+
+        let mut lines = vec![
+            Line::from(Span::styled(
+                self.app.config().title.clone(),
+                Style::default().fg(Color::Cyan),
+            )),
+            Line::from(""),
+        ];
+        // the speaker's avatar, large, above their name
+        let name = speakers[current_idx].name();
+        let avatar = self.app.avatars().has_image(name);
+        if avatar {
+            lines.extend((0..=KIOSK_AVATAR.1).map(|_| Line::from("")));
+        }
+        let mut heading = vec![Span::styled(
+            name.to_uppercase(),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )];
+        if self.app.avatars().is_enabled() && !avatar {
+            heading.splice(0..0, [badge(name), Span::raw(" ")]);
+        }
+        lines.extend([Line::from(heading), Line::from("")]);
+        lines.extend(
+            big_text(&clock)
+                .into_iter()
+                .map(|row| Line::from(Span::styled(row, Style::default().fg(Color::Rgb(r, g, b))))),
+        );
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            match (self.app.phase(), speakers.get(current_idx + 1)) {
+                _ if self.app.is_paused() => "⏸ PAUSED".to_string(),
+                (Phase::WarmUp, _) => "first turn coming up".to_string(),
+                (_, Some(next)) => format!("next: {}", next.name()),
+                (_, None) => "last speaker".to_string(),
+            },
+            Style::default().fg(Color::Gray),
+        )));
+
+        // vertically centred, with the progress bar along the bottom
+        let height = lines.len() as u16;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Fill(1),
+                Constraint::Length(height),
+                Constraint::Fill(1),
+                Constraint::Length(1),
+            ])
+            .split(area);
+        f.render_widget(
+            Paragraph::new(lines).alignment(Alignment::Center),
+            chunks[1],
+        );
+        if avatar && chunks[1].width >= KIOSK_AVATAR.0 {
+            let (width, height) = KIOSK_AVATAR;
+            self.placements.borrow_mut().push(Placement {
+                name: name.to_string(),
+                area: Rect::new(
+                    chunks[1].x + (chunks[1].width - width) / 2,
+                    chunks[1].y + 2,
+                    width,
+                    height,
+                ),
+            });
+        }
+        f.render_widget(
+            LineGauge::default()
+                .filled_style(gauge_style(progress))
+                .ratio(progress.clamp(0.0, 1.0))
+                .label(""),
+            chunks[3],
+        );
     }
 
     /// Render the list of names
     fn render_names_widget(&self, f: &mut Frame, area: Rect) {
-        let names = self.app.names();
+        let speakers = self.app.speakers();
         let timers = self.app.per_person_timers();
         let current_idx = self.app.current_person_index();
+        // what row bars measure against when there's no slot to speak of
+        let spoken: Vec<Duration> = timers.iter().copied().filter(|t| !t.is_zero()).collect();
+        let average = spoken.iter().sum::<Duration>() / spoken.len().max(1) as u32;
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(self.order_title());
+        let highlight = Style::default().add_modifier(Modifier::BOLD);
 
-        // create list items with timer info
-        let items: Vec<ListItem> = names
+        if self.app.has_teams() {
+            // a roster split into teams is a tree: a heading per team with its members below
+            let mut nodes = Vec::new();
+            let mut selected = 0;
+            let mut last_team = None;
+            for (i, speaker) in speakers.iter().enumerate() {
+                let team = speaker.member.team.as_deref();
+                if let Some(team) = team
+                    && last_team != Some(team)
+                {
+                    nodes.push(self.team_node(team));
+                }
+                last_team = team;
+
+                if i == current_idx {
+                    selected = nodes.len();
+                }
+                let (lines, style) = self.speaker_lines(i, speaker, timers[i], average);
+                let depth = if team.is_some() { 1 } else { 0 };
+                nodes.push(TreeNode::new(depth, lines).style(style));
+            }
+
+            let tree = TreeList::new(nodes)
+                .block(block)
+                .highlight_style(highlight)
+                .highlight_symbol("> ");
+            let mut state = ListState::default();
+            state.select(Some(selected));
+            f.render_stateful_widget(tree, area, &mut state);
+        } else {
+            let inner = block.inner(area);
+            let items: Vec<ListItem> = speakers
+                .iter()
+                .enumerate()
+                .map(|(i, speaker)| {
+                    let (lines, style) = self.speaker_lines(i, speaker, timers[i], average);
+                    ListItem::new(lines).style(style)
+                })
+                .collect();
+            let heights: Vec<u16> = items.iter().map(|item| item.height() as u16).collect();
+
+            // create the list widget
+            let list = List::new(items)
+                .block(block)
+                .highlight_style(highlight)
+                .highlight_symbol("> ");
+
+            // create list state to track selection
+            let mut state = ListState::default();
+            state.select(Some(current_idx));
+
+            f.render_stateful_widget(list, area, &mut state);
+            self.place_list_avatars(inner, state.offset(), &heights);
+        }
+
+        if let Some(server) = self.app.server() {
+            self.render_qr_code(f, area, server.qr_code(), server.url());
+        }
+    }
+
+    /// Leave the avatars of the rows the list scrolled to in the slot before their number, which
+    /// comes after the two columns of the highlight symbol
+    fn place_list_avatars(&self, inner: Rect, offset: usize, heights: &[u16]) {
+        let speakers = self.app.speakers();
+        let mut placements = self.placements.borrow_mut();
+        let mut y = inner.y;
+        for (speaker, height) in speakers.iter().zip(heights).skip(offset) {
+            if y + LIST_AVATAR.1 > inner.bottom() {
+                break;
+            }
+            if self.app.avatars().has_image(speaker.name()) && inner.width > 2 + LIST_AVATAR.0 {
+                placements.push(Placement {
+                    name: speaker.name().to_string(),
+                    area: Rect::new(inner.x + 2, y, LIST_AVATAR.0, LIST_AVATAR.1),
+                });
+            }
+            y += height;
+        }
+    }
+
+    /// The order's title: the meeting, and whether it's locked, the round and who facilitates
+    fn order_title(&self) -> String {
+        let mut title = self.app.config().title.clone();
+        if self.app.is_order_locked() {
+            title.insert_str(0, "🔒 ");
+        }
+        if let Some(round) = self.app.round_label() {
+            title.push_str(&format!(" · {}", round));
+        }
+        if let Some(day) = self.app.sprint_day() {
+            title.push_str(&format!(" · {}", day));
+        }
+        if let Some(facilitator) = self.app.facilitator() {
+            title.push_str(&format!(" · facilitated by {}", facilitator));
+        }
+        if self.app.is_read_only() {
+            title.push_str(" · attached (read-only)");
+        }
+        title
+    }
+
+    /// Render the order around a wheel, first speaker at the top and clockwise from there, with
+    /// a pointer from the hub to whoever's speaking. Falls back to the list when the wheel and
+    /// its names don't fit.
+    fn render_wheel_widget(&self, f: &mut Frame, area: Rect) {
+        let speakers = self.app.speakers();
+        let current_idx = self.app.current_person_index();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(self.order_title());
+        let inner = block.inner(area);
+
+        let labels: Vec<String> = speakers
             .iter()
             .enumerate()
-            .map(|(i, name)| {
-                let timer_text = if timers[i] >= Duration::from_secs(5) {
-                    format!(" ({})", format_duration(timers[i]))
-                } else {
-                    String::new()
-                };
+            .map(|(i, speaker)| wheel_label(i, speaker.name()))
+            .collect();
+        // a badge goes in front of each name, with a space after it
+        let badges = self.app.avatars().is_enabled();
+        let badge_width = if badges {
+            LIST_AVATAR.0 as usize + 1
+        } else {
+            0
+        };
+        let label_width =
+            labels.iter().map(|l| l.chars().count()).max().unwrap_or(0) as f64 + badge_width as f64;
 
-                let content = format!("{}:  {}{}", i + 1, name, timer_text);
+        // one unit per column across and two per row down, which makes a circle come out round
+        let half_width = inner.width as f64 / 2.0;
+        let half_height = inner.height as f64;
+        let radius = (half_width - label_width - 2.0).min(half_height - 4.0);
+        if radius < WHEEL_MIN_RADIUS {
+            self.render_names_widget(f, area);
+            return;
+        }
 
-                // highlight current person
-                if i == current_idx {
-                    ListItem::new(content)
-                        .style(Style::default().bg(Color::Yellow).fg(Color::Black))
-                } else {
-                    ListItem::new(content)
+        let step = TAU / speakers.len() as f64;
+        let angle = |i: f64| FRAC_PI_2 - i * step;
+        let point = |a: f64, r: f64| (r * a.cos(), r * a.sin());
+
+        let canvas = Canvas::default()
+            .block(block)
+            .marker(Marker::Braille)
+            .x_bounds([-half_width, half_width])
+            .y_bounds([-half_height, half_height])
+            .paint(|ctx| {
+                ctx.draw(&Circle {
+                    x: 0.0,
+                    y: 0.0,
+                    radius,
+                    color: Color::Gray,
+                });
+                // spokes between everyone's slice of the wheel
+                if speakers.len() > 1 {
+                    for i in 0..speakers.len() {
+                        let (x, y) = point(angle(i as f64 + 0.5), radius);
+                        ctx.draw(&CanvasLine::new(0.0, 0.0, x, y, Color::DarkGray));
+                    }
                 }
+                ctx.layer();
+
+                let (x, y) = point(angle(current_idx as f64), radius - 1.0);
+                ctx.draw(&CanvasLine::new(0.0, 0.0, x, y, Color::Yellow));
+                ctx.draw(&Circle {
+                    x: 0.0,
+                    y: 0.0,
+                    radius: 1.0,
+                    color: Color::Yellow,
+                });
+
+                for (i, label) in labels.iter().enumerate() {
+                    let a = angle(i as f64);
+                    // a row clear of the wheel above and below it, two columns to the sides
+                    let (x, y) = (point(a, radius + 2.0).0, point(a, radius + 3.0).1);
+                    // names to the left end at the wheel, names to the right start at it
+                    let width = (label.chars().count() + badge_width) as f64;
+                    let x = if a.cos() < -0.3 {
+                        x - width
+                    } else if a.cos() < 0.3 {
+                        x - width / 2.0
+                    } else {
+                        x
+                    };
+
+                    let style = if i == current_idx {
+                        Style::default()
+                            .bg(Color::Yellow)
+                            .fg(Color::Black)
+                            .add_modifier(Modifier::BOLD)
+                    } else if self.app.is_absent(i) || i < current_idx {
+                        Style::default().fg(Color::DarkGray)
+                    } else {
+                        Style::default()
+                    };
+                    let mut spans = vec![Span::styled(label.clone(), style)];
+                    if badges {
+                        spans.splice(0..0, [badge(speakers[i].name()), Span::raw(" ")]);
+                    }
+                    ctx.print(x, y, Line::from(spans));
+                }
+            });
+        f.render_widget(canvas, area);
+
+        if let Some(server) = self.app.server() {
+            self.render_qr_code(f, area, server.qr_code(), server.url());
+        }
+    }
+
+    /// One person in the order, highlighted with the topics they queued up while it's their turn
+    fn speaker_lines(
+        &self,
+        i: usize,
+        speaker: &Speaker,
+        timer: Duration,
+        average: Duration,
+    ) -> (Vec<Line<'static>>, Style) {
+        let format = self.app.config().time_format;
+        let slot = self.app.slot(i);
+        let timer_text = if self.app.has_weighted_slots() && format != TimeFormat::Percent {
+            format!(
+                " ({} / {})",
+                format.format(timer, slot),
+                format.format(slot, slot)
+            )
+        } else if self.app.has_weighted_slots() || timer >= Duration::from_secs(5) {
+            format!(" ({})", format.format(timer, slot))
+        } else {
+            String::new()
+        };
+
+        let absent = if self.app.is_absent(i) {
+            match self.app.attendance(speaker.name()) {
+                Some(Attendance::Async) => " (async)",
+                _ => " (absent)",
+            }
+        } else if !self.app.is_hybrid() {
+            ""
+        } else if self.app.has_update(speaker.name()) {
+            // a hybrid standup marks who wrote their update and who speaks live
+            " ✍ written"
+        } else {
+            " 🎤 live"
+        };
+        let content = format!("{}{}{}", speaker.name(), timer_text, absent);
+        let current = i == self.app.current_person_index();
+        let mut row = if self.app.config().row_bars {
+            // against their slot, or the average time spoken when there's no time to split into slots
+            let reference = if slot.is_zero() { average } else { slot };
+            let share = if reference.is_zero() {
+                0.0
+            } else {
+                timer.as_secs_f64() / reference.as_secs_f64()
+            };
+            Line::from(vec![
+                Span::raw(format!("{}:  ", i + 1)),
+                row_bar(share, current),
+                Span::raw(format!(" {}", content)),
+            ])
+        } else {
+            Line::from(format!("{}:  {}", i + 1, content))
+        };
+        if speaker.member.guest {
+            row.spans.push(Span::styled(
+                " · guest",
+                Style::default().fg(Color::Magenta),
+            ));
+        }
+        // someone who's gone quiet, until they speak today
+        if let Some(standups) = self.app.silent_for(speaker.name())
+            && timer < Duration::from_secs(5)
+            && !self.app.is_absent(i)
+        {
+            row.spans.push(Span::styled(
+                format!(" · last spoke {} standups ago", standups),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        // someone who's used most of their talk time for the week
+        if let Some(used) = self.app.airtime_used(i)
+            && !self.app.is_absent(i)
+        {
+            let style = match used {
+                // the current row has its own colours
+                _ if current => Style::default(),
+                100.. => Style::default().fg(Color::LightRed),
+                _ => Style::default().fg(Color::Yellow),
+            };
+            row.spans.push(Span::styled(
+                format!(" · {}% of weekly airtime", used),
+                style,
+            ));
+        }
+        // room for the avatar, which only the plain list draws images in, or a badge instead
+        let avatars = self.app.avatars();
+        if avatars.is_enabled() {
+            let name = speaker.name();
+            let slot = if avatars.has_image(name) && !self.app.has_teams() {
+                Span::raw(" ".repeat(LIST_AVATAR.0 as usize))
+            } else {
+                badge(name)
+            };
+            row.spans.splice(0..0, [slot, Span::raw(" ")]);
+        }
+
+        if !current {
+            return (vec![row], Style::default());
+        }
+
+        let mut lines = vec![row];
+        for topic in self.app.topics_for(speaker.name()) {
+            let check = if topic.done { "☑" } else { "☐" };
+            lines.push(Line::from(format!("      {} {}", check, topic.text)));
+        }
+        // hard mode flashes the row once the slot has run out, per-person limits as it nearly does
+        let flash_on = (self.app.elapsed().as_millis() / FLASH_PERIOD_MS).is_multiple_of(2);
+        let bg = if self.app.cutoff_in().is_some() && flash_on {
+            Color::Red
+        } else if self.app.slot_nearly_up() && flash_on {
+            Color::LightRed
+        } else {
+            Color::Yellow
+        };
+        (lines, Style::default().bg(bg).fg(Color::Black))
+    }
+
+    /// A team's heading in the order
+    fn team_node(&self, team: &str) -> TreeNode<'static> {
+        let size = self.app.team_size(team);
+        let members = if size == 1 { "member" } else { "members" };
+
+        TreeNode::new(
+            0,
+            vec![Line::from(format!("{} · {} {}", team, size, members))],
+        )
+        .style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .collapsed(self.app.is_collapsed(team))
+    }
+
+    /// Render the web view's QR code in the top-right corner, if there's room for it
+    fn render_qr_code(&self, f: &mut Frame, area: Rect, qr_code: &str, url: &str) {
+        let lines: Vec<&str> = qr_code.lines().collect();
+        let qr_width = lines.first().map_or(0, |l| l.chars().count()) as u16;
+        let width = qr_width.max(url.len() as u16);
+        let height = lines.len() as u16 + 1;
+
+        // keep clear of the names and the border
+        if area.width < width + 40 || area.height < height + 2 {
+            return;
+        }
+        let corner = Rect::new(area.right() - width - 1, area.y + 1, width, height);
+
+        let mut text: Vec<Line> = lines
+            .into_iter()
+            .map(|line| {
+                Line::from(Span::styled(
+                    line,
+                    Style::default().fg(Color::White).bg(Color::Black),
+                ))
+            })
+            .collect();
+        text.push(Line::from(Span::styled(
+            url,
+            Style::default().fg(Color::Gray),
+        )));
+
+        f.render_widget(Paragraph::new(text).alignment(Alignment::Center), corner);
+    }
+
+    /// Render today's birthdays and anniversaries, with confetti while a celebrant speaks
+    fn render_celebration_banner(&self, f: &mut Frame, area: Rect) {
+        let message = self
+            .app
+            .celebrations()
+            .iter()
+            .map(|(name, celebration)| celebration.message(name))
+            .collect::<Vec<_>>()
+            .join("   ");
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta));
+
+        let line = if self.app.is_celebrating_now() {
+            confetti_line(message, block.inner(area).width)
+        } else {
+            Line::from(message)
+        };
+
+        let paragraph = Paragraph::new(line)
+            .block(block)
+            .style(Style::default().add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
+
+        f.render_widget(paragraph, area);
+    }
+
+    /// Render the written updates of people who checked in as async
+    fn render_async_updates_widget(&self, f: &mut Frame, area: Rect) {
+        let lines: Vec<Line> = self
+            .app
+            .async_updates()
+            .map(|check_in| {
+                let update = check_in
+                    .update
+                    .as_deref()
+                    .map_or("(no written update)".to_string(), |u| u.replace('\n', " "));
+                Line::from(vec![
+                    Span::styled(
+                        format!("✍ {}: ", check_in.name),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(update),
+                ])
             })
             .collect();
 
-        // create the list widget
-        let list = List::new(items)
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Async updates"),
+        );
+
+        f.render_widget(paragraph, area);
+    }
+
+    /// Render the written update the current speaker sent instead of speaking
+    fn render_update_widget(&self, f: &mut Frame, area: Rect, update: &str) {
+        let name = self.app.speakers()[self.app.current_person_index()].name();
+
+        let paragraph = Paragraph::new(update.to_string())
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title(self.app.config().title.as_str()),
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .title(format!("✍ Written update from {}", name)),
             )
-            .highlight_style(Style::default().add_modifier(Modifier::BOLD))
-            .highlight_symbol("> ");
+            .wrap(Wrap { trim: false });
 
-        // create list state to track selection
-        let mut state = ListState::default();
-        state.select(Some(current_idx));
+        f.render_widget(paragraph, area);
+    }
+
+    /// Render the current speaker's question of the day
+    fn render_question_widget(&self, f: &mut Frame, area: Rect, question: &str) {
+        let name = self.app.speakers()[self.app.current_person_index()].name();
+
+        let paragraph = Paragraph::new(format!("💬 {}", question))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("Question for {}", name)),
+            )
+            .alignment(Alignment::Center);
 
-        f.render_stateful_widget(list, area, &mut state);
+        f.render_widget(paragraph, area);
     }
 
-    /// Render the timer widget with adaptive colors for light/dark backgrounds
-    fn render_timer_widget(&self, f: &mut Frame, area: Rect) {
+    /// Fraction of the meeting remaining (0.0 to 1.0)
+    fn progress(&self) -> f64 {
         let remaining = self.app.remaining_time();
-        let total = self.app.config().duration;
-        let is_dark = self.app.is_dark_background();
+        // as adjusted during the meeting
+        let total = self.app.duration();
 
-        // calculate progress (0.0 to 1.0)
-        let progress = if total.as_secs() > 0 {
+        if total.as_secs() > 0 {
             // gauge decreases: start full (1.0) and goes to empty (0.0)
             remaining.as_secs() as f64 / total.as_secs() as f64
         } else {
             0.0
+        }
+    }
+
+    /// Render the turns left against the planned pace, red when they're heading past the end
+    fn render_burndown_widget(&self, f: &mut Frame, area: Rect, lines: &Lines) {
+        let heading = if self.app.projected_overrun().is_some() {
+            Color::Red
+        } else {
+            Color::Green
+        };
+        let line = |name, color, data| {
+            Dataset::default()
+                .name(name)
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(color))
+                .data(data)
+        };
+        let datasets = vec![
+            line("plan", Color::DarkGray, &lines.plan),
+            line("pace", heading, &lines.pace),
+            line("turns left", Color::Cyan, &lines.actual),
+        ];
+
+        let chart = Chart::new(datasets)
+            .block(Block::default().borders(Borders::ALL).title("Burndown"))
+            .x_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([0.0, lines.length])
+                    .labels(["0m".to_string(), format!("{:.0}m", lines.length)]),
+            )
+            .y_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::Gray))
+                    .bounds([0.0, lines.turns.max(1.0)])
+                    .labels(["0".to_string(), format!("{:.0}", lines.turns)]),
+            )
+            .legend_position(Some(LegendPosition::TopRight))
+            // the chart is short, so the legend may take its full height
+            .hidden_legend_constraints((Constraint::Ratio(1, 4), Constraint::Ratio(1, 1)));
+        f.render_widget(chart, area);
+    }
+
+    /// Render the timer widget with adaptive colors for light/dark backgrounds
+    fn render_timer_widget(&self, f: &mut Frame, area: Rect) {
+        let progress = self.progress();
+
+        // create gauge color gradient style based on remaining time, grayed out while paused
+        let gauge_style = if self.app.is_paused() {
+            Style::default().fg(Color::DarkGray)
+        } else {
+            gauge_style(progress)
         };
 
-        // choose icon based on remaining time
-        let icon = if remaining.as_secs() > 180 {
-            "⏳"
+        let gauge = Gauge::default()
+            .block(self.timer_block())
+            .gauge_style(gauge_style)
+            .percent((progress * 100.0) as u16)
+            .label(self.timer_label());
+
+        f.render_widget(gauge, area);
+    }
+
+    /// The current speaker's slot as a line under the timer, counting down to nothing and then
+    /// how far over they are
+    fn render_slot_widget(&self, f: &mut Frame, area: Rect, status: &SlotStatus) {
+        let ratio = status.ratio();
+        let time = if status.spoken.saturating_sub(status.slot).as_secs() > 0 {
+            format!("{} over", format_duration(status.spoken - status.slot))
+        } else {
+            format!(
+                "{} of {} left",
+                format_duration(status.left()),
+                format_duration(status.slot)
+            )
+        };
+        // earlier turns that ran over, e.g. in a previous round
+        let earlier = status
+            .overage
+            .saturating_sub(status.spoken.saturating_sub(status.slot));
+        let earlier = if earlier.as_secs() > 0 {
+            format!(" · {} over before", format_duration(earlier))
         } else {
-            "⌛"
+            String::new()
         };
 
+        let gauge = LineGauge::default()
+            .filled_style(gauge_style(ratio))
+            .ratio(ratio)
+            .label(format!(" {}: {}{} ", status.name, time, earlier));
+        f.render_widget(gauge, area);
+    }
+
+    /// The timer's frame, with a PAUSED banner across the top while the clock is stopped
+    fn timer_block(&self) -> Block<'static> {
+        let block = Block::default().borders(Borders::ALL);
+        if !self.app.is_paused() {
+            // an escalation rule flashes the border as time runs out
+            let flash_on = (self.app.elapsed().as_millis() / FLASH_PERIOD_MS).is_multiple_of(2);
+            if self.app.is_timer_blinking() && flash_on {
+                return block
+                    .border_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD));
+            }
+            return block;
+        }
+        block.title(
+            Line::from(" ⏸ PAUSED ").centered().style(
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        )
+    }
+
+    /// Render the countdown as a clock face: a pie of the time left, shrinking clockwise from
+    /// twelve o'clock in the gauge's colors, with the time left beside it
+    fn render_analog_timer_widget(&self, f: &mut Frame, area: Rect) {
+        let progress = self.progress().clamp(0.0, 1.0);
+        let block = self.timer_block();
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        // a face twice as wide as it's tall in cells comes out round
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(inner.height * 2 + 4),
+                Constraint::Fill(1),
+            ])
+            .split(inner);
+
+        // one unit per column across and two per row down, as on the wheel
+        let half_width = chunks[0].width as f64 / 2.0;
+        let half_height = chunks[0].height as f64;
+        let radius = half_width.min(half_height) - 1.0;
+        let (r, g, b) = gauge_color(progress);
+        let color = Color::Rgb(r, g, b);
+
+        let face = Canvas::default()
+            .marker(Marker::Braille)
+            .x_bounds([-half_width, half_width])
+            .y_bounds([-half_height, half_height])
+            .paint(|ctx| {
+                // the pie, filled in with a spoke for every dot along its rim
+                let spokes = (progress * TAU * radius * 4.0).ceil() as usize;
+                for i in 0..=spokes {
+                    let angle = FRAC_PI_2 - TAU * progress * i as f64 / spokes.max(1) as f64;
+                    let (x, y) = (radius * angle.cos(), radius * angle.sin());
+                    ctx.draw(&CanvasLine::new(0.0, 0.0, x, y, color));
+                }
+                ctx.layer();
+
+                ctx.draw(&Circle {
+                    x: 0.0,
+                    y: 0.0,
+                    radius,
+                    color: Color::Gray,
+                });
+                // a tick at every hour
+                for hour in 0..12 {
+                    let angle = TAU * hour as f64 / 12.0;
+                    let (cos, sin) = (angle.cos(), angle.sin());
+                    ctx.draw(&CanvasLine::new(
+                        (radius - 1.0) * cos,
+                        (radius - 1.0) * sin,
+                        radius * cos,
+                        radius * sin,
+                        Color::Gray,
+                    ));
+                }
+            });
+        f.render_widget(face, chunks[0]);
+
+        let text = chunks[1];
+        let middle = Rect {
+            y: text.y + text.height / 2,
+            height: 1.min(text.height),
+            ..text
+        };
+        f.render_widget(Paragraph::new(Line::from(self.timer_label())), middle);
+    }
+
+    /// What the timer says: the time left, the phase, and warnings about running over
+    fn timer_label(&self) -> Span<'static> {
+        let remaining = self.app.remaining_time();
+        let is_dark = self.app.is_dark_background();
+
         // Choose text color based on terminal background
         // For dark backgrounds: use light text (white)
         // For light backgrounds: use dark text (black/dark gray) for contrast
@@ -142,56 +1353,87 @@ impl<'a> UI<'a> {
             Color::Rgb(240, 240, 240) // Light background for dark text
         };
 
-        let timer_text = Span::styled(
-            format!("{} {} left", icon, format_duration(remaining)),
+        // warn early when the remaining speakers' slots won't fit
+        let projection = self
+            .app
+            .projected_overrun()
+            .filter(|_| !remaining.is_zero())
+            .map(|over| format!(" · projected {} over", format_duration(over)))
+            .unwrap_or_default();
+        let clash = match self.app.next_event() {
+            Some(event) if self.app.runs_into_next_event() => {
+                format!(" · runs into {}", event.summary)
+            }
+            _ => String::new(),
+        };
+
+        let phase = match self.app.phase() {
+            Phase::WarmUp => format!(
+                " · warm-up, first turn in {}",
+                format_duration(self.app.warm_up_left().unwrap_or_default())
+            ),
+            Phase::WrapUp => " · wrap-up".to_string(),
+            Phase::Rounds => String::new(),
+        };
+
+        let left = self
+            .app
+            .timer_theme()
+            .timer_text(remaining, self.app.overtime());
+
+        Span::styled(
+            format!(
+                "{}{}{}{}{}",
+                left,
+                phase,
+                projection,
+                clash,
+                if self.app.is_paused() {
+                    " · paused"
+                } else {
+                    ""
+                }
+            ),
             Style::default()
                 .fg(text_color)
                 .bg(text_bg)
                 .add_modifier(Modifier::BOLD),
-        );
+        )
+    }
 
-        // create gauge color gradient style based on remaining time
-        let gauge_style = if progress > 0.75 {
-            // 75-100%: Bright green (plenty of time)
-            Style::default().fg(Color::Rgb(34, 197, 94)) //Green-500
-        } else if progress > 0.5 {
-            // 50-75%: Light green
-            Style::default().fg(Color::Rgb(132, 204, 22)) // Lime-500
-        } else if progress > 0.35 {
-            // 35-50%: Yellow-green
-            Style::default().fg(Color::Rgb(163, 163, 0)) // Yellow-green mix
-        } else if progress > 0.25 {
-            // 25-35%: Yellow (caution)
-            Style::default().fg(Color::Rgb(234, 179, 8)) // Yellow-500
-        } else if progress > 0.15 {
-            // 15-25%: Orange (warning)
-            Style::default().fg(Color::Rgb(249, 115, 22)) // Orange-500
-        } else if progress > 0.05 {
-            // 5-15%: Red-orange (urgent)
-            Style::default().fg(Color::Rgb(239, 68, 68)) // Red-500
-        } else {
-            // 0-5%: Bright red (critical)
-            Style::default().fg(Color::Rgb(220, 38, 38)) // Red-600
-        };
+    /// Render the line a topic for the current speaker is typed into, with its key hints below
+    fn render_topic_prompt(&self, f: &mut Frame, area: Rect, text: &str) {
+        let name = self.app.speakers()[self.app.current_person_index()].name();
+        let lines = vec![
+            Line::from(format!("Topic for {}: {}█", name, text)),
+            Line::from(Help::for_app(self.app).to_string()).style(Style::default().fg(Color::Gray)),
+        ];
 
-        let gauge = Gauge::default()
-            .block(Block::default().borders(Borders::ALL))
-            .gauge_style(gauge_style)
-            .percent((progress * 100.0) as u16)
-            .label(timer_text);
-
-        f.render_widget(gauge, area);
+        f.render_widget(Paragraph::new(lines).alignment(Alignment::Center), area);
     }
 
-    /// Render the help widget
+    /// Render the help widget, below how long until the next event on the calendar
     fn render_help_widget(&self, f: &mut Frame, area: Rect) {
-        let help_text = if self.app.config().hide_timer {
-            "<Ctrl+N> Reshuffle names | <Tab/↓> Next | <↑> Previous | <Q> Quit"
-        } else {
-            "<Ctrl+R> Reset timer | <Ctrl+N> Reshuffle names | <Tab/↓> Next | <↑> Previous | <Q> Quit"
-        };
+        let mut lines = Vec::new();
+        if let Some(event) = self.app.next_event() {
+            let style = if self.app.runs_into_next_event() {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            let until = (event.start - Local::now()).to_std().unwrap_or_default();
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "Next meeting in {} ({})",
+                    format_minutes(until),
+                    event.summary
+                ),
+                style,
+            )));
+        }
+        lines.push(Line::from(Help::for_app(self.app).to_string()));
 
-        let paragraph = Paragraph::new(help_text)
+        let paragraph = Paragraph::new(lines)
             .style(Style::default().fg(Color::Gray))
             .alignment(Alignment::Center);
 
@@ -199,15 +1441,311 @@ impl<'a> UI<'a> {
     }
 }
 
-/// Format duration for display
-fn format_duration(duration: Duration) -> String {
-    let total_seconds = duration.as_secs();
-    let minutes = total_seconds / 60;
-    let seconds = total_seconds % 60;
+/// A row of a [`TreeList`], nested under the closest row above it with a smaller depth
+pub struct TreeNode<'a> {
+    depth: usize,
+    lines: Vec<Line<'a>>,
+    style: Style,
+    // whether the rows nested under it are hidden
+    collapsed: bool,
+}
+
+impl<'a> TreeNode<'a> {
+    pub fn new(depth: usize, lines: Vec<Line<'a>>) -> Self {
+        Self {
+            depth,
+            lines,
+            style: Style::default(),
+            collapsed: false,
+        }
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn collapsed(mut self, collapsed: bool) -> Self {
+        self.collapsed = collapsed;
+        self
+    }
+}
+
+/// A list drawn as a tree: rows with children get ▾ (or ▸ when collapsed, hiding them), and
+/// nested rows hang off indent guides. The selection is the index of a node; when it's hidden
+/// inside a collapsed node, that node is selected instead.
+pub struct TreeList<'a> {
+    nodes: Vec<TreeNode<'a>>,
+    block: Option<Block<'a>>,
+    highlight_style: Style,
+    highlight_symbol: &'a str,
+}
+
+impl<'a> TreeList<'a> {
+    pub fn new(nodes: Vec<TreeNode<'a>>) -> Self {
+        Self {
+            nodes,
+            block: None,
+            highlight_style: Style::default(),
+            highlight_symbol: "",
+        }
+    }
+
+    pub fn block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+
+    pub fn highlight_symbol(mut self, symbol: &'a str) -> Self {
+        self.highlight_symbol = symbol;
+        self
+    }
+}
 
-    if minutes > 0 {
-        format!("{}m {}s", minutes, seconds)
+impl StatefulWidget for TreeList<'_> {
+    type State = ListState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut ListState) {
+        let depths: Vec<usize> = self.nodes.iter().map(|node| node.depth).collect();
+        let selected = state.selected();
+
+        let mut items = Vec::new();
+        let mut visible_selected = None;
+        // for each level above the current row: whether that ancestor was the last of its siblings
+        let mut ancestors_last: Vec<bool> = Vec::new();
+        // depth below which rows are hidden by a collapsed node
+        let mut hidden_below: Option<usize> = None;
+
+        for (i, node) in self.nodes.into_iter().enumerate() {
+            let depth = node.depth;
+            if let Some(limit) = hidden_below {
+                if depth > limit {
+                    if selected == Some(i) {
+                        visible_selected = Some(items.len() - 1);
+                    }
+                    continue;
+                }
+                hidden_below = None;
+            }
+
+            let next_depth = depths[i + 1..].iter().copied().find(|d| *d <= depth);
+            let is_last = next_depth.is_none_or(|d| d < depth);
+            let has_children = depths.get(i + 1).is_some_and(|d| *d > depth);
+            ancestors_last.truncate(depth.saturating_sub(1));
+
+            // guides for the levels above, then this row's own branch
+            let mut prefix: String = ancestors_last
+                .iter()
+                .map(|last| if *last { "   " } else { "│  " })
+                .collect();
+            let mut continuation = prefix.clone();
+            if depth > 0 {
+                prefix.push_str(if is_last { "└─ " } else { "├─ " });
+                continuation.push_str(if is_last { "   " } else { "│  " });
+            }
+            if has_children {
+                prefix.push_str(if node.collapsed { "▸ " } else { "▾ " });
+                continuation.push_str("  ");
+            }
+
+            if depth > 0 {
+                ancestors_last.push(is_last);
+            }
+            if has_children && node.collapsed {
+                hidden_below = Some(depth);
+            }
+
+            let lines: Vec<Line> = node
+                .lines
+                .into_iter()
+                .enumerate()
+                .map(|(n, mut line)| {
+                    let guide = if n == 0 { &prefix } else { &continuation };
+                    line.spans.insert(0, Span::raw(guide.clone()));
+                    line
+                })
+                .collect();
+
+            if selected == Some(i) {
+                visible_selected = Some(items.len());
+            }
+            items.push(ListItem::new(lines).style(node.style));
+        }
+
+        let mut list = List::new(items)
+            .highlight_style(self.highlight_style)
+            .highlight_symbol(self.highlight_symbol);
+        if let Some(block) = self.block {
+            list = list.block(block);
+        }
+
+        let mut list_state = ListState::default().with_offset(state.offset());
+        list_state.select(visible_selected);
+        StatefulWidget::render(list, area, buf, &mut list_state);
+        *state.offset_mut() = list_state.offset();
+    }
+}
+
+/// Gauge color gradient style based on the remaining fraction of the meeting
+fn gauge_style(progress: f64) -> Style {
+    let (r, g, b) = gauge_color(progress);
+    Style::default().fg(Color::Rgb(r, g, b))
+}
+
+/// Gauge color for the remaining fraction of the meeting, shared with the web view
+pub fn gauge_color(progress: f64) -> (u8, u8, u8) {
+    if progress > 0.75 {
+        // 75-100%: Bright green (plenty of time)
+        (34, 197, 94) //Green-500
+    } else if progress > 0.5 {
+        // 50-75%: Light green
+        (132, 204, 22) // Lime-500
+    } else if progress > 0.35 {
+        // 35-50%: Yellow-green
+        (163, 163, 0) // Yellow-green mix
+    } else if progress > 0.25 {
+        // 25-35%: Yellow (caution)
+        (234, 179, 8) // Yellow-500
+    } else if progress > 0.15 {
+        // 15-25%: Orange (warning)
+        (249, 115, 22) // Orange-500
+    } else if progress > 0.05 {
+        // 5-15%: Red-orange (urgent)
+        (239, 68, 68) // Red-500
     } else {
-        format!("{}s", seconds)
+        // 0-5%: Bright red (critical)
+        (220, 38, 38) // Red-600
     }
 }
+
+/// Surround a message with randomly placed confetti; each call draws a new frame
+fn confetti_line(message: String, width: u16) -> Line<'static> {
+    const CONFETTI: [&str; 5] = ["*", "•", "✦", "°", "+"];
+    const COLORS: [Color; 6] = [
+        Color::Red,
+        Color::Yellow,
+        Color::Green,
+        Color::Cyan,
+        Color::Magenta,
+        Color::Blue,
+    ];
+
+    let message_width = Span::raw(message.as_str()).width();
+    let side = (width as usize).saturating_sub(message_width + 2) / 2;
+
+    let mut rng = rand::rng();
+    let mut confetti = |count: usize| -> Vec<Span<'static>> {
+        (0..count)
+            .map(|_| {
+                if rng.random_bool(0.4) {
+                    let piece = CONFETTI.choose(&mut rng).copied().unwrap_or("*");
+                    let color = COLORS.choose(&mut rng).copied().unwrap_or(Color::Yellow);
+                    Span::styled(piece, Style::default().fg(color))
+                } else {
+                    Span::raw(" ")
+                }
+            })
+            .collect()
+    };
+
+    let mut spans = confetti(side);
+    spans.push(Span::raw(format!(" {} ", message)));
+    spans.extend(confetti(side));
+    Line::from(spans)
+}
+
+/// A row's bar, filled to `share` of its width in eighths of a cell; past a full bar it turns red.
+/// The current row is already highlighted, so its bar is drawn in black until then.
+fn row_bar(share: f64, current: bool) -> Span<'static> {
+    const EIGHTHS: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+    let eighths = (share.clamp(0.0, 1.0) * (ROW_BAR_CELLS * 8) as f64).round() as usize;
+    let full = eighths / 8;
+    let mut bar = "█".repeat(full);
+    if full < ROW_BAR_CELLS {
+        bar.push_str(EIGHTHS[eighths % 8]);
+        bar.push_str(&"░".repeat(ROW_BAR_CELLS - bar.chars().count()));
+    }
+
+    let color = match (share > 1.0, current) {
+        (true, _) => Color::Red,
+        (false, true) => Color::Black,
+        (false, false) => Color::Green,
+    };
+    Span::styled(bar, Style::default().fg(color))
+}
+
+/// A name on the wheel with its place in the order, e.g. "3 Margaret", cut short if it's long
+fn wheel_label(i: usize, name: &str) -> String {
+    let name = if name.chars().count() > WHEEL_NAME_CHARS {
+        let short: String = name.chars().take(WHEEL_NAME_CHARS - 1).collect();
+        format!("{}…", short)
+    } else {
+        name.to_string()
+    };
+    format!("{} {}", i + 1, name)
+}
+
+/// Time paused and why, e.g. "4m 10s, 2 pauses (technical issue, break)"
+fn pause_summary(paused: Duration, pauses: &[Pause]) -> String {
+    let mut summary = format_duration(paused);
+    if pauses.len() > 1 {
+        summary.push_str(&format!(", {} pauses", pauses.len()));
+    }
+    let reasons: Vec<&str> = pauses.iter().filter_map(|p| p.reason.as_deref()).collect();
+    if !reasons.is_empty() {
+        summary.push_str(&format!(" ({})", reasons.join(", ")));
+    }
+    summary
+}
+
+/// How many lines `text` takes when wrapped at word boundaries to `width` columns
+fn wrapped_lines(text: &str, width: u16) -> u16 {
+    let width = usize::from(width.max(1));
+    let mut lines = 1;
+    let mut column = 0;
+    for word in text.split_whitespace() {
+        let len = word.chars().count();
+        if column > 0 && column + 1 + len > width {
+            lines += 1;
+            column = 0;
+        }
+        column += if column > 0 { 1 + len } else { len };
+    }
+    lines
+}
+
+/// A clock reading such as `14:05` or `+01:30` in five-row block digits, for the kiosk display
+pub fn big_text(text: &str) -> Vec<String> {
+    const ROWS: usize = 5;
+    let glyph = |c: char| -> [&str; ROWS] {
+        match c {
+            '0' => ["███", "█ █", "█ █", "█ █", "███"],
+            '1' => [" ██", "  █", "  █", "  █", "  █"],
+            '2' => ["███", "  █", "███", "█  ", "███"],
+            '3' => ["███", "  █", "███", "  █", "███"],
+            '4' => ["█ █", "█ █", "███", "  █", "  █"],
+            '5' => ["███", "█  ", "███", "  █", "███"],
+            '6' => ["███", "█  ", "███", "█ █", "███"],
+            '7' => ["███", "  █", "  █", "  █", "  █"],
+            '8' => ["███", "█ █", "███", "█ █", "███"],
+            '9' => ["███", "█ █", "███", "  █", "███"],
+            ':' => [" ", "█", " ", "█", " "],
+            '+' => ["   ", " █ ", "███", " █ ", "   "],
+            _ => ["   "; ROWS],
+        }
+    };
+
+    (0..ROWS)
+        .map(|row| {
+            text.chars()
+                .map(|c| glyph(c)[row])
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect()
+}
@@ -0,0 +1,255 @@
+use hmac_sha256::Hash;
+use serde::Deserialize;
+use std::env;
+use std::fs;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+use std::sync::{Arc, OnceLock};
+use std::thread;
+
+use crate::error::{AppError, Result};
+
+// Where releases are published
+const RELEASES_URL: &str = "https://api.github.com/repos/slipperypenguin/nextup/releases/latest";
+
+/// A published release, as the GitHub API describes it
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    assets: Vec<Asset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+}
+
+impl Release {
+    fn latest() -> Result<Self> {
+        ureq::get(RELEASES_URL)
+            .set("Accept", "application/vnd.github+json")
+            .set("User-Agent", concat!("nextup/", env!("CARGO_PKG_VERSION")))
+            .call()
+            .map_err(|e| AppError::UpdateError(e.to_string()))?
+            .into_json()
+            .map_err(|e| AppError::UpdateError(e.to_string()).into())
+    }
+
+    fn version(&self) -> &str {
+        self.tag_name.trim_start_matches('v')
+    }
+
+    fn is_newer(&self) -> bool {
+        parse_version(self.version()) > parse_version(env!("CARGO_PKG_VERSION"))
+    }
+
+    /// The build for this platform, e.g. `nextup-x86_64-unknown-linux-gnu.tar.gz`
+    fn asset(&self) -> Option<&Asset> {
+        let os = match env::consts::OS {
+            "macos" => "apple-darwin",
+            "windows" => "windows",
+            os => os,
+        };
+        self.assets.iter().find(|asset| {
+            asset.name.contains(env::consts::ARCH)
+                && asset.name.contains(os)
+                && !asset.name.ends_with(".sha256")
+        })
+    }
+
+    /// The checksum published next to a build, e.g. `nextup-x86_64-unknown-linux-gnu.tar.gz.sha256`
+    fn checksum(&self, asset: &Asset) -> Option<&Asset> {
+        let name = format!("{}.sha256", asset.name);
+        self.assets.iter().find(|checksum| checksum.name == name)
+    }
+}
+
+/// Looks for a newer release in the background, so the closing screen can point it out
+pub struct UpdateCheck {
+    newer: Arc<OnceLock<Option<String>>>,
+}
+
+impl UpdateCheck {
+    pub fn start() -> Self {
+        let newer = Arc::new(OnceLock::new());
+        let result = Arc::clone(&newer);
+        thread::spawn(move || {
+            // offline or rate-limited, there's just nothing to point out
+            let version = Release::latest()
+                .ok()
+                .filter(Release::is_newer)
+                .map(|release| release.version().to_string());
+            let _ = result.set(version);
+        });
+        Self { newer }
+    }
+
+    /// The newer version, once the check has found one
+    pub fn newer_version(&self) -> Option<&str> {
+        self.newer.get()?.as_deref()
+    }
+}
+
+/// Replace this binary with the latest release, for `nextup self-update`
+pub fn run() -> Result<()> {
+    let release = Release::latest()?;
+    if !release.is_newer() {
+        println!(
+            "nextup {} is the latest version.",
+            env!("CARGO_PKG_VERSION")
+        );
+        return Ok(());
+    }
+
+    // installs from crates.io or Homebrew are updated the way they were installed
+    let Some(asset) = release.asset() else {
+        println!(
+            "nextup {} is out, but there's no build of it for {} {}. Update with `cargo install nextup-tui` or `brew upgrade nextup-tui` instead.",
+            release.version(),
+            env::consts::OS,
+            env::consts::ARCH
+        );
+        return Ok(());
+    };
+    let checksum = release.checksum(asset).ok_or_else(|| {
+        AppError::UpdateError(format!("no checksum published for {}", asset.name))
+    })?;
+    println!("Downloading nextup {}...", release.version());
+
+    let bytes = download(&asset.browser_download_url)?;
+    verify(
+        &bytes,
+        &download(&checksum.browser_download_url)?,
+        &asset.name,
+    )?;
+
+    // a fresh directory only this user can write to, removed when it's dropped
+    let dir = tempfile::Builder::new().prefix("nextup-").tempdir()?;
+    let download = dir.path().join(&asset.name);
+    fs::write(&download, bytes)?;
+
+    let binary = unpack(&download, dir.path())?;
+    replace_current_exe(&binary)?;
+
+    println!(
+        "Updated nextup {} to {}.",
+        env!("CARGO_PKG_VERSION"),
+        release.version()
+    );
+    Ok(())
+}
+
+fn download(url: &str) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    ureq::get(url)
+        .call()
+        .map_err(|e| AppError::UpdateError(e.to_string()))?
+        .into_reader()
+        .read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Check a download against its published checksum, written the way `sha256sum` prints it
+fn verify(bytes: &[u8], checksum: &[u8], name: &str) -> Result<()> {
+    let expected = String::from_utf8_lossy(checksum)
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    if hex(&Hash::hash(bytes)) != expected {
+        return Err(AppError::UpdateError(format!(
+            "{} doesn't match its published checksum",
+            name
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// The binary inside a downloaded release, which may be an archive or the binary itself. Only
+/// the archive's own entry for it is unpacked.
+fn unpack(download: &Path, dir: &Path) -> Result<PathBuf> {
+    let name = download
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    if !name.ends_with(".tar.gz") && !name.ends_with(".tgz") && !name.ends_with(".zip") {
+        return Ok(download.to_path_buf());
+    }
+
+    let binary = if cfg!(windows) {
+        "nextup.exe"
+    } else {
+        "nextup"
+    };
+    // tar handles zip files too on macOS and Windows
+    let listing = Command::new("tar")
+        .arg("-tf")
+        .arg(download)
+        .output()
+        .map_err(|e| AppError::UpdateError(format!("tar: {}", e)))?;
+    if !listing.status.success() {
+        return Err(AppError::UpdateError(format!("couldn't read {}", name)).into());
+    }
+    let entry = String::from_utf8_lossy(&listing.stdout)
+        .lines()
+        .map(PathBuf::from)
+        .find(|entry| {
+            entry.file_name().is_some_and(|n| n == binary)
+                && entry
+                    .components()
+                    .all(|c| matches!(c, Component::Normal(_)))
+        })
+        .ok_or_else(|| AppError::UpdateError(format!("no {} in {}", binary, name)))?;
+
+    let status = Command::new("tar")
+        .arg("-xf")
+        .arg(download)
+        .arg("-C")
+        .arg(dir)
+        .arg(&entry)
+        .status()
+        .map_err(|e| AppError::UpdateError(format!("tar: {}", e)))?;
+    if !status.success() {
+        return Err(AppError::UpdateError(format!("couldn't unpack {}", name)).into());
+    }
+    Ok(dir.join(entry))
+}
+
+/// Swap the running binary for a new one; the old one is moved aside first, which Windows needs
+fn replace_current_exe(binary: &Path) -> Result<()> {
+    let current = env::current_exe()?;
+    let staged = current.with_extension("new");
+    let old = current.with_extension("old");
+
+    fs::copy(binary, &staged)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))?;
+    }
+
+    let _ = fs::remove_file(&old);
+    fs::rename(&current, &old)?;
+    if let Err(e) = fs::rename(&staged, &current) {
+        // put the old binary back rather than leave none
+        let _ = fs::rename(&old, &current);
+        return Err(e.into());
+    }
+    let _ = fs::remove_file(&old);
+    Ok(())
+}
+
+/// `1.2.10` as `[1, 2, 10]`, so versions compare numerically
+fn parse_version(version: &str) -> Vec<u64> {
+    version
+        .split(['.', '-'])
+        .map_while(|part| part.parse().ok())
+        .collect()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
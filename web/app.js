@@ -0,0 +1,174 @@
+// Polls the meeting state and renders it; colors come from the server so they
+// match the terminal gauge.
+
+const POLL_MS = 1000;
+
+function formatDuration(secs) {
+  const minutes = Math.floor(secs / 60);
+  const seconds = secs % 60;
+  return minutes > 0 ? `${minutes}m ${seconds}s` : `${seconds}s`;
+}
+
+// only rebuild a name picker when the names change, so a choice in progress survives
+function fillPicker(id, names) {
+  const picker = document.getElementById(id);
+  if (picker.dataset.names !== names.join("\n")) {
+    const selected = picker.value;
+    picker.replaceChildren(
+      ...names.map((name) => new Option(name, name, false, name === selected)),
+    );
+    picker.dataset.names = names.join("\n");
+  }
+}
+
+function render(state) {
+  document.title = `${state.title} · nextup`;
+  document.getElementById("title").textContent = state.title;
+  document.getElementById("round").textContent = state.check_in
+    ? "Check in before the meeting"
+    : state.round || "";
+  // before the meeting there's no speaker or clock, just the team
+  document.body.classList.toggle("checkin", state.check_in);
+
+  const current = state.speakers[state.current];
+  document.getElementById("speaker").textContent = current ? current.name : "";
+  document.getElementById("speaker-time").textContent = current
+    ? formatDuration(current.secs)
+    : "";
+  // in a hybrid standup, the written update they sent instead of speaking
+  document.getElementById("speaker-update").textContent =
+    current && !current.absent ? current.update || "" : "";
+
+  document.getElementById("topics").replaceChildren(
+    ...(current ? current.topics : []).map((topic) => {
+      const item = document.createElement("li");
+      item.textContent = topic.text;
+      if (topic.done) {
+        item.className = "done";
+      }
+      return item;
+    }),
+  );
+
+  const names = state.speakers.map((speaker) => speaker.name);
+  fillPicker("topic-name", names);
+  fillPicker("checkin-name", names);
+
+  const order = document.getElementById("order");
+  order.replaceChildren(
+    ...state.speakers.map((speaker, i) => {
+      const item = document.createElement("li");
+      item.textContent = speaker.name;
+      if (speaker.secs >= 5) {
+        const time = document.createElement("span");
+        time.className = "time";
+        time.textContent = `(${formatDuration(speaker.secs)})`;
+        item.append(time);
+      }
+      if (speaker.attendance === "async") {
+        const note = document.createElement("span");
+        note.className = "time";
+        note.textContent = "(async)";
+        item.append(note);
+      }
+      if (speaker.update) {
+        const update = document.createElement("p");
+        update.className = "update";
+        update.textContent = speaker.update;
+        item.append(update);
+      }
+      if (speaker.absent) {
+        item.className = "absent";
+      }
+      if (i === state.current && !state.check_in) {
+        item.className = "current";
+      }
+      return item;
+    }),
+  );
+
+  const fill = document.getElementById("gauge-fill");
+  fill.style.width = `${Math.round(state.progress * 100)}%`;
+  fill.style.backgroundColor = state.color;
+
+  document.getElementById("remaining").textContent =
+    `${state.icon} ${formatDuration(state.remaining_secs)} left` +
+    (state.paused ? " · paused" : "");
+  document.body.classList.remove("ended");
+}
+
+// the share link's token, passed on to every poll
+const SHARE_TOKEN = new URLSearchParams(location.search).get("token") ?? "";
+
+async function refresh() {
+  try {
+    const response = await fetch(
+      `state.json?token=${encodeURIComponent(SHARE_TOKEN)}`,
+      { cache: "no-store" },
+    );
+    if (response.status === 401) {
+      // the link has expired mid-meeting
+      document.getElementById("remaining").textContent = await response.text();
+      document.body.classList.add("ended");
+      return;
+    }
+    render(await response.json());
+  } catch {
+    // the terminal side has quit
+    document.getElementById("remaining").textContent = "Meeting ended";
+    document.body.classList.add("ended");
+  }
+}
+
+// topics only need the share link, not the control API's token
+async function submitTopic(event) {
+  event.preventDefault();
+  const name = document.getElementById("topic-name").value;
+  const text = document.getElementById("topic-text");
+  const status = document.getElementById("topic-status");
+
+  const response = await fetch(
+    `topics/${encodeURIComponent(name)}?token=${encodeURIComponent(SHARE_TOKEN)}`,
+    { method: "POST", body: text.value },
+  );
+  if (response.ok) {
+    text.value = "";
+    status.textContent = "Added";
+  } else if (response.status === 401) {
+    status.textContent = "This link has expired; ask for a new one";
+  } else {
+    status.textContent = "Couldn't add the topic";
+  }
+}
+
+// so do check-ins; "here" takes one back
+async function submitCheckIn(event) {
+  event.preventDefault();
+  const name = document.getElementById("checkin-name").value;
+  const status = document.getElementById("checkin-status").value;
+  const update = document.getElementById("checkin-update");
+  const result = document.getElementById("checkin-result");
+
+  const response = await fetch(
+    `checkin/${encodeURIComponent(name)}?status=${encodeURIComponent(status)}` +
+      `&token=${encodeURIComponent(SHARE_TOKEN)}`,
+    {
+      method: "POST",
+      body: status === "async" ? update.value : "",
+    },
+  );
+  if (response.ok) {
+    update.value = "";
+    result.textContent = "Checked in";
+  } else if (response.status === 401) {
+    result.textContent = "This link has expired; ask for a new one";
+  } else {
+    result.textContent = "Couldn't check in";
+  }
+}
+
+document.getElementById("topic-form").addEventListener("submit", submitTopic);
+document.getElementById("checkin-form").addEventListener("submit", submitCheckIn);
+
+refresh();
+setInterval(refresh, POLL_MS);